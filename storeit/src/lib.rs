@@ -45,12 +45,18 @@
 
 // Re-export all core traits and helper macros so generated code can reference `::storeit::...` paths.
 pub use storeit_core::{
-    async_trait, Fetchable, Identifiable, Insertable, ParamValue, RepoError, RepoResult,
-    Repository, RowAdapter, Updatable,
+    async_trait, clock, embed, projection, schema, tenant, value, BelongsTo, Criteria, Cursor,
+    CursorPage, Direction, Embeddable, EntityListener, Fetchable, FromColumn, Identifiable,
+    Insertable, KeyValues, Listenable, Order, Page, Pageable, ParamValue, Projection, Relations,
+    RepoError, RepoResult, Repository, RowAdapter, Schema, Sort, ToParamValue, Updatable,
 };
 
+// Serde conversions used by `#[fetch(json)]` fields.
+#[cfg(feature = "json")]
+pub use storeit_core::json;
+
 // Re-export all procedural macros.
-pub use storeit_macros::{repository, Entity};
+pub use storeit_macros::{repository, DbEnum, Embeddable, Entity, Projection};
 
 // Optional re-export of the SQL builder helpers.
 #[cfg(feature = "sql-builder")]
@@ -59,26 +65,21 @@ pub use storeit_sql_builder as sql_builder;
 // Re-export backend-agnostic transactions API so end-users can import from `repository`.
 pub use storeit_core::transactions;
 
-// Optional query ergonomics: simple paginate helper in-memory.
+// Optional query ergonomics: pagination shorthand over `Repository::find_page_by_field`.
 #[cfg(feature = "query-ext")]
 pub mod query_ext {
     use crate::RepoResult;
 
-    #[derive(Debug, Clone, PartialEq, Eq)]
-    pub struct Page<T> {
-        pub items: Vec<T>,
-        pub total: usize,
-    }
+    pub use storeit_core::Page;
 
     #[async_trait::async_trait]
     pub trait RepositoryExt<T>: storeit_core::Repository<T>
     where
         T: storeit_core::Identifiable + Send + Sync + 'static,
     {
-        /// Naive pagination over an equality filter. This method fetches all matching rows
-        /// using `find_by_field` and then slices in-memory. Intended for quick prototyping.
-        /// For efficient, large-scale pagination, implement a backend-specific method that uses
-        /// LIMIT/OFFSET or keyset and a COUNT query.
+        /// Unsorted page `page` of `size` rows matching an equality filter. The backend runs it
+        /// with LIMIT/OFFSET and a COUNT query; use `find_page_by_field` with a
+        /// [`Pageable`](storeit_core::Pageable) to sort the rows.
         async fn paginate_by_field(
             &self,
             field_name: &str,
@@ -86,18 +87,8 @@ pub mod query_ext {
             page: usize,
             size: usize,
         ) -> RepoResult<Page<T>> {
-            let all = self.find_by_field(field_name, value).await?;
-            let total = all.len();
-            let start = page.saturating_mul(size);
-            if start >= total {
-                return Ok(Page {
-                    items: Vec::new(),
-                    total,
-                });
-            }
-            let end = usize::min(start + size, total);
-            let items = all.into_iter().skip(start).take(end - start).collect();
-            Ok(Page { items, total })
+            self.find_page_by_field(field_name, value, &storeit_core::Pageable::new(page, size))
+                .await
         }
     }
 
@@ -230,20 +221,56 @@ pub mod upsert_ext {
 
 // Generic row reading by index for auto-generated adapters (no cfg leakage into user crates)
 pub mod row {
-    use crate::RepoError;
+    use crate::{FromColumn, ParamValue, RepoError};
 
     pub trait RowRead {
-        fn get_i64(&self, idx: usize) -> Result<i64, RepoError>;
-        fn get_i32(&self, idx: usize) -> Result<i32, RepoError>;
-        fn get_f64(&self, idx: usize) -> Result<f64, RepoError>;
-        fn get_bool(&self, idx: usize) -> Result<bool, RepoError>;
-        fn get_string(&self, idx: usize) -> Result<String, RepoError>;
+        /// Reads a column as a dynamically typed value; generated adapters convert it with
+        /// [`FromColumn`] for field types without a dedicated getter.
+        fn get_value(&self, idx: usize) -> Result<ParamValue, RepoError>;
+
+        fn get_i64(&self, idx: usize) -> Result<i64, RepoError> {
+            i64::from_column(self.get_value(idx)?)
+        }
+        fn get_i32(&self, idx: usize) -> Result<i32, RepoError> {
+            i32::from_column(self.get_value(idx)?)
+        }
+        fn get_f64(&self, idx: usize) -> Result<f64, RepoError> {
+            f64::from_column(self.get_value(idx)?)
+        }
+        fn get_bool(&self, idx: usize) -> Result<bool, RepoError> {
+            bool::from_column(self.get_value(idx)?)
+        }
+        fn get_string(&self, idx: usize) -> Result<String, RepoError> {
+            String::from_column(self.get_value(idx)?)
+        }
         // Optional getters (map NULL -> None)
-        fn get_opt_i64(&self, idx: usize) -> Result<Option<i64>, RepoError>;
-        fn get_opt_i32(&self, idx: usize) -> Result<Option<i32>, RepoError>;
-        fn get_opt_f64(&self, idx: usize) -> Result<Option<f64>, RepoError>;
-        fn get_opt_bool(&self, idx: usize) -> Result<Option<bool>, RepoError>;
-        fn get_opt_string(&self, idx: usize) -> Result<Option<String>, RepoError>;
+        fn get_opt_i64(&self, idx: usize) -> Result<Option<i64>, RepoError> {
+            Option::from_column(self.get_value(idx)?)
+        }
+        fn get_opt_i32(&self, idx: usize) -> Result<Option<i32>, RepoError> {
+            Option::from_column(self.get_value(idx)?)
+        }
+        fn get_opt_f64(&self, idx: usize) -> Result<Option<f64>, RepoError> {
+            Option::from_column(self.get_value(idx)?)
+        }
+        fn get_opt_bool(&self, idx: usize) -> Result<Option<bool>, RepoError> {
+            Option::from_column(self.get_value(idx)?)
+        }
+        fn get_opt_string(&self, idx: usize) -> Result<Option<String>, RepoError> {
+            Option::from_column(self.get_value(idx)?)
+        }
+    }
+
+    // The column values of an `Embeddable`, read by its generated `from_values`.
+    impl RowRead for [ParamValue] {
+        fn get_value(&self, idx: usize) -> Result<ParamValue, RepoError> {
+            self.get(idx).cloned().ok_or_else(|| {
+                RepoError::mapping(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!("missing embedded column {idx}"),
+                ))
+            })
+        }
     }
 
     // Backend row type aliases; always present for name resolution in generated code.
@@ -263,8 +290,16 @@ pub mod row {
     #[cfg(not(feature = "libsql-backend"))]
     pub enum LibRow {}
 
+    #[cfg(feature = "memory-backend")]
+    pub type MemRow = storeit_memory::MemoryRow;
+    #[cfg(not(feature = "memory-backend"))]
+    pub enum MemRow {}
+
     #[cfg(feature = "postgres-backend")]
     impl RowRead for tokio_postgres::Row {
+        fn get_value(&self, idx: usize) -> Result<ParamValue, RepoError> {
+            storeit_tokio_postgres::column_value(self, idx)
+        }
         fn get_i64(&self, idx: usize) -> Result<i64, RepoError> {
             use tokio_postgres::Row as PgRow;
             PgRow::try_get(self, idx).map_err(RepoError::mapping)
@@ -309,6 +344,9 @@ pub mod row {
 
     #[cfg(feature = "mysql-async")]
     impl RowRead for mysql_async::Row {
+        fn get_value(&self, idx: usize) -> Result<ParamValue, RepoError> {
+            storeit_mysql_async::column_value(self, idx)
+        }
         fn get_i64(&self, idx: usize) -> Result<i64, RepoError> {
             self.get(idx).ok_or_else(|| {
                 RepoError::mapping(std::io::Error::new(
@@ -369,6 +407,9 @@ pub mod row {
 
     #[cfg(feature = "libsql-backend")]
     impl RowRead for libsql::Row {
+        fn get_value(&self, idx: usize) -> Result<ParamValue, RepoError> {
+            storeit_libsql::column_value(self, idx)
+        }
         fn get_i64(&self, idx: usize) -> Result<i64, RepoError> {
             self.get(idx as i32).map_err(RepoError::mapping)
         }
@@ -401,6 +442,18 @@ pub mod row {
             self.get(idx as i32).map_err(RepoError::mapping)
         }
     }
+
+    #[cfg(feature = "memory-backend")]
+    impl RowRead for storeit_memory::MemoryRow {
+        fn get_value(&self, idx: usize) -> Result<ParamValue, RepoError> {
+            self.values().get(idx).cloned().ok_or_else(|| {
+                RepoError::mapping(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!("missing column {idx}"),
+                ))
+            })
+        }
+    }
 }
 
 pub mod backends {
@@ -414,4 +467,7 @@ pub mod backends {
     // Same for Postgres backend types.
     #[cfg(feature = "dep:storeit_tokio_postgres")]
     pub use storeit_tokio_postgres::{TokioPostgresRepository, TokioPostgresTransactionManager};
+
+    #[cfg(feature = "memory-backend")]
+    pub use storeit_memory::{MemoryDatabase, MemoryRepository, MemoryTransactionManager};
 }
@@ -0,0 +1,68 @@
+#![allow(unexpected_cfgs)]
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use storeit::*;
+
+#[derive(Entity, Clone, Debug, PartialEq)]
+struct Article {
+    #[fetch(id)]
+    id: Option<i64>,
+    title: String,
+    #[fetch(created_at)]
+    created_at: chrono::DateTime<chrono::Utc>,
+    #[fetch(updated_at)]
+    updated_at: Option<SystemTime>,
+}
+
+#[test]
+fn audit_fields_are_filled_from_the_clock() {
+    let at = UNIX_EPOCH + Duration::from_micros(1_700_000_000_123_456);
+    let a = Article {
+        id: Some(3),
+        title: "t".into(),
+        created_at: chrono::DateTime::UNIX_EPOCH,
+        updated_at: None,
+    };
+    assert_eq!(
+        Article::INSERT_COLUMNS,
+        &["title", "created_at", "updated_at"]
+    );
+    // created_at is never part of an UPDATE.
+    assert_eq!(Article::UPDATE_COLUMNS, &["title", "updated_at"]);
+
+    const STAMP: i64 = 1_700_000_000_123_456;
+    let inserted = clock::with_now(at, || a.insert_values());
+    assert!(matches!(
+        &inserted[1..],
+        [ParamValue::Timestamp(STAMP), ParamValue::Timestamp(STAMP)]
+    ));
+    let updated = clock::with_now(at, || a.update_values());
+    assert!(matches!(
+        updated.as_slice(),
+        [
+            ParamValue::String(_),
+            ParamValue::Timestamp(STAMP),
+            ParamValue::I64(3)
+        ]
+    ));
+
+    let mut touched = a.clone();
+    touched.touch(at);
+    assert_eq!(touched.updated_at, Some(at));
+    assert_eq!(touched.created_at, a.created_at);
+}
+
+#[test]
+fn installed_clock_drives_values_outside_a_pinned_scope() {
+    let fixed = clock::FixedClock::new(UNIX_EPOCH + Duration::from_secs(60));
+    clock::set_clock(std::sync::Arc::new(fixed.clone()));
+    fixed.advance(Duration::from_secs(1));
+    let values = Article {
+        id: None,
+        title: "t".into(),
+        created_at: chrono::DateTime::UNIX_EPOCH,
+        updated_at: None,
+    }
+    .insert_values();
+    clock::reset_clock();
+    assert!(matches!(values[1], ParamValue::Timestamp(61_000_000)));
+}
@@ -0,0 +1,95 @@
+#![cfg(feature = "libsql-backend")]
+#![allow(unexpected_cfgs)]
+use storeit::*;
+
+#[derive(DbEnum, Clone, Copy, Debug, PartialEq)]
+#[db_enum(rename_all = "snake_case")]
+pub enum Status {
+    Open,
+    InProgress,
+    #[db_enum(rename = "done")]
+    Closed,
+}
+
+#[derive(DbEnum, Clone, Copy, Debug, PartialEq)]
+#[db_enum(integer)]
+pub enum Priority {
+    Low = 1,
+    Normal,
+    Urgent = 10,
+}
+
+#[derive(Entity, Clone, Debug, PartialEq)]
+#[entity(table = "tickets")]
+pub struct Ticket {
+    #[fetch(id)]
+    pub id: Option<i64>,
+    pub status: Status,
+    pub priority: Priority,
+    pub previous: Option<Status>,
+}
+
+#[repository(entity = Ticket, backend = Libsql, finders(find_by_status: Status))]
+pub mod tickets {}
+
+#[test]
+fn variants_map_to_their_stored_form() {
+    assert!(
+        matches!(Status::InProgress.to_param_value(), ParamValue::String(s) if s == "in_progress")
+    );
+    assert!(matches!(Status::Closed.to_param_value(), ParamValue::String(s) if s == "done"));
+    assert!(matches!(
+        Priority::Normal.to_param_value(),
+        ParamValue::I32(2)
+    ));
+    assert_eq!(
+        Priority::from_column(ParamValue::I64(10)).unwrap(),
+        Priority::Urgent
+    );
+    assert!(Status::from_column(ParamValue::String("Closed".into())).is_err());
+    assert!(Priority::from_column(ParamValue::I32(3)).is_err());
+}
+
+#[tokio::test]
+async fn enum_fields_round_trip_and_filter() {
+    let path = std::env::temp_dir().join(format!("storeit_db_enum_{}.sqlite3", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let url = format!("file:{}?mode=rwc", path.display());
+    {
+        #[allow(deprecated)]
+        let db = libsql::Database::open(url.clone()).expect("open db");
+        db.connect()
+            .expect("connect")
+            .execute(
+                "CREATE TABLE tickets (id INTEGER PRIMARY KEY AUTOINCREMENT, status TEXT NOT NULL, priority INTEGER NOT NULL, previous TEXT)",
+                (),
+            )
+            .await
+            .expect("schema");
+    }
+    let repo = tickets::Repository::from_url(&url).await.expect("repo");
+    let mut ticket = repo
+        .insert(&Ticket {
+            id: None,
+            status: Status::Open,
+            priority: Priority::Urgent,
+            previous: None,
+        })
+        .await
+        .expect("insert");
+    ticket.previous = Some(ticket.status);
+    ticket.status = Status::InProgress;
+    repo.update(&ticket).await.expect("update");
+
+    assert_eq!(
+        repo.find_by_status(&Status::InProgress).await.unwrap(),
+        vec![ticket.clone()]
+    );
+    assert!(repo.find_by_status(&Status::Open).await.unwrap().is_empty());
+    let urgent = repo
+        .find_where(&Criteria::eq("priority", Priority::Urgent))
+        .await
+        .unwrap();
+    assert_eq!(urgent, vec![ticket]);
+    let _ = std::fs::remove_file(&path);
+}
@@ -0,0 +1,148 @@
+#![cfg(feature = "libsql-backend")]
+#![allow(unexpected_cfgs)]
+use storeit::*;
+
+#[derive(Embeddable, Clone, Debug, PartialEq)]
+pub struct Address {
+    pub street: String,
+    #[fetch(column = "town")]
+    pub city: String,
+    pub zip: Option<String>,
+}
+
+#[derive(Embeddable, Clone, Debug, PartialEq)]
+pub struct Money {
+    pub amount_cents: i64,
+    pub currency: String,
+}
+
+#[derive(Entity, Clone, Debug, PartialEq)]
+#[entity(table = "customers")]
+pub struct Customer {
+    #[fetch(id)]
+    pub id: Option<i64>,
+    pub name: String,
+    #[fetch(flatten, prefix = "billing_")]
+    pub billing: Address,
+    #[fetch(flatten, prefix = "shipping_")]
+    pub shipping: Option<Address>,
+    #[fetch(flatten)]
+    pub credit: Money,
+}
+
+#[derive(Projection, Debug, PartialEq)]
+#[projection(of = Customer)]
+pub struct BillingLabel {
+    pub name: String,
+    #[fetch(flatten, prefix = "billing_")]
+    pub address: Address,
+}
+
+#[repository(entity = Customer, backend = Libsql)]
+pub mod customers {}
+
+fn address(street: &str) -> Address {
+    Address {
+        street: street.into(),
+        city: "Springfield".into(),
+        zip: None,
+    }
+}
+
+#[test]
+fn flattened_columns_are_prefixed() {
+    assert_eq!(Address::COLUMNS, &["street", "town", "zip"]);
+    assert_eq!(
+        Customer::SELECT_COLUMNS,
+        &[
+            "id",
+            "name",
+            "billing_street",
+            "billing_town",
+            "billing_zip",
+            "shipping_street",
+            "shipping_town",
+            "shipping_zip",
+            "amount_cents",
+            "currency",
+        ]
+    );
+    assert_eq!(Customer::INSERT_COLUMNS, &Customer::SELECT_COLUMNS[1..]);
+    assert_eq!(Customer::UPDATE_COLUMNS, &Customer::SELECT_COLUMNS[1..]);
+    assert_eq!(
+        BillingLabel::COLUMNS,
+        &["name", "billing_street", "billing_town", "billing_zip"]
+    );
+}
+
+#[test]
+fn flattened_columns_are_part_of_the_table_def() {
+    let table = Customer::table_def();
+    let names: Vec<&str> = table.columns.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(names, Customer::SELECT_COLUMNS);
+    let nullable = |name: &str| table.column(name).unwrap().nullable;
+    assert!(!nullable("billing_street") && nullable("billing_zip"));
+    assert!(nullable("shipping_street"));
+    assert_eq!(
+        table.column("amount_cents").unwrap().sql_type,
+        schema::SqlType::BigInt
+    );
+}
+
+#[tokio::test]
+async fn embedded_values_round_trip() {
+    let path =
+        std::env::temp_dir().join(format!("storeit_embedded_{}.sqlite3", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let url = format!("file:{}?mode=rwc", path.display());
+    {
+        #[allow(deprecated)]
+        let db = libsql::Database::open(url.clone()).expect("open db");
+        db.connect()
+            .expect("connect")
+            .execute(
+                "CREATE TABLE customers (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT NOT NULL, \
+                 billing_street TEXT NOT NULL, billing_town TEXT NOT NULL, billing_zip TEXT, \
+                 shipping_street TEXT, shipping_town TEXT, shipping_zip TEXT, \
+                 amount_cents INTEGER NOT NULL, currency TEXT NOT NULL)",
+                (),
+            )
+            .await
+            .expect("schema");
+    }
+    let repo = customers::Repository::from_url(&url).await.expect("repo");
+
+    let mut ann = repo
+        .insert(&Customer {
+            id: None,
+            name: "ann".into(),
+            billing: address("1 Main St"),
+            shipping: None,
+            credit: Money {
+                amount_cents: 1250,
+                currency: "EUR".into(),
+            },
+        })
+        .await
+        .expect("insert");
+    assert_eq!(ann.shipping, None);
+    let found = repo.find_by_id(&ann.id.unwrap()).await.unwrap().unwrap();
+    assert_eq!(found, ann);
+
+    ann.shipping = Some(Address {
+        zip: Some("12345".into()),
+        ..address("2 Side St")
+    });
+    ann.credit.amount_cents = 0;
+    repo.update(&ann).await.expect("update");
+    let found = repo.find_by_id(&ann.id.unwrap()).await.unwrap().unwrap();
+    assert_eq!(found, ann);
+
+    let label = repo
+        .find_by_id_as::<BillingLabel>(&ann.id.unwrap())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(label.address, address("1 Main St"));
+    let _ = std::fs::remove_file(&path);
+}
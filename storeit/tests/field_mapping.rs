@@ -0,0 +1,161 @@
+#![allow(unexpected_cfgs)]
+use storeit::row::RowRead;
+use storeit::*;
+
+// A newtype persisted through the public conversion traits.
+#[derive(Clone, Debug, PartialEq)]
+struct Email(String);
+
+impl ToParamValue for Email {
+    fn to_param_value(&self) -> ParamValue {
+        ParamValue::String(self.0.clone())
+    }
+}
+
+impl FromColumn for Email {
+    fn from_column(value: ParamValue) -> RepoResult<Self> {
+        String::from_column(value).map(Email)
+    }
+}
+
+// A newtype whose name ends like a well-known type; it still maps through the traits.
+#[derive(Clone, Debug, PartialEq)]
+struct OrderUuid([u8; 16]);
+
+impl ToParamValue for OrderUuid {
+    fn to_param_value(&self) -> ParamValue {
+        ParamValue::Uuid(self.0)
+    }
+}
+
+impl FromColumn for OrderUuid {
+    fn from_column(value: ParamValue) -> RepoResult<Self> {
+        storeit::value::uuid_from_column(value).map(OrderUuid)
+    }
+}
+
+// A third-party-like type mapped with `#[fetch(with = "...")]` (stored as cents).
+#[derive(Clone, Debug, PartialEq)]
+struct Money {
+    cents: i64,
+}
+
+mod money_cents {
+    use super::Money;
+    use storeit::{FromColumn, ParamValue, RepoResult};
+
+    pub fn to_param_value(m: &Money) -> ParamValue {
+        ParamValue::I64(m.cents)
+    }
+
+    pub fn from_column(value: ParamValue) -> RepoResult<Money> {
+        i64::from_column(value).map(|cents| Money { cents })
+    }
+}
+
+#[derive(Entity, Clone, Debug, PartialEq)]
+struct Customer {
+    #[fetch(id)]
+    id: Option<i64>,
+    email: Email,
+    backup_email: Option<Email>,
+    #[fetch(with = "money_cents")]
+    balance: Money,
+    #[fetch(skip)]
+    cached_rank: u32,
+    joined: chrono::NaiveDate,
+    last_order: Option<OrderUuid>,
+}
+
+/// A row backed by the values an entity would have bound.
+struct ValuesRow(Vec<ParamValue>);
+
+impl RowRead for ValuesRow {
+    fn get_value(&self, idx: usize) -> Result<ParamValue, RepoError> {
+        Ok(self.0[idx].clone())
+    }
+}
+
+#[test]
+fn custom_field_types_roundtrip_through_generated_adapter() {
+    let c = Customer {
+        id: Some(7),
+        email: Email("a@x".into()),
+        backup_email: None,
+        balance: Money { cents: 1250 },
+        cached_rank: 3,
+        joined: chrono::NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+        last_order: Some(OrderUuid([7; 16])),
+    };
+    assert_eq!(
+        Customer::SELECT_COLUMNS,
+        &[
+            "id",
+            "email",
+            "backup_email",
+            "balance",
+            "joined",
+            "last_order"
+        ]
+    );
+
+    // Only the exact `Uuid` type gets a UUID column; the newtype is stored as text.
+    let last_order = Customer::table_def()
+        .columns
+        .into_iter()
+        .find(|col| col.name == "last_order")
+        .unwrap();
+    assert_eq!(last_order.sql_type, schema::SqlType::Text);
+
+    let values = c.insert_values();
+    assert!(matches!(values[2], ParamValue::I64(1250)));
+    let mut row = vec![ParamValue::I64(7)];
+    row.extend(values);
+
+    let loaded = CustomerRowAdapter::<ValuesRow>::new()
+        .from_row(&ValuesRow(row))
+        .expect("map row");
+    assert_eq!(
+        loaded,
+        Customer {
+            cached_rank: 0,
+            ..c
+        }
+    );
+}
+
+#[test]
+fn text_forms_map_to_native_field_types() {
+    // SQLite-style row: dates come back as text and integers as i64.
+    let row = ValuesRow(vec![
+        ParamValue::I64(1),
+        ParamValue::String("b@x".into()),
+        ParamValue::String("c@x".into()),
+        ParamValue::I64(-5),
+        ParamValue::String("2023-12-31".into()),
+        ParamValue::Null,
+    ]);
+    let loaded = CustomerRowAdapter::<ValuesRow>::new()
+        .from_row(&row)
+        .expect("map row");
+    assert_eq!(loaded.backup_email, Some(Email("c@x".into())));
+    assert_eq!(loaded.balance, Money { cents: -5 });
+    assert_eq!(
+        loaded.joined,
+        chrono::NaiveDate::from_ymd_opt(2023, 12, 31).unwrap()
+    );
+    assert_eq!(loaded.last_order, None);
+
+    let bad = ValuesRow(vec![
+        ParamValue::I64(1),
+        ParamValue::Bool(true),
+        ParamValue::Null,
+        ParamValue::I64(0),
+        ParamValue::String("2023-12-31".into()),
+        ParamValue::Null,
+    ]);
+    assert!(matches!(
+        CustomerRowAdapter::<ValuesRow>::new().from_row(&bad),
+        Err(RepoError::Mapping { .. })
+    ));
+}
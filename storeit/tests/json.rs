@@ -0,0 +1,95 @@
+#![cfg(all(feature = "libsql-backend", feature = "json"))]
+#![allow(unexpected_cfgs)]
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use storeit::*;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Settings {
+    pub theme: String,
+    pub notifications: bool,
+}
+
+#[derive(Entity, Clone, Debug, PartialEq)]
+#[entity(table = "profiles")]
+pub struct Profile {
+    #[fetch(id)]
+    pub id: Option<i64>,
+    pub name: String,
+    #[fetch(json)]
+    pub settings: Settings,
+    #[fetch(json)]
+    pub labels: Option<BTreeMap<String, String>>,
+}
+
+#[repository(entity = Profile, backend = Libsql)]
+pub mod profiles {}
+
+#[tokio::test]
+async fn json_fields_round_trip_and_filter_by_path() {
+    let path = std::env::temp_dir().join(format!("storeit_json_{}.sqlite3", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let url = format!("file:{}?mode=rwc", path.display());
+    {
+        #[allow(deprecated)]
+        let db = libsql::Database::open(url.clone()).expect("open db");
+        db.connect()
+            .expect("connect")
+            .execute(
+                "CREATE TABLE profiles (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT NOT NULL, settings TEXT NOT NULL, labels TEXT)",
+                (),
+            )
+            .await
+            .expect("schema");
+    }
+    let repo = profiles::Repository::from_url(&url).await.expect("repo");
+    let ann = repo
+        .insert(&Profile {
+            id: None,
+            name: "ann".into(),
+            settings: Settings {
+                theme: "dark".into(),
+                notifications: true,
+            },
+            labels: Some(BTreeMap::from([("team".to_string(), "core".to_string())])),
+        })
+        .await
+        .expect("insert");
+    let mut bob = repo
+        .insert(&Profile {
+            id: None,
+            name: "bob".into(),
+            settings: Settings {
+                theme: "light".into(),
+                notifications: false,
+            },
+            labels: None,
+        })
+        .await
+        .expect("insert");
+    assert_eq!(
+        repo.find_by_id(&ann.id.unwrap()).await.unwrap(),
+        Some(ann.clone())
+    );
+
+    bob.settings.theme = "dark".into();
+    repo.update(&bob).await.expect("update");
+    let dark = repo
+        .find_where(&Criteria::eq("settings->theme", "dark"))
+        .await
+        .unwrap();
+    assert_eq!(dark, vec![ann.clone(), bob.clone()]);
+    let core = repo
+        .find_where(&Criteria::eq("labels->team", "core"))
+        .await
+        .unwrap();
+    assert_eq!(core, vec![ann]);
+    let unlabelled = repo.find_where(&Criteria::is_null("labels")).await.unwrap();
+    assert_eq!(unlabelled, vec![bob]);
+    // The JSON column of a path must be one of the entity's columns.
+    assert!(repo
+        .find_where(&Criteria::eq("(SELECT 1)->theme", "dark"))
+        .await
+        .is_err());
+    let _ = std::fs::remove_file(&path);
+}
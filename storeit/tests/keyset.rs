@@ -0,0 +1,89 @@
+#![cfg(feature = "libsql-backend")]
+#![allow(unexpected_cfgs)]
+use storeit::*;
+
+#[derive(Entity, Clone, Debug, PartialEq)]
+#[entity(table = "contacts")]
+pub struct Contact {
+    #[fetch(id)]
+    pub id: Option<i64>,
+    pub email: String,
+    pub nickname: Option<String>,
+}
+
+#[repository(entity = Contact, backend = Libsql)]
+pub mod contacts {}
+
+// Follow `next` tokens from the first page to the last, then `prev` tokens back to the start.
+async fn walk(
+    repo: &(impl Repository<Contact> + Sync),
+    order: &[Order],
+) -> (Vec<String>, Vec<String>) {
+    let emails = |items: &[Contact]| items.iter().map(|c| c.email.clone()).collect::<Vec<_>>();
+    let mut forward = Vec::new();
+    let mut page = repo.find_page_after(None, 2, order).await.expect("first");
+    loop {
+        forward.extend(emails(&page.items));
+        let Some(next) = page.next.clone() else {
+            break;
+        };
+        let token: Cursor = next.encode().parse().expect("decode");
+        page = repo
+            .find_page_after(Some(token), 2, order)
+            .await
+            .expect("next");
+    }
+    let mut backward = emails(&page.items);
+    while let Some(prev) = page.prev.clone() {
+        page = repo
+            .find_page_after(Some(prev), 2, order)
+            .await
+            .expect("prev");
+        backward.splice(0..0, emails(&page.items));
+    }
+    (forward, backward)
+}
+
+#[tokio::test]
+async fn nullable_sort_keys_page_through_every_row() {
+    let path = std::env::temp_dir().join(format!("storeit_keyset_{}.sqlite3", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let url = format!("file:{}?mode=rwc", path.display());
+    {
+        #[allow(deprecated)]
+        let db = libsql::Database::open(url.clone()).expect("open db");
+        db.connect()
+            .expect("connect")
+            .execute(
+                "CREATE TABLE contacts (id INTEGER PRIMARY KEY AUTOINCREMENT, email TEXT NOT NULL, nickname TEXT)",
+                (),
+            )
+            .await
+            .expect("schema");
+    }
+    let repo = contacts::Repository::from_url(&url).await.expect("repo");
+    for (email, nickname) in [
+        ("a@x", Some("m")),
+        ("b@x", None),
+        ("c@x", Some("k")),
+        ("d@x", None),
+        ("e@x", Some("m")),
+    ] {
+        repo.insert(&Contact {
+            id: None,
+            email: email.into(),
+            nickname: nickname.map(Into::into),
+        })
+        .await
+        .expect("insert");
+    }
+
+    // NULL sorts first ascending and last descending; the id breaks ties.
+    let (forward, backward) = walk(&repo, &[Order::asc("nickname")]).await;
+    assert_eq!(forward, ["b@x", "d@x", "c@x", "a@x", "e@x"]);
+    assert_eq!(backward, forward);
+    let (forward, backward) = walk(&repo, &[Order::desc("nickname")]).await;
+    assert_eq!(forward, ["a@x", "e@x", "c@x", "b@x", "d@x"]);
+    assert_eq!(backward, forward);
+    let _ = std::fs::remove_file(&path);
+}
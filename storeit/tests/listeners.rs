@@ -0,0 +1,129 @@
+#![cfg(feature = "libsql-backend")]
+#![allow(unexpected_cfgs)]
+// Generated finders take `&String` for `String` finder arguments.
+#![allow(clippy::ptr_arg)]
+use std::sync::{Arc, Mutex};
+use storeit::*;
+
+#[derive(Entity, Clone, Debug, PartialEq)]
+#[entity(table = "members", listener = "NormalizeEmail")]
+pub struct Member {
+    #[fetch(id)]
+    pub id: Option<i64>,
+    pub email: String,
+}
+
+#[derive(Default)]
+pub struct NormalizeEmail;
+
+impl EntityListener<Member> for NormalizeEmail {
+    fn pre_insert(&self, member: &mut Member) -> RepoResult<()> {
+        member.email = member.email.trim().to_lowercase();
+        Ok(())
+    }
+
+    fn pre_update(&self, member: &mut Member) -> RepoResult<()> {
+        self.pre_insert(member)
+    }
+}
+
+#[derive(Clone, Default)]
+struct Events(Arc<Mutex<Vec<String>>>);
+
+impl EntityListener<Member> for Events {
+    fn post_insert(&self, member: &Member) -> RepoResult<()> {
+        self.0
+            .lock()
+            .unwrap()
+            .push(format!("inserted {}", member.email));
+        Ok(())
+    }
+
+    fn post_load(&self, member: &mut Member) -> RepoResult<()> {
+        self.0
+            .lock()
+            .unwrap()
+            .push(format!("loaded {}", member.email));
+        Ok(())
+    }
+
+    fn pre_delete(&self, id: &i64) -> RepoResult<()> {
+        if *id == 1 {
+            return Err(RepoError::backend(std::io::Error::new(
+                std::io::ErrorKind::PermissionDenied,
+                "the first member cannot be deleted",
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[repository(entity = Member, backend = Libsql, finders(find_by_email: String))]
+pub mod members {}
+
+#[tokio::test]
+async fn listeners_run_around_persistence() {
+    let path =
+        std::env::temp_dir().join(format!("storeit_listeners_{}.sqlite3", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let url = format!("file:{}?mode=rwc", path.display());
+    {
+        #[allow(deprecated)]
+        let db = libsql::Database::open(url.clone()).expect("open db");
+        db.connect()
+            .expect("connect")
+            .execute(
+                "CREATE TABLE members (id INTEGER PRIMARY KEY AUTOINCREMENT, email TEXT NOT NULL)",
+                (),
+            )
+            .await
+            .expect("schema");
+    }
+    let events = Events::default();
+    let repo = members::Repository::from_url(&url)
+        .await
+        .expect("repo")
+        .with_listener(events.clone());
+
+    let first = repo
+        .insert(&Member {
+            id: None,
+            email: " Ann@Example.COM ".into(),
+        })
+        .await
+        .expect("insert");
+    assert_eq!(first.email, "ann@example.com");
+    let mut second = repo
+        .insert(&Member {
+            id: None,
+            email: "bob@example.com".into(),
+        })
+        .await
+        .expect("insert");
+
+    second.email = "BOB@example.com".into();
+    assert_eq!(
+        repo.update(&second).await.expect("update").email,
+        "bob@example.com"
+    );
+    let found = repo
+        .find_by_email(&"bob@example.com".to_string())
+        .await
+        .expect("finder");
+    assert_eq!(found.len(), 1);
+
+    assert!(repo.delete_by_id(&first.id.unwrap()).await.is_err());
+    assert!(repo.find_by_id(&first.id.unwrap()).await.unwrap().is_some());
+    assert!(repo.delete_by_id(&second.id.unwrap()).await.unwrap());
+
+    assert_eq!(
+        *events.0.lock().unwrap(),
+        vec![
+            "inserted ann@example.com",
+            "inserted bob@example.com",
+            "loaded bob@example.com",
+            "loaded ann@example.com",
+        ]
+    );
+    let _ = std::fs::remove_file(&path);
+}
@@ -0,0 +1,103 @@
+#![cfg(feature = "libsql-backend")]
+#![allow(unexpected_cfgs)]
+use storeit::*;
+
+#[derive(Entity, Clone, Debug, PartialEq)]
+#[entity(table = "users", soft_delete = "deleted_at")]
+pub struct User {
+    #[fetch(id)]
+    pub id: Option<i64>,
+    pub name: String,
+    #[fetch(column = "email_address")]
+    pub email: String,
+    pub bio: Option<String>,
+    pub deleted_at: Option<std::time::SystemTime>,
+}
+
+#[derive(Projection, Debug, PartialEq)]
+#[projection(of = User)]
+pub struct UserSummary {
+    pub id: i64,
+    pub name: String,
+    #[fetch(skip)]
+    pub label: String,
+}
+
+#[derive(Projection, Debug, PartialEq)]
+#[projection(of = User)]
+pub struct Contact {
+    #[fetch(column = "email_address")]
+    pub address: String,
+}
+
+#[repository(entity = User, backend = Libsql)]
+pub mod users {}
+
+#[tokio::test]
+async fn projections_select_only_their_columns() {
+    assert_eq!(UserSummary::COLUMNS, &["id", "name"]);
+    assert_eq!(Contact::COLUMNS, &["email_address"]);
+
+    let path =
+        std::env::temp_dir().join(format!("storeit_projection_{}.sqlite3", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let url = format!("file:{}?mode=rwc", path.display());
+    {
+        #[allow(deprecated)]
+        let db = libsql::Database::open(url.clone()).expect("open db");
+        db.connect()
+            .expect("connect")
+            .execute(
+                "CREATE TABLE users (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT NOT NULL, email_address TEXT NOT NULL, bio TEXT, deleted_at TEXT)",
+                (),
+            )
+            .await
+            .expect("schema");
+    }
+    let repo = users::Repository::from_url(&url).await.expect("repo");
+    let mut ids = Vec::new();
+    for (name, email) in [("ann", "ann@example.com"), ("bob", "bob@example.com")] {
+        let user = User {
+            id: None,
+            name: name.into(),
+            email: email.into(),
+            bio: Some("a long biography".into()),
+            deleted_at: None,
+        };
+        ids.push(repo.insert(&user).await.expect("insert").id.unwrap());
+    }
+
+    let summary = repo.find_by_id_as::<UserSummary>(&ids[0]).await.unwrap();
+    assert_eq!(
+        summary,
+        Some(UserSummary {
+            id: ids[0],
+            name: "ann".into(),
+            label: String::new(),
+        })
+    );
+    let contacts = repo
+        .find_by_field_as::<Contact>("name", ParamValue::String("bob".into()))
+        .await
+        .unwrap();
+    assert_eq!(
+        contacts,
+        vec![Contact {
+            address: "bob@example.com".into()
+        }]
+    );
+
+    // Projections read through the entity's soft-delete filter.
+    assert!(repo.delete_by_id(&ids[1]).await.unwrap());
+    assert!(repo
+        .find_by_id_as::<UserSummary>(&ids[1])
+        .await
+        .unwrap()
+        .is_none());
+    assert!(repo
+        .find_by_field_as::<Contact>("name", ParamValue::String("bob".into()))
+        .await
+        .unwrap()
+        .is_empty());
+    let _ = std::fs::remove_file(&path);
+}
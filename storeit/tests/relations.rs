@@ -0,0 +1,97 @@
+#![cfg(feature = "libsql-backend")]
+#![allow(unexpected_cfgs)]
+use storeit::*;
+
+#[derive(Entity, Clone, Debug, PartialEq)]
+#[entity(table = "customers")]
+pub struct Customer {
+    #[fetch(id)]
+    pub id: Option<i64>,
+    pub name: String,
+}
+
+#[derive(Entity, Clone, Debug, PartialEq)]
+#[entity(table = "orders")]
+pub struct Order {
+    #[fetch(id)]
+    pub id: Option<i64>,
+    #[fetch(belongs_to = "Customer")]
+    pub customer_id: Option<i64>,
+    pub total: i64,
+}
+
+#[repository(entity = Customer, backend = Libsql)]
+pub mod customers {}
+
+#[repository(entity = Order, backend = Libsql)]
+pub mod orders {}
+
+#[tokio::test]
+async fn parents_and_children_are_loaded_through_foreign_keys() {
+    let path =
+        std::env::temp_dir().join(format!("storeit_relations_{}.sqlite3", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let url = format!("file:{}?mode=rwc", path.display());
+    {
+        #[allow(deprecated)]
+        let db = libsql::Database::open(url.clone()).expect("open db");
+        let conn = db.connect().expect("connect");
+        conn.execute(
+            "CREATE TABLE customers (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT NOT NULL)",
+            (),
+        )
+        .await
+        .expect("schema");
+        conn.execute(
+            "CREATE TABLE orders (id INTEGER PRIMARY KEY AUTOINCREMENT, customer_id INTEGER, total INTEGER NOT NULL)",
+            (),
+        )
+        .await
+        .expect("schema");
+    }
+    let customers = customers::Repository::from_url(&url).await.expect("repo");
+    let orders = orders::Repository::from_url(&url).await.expect("repo");
+
+    assert_eq!(<Order as BelongsTo<Customer>>::FOREIGN_KEY, "customer_id");
+
+    let mut people = Vec::new();
+    for name in ["ann", "bob", "cy"] {
+        let customer = Customer {
+            id: None,
+            name: name.into(),
+        };
+        people.push(customers.insert(&customer).await.expect("insert"));
+    }
+    let (ann, bob) = (people[0].id.unwrap(), people[1].id.unwrap());
+    for (customer_id, total) in [
+        (Some(ann), 10),
+        (Some(bob), 20),
+        (Some(ann), 30),
+        (None, 40),
+    ] {
+        let order = Order {
+            id: None,
+            customer_id,
+            total,
+        };
+        orders.insert(&order).await.expect("insert");
+    }
+
+    let first = orders.find_by_id(&1).await.unwrap().unwrap();
+    assert_eq!(
+        customers.load_parent(&first).await.unwrap(),
+        Some(people[0].clone())
+    );
+    let walk_in = orders.find_by_id(&4).await.unwrap().unwrap();
+    assert_eq!(customers.load_parent(&walk_in).await.unwrap(), None);
+
+    let of_ann = orders.load_children(&people[0]).await.unwrap();
+    assert_eq!(of_ann.iter().map(|o| o.total).collect::<Vec<_>>(), [10, 30]);
+
+    let grouped = orders.load_children_for(&people).await.unwrap();
+    assert_eq!(grouped.len(), 3);
+    assert_eq!(grouped[&ann].len(), 2);
+    assert_eq!(grouped[&bob][0].total, 20);
+    assert!(grouped[&people[2].id.unwrap()].is_empty());
+    let _ = std::fs::remove_file(&path);
+}
@@ -0,0 +1,74 @@
+#![cfg(feature = "libsql-backend")]
+#![allow(unexpected_cfgs)]
+use storeit::schema::SchemaIssue;
+use storeit::*;
+
+#[derive(Entity, Clone, Debug, PartialEq)]
+#[entity(table = "accounts")]
+pub struct Account {
+    #[fetch(id)]
+    pub id: Option<i64>,
+    pub name: String,
+    #[fetch(unique)]
+    pub email: String,
+    pub nickname: Option<String>,
+}
+
+#[repository(entity = Account, backend = Libsql)]
+pub mod accounts {}
+
+async fn open(name: &str, ddl: &str) -> String {
+    let path = std::env::temp_dir().join(format!(
+        "storeit_schema_{name}_{}.sqlite3",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_file(&path);
+    let url = format!("file:{}?mode=rwc", path.display());
+    #[allow(deprecated)]
+    let db = libsql::Database::open(url.clone()).expect("open db");
+    db.connect()
+        .expect("connect")
+        .execute_batch(ddl)
+        .await
+        .expect("schema");
+    url
+}
+
+#[tokio::test]
+async fn matching_table_validates() {
+    let url = open(
+        "matching",
+        "CREATE TABLE accounts (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT NOT NULL, \
+         email TEXT NOT NULL UNIQUE, nickname TEXT, created_by TEXT DEFAULT 'system')",
+    )
+    .await;
+    let repo = accounts::Repository::from_url(&url).await.expect("repo");
+    let report = repo.validate_schema().await.expect("introspect");
+    assert!(report.is_ok(), "{report}");
+    assert!(report.into_result().is_ok());
+}
+
+#[tokio::test]
+async fn drift_is_reported() {
+    let url = open(
+        "drift",
+        "CREATE TABLE accounts (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT, \
+         email_address TEXT NOT NULL, nickname TEXT)",
+    )
+    .await;
+    let repo = accounts::Repository::from_url(&url).await.expect("repo");
+    let report = repo.validate_schema().await.expect("introspect");
+    assert_eq!(
+        report.issues,
+        vec![
+            SchemaIssue::Nullability {
+                column: "name".into(),
+                expected_nullable: false,
+            },
+            SchemaIssue::MissingColumn("email".into()),
+            SchemaIssue::UnmappedRequiredColumn("email_address".into()),
+        ]
+    );
+    let err = report.into_result().unwrap_err();
+    assert!(matches!(err, RepoError::Mapping { .. }), "{err:?}");
+}
@@ -0,0 +1,116 @@
+#![cfg(feature = "libsql-backend")]
+#![allow(unexpected_cfgs)]
+// Generated finders take `&String` for `String` finder arguments.
+#![allow(clippy::ptr_arg)]
+use storeit::*;
+
+#[derive(Entity, Clone, Debug, PartialEq)]
+#[entity(table = "customers", soft_delete = "deleted_at")]
+pub struct Customer {
+    #[fetch(id)]
+    pub id: Option<i64>,
+    pub email: String,
+    pub deleted_at: Option<std::time::SystemTime>,
+}
+
+#[repository(entity = Customer, backend = Libsql, finders(find_by_email: String))]
+pub mod customers {}
+
+#[tokio::test]
+async fn soft_deleted_rows_are_hidden_until_restored_or_purged() {
+    let path = std::env::temp_dir().join(format!(
+        "storeit_soft_delete_{}.sqlite3",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_file(&path);
+    let url = format!("file:{}?mode=rwc", path.display());
+    {
+        #[allow(deprecated)]
+        let db = libsql::Database::open(url.clone()).expect("open db");
+        db.connect()
+            .expect("connect")
+            .execute(
+                "CREATE TABLE customers (id INTEGER PRIMARY KEY AUTOINCREMENT, email TEXT NOT NULL, deleted_at TEXT)",
+                (),
+            )
+            .await
+            .expect("schema");
+    }
+    let repo = customers::Repository::from_url(&url).await.expect("repo");
+    assert_eq!(Customer::INSERT_COLUMNS, &["email"]);
+    assert_eq!(Customer::UPDATE_COLUMNS, &["email"]);
+
+    let kept = repo
+        .insert(&Customer {
+            id: None,
+            email: "kept@x".into(),
+            deleted_at: None,
+        })
+        .await
+        .expect("insert");
+    let gone = repo
+        .insert(&Customer {
+            id: None,
+            email: "gone@x".into(),
+            deleted_at: None,
+        })
+        .await
+        .expect("insert");
+    let gone_id = gone.id.unwrap();
+
+    assert!(repo.delete_by_id(&gone_id).await.expect("delete"));
+    assert!(!repo.delete_by_id(&gone_id).await.expect("delete twice"));
+    assert!(repo.find_by_id(&gone_id).await.expect("find").is_none());
+    assert!(repo
+        .find_by_email(&"gone@x".to_string())
+        .await
+        .expect("finder")
+        .is_empty());
+    assert_eq!(repo.count_where(&Criteria::all()).await.expect("count"), 1);
+    let deleted = repo
+        .find_with_deleted(&gone_id)
+        .await
+        .expect("find with deleted")
+        .expect("row kept");
+    assert!(deleted.deleted_at.is_some());
+    // Updates skip soft-deleted rows.
+    repo.update(&Customer {
+        email: "changed@x".into(),
+        ..gone.clone()
+    })
+    .await
+    .expect("update");
+    let deleted = repo
+        .find_with_deleted(&gone_id)
+        .await
+        .expect("find with deleted")
+        .expect("row kept");
+    assert_eq!(deleted.email, "gone@x");
+
+    assert!(repo.restore_by_id(&gone_id).await.expect("restore"));
+    assert!(repo.find_by_id(&gone_id).await.expect("find").is_some());
+
+    assert_eq!(
+        repo.delete_where(&Criteria::like("email", "%@x"))
+            .await
+            .expect("delete where"),
+        2
+    );
+    assert!(repo
+        .find_by_id(&kept.id.unwrap())
+        .await
+        .expect("find")
+        .is_none());
+    assert!(repo.purge_by_id(&gone_id).await.expect("purge"));
+    assert!(repo
+        .find_with_deleted(&gone_id)
+        .await
+        .expect("find with deleted")
+        .is_none());
+    assert!(repo
+        .find_with_deleted(&kept.id.unwrap())
+        .await
+        .expect("find with deleted")
+        .is_some());
+    let _ = std::fs::remove_file(&path);
+}
@@ -0,0 +1,107 @@
+#![cfg(feature = "libsql-backend")]
+#![allow(unexpected_cfgs)]
+// Generated finders take `&String` for `String` finder arguments.
+#![allow(clippy::ptr_arg)]
+use storeit::*;
+
+#[derive(Entity, Clone, Debug, PartialEq)]
+#[entity(table = "notes", tenant = "tenant_id")]
+pub struct Note {
+    #[fetch(id)]
+    pub id: Option<i64>,
+    pub tenant_id: String,
+    pub body: String,
+}
+
+#[repository(entity = Note, backend = Libsql, finders(find_by_body: String))]
+pub mod notes {}
+
+fn note(body: &str) -> Note {
+    Note {
+        id: None,
+        tenant_id: String::new(),
+        body: body.into(),
+    }
+}
+
+#[tokio::test]
+async fn tenants_only_see_their_own_rows() {
+    let path = std::env::temp_dir().join(format!("storeit_tenant_{}.sqlite3", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let url = format!("file:{}?mode=rwc", path.display());
+    {
+        #[allow(deprecated)]
+        let db = libsql::Database::open(url.clone()).expect("open db");
+        db.connect()
+            .expect("connect")
+            .execute(
+                "CREATE TABLE notes (id INTEGER PRIMARY KEY AUTOINCREMENT, tenant_id TEXT NOT NULL, body TEXT NOT NULL)",
+                (),
+            )
+            .await
+            .expect("schema");
+    }
+    // Built under bypass, the repository still scopes every later call.
+    let repo = tenant::bypass(notes::Repository::from_url(&url))
+        .await
+        .expect("repo");
+
+    // Without a scope every operation is refused.
+    assert!(matches!(
+        repo.insert(&note("orphan")).await,
+        Err(RepoError::MissingTenant { table: "notes" })
+    ));
+    assert!(matches!(
+        repo.count_where(&Criteria::all()).await,
+        Err(RepoError::MissingTenant { .. })
+    ));
+    // A NULL tenant is refused too, instead of matching every tenant.
+    assert!(matches!(
+        tenant::scope(None::<String>, repo.find_where(&Criteria::all())).await,
+        Err(RepoError::MissingTenant { table: "notes" })
+    ));
+
+    let acme = tenant::scope("acme", async {
+        let a = repo.insert(&note("shared")).await.expect("insert");
+        assert_eq!(a.tenant_id, "acme");
+        repo.insert(&note("acme only")).await.expect("insert");
+        a
+    })
+    .await;
+    let globex = tenant::scope("globex", async {
+        repo.insert(&note("shared")).await.expect("insert")
+    })
+    .await;
+
+    tenant::scope("globex", async {
+        assert!(repo.find_by_id(&acme.id.unwrap()).await.unwrap().is_none());
+        let shared = repo.find_by_body(&"shared".to_string()).await.unwrap();
+        assert_eq!(shared, vec![globex.clone()]);
+        assert_eq!(repo.count_where(&Criteria::all()).await.unwrap(), 1);
+        let page = repo.find_page(&Pageable::new(0, 10)).await.unwrap();
+        assert_eq!(page.total, 1);
+
+        // Updates and deletes cannot reach another tenant's rows either.
+        let mut stolen = acme.clone();
+        stolen.body = "hijacked".into();
+        assert!(matches!(
+            repo.update(&stolen).await,
+            Err(RepoError::NotFound)
+        ));
+        assert!(!repo.delete_by_id(&acme.id.unwrap()).await.unwrap());
+        assert_eq!(repo.delete_where(&Criteria::all()).await.unwrap(), 1);
+    })
+    .await;
+
+    tenant::bypass(async {
+        let all = repo.find_where(&Criteria::all()).await.unwrap();
+        assert_eq!(all.len(), 2);
+        assert!(all.iter().all(|n| n.tenant_id == "acme"));
+        assert!(all.iter().all(|n| n.body != "hijacked"));
+        // Cached by-id statements are rendered without the tenant condition as well.
+        let found = repo.find_by_id(&acme.id.unwrap()).await.unwrap();
+        assert_eq!(found.map(|n| n.tenant_id), Some("acme".to_string()));
+    })
+    .await;
+    let _ = std::fs::remove_file(&path);
+}
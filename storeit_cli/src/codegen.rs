@@ -0,0 +1,494 @@
+//! Rust source generation from a read catalog.
+//!
+//! Each table with a primary key becomes a `#[derive(Entity)]` struct plus a `#[repository]`
+//! module with finders for its indexed columns. SQL types map onto the field types the derive
+//! supports natively; anything else falls back to `String` with a comment naming the SQL type.
+
+use crate::Table;
+use std::collections::HashSet;
+use std::fmt::Write as _;
+use storeit_sql_builder::Dialect;
+
+/// Generate the source for `tables` read from a `dialect` database.
+pub fn generate(dialect: Dialect, tables: &[Table]) -> String {
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "// Generated by `storeit-cli introspect` from a {} database.",
+        dialect_name(dialect)
+    );
+    out.push_str("// Review names and types before use: comments mark guessed or widened types.\n");
+    out.push_str("use storeit::{repository, Entity};\n");
+
+    let mut structs = HashSet::new();
+    for table in tables {
+        out.push('\n');
+        if !table.columns.iter().any(|c| c.primary_key) {
+            let _ = writeln!(
+                out,
+                "// Skipped table `{}`: it has no primary key, which #[derive(Entity)] requires.",
+                table.name
+            );
+            continue;
+        }
+        let mut name = entity_name(&table.name);
+        if !structs.insert(name.clone()) {
+            name = pascal_case(&table.name);
+            structs.insert(name.clone());
+        }
+        write_table(&mut out, dialect, table, &name);
+    }
+    out
+}
+
+fn write_table(out: &mut String, dialect: Dialect, table: &Table, name: &str) {
+    let keys = table.columns.iter().filter(|c| c.primary_key).count();
+    let mut finders = Vec::new();
+
+    let _ = writeln!(out, "/// A row of the `{}` table.", table.name);
+    out.push_str("#[derive(Entity, Clone, Debug)]\n");
+    let _ = writeln!(out, "#[entity(table = \"{}\")]", escape(&table.name));
+    let _ = writeln!(out, "pub struct {} {{", name);
+    for column in &table.columns {
+        let mapped = rust_type(dialect, &column.sql_type);
+        let field = field_name(&column.name);
+        let mut attrs = Vec::new();
+        let ty = if column.primary_key {
+            attrs.push("id");
+            if keys > 1 {
+                mapped.ty.to_string()
+            } else if column.generated {
+                format!("Option<{}>", mapped.ty)
+            } else {
+                attrs.push("generated = false");
+                mapped.ty.to_string()
+            }
+        } else {
+            if let Some(unique) = single_column_index(table, &column.name) {
+                attrs.push(if unique { "unique" } else { "index" });
+            }
+            if leads_index(table, &column.name) && field == column.name && mapped.finder {
+                finders.push(format!("find_by_{}: {}", field, mapped.ty));
+            }
+            if column.nullable {
+                format!("Option<{}>", mapped.ty)
+            } else {
+                mapped.ty.to_string()
+            }
+        };
+        let column_attr;
+        if field != column.name {
+            column_attr = format!("column = \"{}\"", escape(&column.name));
+            attrs.insert(0, &column_attr);
+        }
+
+        if let Some(note) = mapped.note {
+            let _ = writeln!(out, "    // SQL type `{}`: {}", column.sql_type, note);
+        }
+        if !attrs.is_empty() {
+            let _ = writeln!(out, "    #[fetch({})]", attrs.join(", "));
+        }
+        let _ = writeln!(out, "    pub {}: {},", field, ty);
+    }
+    out.push_str("}\n\n");
+
+    let _ = write!(
+        out,
+        "#[repository(entity = {}, backend = {}",
+        name,
+        backend_name(dialect)
+    );
+    if !finders.is_empty() {
+        let _ = write!(out, ", finders({})", finders.join(", "));
+    }
+    out.push_str(")]\n");
+    let _ = writeln!(out, "pub mod {} {{}}", module_name(&table.name));
+}
+
+// `Some(unique)` when a non-key index covers exactly this column.
+fn single_column_index(table: &Table, column: &str) -> Option<bool> {
+    let mut found = None;
+    for index in &table.indexes {
+        if index.columns.len() == 1 && index.columns[0] == column {
+            found = Some(found.unwrap_or(false) || index.unique);
+        }
+    }
+    found
+}
+
+fn leads_index(table: &Table, column: &str) -> bool {
+    table
+        .indexes
+        .iter()
+        .any(|i| i.columns.first().is_some_and(|c| c == column))
+}
+
+/// The Rust field type for a SQL type.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RustType {
+    pub ty: &'static str,
+    /// Why the mapping is approximate, if it is.
+    pub note: Option<&'static str>,
+    /// Whether `#[repository(finders(...))]` accepts the type.
+    pub finder: bool,
+}
+
+impl RustType {
+    fn exact(ty: &'static str) -> Self {
+        RustType {
+            ty,
+            note: None,
+            finder: matches!(ty, "String" | "i32" | "i64" | "f64" | "bool"),
+        }
+    }
+
+    fn noted(ty: &'static str, note: &'static str) -> Self {
+        RustType {
+            note: Some(note),
+            ..RustType::exact(ty)
+        }
+    }
+}
+
+/// Map a SQL type, as the `dialect` catalog reports it, onto a field type.
+pub fn rust_type(dialect: Dialect, sql_type: &str) -> RustType {
+    let t = sql_type.trim().to_ascii_lowercase();
+    // Strip a length/precision suffix: `varchar(255)`, `numeric(10, 2)`, `int(11) unsigned`.
+    let base = t.split('(').next().unwrap_or("").trim();
+    let base = base
+        .trim_end_matches(" zerofill")
+        .trim_end_matches(" unsigned");
+    let unsigned = t.contains("unsigned");
+
+    if dialect == Dialect::MySql && t.starts_with("tinyint(1)") {
+        return RustType::exact("bool");
+    }
+    if base == "bool" || base == "boolean" {
+        return RustType::exact("bool");
+    }
+    if dialect == Dialect::Sqlite {
+        return sqlite_type(base);
+    }
+    match base {
+        "bigint" | "int8" | "bigserial" | "serial8" if unsigned => {
+            RustType::noted("i64", "unsigned values above i64::MAX do not fit")
+        }
+        "bigint" | "int8" | "bigserial" | "serial8" => RustType::exact("i64"),
+        "int" | "integer" | "mediumint" | "int4" | "serial" | "serial4" if unsigned => {
+            RustType::noted("i64", "widened to hold unsigned values")
+        }
+        "int" | "integer" | "mediumint" | "int4" | "serial" | "serial4" => RustType::exact("i32"),
+        "smallint" | "int2" | "smallserial" | "tinyint" | "year" => RustType::noted(
+            "i32",
+            "widened; the column may need to be altered to an int",
+        ),
+        "double" | "double precision" | "float8" => RustType::exact("f64"),
+        "real" | "float" | "float4" => RustType::noted(
+            "f64",
+            "widened; the column may need to be altered to a double",
+        ),
+        "numeric" | "decimal" => RustType::exact("rust_decimal::Decimal"),
+        "text" | "varchar" | "character varying" | "char" | "character" | "bpchar" | "tinytext"
+        | "mediumtext" | "longtext" | "citext" | "name" => RustType::exact("String"),
+        "enum" | "set" => RustType::noted("String", "a #[derive(DbEnum)] enum may fit better"),
+        "bytea" | "blob" | "tinyblob" | "mediumblob" | "longblob" | "binary" | "varbinary" => {
+            RustType::exact("Vec<u8>")
+        }
+        "uuid" => RustType::exact("uuid::Uuid"),
+        "timestamp with time zone" | "timestamptz" => {
+            RustType::exact("chrono::DateTime<chrono::Utc>")
+        }
+        "timestamp" | "timestamp without time zone" | "datetime" => {
+            RustType::exact("chrono::NaiveDateTime")
+        }
+        "date" => RustType::exact("chrono::NaiveDate"),
+        "time" | "time without time zone" => RustType::exact("chrono::NaiveTime"),
+        "json" | "jsonb" => RustType::exact("serde_json::Value"),
+        _ => RustType::noted("String", "no direct mapping"),
+    }
+}
+
+// SQLite columns follow type affinity: the declared name only hints at the storage class.
+fn sqlite_type(base: &str) -> RustType {
+    match base {
+        "date" => RustType::exact("chrono::NaiveDate"),
+        "datetime" | "timestamp" => RustType::exact("chrono::NaiveDateTime"),
+        "time" => RustType::exact("chrono::NaiveTime"),
+        "decimal" | "numeric" => RustType::exact("rust_decimal::Decimal"),
+        "uuid" => RustType::exact("uuid::Uuid"),
+        "json" | "jsonb" => RustType::exact("serde_json::Value"),
+        _ if base.contains("int") => RustType::exact("i64"),
+        _ if base.contains("char") || base.contains("clob") || base.contains("text") => {
+            RustType::exact("String")
+        }
+        "" => RustType::noted("Vec<u8>", "the column has no declared type"),
+        _ if base.contains("blob") => RustType::exact("Vec<u8>"),
+        _ if base.contains("real") || base.contains("floa") || base.contains("doub") => {
+            RustType::exact("f64")
+        }
+        _ => RustType::noted("String", "no direct mapping"),
+    }
+}
+
+fn dialect_name(dialect: Dialect) -> &'static str {
+    match dialect {
+        Dialect::Postgres => "Postgres",
+        Dialect::MySql => "MySQL",
+        Dialect::Sqlite => "SQLite",
+    }
+}
+
+fn backend_name(dialect: Dialect) -> &'static str {
+    match dialect {
+        Dialect::Postgres => "TokioPostgres",
+        Dialect::MySql => "MysqlAsync",
+        Dialect::Sqlite => "Libsql",
+    }
+}
+
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "box", "break", "const", "continue", "crate", "do", "dyn", "else",
+    "enum", "extern", "false", "final", "fn", "for", "if", "impl", "in", "let", "loop", "macro",
+    "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return", "self", "static",
+    "struct", "super", "trait", "true", "try", "type", "typeof", "unsafe", "unsized", "use",
+    "virtual", "where", "while", "yield",
+];
+
+/// The snake_case field name for a column: `createdAt` becomes `created_at`, and keywords
+/// such as `type` get a trailing underscore.
+pub fn field_name(column: &str) -> String {
+    let mut name = String::new();
+    let mut prev_lower = false;
+    for ch in column.chars() {
+        if ch.is_ascii_uppercase() {
+            if prev_lower {
+                name.push('_');
+            }
+            name.push(ch.to_ascii_lowercase());
+            prev_lower = false;
+        } else if ch.is_ascii_alphanumeric() {
+            name.push(ch);
+            prev_lower = true;
+        } else {
+            if !name.ends_with('_') {
+                name.push('_');
+            }
+            prev_lower = false;
+        }
+    }
+    let mut name = name.trim_matches('_').to_string();
+    if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+        name.insert(0, '_');
+    }
+    if KEYWORDS.contains(&name.as_str()) {
+        name.push('_');
+    }
+    name
+}
+
+/// The repository module name for a table.
+pub fn module_name(table: &str) -> String {
+    field_name(table)
+}
+
+/// The struct name for a table: PascalCase, naively singularized (`order_items` becomes
+/// `OrderItem`, `categories` becomes `Category`).
+pub fn entity_name(table: &str) -> String {
+    pascal_case(&singularize(&field_name(table)))
+}
+
+fn singularize(name: &str) -> String {
+    if let Some(stem) = name.strip_suffix("ies") {
+        format!("{}y", stem)
+    } else if let Some(stem) = name.strip_suffix("sses") {
+        format!("{}ss", stem)
+    } else if name.ends_with("ss") || name.ends_with("us") || name.len() <= 1 {
+        name.to_string()
+    } else {
+        name.strip_suffix('s').unwrap_or(name).to_string()
+    }
+}
+
+fn pascal_case(name: &str) -> String {
+    let name = field_name(name);
+    let mut out = String::new();
+    for part in name.split('_').filter(|p| !p.is_empty()) {
+        let mut chars = part.chars();
+        if let Some(first) = chars.next() {
+            out.push(first.to_ascii_uppercase());
+            out.extend(chars);
+        }
+    }
+    if out.is_empty() || out.starts_with(|c: char| c.is_ascii_digit()) {
+        out.insert(0, 'T');
+    }
+    out
+}
+
+// Escape a name for a string literal.
+fn escape(name: &str) -> String {
+    name.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Column;
+    use storeit_core::schema::LiveIndex;
+
+    fn column(name: &str, sql_type: &str, nullable: bool) -> Column {
+        Column {
+            name: name.into(),
+            sql_type: sql_type.into(),
+            nullable,
+            primary_key: false,
+            generated: false,
+        }
+    }
+
+    fn key(name: &str, sql_type: &str, generated: bool) -> Column {
+        Column {
+            primary_key: true,
+            generated,
+            ..column(name, sql_type, false)
+        }
+    }
+
+    fn index(name: &str, columns: &[&str], unique: bool) -> LiveIndex {
+        LiveIndex {
+            name: name.into(),
+            columns: columns.iter().map(|c| c.to_string()).collect(),
+            unique,
+        }
+    }
+
+    #[test]
+    fn names_follow_rust_conventions() {
+        assert_eq!(entity_name("users"), "User");
+        assert_eq!(entity_name("order_items"), "OrderItem");
+        assert_eq!(entity_name("categories"), "Category");
+        assert_eq!(entity_name("addresses"), "Address");
+        assert_eq!(entity_name("status"), "Status");
+        assert_eq!(entity_name("BlogPosts"), "BlogPost");
+        assert_eq!(field_name("createdAt"), "created_at");
+        assert_eq!(field_name("type"), "type_");
+        assert_eq!(field_name("first name"), "first_name");
+        assert_eq!(field_name("2fa"), "_2fa");
+    }
+
+    #[test]
+    fn types_map_per_dialect() {
+        let ty = |d, s| rust_type(d, s).ty;
+        assert_eq!(ty(Dialect::Postgres, "integer"), "i32");
+        assert_eq!(ty(Dialect::Postgres, "bigint"), "i64");
+        assert_eq!(ty(Dialect::Postgres, "character varying"), "String");
+        assert_eq!(
+            ty(Dialect::Postgres, "timestamp with time zone"),
+            "chrono::DateTime<chrono::Utc>"
+        );
+        assert_eq!(ty(Dialect::Postgres, "jsonb"), "serde_json::Value");
+        assert_eq!(ty(Dialect::Postgres, "uuid"), "uuid::Uuid");
+        assert_eq!(ty(Dialect::MySql, "tinyint(1)"), "bool");
+        assert_eq!(ty(Dialect::MySql, "int(11)"), "i32");
+        assert_eq!(ty(Dialect::MySql, "int unsigned"), "i64");
+        assert_eq!(ty(Dialect::MySql, "bigint unsigned"), "i64");
+        assert!(rust_type(Dialect::MySql, "bigint unsigned").note.is_some());
+        assert_eq!(ty(Dialect::MySql, "decimal(10,2)"), "rust_decimal::Decimal");
+        assert_eq!(ty(Dialect::MySql, "varbinary(16)"), "Vec<u8>");
+        assert_eq!(ty(Dialect::Sqlite, "INTEGER"), "i64");
+        assert_eq!(ty(Dialect::Sqlite, "VARCHAR(40)"), "String");
+        assert_eq!(ty(Dialect::Sqlite, "DOUBLE"), "f64");
+        assert_eq!(ty(Dialect::Sqlite, "BOOLEAN"), "bool");
+        let unknown = rust_type(Dialect::Postgres, "tsvector");
+        assert_eq!(unknown.ty, "String");
+        assert!(unknown.note.is_some());
+    }
+
+    #[test]
+    fn generates_entity_and_repository() {
+        let table = Table {
+            name: "users".into(),
+            columns: vec![
+                key("id", "INTEGER", true),
+                column("email", "TEXT", false),
+                column("nickname", "TEXT", true),
+                column("type", "TEXT", false),
+                column("age", "INTEGER", true),
+            ],
+            indexes: vec![
+                index("idx_users_email", &["email"], true),
+                index("idx_users_age_nick", &["age", "nickname"], false),
+            ],
+        };
+        let src = generate(Dialect::Sqlite, &[table]);
+        let expected = "\
+/// A row of the `users` table.
+#[derive(Entity, Clone, Debug)]
+#[entity(table = \"users\")]
+pub struct User {
+    #[fetch(id)]
+    pub id: Option<i64>,
+    #[fetch(unique)]
+    pub email: String,
+    pub nickname: Option<String>,
+    #[fetch(column = \"type\")]
+    pub type_: String,
+    pub age: Option<i64>,
+}
+
+#[repository(entity = User, backend = Libsql, finders(find_by_email: String, find_by_age: i64))]
+pub mod users {}
+";
+        assert!(src.starts_with("// Generated by `storeit-cli introspect` from a SQLite database."));
+        assert!(src.contains("use storeit::{repository, Entity};\n"));
+        assert!(src.ends_with(expected), "{}", src);
+    }
+
+    #[test]
+    fn keys_without_generation_or_composite_are_required() {
+        let natural = Table {
+            name: "countries".into(),
+            columns: vec![
+                key("code", "character", false),
+                column("name", "text", false),
+            ],
+            indexes: vec![index("countries_pkey", &["code"], true)],
+        };
+        let composite = Table {
+            name: "memberships".into(),
+            columns: vec![
+                key("user_id", "bigint", false),
+                key("group_id", "bigint", false),
+                column("role", "text", false),
+            ],
+            indexes: vec![index("memberships_pkey", &["user_id", "group_id"], true)],
+        };
+        let src = generate(Dialect::Postgres, &[natural, composite]);
+        assert!(src.contains("    #[fetch(id, generated = false)]\n    pub code: String,\n"));
+        assert!(src.contains(
+            "    #[fetch(id)]\n    pub user_id: i64,\n    #[fetch(id)]\n    pub group_id: i64,\n"
+        ));
+        assert!(src.contains(
+            "#[repository(entity = Country, backend = TokioPostgres)]\npub mod countries {}\n"
+        ));
+    }
+
+    #[test]
+    fn tables_without_keys_are_skipped_and_clashes_renamed() {
+        let log = Table {
+            name: "log".into(),
+            columns: vec![column("line", "text", false)],
+            indexes: vec![],
+        };
+        let user = |name: &str| Table {
+            name: name.into(),
+            columns: vec![key("id", "int(11)", true)],
+            indexes: vec![],
+        };
+        let src = generate(Dialect::MySql, &[log, user("user"), user("users")]);
+        assert!(src.contains("// Skipped table `log`: it has no primary key"));
+        assert!(src.contains("pub struct User {"));
+        assert!(src.contains("pub struct Users {"));
+        assert!(src.contains("#[repository(entity = Users, backend = MysqlAsync)]"));
+    }
+}
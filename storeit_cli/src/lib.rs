@@ -0,0 +1,109 @@
+#![forbid(unsafe_code)]
+//! storeit_cli: command-line tools for storeit.
+//!
+//! `storeit-cli introspect --url <url>` reads the table catalog of an existing SQLite/libsql,
+//! Postgres or MySQL database and prints Rust source for `#[derive(Entity)]` structs and
+//! `#[repository]` modules, as a starting point for mapping a legacy schema.
+//!
+//! The library half holds the catalog model ([`Table`], [`Column`]), one catalog reader per
+//! database behind its backend feature, and the pure code generator in [`codegen`].
+
+use storeit_core::schema::LiveIndex;
+use storeit_core::{RepoError, RepoResult};
+use storeit_sql_builder::Dialect;
+
+pub mod codegen;
+
+// Catalog readers, one per database
+#[cfg(feature = "mysql-async")]
+pub mod mysql;
+#[cfg(feature = "postgres-backend")]
+pub mod postgres;
+#[cfg(feature = "libsql-backend")]
+pub mod sqlite;
+
+/// A table as read from the database catalog.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Table {
+    pub name: String,
+    /// Columns in declaration order.
+    pub columns: Vec<Column>,
+    /// Indexes, including those backing primary key and unique constraints.
+    pub indexes: Vec<LiveIndex>,
+}
+
+/// A column as read from the database catalog.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Column {
+    pub name: String,
+    /// The declared type as the database reports it, e.g. `character varying` or `tinyint(1)`.
+    pub sql_type: String,
+    pub nullable: bool,
+    pub primary_key: bool,
+    /// Whether the database assigns the value: an identity, serial, AUTO_INCREMENT or
+    /// SQLite rowid alias column.
+    pub generated: bool,
+}
+
+/// Work out the database a connection URL points at.
+///
+/// `postgres://`/`postgresql://` and `mysql://` URLs select those databases; anything else is
+/// taken as a SQLite/libsql file path (optionally prefixed with `sqlite:` or `file:`).
+pub fn dialect_for_url(url: &str) -> Dialect {
+    if url.starts_with("postgres://") || url.starts_with("postgresql://") {
+        Dialect::Postgres
+    } else if url.starts_with("mysql://") {
+        Dialect::MySql
+    } else {
+        Dialect::Sqlite
+    }
+}
+
+/// Read the tables of the database at `url`, sorted by name.
+///
+/// `only` restricts the result to the named tables; an empty slice reads every table.
+/// Reading a database whose backend feature is disabled is an error.
+pub async fn introspect(url: &str, only: &[String]) -> RepoResult<Vec<Table>> {
+    let mut tables = read_tables(url).await?;
+    if !only.is_empty() {
+        if let Some(missing) = only.iter().find(|t| !tables.iter().any(|x| &x.name == *t)) {
+            return Err(RepoError::backend(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("table `{}` not found", missing),
+            )));
+        }
+        tables.retain(|t| only.contains(&t.name));
+    }
+    tables.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(tables)
+}
+
+async fn read_tables(url: &str) -> RepoResult<Vec<Table>> {
+    match dialect_for_url(url) {
+        #[cfg(feature = "postgres-backend")]
+        Dialect::Postgres => postgres::read_tables(url).await,
+        #[cfg(feature = "mysql-async")]
+        Dialect::MySql => mysql::read_tables(url).await,
+        #[cfg(feature = "libsql-backend")]
+        Dialect::Sqlite => sqlite::read_tables(url).await,
+        #[allow(unreachable_patterns)]
+        dialect => Err(RepoError::backend(std::io::Error::new(
+            std::io::ErrorKind::Unsupported,
+            format!("storeit-cli was built without support for {:?}", dialect),
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dialect_follows_url_scheme() {
+        assert_eq!(dialect_for_url("postgres://u:p@h/db"), Dialect::Postgres);
+        assert_eq!(dialect_for_url("postgresql://h/db"), Dialect::Postgres);
+        assert_eq!(dialect_for_url("mysql://u@h/db"), Dialect::MySql);
+        assert_eq!(dialect_for_url("app.db"), Dialect::Sqlite);
+        assert_eq!(dialect_for_url("sqlite:app.db"), Dialect::Sqlite);
+    }
+}
@@ -0,0 +1,99 @@
+#![forbid(unsafe_code)]
+//! The `storeit-cli` binary. Run `storeit-cli --help` for usage.
+
+use std::process::ExitCode;
+
+const USAGE: &str = "\
+Usage: storeit-cli introspect --url <URL> [--tables <a,b,...>] [--out <FILE>]
+
+Reads the table catalog of an existing database and prints Rust source for
+#[derive(Entity)] structs and #[repository] modules.
+
+Options:
+  --url <URL>        postgres://..., mysql://..., or a SQLite/libsql file path
+  --tables <LIST>    comma-separated tables to generate (default: all)
+  --out <FILE>       write the source to FILE instead of stdout
+  -h, --help         print this help";
+
+struct Introspect {
+    url: String,
+    tables: Vec<String>,
+    out: Option<String>,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Option<Introspect>, String> {
+    match args.next().as_deref() {
+        Some("introspect") => {}
+        Some("-h") | Some("--help") | None => return Ok(None),
+        Some(other) => return Err(format!("unknown command `{}`", other)),
+    }
+    let mut url = None;
+    let mut tables = Vec::new();
+    let mut out = None;
+    while let Some(arg) = args.next() {
+        // Accept both `--flag value` and `--flag=value`.
+        let (flag, inline) = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => {
+                (flag.to_string(), Some(value.to_string()))
+            }
+            _ => (arg, None),
+        };
+        if flag == "-h" || flag == "--help" {
+            return Ok(None);
+        }
+        let value = inline
+            .or_else(|| args.next())
+            .ok_or_else(|| format!("`{}` needs a value", flag))?;
+        match flag.as_str() {
+            "--url" => url = Some(value),
+            "--tables" => tables.extend(
+                value
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|t| !t.is_empty())
+                    .map(String::from),
+            ),
+            "--out" => out = Some(value),
+            _ => return Err(format!("unknown option `{}`", flag)),
+        }
+    }
+    let url = url.ok_or("`--url` is required")?;
+    Ok(Some(Introspect { url, tables, out }))
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let cmd = match parse_args(std::env::args().skip(1)) {
+        Ok(Some(cmd)) => cmd,
+        Ok(None) => {
+            println!("{}", USAGE);
+            return ExitCode::SUCCESS;
+        }
+        Err(e) => {
+            eprintln!("error: {}\n\n{}", e, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    let tables = match storeit_cli::introspect(&cmd.url, &cmd.tables).await {
+        Ok(tables) => tables,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    if tables.is_empty() {
+        eprintln!("warning: the database has no tables");
+    }
+    let source = storeit_cli::codegen::generate(storeit_cli::dialect_for_url(&cmd.url), &tables);
+    match cmd.out {
+        Some(path) => {
+            if let Err(e) = std::fs::write(&path, source) {
+                eprintln!("error: cannot write `{}`: {}", path, e);
+                return ExitCode::FAILURE;
+            }
+        }
+        None => print!("{}", source),
+    }
+    ExitCode::SUCCESS
+}
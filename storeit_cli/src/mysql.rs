@@ -0,0 +1,83 @@
+//! Catalog reader for MySQL.
+//!
+//! Reads the base tables of the connection's database from `information_schema`.
+//! `AUTO_INCREMENT` columns count as generated; the full `COLUMN_TYPE` is kept so that
+//! `tinyint(1)` and `unsigned` survive into the type mapping.
+
+use crate::{Column, Table};
+use mysql_async::prelude::*;
+use mysql_async::{Conn, Pool};
+use storeit_core::schema::LiveIndex;
+use storeit_core::RepoResult;
+use storeit_mysql_async::map_mysql_error;
+
+const TABLES_SQL: &str = "SELECT TABLE_NAME FROM information_schema.TABLES \
+     WHERE TABLE_SCHEMA = DATABASE() AND TABLE_TYPE = 'BASE TABLE' \
+     AND TABLE_NAME <> 'storeit_schema_history' ORDER BY TABLE_NAME";
+
+const COLUMNS_SQL: &str = "SELECT COLUMN_NAME, COLUMN_TYPE, IS_NULLABLE = 'YES', \
+     EXTRA LIKE '%auto_increment%', COLUMN_KEY = 'PRI' \
+     FROM information_schema.COLUMNS \
+     WHERE TABLE_SCHEMA = DATABASE() AND TABLE_NAME = ? \
+     ORDER BY ORDINAL_POSITION";
+
+// Same query as storeit_mysql_async's schema validation.
+const INDEXES_SQL: &str = "SELECT INDEX_NAME, NON_UNIQUE = 0, COLUMN_NAME \
+     FROM information_schema.STATISTICS \
+     WHERE TABLE_SCHEMA = DATABASE() AND TABLE_NAME = ? \
+     ORDER BY INDEX_NAME, SEQ_IN_INDEX";
+
+/// Read every base table of the MySQL database named in `url`.
+pub async fn read_tables(url: &str) -> RepoResult<Vec<Table>> {
+    let pool = Pool::from_url(url).map_err(map_mysql_error)?;
+    let mut conn = pool.get_conn().await.map_err(map_mysql_error)?;
+    let tables = read_all(&mut conn).await;
+    drop(conn);
+    pool.disconnect().await.map_err(map_mysql_error)?;
+    tables
+}
+
+async fn read_all(conn: &mut Conn) -> RepoResult<Vec<Table>> {
+    let names: Vec<String> = conn.query(TABLES_SQL).await.map_err(map_mysql_error)?;
+    let mut tables = Vec::with_capacity(names.len());
+    for name in names {
+        let columns = read_columns(conn, &name).await?;
+        let indexes = read_indexes(conn, &name).await?;
+        tables.push(Table {
+            name,
+            columns,
+            indexes,
+        });
+    }
+    Ok(tables)
+}
+
+async fn read_columns(conn: &mut Conn, table: &str) -> RepoResult<Vec<Column>> {
+    let rows: Vec<(String, String, i64, i64, i64)> = conn
+        .exec(COLUMNS_SQL, (table,))
+        .await
+        .map_err(map_mysql_error)?;
+    Ok(rows
+        .into_iter()
+        .map(
+            |(name, sql_type, nullable, generated, primary_key)| Column {
+                name,
+                sql_type,
+                nullable: nullable != 0,
+                primary_key: primary_key != 0,
+                generated: generated != 0,
+            },
+        )
+        .collect())
+}
+
+async fn read_indexes(conn: &mut Conn, table: &str) -> RepoResult<Vec<LiveIndex>> {
+    let rows: Vec<(String, i64, String)> = conn
+        .exec(INDEXES_SQL, (table,))
+        .await
+        .map_err(map_mysql_error)?;
+    Ok(LiveIndex::group(
+        rows.into_iter()
+            .map(|(name, unique, column)| (name, unique != 0, column)),
+    ))
+}
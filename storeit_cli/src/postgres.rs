@@ -0,0 +1,102 @@
+//! Catalog reader for Postgres.
+//!
+//! Reads the base tables of the connection's current schema from `information_schema`, and
+//! their indexes from `pg_index`. Serial (`nextval`) and identity columns count as generated.
+
+use crate::{Column, Table};
+use storeit_core::schema::LiveIndex;
+use storeit_core::{RepoError, RepoResult};
+use storeit_tokio_postgres::{map_pg_error, TokioPostgresTransactionManager};
+use tokio_postgres::Client;
+
+const TABLES_SQL: &str = "SELECT table_name::text FROM information_schema.tables \
+     WHERE table_schema = current_schema() AND table_type = 'BASE TABLE' \
+     AND table_name <> 'storeit_schema_history' ORDER BY table_name";
+
+// Enum, domain and array columns report `USER-DEFINED`/`ARRAY`; their `udt_name` says more.
+const COLUMNS_SQL: &str = "SELECT c.column_name::text, \
+     CASE WHEN c.data_type IN ('USER-DEFINED', 'ARRAY') THEN c.udt_name::text ELSE c.data_type::text END, \
+     c.is_nullable = 'YES', \
+     c.column_default LIKE 'nextval(%' OR c.is_identity = 'YES', \
+     EXISTS (SELECT 1 FROM information_schema.table_constraints tc \
+     JOIN information_schema.key_column_usage k \
+     ON k.constraint_schema = tc.constraint_schema AND k.constraint_name = tc.constraint_name \
+     WHERE tc.constraint_type = 'PRIMARY KEY' AND tc.table_schema = c.table_schema \
+     AND tc.table_name = c.table_name AND k.column_name = c.column_name) \
+     FROM information_schema.columns c \
+     WHERE c.table_schema = current_schema() AND c.table_name = $1::text \
+     ORDER BY c.ordinal_position";
+
+// Same query as storeit_tokio_postgres's schema validation.
+const INDEXES_SQL: &str = "SELECT i.relname::text, ix.indisunique, a.attname::text \
+     FROM pg_index ix \
+     JOIN pg_class t ON t.oid = ix.indrelid \
+     JOIN pg_class i ON i.oid = ix.indexrelid \
+     JOIN pg_namespace n ON n.oid = t.relnamespace \
+     CROSS JOIN LATERAL unnest(ix.indkey::int2[]) WITH ORDINALITY AS k(attnum, ord) \
+     JOIN pg_attribute a ON a.attrelid = t.oid AND a.attnum = k.attnum \
+     WHERE n.nspname = current_schema() AND t.relname = $1::text \
+     ORDER BY i.relname, k.ord";
+
+/// Read every base table in the current schema of the Postgres database at `url`.
+pub async fn read_tables(url: &str) -> RepoResult<Vec<Table>> {
+    let client = TokioPostgresTransactionManager::new(url).connect().await?;
+    let names = client
+        .query(TABLES_SQL, &[])
+        .await
+        .map_err(map_pg_error)?
+        .iter()
+        .map(|row| row.try_get::<_, String>(0).map_err(RepoError::mapping))
+        .collect::<RepoResult<Vec<_>>>()?;
+
+    let mut tables = Vec::with_capacity(names.len());
+    for name in names {
+        let columns = read_columns(&client, &name).await?;
+        let indexes = read_indexes(&client, &name).await?;
+        tables.push(Table {
+            name,
+            columns,
+            indexes,
+        });
+    }
+    Ok(tables)
+}
+
+async fn read_columns(client: &Client, table: &str) -> RepoResult<Vec<Column>> {
+    let rows = client
+        .query(COLUMNS_SQL, &[&table])
+        .await
+        .map_err(map_pg_error)?;
+    rows.iter()
+        .map(|row| {
+            Ok(Column {
+                name: row.try_get(0).map_err(RepoError::mapping)?,
+                sql_type: row.try_get(1).map_err(RepoError::mapping)?,
+                nullable: row.try_get(2).map_err(RepoError::mapping)?,
+                generated: row
+                    .try_get::<_, Option<bool>>(3)
+                    .map_err(RepoError::mapping)?
+                    .unwrap_or(false),
+                primary_key: row.try_get(4).map_err(RepoError::mapping)?,
+            })
+        })
+        .collect()
+}
+
+async fn read_indexes(client: &Client, table: &str) -> RepoResult<Vec<LiveIndex>> {
+    let rows = client
+        .query(INDEXES_SQL, &[&table])
+        .await
+        .map_err(map_pg_error)?;
+    let entries = rows
+        .iter()
+        .map(|row| {
+            Ok((
+                row.try_get(0).map_err(RepoError::mapping)?,
+                row.try_get(1).map_err(RepoError::mapping)?,
+                row.try_get(2).map_err(RepoError::mapping)?,
+            ))
+        })
+        .collect::<RepoResult<Vec<(String, bool, String)>>>()?;
+    Ok(LiveIndex::group(entries))
+}
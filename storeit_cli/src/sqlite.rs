@@ -0,0 +1,102 @@
+//! Catalog reader for SQLite/libsql files.
+//!
+//! Tables come from `sqlite_master`; columns, keys and indexes from the `pragma_table_info`,
+//! `pragma_index_list` and `pragma_index_info` table-valued functions. A single `INTEGER`
+//! primary key aliases the rowid and counts as generated.
+
+use crate::{Column, Table};
+use libsql::{Connection, Database};
+use storeit_core::schema::LiveIndex;
+use storeit_core::{RepoError, RepoResult};
+use storeit_libsql::map_libsql_error;
+
+// Internal SQLite tables and the migration history are not entities.
+const TABLES_SQL: &str = "SELECT name FROM sqlite_master WHERE type = 'table' \
+     AND name NOT LIKE 'sqlite_%' AND name <> 'storeit_schema_history' ORDER BY name";
+
+const COLUMNS_SQL: &str =
+    "SELECT name, type, \"notnull\", pk FROM pragma_table_info(?1) ORDER BY cid";
+
+const INDEXES_SQL: &str =
+    "SELECT il.name, il.\"unique\", ii.name FROM pragma_index_list(?1) AS il, \
+     pragma_index_info(il.name) AS ii ORDER BY il.name, ii.seqno";
+
+/// Read every table of the SQLite/libsql database at `url`.
+///
+/// `url` is a file path, optionally prefixed with `sqlite://` or `sqlite:`, or a libsql
+/// `file:` URL. A plain path must exist: introspection never creates a database.
+pub async fn read_tables(url: &str) -> RepoResult<Vec<Table>> {
+    let path = url
+        .strip_prefix("sqlite://")
+        .or_else(|| url.strip_prefix("sqlite:"))
+        .unwrap_or(url);
+    if !path.starts_with("file:") && !std::path::Path::new(path).exists() {
+        return Err(RepoError::backend(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            format!("database file `{}` does not exist", path),
+        )));
+    }
+    // Database::open is deprecated upstream; same narrow allow as storeit_libsql.
+    #[allow(deprecated)]
+    let db = Database::open(path).map_err(map_libsql_error)?;
+    let conn = db.connect().map_err(map_libsql_error)?;
+
+    let mut names = Vec::new();
+    let mut rows = conn.query(TABLES_SQL, ()).await.map_err(map_libsql_error)?;
+    while let Some(row) = rows.next().await.map_err(map_libsql_error)? {
+        names.push(row.get::<String>(0).map_err(map_libsql_error)?);
+    }
+
+    let mut tables = Vec::with_capacity(names.len());
+    for name in names {
+        let columns = read_columns(&conn, &name).await?;
+        let indexes = read_indexes(&conn, &name).await?;
+        tables.push(Table {
+            name,
+            columns,
+            indexes,
+        });
+    }
+    Ok(tables)
+}
+
+async fn read_columns(conn: &Connection, table: &str) -> RepoResult<Vec<Column>> {
+    let mut rows = conn
+        .query(COLUMNS_SQL, [table])
+        .await
+        .map_err(map_libsql_error)?;
+    let mut columns = Vec::new();
+    while let Some(row) = rows.next().await.map_err(map_libsql_error)? {
+        let primary_key = row.get::<i64>(3).map_err(map_libsql_error)? != 0;
+        columns.push(Column {
+            name: row.get::<String>(0).map_err(map_libsql_error)?,
+            sql_type: row.get::<String>(1).map_err(map_libsql_error)?,
+            // SQLite lets primary key columns (other than the rowid alias) hold NULL unless
+            // declared NOT NULL; storeit treats keys as required either way.
+            nullable: row.get::<i64>(2).map_err(map_libsql_error)? == 0 && !primary_key,
+            primary_key,
+            generated: false,
+        });
+    }
+    let mut keys = columns.iter_mut().filter(|c| c.primary_key);
+    if let (Some(key), None) = (keys.next(), keys.next()) {
+        key.generated = key.sql_type.eq_ignore_ascii_case("INTEGER");
+    }
+    Ok(columns)
+}
+
+async fn read_indexes(conn: &Connection, table: &str) -> RepoResult<Vec<LiveIndex>> {
+    let mut rows = conn
+        .query(INDEXES_SQL, [table])
+        .await
+        .map_err(map_libsql_error)?;
+    let mut entries = Vec::new();
+    while let Some(row) = rows.next().await.map_err(map_libsql_error)? {
+        entries.push((
+            row.get::<String>(0).map_err(map_libsql_error)?,
+            row.get::<i64>(1).map_err(map_libsql_error)? != 0,
+            row.get::<String>(2).map_err(map_libsql_error)?,
+        ));
+    }
+    Ok(LiveIndex::group(entries))
+}
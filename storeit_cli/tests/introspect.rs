@@ -0,0 +1,87 @@
+#![cfg(feature = "libsql-backend")]
+
+use storeit_cli::{codegen, introspect, Column};
+use storeit_sql_builder::Dialect;
+
+async fn fixture(dir: &tempfile::TempDir) -> String {
+    let path = dir.path().join("legacy.db");
+    // Database::open is deprecated upstream; narrow allow inside tests setup only.
+    #[allow(deprecated)]
+    let db = libsql::Database::open(format!("file:{}?mode=rwc", path.display())).expect("open db");
+    let conn = db.connect().expect("connect");
+    conn.execute_batch(
+        "CREATE TABLE categories (id INTEGER PRIMARY KEY, name TEXT NOT NULL UNIQUE);
+         CREATE TABLE products (
+             id INTEGER PRIMARY KEY AUTOINCREMENT,
+             category_id INTEGER NOT NULL REFERENCES categories(id),
+             title VARCHAR(200) NOT NULL,
+             price REAL,
+             createdAt DATETIME
+         );
+         CREATE INDEX idx_products_category ON products(category_id);
+         CREATE TABLE tags (product_id INTEGER NOT NULL, tag TEXT NOT NULL,
+             PRIMARY KEY (product_id, tag));
+         CREATE TABLE audit_log (line TEXT);",
+    )
+    .await
+    .expect("create schema");
+    path.display().to_string()
+}
+
+#[tokio::test]
+async fn reads_sqlite_catalog() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let url = fixture(&dir).await;
+
+    let tables = introspect(&url, &[]).await.expect("introspect");
+    let names: Vec<_> = tables.iter().map(|t| t.name.as_str()).collect();
+    assert_eq!(names, ["audit_log", "categories", "products", "tags"]);
+
+    let products = &tables[2];
+    assert_eq!(
+        products.columns[0],
+        Column {
+            name: "id".into(),
+            sql_type: "INTEGER".into(),
+            nullable: false,
+            primary_key: true,
+            generated: true,
+        }
+    );
+    assert!(products.columns[3].nullable);
+    assert!(products
+        .indexes
+        .iter()
+        .any(|i| i.columns == ["category_id"]));
+    // Composite keys are supplied by the caller.
+    assert!(tables[3]
+        .columns
+        .iter()
+        .all(|c| c.primary_key && !c.generated));
+
+    let only = introspect(&url, &["tags".to_string()])
+        .await
+        .expect("filter");
+    assert_eq!(only.len(), 1);
+    assert!(introspect(&url, &["missing".to_string()]).await.is_err());
+    assert!(introspect(&format!("{}.nope", url), &[]).await.is_err());
+}
+
+#[tokio::test]
+async fn generates_entities_for_sqlite_catalog() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let url = fixture(&dir).await;
+    let tables = introspect(&url, &[]).await.expect("introspect");
+    let src = codegen::generate(Dialect::Sqlite, &tables);
+
+    assert!(src.contains("// Skipped table `audit_log`"));
+    assert!(src.contains(
+        "#[entity(table = \"categories\")]\npub struct Category {\n    #[fetch(id)]\n    pub id: Option<i64>,\n    #[fetch(unique)]\n    pub name: String,\n}"
+    ));
+    assert!(src.contains("    #[fetch(index)]\n    pub category_id: i64,\n"));
+    assert!(src.contains("    #[fetch(column = \"createdAt\")]\n    pub created_at: Option<chrono::NaiveDateTime>,\n"));
+    assert!(src.contains(
+        "#[repository(entity = Product, backend = Libsql, finders(find_by_category_id: i64))]\npub mod products {}"
+    ));
+    assert!(src.contains("pub struct Tag {\n    #[fetch(id)]\n    pub product_id: i64,\n    #[fetch(id)]\n    pub tag: String,\n}"));
+}
@@ -0,0 +1,118 @@
+//! The time source for audit columns.
+//!
+//! `#[fetch(created_at)]` and `#[fetch(updated_at)]` fields are filled from [`now`] when the
+//! derive builds `insert_values`/`update_values`. The process-wide clock is the system clock
+//! until [`set_clock`] installs another one (e.g. a [`FixedClock`] in tests); [`with_now`]
+//! pins the time for the current thread, which backends use so the row written and the
+//! entity returned from `update` carry the same timestamp.
+
+use std::cell::Cell;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, SystemTime};
+
+/// A source of the current time.
+pub trait Clock: Send + Sync {
+    fn now(&self) -> SystemTime;
+}
+
+/// The default clock: `SystemTime::now()`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
+
+/// A clock that only moves when told to. Clones share the same time.
+#[derive(Debug, Clone)]
+pub struct FixedClock(Arc<Mutex<SystemTime>>);
+
+impl FixedClock {
+    pub fn new(at: SystemTime) -> Self {
+        Self(Arc::new(Mutex::new(at)))
+    }
+
+    pub fn set(&self, at: SystemTime) {
+        *self.0.lock().unwrap_or_else(|e| e.into_inner()) = at;
+    }
+
+    pub fn advance(&self, by: Duration) {
+        let mut at = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        *at += by;
+    }
+}
+
+impl Clock for FixedClock {
+    fn now(&self) -> SystemTime {
+        *self.0.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+static CLOCK: RwLock<Option<Arc<dyn Clock>>> = RwLock::new(None);
+
+thread_local! {
+    static PINNED: Cell<Option<SystemTime>> = const { Cell::new(None) };
+}
+
+/// Install `clock` as the process-wide time source.
+pub fn set_clock(clock: Arc<dyn Clock>) {
+    *CLOCK.write().unwrap_or_else(|e| e.into_inner()) = Some(clock);
+}
+
+/// Restore the system clock.
+pub fn reset_clock() {
+    *CLOCK.write().unwrap_or_else(|e| e.into_inner()) = None;
+}
+
+/// The current time: the time pinned by [`with_now`] on this thread, else the installed clock.
+pub fn now() -> SystemTime {
+    if let Some(at) = PINNED.with(Cell::get) {
+        return at;
+    }
+    match CLOCK.read().unwrap_or_else(|e| e.into_inner()).as_ref() {
+        Some(clock) => clock.now(),
+        None => SystemTime::now(),
+    }
+}
+
+/// Run `f` with [`now`] returning `at` on the current thread.
+pub fn with_now<R>(at: SystemTime, f: impl FnOnce() -> R) -> R {
+    struct Restore(Option<SystemTime>);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            PINNED.with(|p| p.set(self.0));
+        }
+    }
+    let _restore = Restore(PINNED.with(|p| p.replace(Some(at))));
+    f()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::UNIX_EPOCH;
+
+    #[test]
+    fn pinned_time_wins_and_nests() {
+        let a = UNIX_EPOCH + Duration::from_secs(10);
+        let b = UNIX_EPOCH + Duration::from_secs(20);
+        with_now(a, || {
+            assert_eq!(now(), a);
+            with_now(b, || assert_eq!(now(), b));
+            assert_eq!(now(), a);
+        });
+        assert_ne!(now(), a);
+    }
+
+    #[test]
+    fn fixed_clock_moves_only_when_told() {
+        let clock = FixedClock::new(UNIX_EPOCH);
+        let shared = clock.clone();
+        clock.advance(Duration::from_secs(5));
+        assert_eq!(shared.now(), UNIX_EPOCH + Duration::from_secs(5));
+        shared.set(UNIX_EPOCH);
+        assert_eq!(clock.now(), UNIX_EPOCH);
+    }
+}
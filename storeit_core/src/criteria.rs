@@ -0,0 +1,172 @@
+//! Backend-agnostic filter trees for the `*_where` repository methods.
+//!
+//! A [`Criteria`] describes a boolean condition over an entity's columns. Backends render it
+//! through `storeit_sql_builder::render_criteria`, which numbers placeholders for the target
+//! dialect and returns the bind values in placeholder order.
+//!
+//! Column names are interpolated into SQL as-is: use the entity's column names (e.g. from
+//! `Fetchable::SELECT_COLUMNS`), never unvalidated user input.
+//!
+//! A column written as `column->key->...` addresses the value at that key path inside a JSON
+//! column (e.g. a `#[fetch(json)]` field): `Criteria::eq("settings->theme", "dark")`. The SQL
+//! builder renders it with the dialect's JSON functions and compares the value as text.
+
+use crate::{ParamValue, ToParamValue};
+
+/// A filter condition: comparisons on single columns combined with `And`/`Or`/`Not`.
+///
+/// ```
+/// use storeit_core::Criteria;
+///
+/// let adults_named_a = Criteria::ge("age", 18)
+///     .and(Criteria::like("name", "A%"))
+///     .and(!Criteria::is_null("email"));
+/// # let _ = adults_named_a;
+/// ```
+#[derive(Debug, Clone)]
+pub enum Criteria {
+    /// `column = value`; an `Eq` against `ParamValue::Null` renders as `IS NULL`.
+    Eq(String, ParamValue),
+    /// `column <> value`; a `Ne` against `ParamValue::Null` renders as `IS NOT NULL`.
+    Ne(String, ParamValue),
+    Lt(String, ParamValue),
+    Le(String, ParamValue),
+    Gt(String, ParamValue),
+    Ge(String, ParamValue),
+    /// `column LIKE pattern`, with `%`/`_` wildcards.
+    Like(String, ParamValue),
+    /// `column IN (...)`; an empty list matches nothing.
+    In(String, Vec<ParamValue>),
+    IsNull(String),
+    /// `column BETWEEN low AND high` (inclusive on both ends).
+    Between(String, ParamValue, ParamValue),
+    /// All conditions hold; an empty list matches everything.
+    And(Vec<Criteria>),
+    /// At least one condition holds; an empty list matches nothing.
+    Or(Vec<Criteria>),
+    Not(Box<Criteria>),
+}
+
+impl Criteria {
+    pub fn eq(column: impl Into<String>, value: impl ToParamValue) -> Self {
+        Criteria::Eq(column.into(), value.to_param_value())
+    }
+
+    pub fn ne(column: impl Into<String>, value: impl ToParamValue) -> Self {
+        Criteria::Ne(column.into(), value.to_param_value())
+    }
+
+    pub fn lt(column: impl Into<String>, value: impl ToParamValue) -> Self {
+        Criteria::Lt(column.into(), value.to_param_value())
+    }
+
+    pub fn le(column: impl Into<String>, value: impl ToParamValue) -> Self {
+        Criteria::Le(column.into(), value.to_param_value())
+    }
+
+    pub fn gt(column: impl Into<String>, value: impl ToParamValue) -> Self {
+        Criteria::Gt(column.into(), value.to_param_value())
+    }
+
+    pub fn ge(column: impl Into<String>, value: impl ToParamValue) -> Self {
+        Criteria::Ge(column.into(), value.to_param_value())
+    }
+
+    pub fn like(column: impl Into<String>, pattern: impl ToParamValue) -> Self {
+        Criteria::Like(column.into(), pattern.to_param_value())
+    }
+
+    pub fn in_list<V, I>(column: impl Into<String>, values: I) -> Self
+    where
+        V: ToParamValue,
+        I: IntoIterator<Item = V>,
+    {
+        Criteria::In(
+            column.into(),
+            values.into_iter().map(|v| v.to_param_value()).collect(),
+        )
+    }
+
+    pub fn is_null(column: impl Into<String>) -> Self {
+        Criteria::IsNull(column.into())
+    }
+
+    pub fn is_not_null(column: impl Into<String>) -> Self {
+        !Criteria::IsNull(column.into())
+    }
+
+    pub fn between(
+        column: impl Into<String>,
+        low: impl ToParamValue,
+        high: impl ToParamValue,
+    ) -> Self {
+        Criteria::Between(column.into(), low.to_param_value(), high.to_param_value())
+    }
+
+    /// A condition that matches every row (an empty `And`).
+    pub fn all() -> Self {
+        Criteria::And(Vec::new())
+    }
+
+    /// Combine with `other` using AND, flattening nested `And`s.
+    pub fn and(self, other: Criteria) -> Self {
+        match self {
+            Criteria::And(mut parts) => {
+                parts.push(other);
+                Criteria::And(parts)
+            }
+            c => Criteria::And(vec![c, other]),
+        }
+    }
+
+    /// Combine with `other` using OR, flattening nested `Or`s.
+    pub fn or(self, other: Criteria) -> Self {
+        match self {
+            Criteria::Or(mut parts) => {
+                parts.push(other);
+                Criteria::Or(parts)
+            }
+            c => Criteria::Or(vec![c, other]),
+        }
+    }
+}
+
+impl std::ops::Not for Criteria {
+    type Output = Criteria;
+
+    fn not(self) -> Criteria {
+        Criteria::Not(Box::new(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn combinators_flatten_and_wrap() {
+        let c = Criteria::eq("a", 1)
+            .and(Criteria::gt("b", 2i64))
+            .and(Criteria::all());
+        match c {
+            Criteria::And(parts) => assert_eq!(parts.len(), 3),
+            other => panic!("expected And, got {other:?}"),
+        }
+
+        let c = Criteria::is_null("a").or(Criteria::like("b", "x%"));
+        assert!(matches!(c, Criteria::Or(ref parts) if parts.len() == 2));
+
+        assert!(matches!(
+            Criteria::is_not_null("a"),
+            Criteria::Not(inner) if matches!(*inner, Criteria::IsNull(ref col) if col == "a")
+        ));
+        assert!(matches!(
+            Criteria::in_list("id", [1i64, 2, 3]),
+            Criteria::In(_, ref values) if values.len() == 3
+        ));
+        assert!(matches!(
+            Criteria::between("n", 1, Option::<i32>::None),
+            Criteria::Between(_, ParamValue::I32(1), ParamValue::Null)
+        ));
+    }
+}
@@ -0,0 +1,211 @@
+//! Value objects stored as a group of columns of the owning entity's table.
+//!
+//! `#[derive(Embeddable)]` implements [`Embeddable`] for a struct such as an address. An entity
+//! field `#[fetch(flatten, prefix = "billing_")] billing: Address` then contributes
+//! `billing_street`, `billing_city`, ... to the entity's column lists and row adapter.
+//! `Option<Address>` is embeddable too: `None` is written as all-NULL columns, and a row whose
+//! columns are all NULL reads back as `None`.
+//!
+//! The derives splice the prefixed names into the entity's `&'static` column constants at
+//! compile time with the `const fn` helpers below.
+
+use crate::schema::{ColumnDef, SqlType};
+use crate::{ParamValue, RepoResult};
+
+/// A group of columns mapped to one Rust value.
+pub trait Embeddable: Sized {
+    /// The unprefixed column names, in the order of [`Embeddable::to_values`].
+    const COLUMNS: &'static [&'static str];
+
+    /// One value per column.
+    fn to_values(&self) -> Vec<ParamValue>;
+
+    /// Rebuild the value from one value per column.
+    fn from_values(values: Vec<ParamValue>) -> RepoResult<Self>;
+
+    /// The unprefixed column definitions, for DDL generation. The default declares every
+    /// column as nullable text; `#[derive(Embeddable)]` derives them from the field types.
+    fn column_defs() -> Vec<ColumnDef> {
+        Self::COLUMNS
+            .iter()
+            .map(|name| ColumnDef {
+                nullable: true,
+                ..ColumnDef::new(*name, SqlType::Text)
+            })
+            .collect()
+    }
+}
+
+impl<E: Embeddable> Embeddable for Option<E> {
+    const COLUMNS: &'static [&'static str] = E::COLUMNS;
+
+    fn to_values(&self) -> Vec<ParamValue> {
+        match self {
+            Some(value) => value.to_values(),
+            None => vec![ParamValue::Null; E::COLUMNS.len()],
+        }
+    }
+
+    fn from_values(values: Vec<ParamValue>) -> RepoResult<Self> {
+        if values.iter().all(|v| matches!(v, ParamValue::Null)) {
+            return Ok(None);
+        }
+        E::from_values(values).map(Some)
+    }
+
+    fn column_defs() -> Vec<ColumnDef> {
+        E::column_defs()
+            .into_iter()
+            .map(|c| ColumnDef {
+                nullable: true,
+                ..c
+            })
+            .collect()
+    }
+}
+
+/// The total length of `columns` with `prefix` prepended to each.
+pub const fn prefixed_len(prefix: &str, columns: &[&str]) -> usize {
+    let mut n = 0;
+    let mut i = 0;
+    while i < columns.len() {
+        n += prefix.len() + columns[i].len();
+        i += 1;
+    }
+    n
+}
+
+/// `columns` with `prefix` prepended to each, back to back; `N` is [`prefixed_len`].
+pub const fn prefixed_bytes<const N: usize>(prefix: &str, columns: &[&str]) -> [u8; N] {
+    let mut out = [0u8; N];
+    let mut at = 0;
+    let mut i = 0;
+    while i < columns.len() {
+        let (p, c) = (prefix.as_bytes(), columns[i].as_bytes());
+        let mut j = 0;
+        while j < p.len() {
+            out[at] = p[j];
+            at += 1;
+            j += 1;
+        }
+        j = 0;
+        while j < c.len() {
+            out[at] = c[j];
+            at += 1;
+            j += 1;
+        }
+        i += 1;
+    }
+    out
+}
+
+/// Split the output of [`prefixed_bytes`] back into the `M` prefixed column names.
+pub const fn prefixed_columns<const M: usize>(
+    bytes: &'static [u8],
+    prefix: &str,
+    columns: &[&str],
+) -> [&'static str; M] {
+    let mut out = [""; M];
+    let mut rest = bytes;
+    let mut i = 0;
+    while i < M {
+        let len = prefix.len() + columns[i].len();
+        let mut head = rest;
+        while head.len() > len {
+            match head.split_last() {
+                Some((_, h)) => head = h,
+                None => break,
+            }
+        }
+        out[i] = match core::str::from_utf8(head) {
+            Ok(s) => s,
+            Err(_) => panic!("column names are valid UTF-8"),
+        };
+        let mut k = 0;
+        while k < len {
+            match rest.split_first() {
+                Some((_, r)) => rest = r,
+                None => break,
+            }
+            k += 1;
+        }
+        i += 1;
+    }
+    out
+}
+
+/// The number of names in `parts`.
+pub const fn concat_len(parts: &[&[&str]]) -> usize {
+    let mut n = 0;
+    let mut i = 0;
+    while i < parts.len() {
+        n += parts[i].len();
+        i += 1;
+    }
+    n
+}
+
+/// The names in `parts`, in order; `N` is [`concat_len`].
+pub const fn concat<const N: usize>(parts: &[&[&'static str]]) -> [&'static str; N] {
+    let mut out = [""; N];
+    let mut at = 0;
+    let mut i = 0;
+    while i < parts.len() {
+        let mut j = 0;
+        while j < parts[i].len() {
+            out[at] = parts[i][j];
+            at += 1;
+            j += 1;
+        }
+        i += 1;
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INNER: &[&str] = &["street", "city"];
+    const BYTES: [u8; prefixed_len("billing_", INNER)] = prefixed_bytes("billing_", INNER);
+    const PREFIXED: [&str; INNER.len()] = prefixed_columns(&BYTES, "billing_", INNER);
+    const ALL: [&str; concat_len(&[&["id"], &PREFIXED, &["name"]])] =
+        concat(&[&["id"], &PREFIXED, &["name"]]);
+
+    #[test]
+    fn prefixed_columns_are_spliced_at_compile_time() {
+        assert_eq!(PREFIXED, ["billing_street", "billing_city"]);
+        assert_eq!(ALL, ["id", "billing_street", "billing_city", "name"]);
+        const EMPTY: [&str; 0] = prefixed_columns(&[], "p_", &[]);
+        assert!(EMPTY.is_empty());
+    }
+
+    struct Money(i64);
+    impl Embeddable for Money {
+        const COLUMNS: &'static [&'static str] = &["cents"];
+        fn to_values(&self) -> Vec<ParamValue> {
+            vec![ParamValue::I64(self.0)]
+        }
+        fn from_values(values: Vec<ParamValue>) -> RepoResult<Self> {
+            match values.as_slice() {
+                [ParamValue::I64(c)] => Ok(Money(*c)),
+                _ => Err(crate::RepoError::mapping(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    "expected cents",
+                ))),
+            }
+        }
+    }
+
+    #[test]
+    fn optional_embeddables_map_to_all_null_columns() {
+        assert!(matches!(None::<Money>.to_values()[..], [ParamValue::Null]));
+        assert!(Option::<Money>::from_values(vec![ParamValue::Null])
+            .unwrap()
+            .is_none());
+        let some = Option::<Money>::from_values(vec![ParamValue::I64(5)]).unwrap();
+        assert_eq!(some.map(|m| m.0), Some(5));
+        assert_eq!(Money::column_defs()[0].sql_type, SqlType::Text);
+        assert!(Option::<Money>::column_defs()[0].nullable);
+    }
+}
@@ -0,0 +1,85 @@
+//! Serde-backed conversions for JSON columns (feature `json`).
+//!
+//! A field marked `#[fetch(json)]` is converted through this module, as if it were declared
+//! `#[fetch(with = "::storeit::json")]`. Its value is serialized to `ParamValue::Json`, which
+//! backends bind as JSONB/JSON on Postgres, JSON on MySQL and TEXT on libsql (where SQLite's
+//! JSON1 functions can read it). A JSON `null`, such as an `Option` field holding `None`, is
+//! stored as SQL NULL, and SQL NULL reads back through `serde_json::Value::Null`.
+//!
+//! To filter on a value inside such a column, address it as `column->key->...` in a
+//! [`Criteria`](crate::Criteria); see the criteria module documentation.
+
+use crate::{value::text_from_column, ParamValue, RepoError, RepoResult};
+use serde::{de::DeserializeOwned, Serialize};
+
+/// Serialize `value` for binding.
+///
+/// # Panics
+///
+/// If `T`'s `Serialize` implementation fails, e.g. for a map with non-string keys. Such
+/// values have no JSON representation; binding is infallible, so this is a programming error.
+pub fn to_param_value<T: Serialize + ?Sized>(value: &T) -> ParamValue {
+    match serde_json::to_value(value) {
+        Ok(serde_json::Value::Null) => ParamValue::Null,
+        Ok(json) => ParamValue::Json(json.to_string()),
+        Err(e) => panic!(
+            "value of a #[fetch(json)] field cannot be serialized: {}",
+            e
+        ),
+    }
+}
+
+/// Deserialize a JSON column, reported as a mapping error when it does not match `T`.
+pub fn from_column<T: DeserializeOwned>(value: ParamValue) -> RepoResult<T> {
+    match value {
+        ParamValue::Null => serde_json::from_value(serde_json::Value::Null),
+        other => serde_json::from_str(&text_from_column(other)?),
+    }
+    .map_err(RepoError::mapping)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Settings {
+        theme: String,
+        font_size: u8,
+    }
+
+    #[test]
+    fn values_round_trip_through_json_text() {
+        let settings = Settings {
+            theme: "dark".into(),
+            font_size: 12,
+        };
+        let bound = to_param_value(&settings);
+        let ParamValue::Json(text) = &bound else {
+            panic!("expected a JSON value, got {bound:?}");
+        };
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(text).unwrap(),
+            serde_json::json!({ "theme": "dark", "font_size": 12 })
+        );
+        assert_eq!(from_column::<Settings>(bound).unwrap(), settings);
+        let text = ParamValue::String(r#"{"theme":"light","font_size":9}"#.into());
+        assert_eq!(from_column::<Settings>(text).unwrap().theme, "light");
+        assert!(from_column::<Settings>(ParamValue::String("[]".into())).is_err());
+    }
+
+    #[test]
+    fn json_null_is_sql_null() {
+        assert!(matches!(
+            to_param_value(&None::<Settings>),
+            ParamValue::Null
+        ));
+        assert_eq!(
+            from_column::<Option<Settings>>(ParamValue::Null).unwrap(),
+            None
+        );
+        assert!(from_column::<Settings>(ParamValue::Null).is_err());
+        assert!(matches!(to_param_value(&vec![1, 2]), ParamValue::Json(j) if j == "[1,2]"));
+    }
+}
@@ -8,6 +8,47 @@ pub use async_trait::async_trait;
 // Public transactions module (backend-agnostic abstractions)
 pub mod transactions;
 
+// Text renderings of ParamValue variants for backends without a native column type
+pub mod value;
+
+// Backend-agnostic filter trees for the `*_where` repository methods
+pub mod criteria;
+pub use criteria::Criteria;
+
+// Page requests and results for the paged repository methods
+pub mod page;
+pub use page::{Cursor, CursorPage, Direction, Order, Page, Pageable, Sort};
+
+// Injectable time source for `#[fetch(created_at)]`/`#[fetch(updated_at)]` fields
+pub mod clock;
+
+// Task-local tenant scope for `#[entity(tenant = "...")]` entities
+pub mod tenant;
+
+// `#[fetch(belongs_to = "...")]` relation metadata and association loading
+pub mod relations;
+pub use relations::{BelongsTo, Relations};
+
+// `#[derive(Embeddable)]` value objects flattened into an entity's columns
+pub mod embed;
+pub use embed::Embeddable;
+
+// `#[derive(Projection)]` read models over a subset of an entity's columns
+pub mod projection;
+pub use projection::Projection;
+
+// Serde-backed conversions for `#[fetch(json)]` fields
+#[cfg(feature = "json")]
+pub mod json;
+
+// Column types and table definitions derived from entities, for DDL generation
+pub mod schema;
+pub use schema::Schema;
+
+// Lifecycle hooks run by the backends around inserts, updates, loads and deletes
+pub mod listener;
+pub use listener::{EntityListener, Listenable, Listeners};
+
 /// Marker trait for types that can be fetched from a database.
 /// Implemented via `#[derive(Fetchable)]` proc-macro in `storeit_macros`.
 ///
@@ -19,11 +60,24 @@ pub trait Fetchable {
     /// A list of (column_name, rust_type) tuples for fields that can be used
     /// to generate `find_by...` methods.
     const FINDABLE_COLUMNS: &'static [(&'static str, &'static str)];
+
+    /// The soft-delete column (`#[entity(soft_delete = "...")]`), if the entity has one.
+    /// Rows where it is non-NULL count as deleted: `delete_by_id` sets it instead of removing
+    /// the row, and reads skip such rows.
+    const SOFT_DELETE_COLUMN: Option<&'static str> = None;
+
+    /// The tenant column (`#[entity(tenant = "...")]`), if rows are partitioned by tenant.
+    /// Statements then only touch the rows of the [`tenant::scope`] they run in.
+    const TENANT_COLUMN: Option<&'static str> = None;
 }
 
 /// A backend-agnostic representation of a database parameter value.
 /// This is used to pass entity field values from generated code to backend adapters
 /// without making `storeit_core` dependent on a specific database driver.
+///
+/// Temporal, UUID, decimal and JSON values use driver-independent representations; each
+/// backend binds them with the closest native type (see the [`value`] module for the text
+/// forms used where no native type exists).
 #[derive(Debug, Clone)]
 pub enum ParamValue {
     String(String),
@@ -31,6 +85,20 @@ pub enum ParamValue {
     I64(i64),
     F64(f64),
     Bool(bool),
+    /// Binary data (`BYTEA`, `BLOB`, `VARBINARY`).
+    Bytes(Vec<u8>),
+    /// A UUID as its 16 big-endian bytes.
+    Uuid([u8; 16]),
+    /// A point in time as microseconds since the Unix epoch, UTC.
+    Timestamp(i64),
+    /// A calendar date as days since 1970-01-01.
+    Date(i32),
+    /// A time of day as microseconds since midnight.
+    Time(i64),
+    /// An exact decimal in its canonical text form (e.g. `"-12.50"`).
+    Decimal(String),
+    /// A serialized JSON document.
+    Json(String),
     Null,
 }
 
@@ -41,12 +109,324 @@ pub trait Identifiable {
     type Key;
 
     /// The name of the primary key column in the database.
+    /// For composite keys this is the first key column.
     const ID_COLUMN: &'static str;
 
+    /// All primary key columns, in the order of the key's components.
+    /// Single-column keys keep the default of `[ID_COLUMN]`; composite keys list every column
+    /// and use a tuple `Key` whose elements follow the same order.
+    const ID_COLUMNS: &'static [&'static str] = &[Self::ID_COLUMN];
+
+    /// Whether the database generates the key on insert (auto-increment/serial).
+    /// When `false` the caller supplies the key: it is part of `INSERT_COLUMNS` and is used to
+    /// read the inserted row back. Composite keys are never generated, hence the default.
+    const ID_GENERATED: bool = Self::ID_COLUMNS.len() == 1;
+
     /// Returns a copy of the entity's ID, if it has one.
     fn id(&self) -> Option<Self::Key>;
 }
 
+/// Conversion of a single value into a [`ParamValue`].
+///
+/// Implemented for the supported scalar types and `Option`s of them, and behind the `uuid`,
+/// `chrono`, `rust_decimal` and `json` features for those crates' types. Implement it for
+/// typed ids (e.g. `struct UserId(Uuid)`) to use them as keys or entity fields; the derive
+/// binds every field through this trait.
+pub trait ToParamValue {
+    fn to_param_value(&self) -> ParamValue;
+}
+
+macro_rules! impl_to_param_value {
+    ($($ty:ty => $variant:ident),* $(,)?) => {
+        $(
+            impl ToParamValue for $ty {
+                fn to_param_value(&self) -> ParamValue {
+                    ParamValue::$variant(self.clone())
+                }
+            }
+        )*
+    };
+}
+
+impl_to_param_value!(String => String, i32 => I32, i64 => I64, f64 => F64, bool => Bool);
+
+impl ToParamValue for &str {
+    fn to_param_value(&self) -> ParamValue {
+        ParamValue::String((*self).to_string())
+    }
+}
+
+impl<T: ToParamValue> ToParamValue for Option<T> {
+    fn to_param_value(&self) -> ParamValue {
+        self.as_ref()
+            .map_or(ParamValue::Null, ToParamValue::to_param_value)
+    }
+}
+
+impl ToParamValue for Vec<u8> {
+    fn to_param_value(&self) -> ParamValue {
+        ParamValue::Bytes(self.clone())
+    }
+}
+
+impl ToParamValue for std::time::SystemTime {
+    fn to_param_value(&self) -> ParamValue {
+        let micros = match self.duration_since(std::time::UNIX_EPOCH) {
+            Ok(d) => d.as_micros() as i64,
+            Err(e) => -(e.duration().as_micros() as i64),
+        };
+        ParamValue::Timestamp(micros)
+    }
+}
+
+#[cfg(feature = "uuid")]
+impl ToParamValue for uuid::Uuid {
+    fn to_param_value(&self) -> ParamValue {
+        ParamValue::Uuid(*self.as_bytes())
+    }
+}
+
+/// Conversion of a column value, as read by a backend, into a field type.
+///
+/// The counterpart of [`ToParamValue`]: the derive's generated `RowAdapter` reads each column
+/// as a [`ParamValue`] and converts it to the field type with this. Backends report
+/// the closest variant for the column's type (e.g. `I64` for any SQLite integer, `String` for
+/// SQLite dates), so impls should accept every representation of their logical type; the
+/// helpers in [`value`] do that for the built-in variants.
+pub trait FromColumn: Sized {
+    fn from_column(value: ParamValue) -> RepoResult<Self>;
+}
+
+impl FromColumn for String {
+    fn from_column(value: ParamValue) -> RepoResult<Self> {
+        value::text_from_column(value)
+    }
+}
+
+impl FromColumn for i64 {
+    fn from_column(value: ParamValue) -> RepoResult<Self> {
+        match value {
+            ParamValue::I64(i) => Ok(i),
+            ParamValue::I32(i) => Ok(i64::from(i)),
+            other => Err(value::unexpected("integer", &other)),
+        }
+    }
+}
+
+impl FromColumn for i32 {
+    fn from_column(value: ParamValue) -> RepoResult<Self> {
+        match value {
+            ParamValue::I32(i) => Ok(i),
+            ParamValue::I64(i) => i32::try_from(i).map_err(RepoError::mapping),
+            other => Err(value::unexpected("integer", &other)),
+        }
+    }
+}
+
+impl FromColumn for f64 {
+    fn from_column(value: ParamValue) -> RepoResult<Self> {
+        match value {
+            ParamValue::F64(f) => Ok(f),
+            ParamValue::I32(i) => Ok(f64::from(i)),
+            ParamValue::I64(i) => Ok(i as f64),
+            other => Err(value::unexpected("floating point", &other)),
+        }
+    }
+}
+
+impl FromColumn for bool {
+    fn from_column(value: ParamValue) -> RepoResult<Self> {
+        match value {
+            ParamValue::Bool(b) => Ok(b),
+            // SQLite and MySQL store booleans as integers.
+            ParamValue::I32(i) => Ok(i != 0),
+            ParamValue::I64(i) => Ok(i != 0),
+            other => Err(value::unexpected("boolean", &other)),
+        }
+    }
+}
+
+impl FromColumn for Vec<u8> {
+    fn from_column(value: ParamValue) -> RepoResult<Self> {
+        value::bytes_from_column(value)
+    }
+}
+
+impl FromColumn for std::time::SystemTime {
+    fn from_column(value: ParamValue) -> RepoResult<Self> {
+        let micros = value::timestamp_from_column(value)?;
+        let offset = std::time::Duration::from_micros(micros.unsigned_abs());
+        Ok(if micros >= 0 {
+            std::time::UNIX_EPOCH + offset
+        } else {
+            std::time::UNIX_EPOCH - offset
+        })
+    }
+}
+
+impl<T: FromColumn> FromColumn for Option<T> {
+    fn from_column(value: ParamValue) -> RepoResult<Self> {
+        match value {
+            ParamValue::Null => Ok(None),
+            other => T::from_column(other).map(Some),
+        }
+    }
+}
+
+#[cfg(feature = "uuid")]
+impl FromColumn for uuid::Uuid {
+    fn from_column(value: ParamValue) -> RepoResult<Self> {
+        value::uuid_from_column(value).map(uuid::Uuid::from_bytes)
+    }
+}
+
+// Days from 0001-01-01 (CE) to 1970-01-01.
+#[cfg(feature = "chrono")]
+const UNIX_EPOCH_DAYS_FROM_CE: i32 = 719_163;
+
+#[cfg(feature = "chrono")]
+impl ToParamValue for chrono::NaiveDateTime {
+    fn to_param_value(&self) -> ParamValue {
+        ParamValue::Timestamp(self.and_utc().timestamp_micros())
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl FromColumn for chrono::NaiveDateTime {
+    fn from_column(value: ParamValue) -> RepoResult<Self> {
+        let micros = value::timestamp_from_column(value)?;
+        chrono::DateTime::from_timestamp_micros(micros)
+            .map(|d| d.naive_utc())
+            .ok_or_else(|| value::out_of_range("timestamp", "NaiveDateTime"))
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl<Tz: chrono::TimeZone> ToParamValue for chrono::DateTime<Tz> {
+    fn to_param_value(&self) -> ParamValue {
+        ParamValue::Timestamp(self.timestamp_micros())
+    }
+}
+
+/// Reads the instant in UTC and converts it into `Tz` (`Utc`, `FixedOffset` or `Local`).
+#[cfg(feature = "chrono")]
+impl<Tz: chrono::TimeZone> FromColumn for chrono::DateTime<Tz>
+where
+    chrono::DateTime<Tz>: From<chrono::DateTime<chrono::Utc>>,
+{
+    fn from_column(value: ParamValue) -> RepoResult<Self> {
+        let micros = value::timestamp_from_column(value)?;
+        chrono::DateTime::from_timestamp_micros(micros)
+            .map(Into::into)
+            .ok_or_else(|| value::out_of_range("timestamp", "DateTime"))
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl ToParamValue for chrono::NaiveDate {
+    fn to_param_value(&self) -> ParamValue {
+        ParamValue::Date(chrono::Datelike::num_days_from_ce(self) - UNIX_EPOCH_DAYS_FROM_CE)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl FromColumn for chrono::NaiveDate {
+    fn from_column(value: ParamValue) -> RepoResult<Self> {
+        let days = value::date_from_column(value)?;
+        days.checked_add(UNIX_EPOCH_DAYS_FROM_CE)
+            .and_then(chrono::NaiveDate::from_num_days_from_ce_opt)
+            .ok_or_else(|| value::out_of_range("date", "NaiveDate"))
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl ToParamValue for chrono::NaiveTime {
+    fn to_param_value(&self) -> ParamValue {
+        use chrono::Timelike;
+        ParamValue::Time(
+            i64::from(self.num_seconds_from_midnight()) * 1_000_000
+                + i64::from(self.nanosecond() / 1_000),
+        )
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl FromColumn for chrono::NaiveTime {
+    fn from_column(value: ParamValue) -> RepoResult<Self> {
+        let micros = value::time_from_column(value)?;
+        u32::try_from(micros / 1_000_000)
+            .ok()
+            .zip(u32::try_from(micros % 1_000_000 * 1_000).ok())
+            .and_then(|(secs, nanos)| {
+                chrono::NaiveTime::from_num_seconds_from_midnight_opt(secs, nanos)
+            })
+            .ok_or_else(|| value::out_of_range("time", "NaiveTime"))
+    }
+}
+
+#[cfg(feature = "rust_decimal")]
+impl ToParamValue for rust_decimal::Decimal {
+    fn to_param_value(&self) -> ParamValue {
+        ParamValue::Decimal(self.to_string())
+    }
+}
+
+#[cfg(feature = "rust_decimal")]
+impl FromColumn for rust_decimal::Decimal {
+    fn from_column(value: ParamValue) -> RepoResult<Self> {
+        value::text_from_column(value)?
+            .parse()
+            .map_err(RepoError::mapping)
+    }
+}
+
+#[cfg(feature = "json")]
+impl ToParamValue for serde_json::Value {
+    fn to_param_value(&self) -> ParamValue {
+        ParamValue::Json(self.to_string())
+    }
+}
+
+#[cfg(feature = "json")]
+impl FromColumn for serde_json::Value {
+    fn from_column(value: ParamValue) -> RepoResult<Self> {
+        serde_json::from_str(&value::text_from_column(value)?).map_err(RepoError::mapping)
+    }
+}
+
+/// Conversion of a primary key into bind parameters, one per entry in
+/// [`Identifiable::ID_COLUMNS`].
+///
+/// Implemented for every [`ToParamValue`] type and for tuples of them (composite keys), so
+/// backends can bind any key without knowing its shape.
+pub trait KeyValues {
+    fn key_values(&self) -> Vec<ParamValue>;
+}
+
+impl<T: ToParamValue> KeyValues for T {
+    fn key_values(&self) -> Vec<ParamValue> {
+        vec![self.to_param_value()]
+    }
+}
+
+macro_rules! impl_key_values_tuple {
+    ($($name:ident),+) => {
+        impl<$($name: KeyValues),+> KeyValues for ($($name,)+) {
+            #[allow(non_snake_case)]
+            fn key_values(&self) -> Vec<ParamValue> {
+                let ($($name,)+) = self;
+                let mut out = Vec::new();
+                $(out.extend($name.key_values());)+
+                out
+            }
+        }
+    };
+}
+
+impl_key_values_tuple!(A, B);
+impl_key_values_tuple!(A, B, C);
+impl_key_values_tuple!(A, B, C, D);
+
 /// Trait for types whose fields can be extracted for an INSERT statement.
 /// This is implemented by the `#[derive(Fetchable)]` macro.
 pub trait Insertable {
@@ -63,8 +443,22 @@ pub trait Updatable {
     /// The columns to be used in an UPDATE statement's SET clause.
     const UPDATE_COLUMNS: &'static [&'static str];
 
-    /// The values of the fields corresponding to `UPDATE_COLUMNS`.
+    /// The optimistic-lock version column (`#[fetch(version)]`), if the entity has one.
+    /// It is not part of `UPDATE_COLUMNS`: updates increment it and require the current value
+    /// in their WHERE clause, so a concurrent change makes them fail with
+    /// [`RepoError::OptimisticLock`].
+    const VERSION_COLUMN: Option<&'static str> = None;
+
+    /// The values of the fields corresponding to `UPDATE_COLUMNS`, followed by the key
+    /// components and then, with a `VERSION_COLUMN`, the expected version.
     fn update_values(&self) -> Vec<ParamValue>;
+
+    /// Advance the version field to the value a successful update wrote. No-op by default.
+    fn bump_version(&mut self) {}
+
+    /// Set the `#[fetch(updated_at)]` field to `now`, the time a successful update wrote
+    /// (see [`clock::with_now`]). No-op by default.
+    fn touch(&mut self, _now: std::time::SystemTime) {}
 }
 
 /// Lightweight, backend-agnostic error type for repository operations.
@@ -85,6 +479,65 @@ pub enum RepoError {
         #[source]
         source: Box<dyn std::error::Error + Send + Sync>,
     },
+    /// A unique or primary key constraint was violated (e.g. a duplicate email).
+    /// `constraint` holds the constraint/index name when the backend reports one
+    /// (SQLite reports the affected `table.column` list instead).
+    #[error("unique constraint violated: {}", .constraint.as_deref().unwrap_or("<unknown>"))]
+    UniqueViolation {
+        constraint: Option<String>,
+        #[source]
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+    /// A foreign key constraint was violated.
+    #[error("foreign key constraint violated: {}", .constraint.as_deref().unwrap_or("<unknown>"))]
+    ForeignKeyViolation {
+        constraint: Option<String>,
+        #[source]
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+    /// A NULL was written to a NOT NULL column.
+    #[error("not-null constraint violated: {}", .column.as_deref().unwrap_or("<unknown>"))]
+    NotNullViolation {
+        column: Option<String>,
+        #[source]
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+    /// The transaction could not be serialized with concurrent transactions; retrying may succeed.
+    #[error("serialization failure")]
+    SerializationFailure {
+        #[source]
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+    /// The transaction was chosen as a deadlock victim; retrying may succeed.
+    #[error("deadlock detected")]
+    Deadlock {
+        #[source]
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+    /// A statement or lock wait timed out (statement timeout, lock wait timeout, busy database).
+    #[error("operation timed out")]
+    Timeout {
+        #[source]
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+    /// The connection to the database was closed or lost.
+    #[error("connection lost")]
+    ConnectionLost {
+        #[source]
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+    /// A versioned update matched no row: the entity was changed or deleted since it was read.
+    #[error("optimistic lock conflict: {table} row was modified or deleted concurrently")]
+    OptimisticLock { table: &'static str },
+    /// A multi-tenant entity was accessed outside `tenant::scope` (and outside `tenant::bypass`).
+    #[error("no tenant scope for {table}: run inside tenant::scope or tenant::bypass")]
+    MissingTenant { table: &'static str },
+    /// A write was attempted in a read-only transaction or against a read-only database.
+    #[error("write attempted in read-only context")]
+    ReadOnlyViolation {
+        #[source]
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
 }
 
 impl RepoError {
@@ -106,6 +559,18 @@ impl RepoError {
             source: Box::new(e),
         }
     }
+
+    /// True for failures that are expected to succeed when the operation (or
+    /// transaction) is retried: serialization failures, deadlocks, timeouts and lost connections.
+    pub fn is_transient(&self) -> bool {
+        matches!(
+            self,
+            RepoError::SerializationFailure { .. }
+                | RepoError::Deadlock { .. }
+                | RepoError::Timeout { .. }
+                | RepoError::ConnectionLost { .. }
+        )
+    }
 }
 
 /// Convenience alias for results returned by repository methods.
@@ -132,6 +597,81 @@ pub trait Repository<T: Identifiable> {
 
     /// Delete an entity by key. Returns true if a row was affected.
     async fn delete_by_id(&self, id: &T::Key) -> RepoResult<bool>;
+
+    /// Fetch all entities matching `criteria`.
+    /// The default implementation reports the operation as unsupported; SQL backends override it.
+    async fn find_where(&self, criteria: &Criteria) -> RepoResult<Vec<T>> {
+        let _ = criteria;
+        Err(unsupported("find_where"))
+    }
+
+    /// Fetch the entities whose `field_name` equals any of `values` in a single query, e.g. the
+    /// children of several parents. Empty `values` match nothing.
+    async fn find_by_field_in(
+        &self,
+        field_name: &str,
+        values: Vec<ParamValue>,
+    ) -> RepoResult<Vec<T>> {
+        if values.is_empty() {
+            return Ok(Vec::new());
+        }
+        self.find_where(&Criteria::In(field_name.to_string(), values))
+            .await
+    }
+
+    /// Count the entities matching `criteria`.
+    async fn count_where(&self, criteria: &Criteria) -> RepoResult<u64> {
+        let _ = criteria;
+        Err(unsupported("count_where"))
+    }
+
+    /// Whether at least one entity matches `criteria`.
+    async fn exists_where(&self, criteria: &Criteria) -> RepoResult<bool> {
+        Ok(self.count_where(criteria).await? > 0)
+    }
+
+    /// Delete all entities matching `criteria`. Returns the number of rows affected.
+    async fn delete_where(&self, criteria: &Criteria) -> RepoResult<u64> {
+        let _ = criteria;
+        Err(unsupported("delete_where"))
+    }
+
+    /// Fetch one page of all entities, sorted by `pageable.sort`, with the total row count.
+    async fn find_page(&self, pageable: &Pageable) -> RepoResult<Page<T>> {
+        let _ = pageable;
+        Err(unsupported("find_page"))
+    }
+
+    /// Fetch one page of the entities whose `field_name` equals `value`, with the total count.
+    async fn find_page_by_field(
+        &self,
+        field_name: &str,
+        value: ParamValue,
+        pageable: &Pageable,
+    ) -> RepoResult<Page<T>> {
+        let _ = (field_name, value, pageable);
+        Err(unsupported("find_page_by_field"))
+    }
+
+    /// Fetch up to `limit` entities continuing from `cursor` (the first page when `None`),
+    /// sorted by `order` with the id columns appended as a tie-breaker. The returned cursors
+    /// are only valid for the same `order`.
+    async fn find_page_after(
+        &self,
+        cursor: Option<Cursor>,
+        limit: usize,
+        order: &[Order],
+    ) -> RepoResult<CursorPage<T>> {
+        let _ = (cursor, limit, order);
+        Err(unsupported("find_page_after"))
+    }
+}
+
+fn unsupported(op: &str) -> RepoError {
+    RepoError::backend(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        format!("{op} is not supported by this repository"),
+    ))
 }
 
 /// A tiny adapter for mapping a backend-specific row type into an entity `T`.
@@ -159,6 +699,45 @@ mod tests {
         assert_eq!(format!("{}", e3), "backend error");
     }
 
+    #[test]
+    fn structured_error_display_and_transience() {
+        let io = || Box::new(std::io::Error::new(std::io::ErrorKind::Other, "driver"));
+        let unique = RepoError::UniqueViolation {
+            constraint: Some("users_email_key".into()),
+            source: io(),
+        };
+        assert_eq!(
+            format!("{}", unique),
+            "unique constraint violated: users_email_key"
+        );
+        assert!(!unique.is_transient());
+
+        let not_null = RepoError::NotNullViolation {
+            column: None,
+            source: io(),
+        };
+        assert_eq!(
+            format!("{}", not_null),
+            "not-null constraint violated: <unknown>"
+        );
+
+        assert!(RepoError::SerializationFailure { source: io() }.is_transient());
+        assert!(RepoError::Deadlock { source: io() }.is_transient());
+        assert!(RepoError::Timeout { source: io() }.is_transient());
+        assert!(RepoError::ConnectionLost { source: io() }.is_transient());
+        assert!(!RepoError::ReadOnlyViolation { source: io() }.is_transient());
+        assert!(!RepoError::NotFound.is_transient());
+        assert!(!RepoError::backend(*io()).is_transient());
+
+        let conflict = RepoError::OptimisticLock { table: "users" };
+        assert_eq!(
+            format!("{}", conflict),
+            "optimistic lock conflict: users row was modified or deleted concurrently"
+        );
+        assert!(!conflict.is_transient());
+        assert!(!RepoError::MissingTenant { table: "users" }.is_transient());
+    }
+
     #[test]
     fn param_value_variants_roundtrip() {
         // Construct all variants and ensure pattern matching reads expected values
@@ -168,6 +747,13 @@ mod tests {
             ParamValue::I64(64),
             ParamValue::F64(6.5),
             ParamValue::Bool(true),
+            ParamValue::Bytes(vec![1, 2]),
+            ParamValue::Uuid([7; 16]),
+            ParamValue::Timestamp(1_000_000),
+            ParamValue::Date(19_000),
+            ParamValue::Time(3_600_000_000),
+            ParamValue::Decimal("1.50".to_string()),
+            ParamValue::Json("{}".to_string()),
             ParamValue::Null,
         ];
 
@@ -178,11 +764,32 @@ mod tests {
                 ParamValue::I64(i) => assert_eq!(i, 64),
                 ParamValue::F64(f) => assert_eq!(f, 6.5),
                 ParamValue::Bool(b) => assert!(b),
+                ParamValue::Bytes(b) => assert_eq!(b, [1, 2]),
+                ParamValue::Uuid(u) => assert_eq!(u, [7; 16]),
+                ParamValue::Timestamp(t) => assert_eq!(t, 1_000_000),
+                ParamValue::Date(d) => assert_eq!(d, 19_000),
+                ParamValue::Time(t) => assert_eq!(t, 3_600_000_000),
+                ParamValue::Decimal(d) => assert_eq!(d, "1.50"),
+                ParamValue::Json(j) => assert_eq!(j, "{}"),
                 ParamValue::Null => assert!(matches!(v, ParamValue::Null)),
             }
         }
     }
 
+    #[test]
+    fn system_time_binds_as_epoch_micros() {
+        let after = std::time::UNIX_EPOCH + std::time::Duration::from_micros(1_500);
+        assert!(matches!(
+            after.to_param_value(),
+            ParamValue::Timestamp(1_500)
+        ));
+        let before = std::time::UNIX_EPOCH - std::time::Duration::from_secs(1);
+        assert!(matches!(
+            before.to_param_value(),
+            ParamValue::Timestamp(-1_000_000)
+        ));
+    }
+
     // A tiny entity and RowAdapter example to exercise trait wiring
     #[derive(Debug, Clone, PartialEq, Eq)]
     struct MiniEntity {
@@ -205,6 +812,45 @@ mod tests {
         }
     }
 
+    #[test]
+    fn key_values_for_scalar_and_composite_keys() {
+        assert_eq!(MiniEntity::ID_COLUMNS, &["id"]);
+        assert!(matches!(7i64.key_values().as_slice(), [ParamValue::I64(7)]));
+
+        let composite = (3i64, String::from("admin"));
+        match composite.key_values().as_slice() {
+            [ParamValue::I64(3), ParamValue::String(role)] => assert_eq!(role, "admin"),
+            other => panic!("unexpected key values: {:?}", other),
+        }
+        assert_eq!((1i32, 2i32, true).key_values().len(), 3);
+    }
+
+    #[derive(Clone)]
+    struct UserId(String);
+
+    impl ToParamValue for UserId {
+        fn to_param_value(&self) -> ParamValue {
+            self.0.to_param_value()
+        }
+    }
+
+    #[test]
+    fn typed_id_newtype_binds_through_to_param_value() {
+        let id = UserId("u-1".into());
+        match id.key_values().as_slice() {
+            [ParamValue::String(s)] => assert_eq!(s, "u-1"),
+            other => panic!("unexpected key values: {:?}", other),
+        }
+        assert!(matches!(
+            (UserId("a".into()), 2i32).key_values().as_slice(),
+            [ParamValue::String(_), ParamValue::I32(2)]
+        ));
+        assert!(matches!(
+            Option::<UserId>::None.to_param_value(),
+            ParamValue::Null
+        ));
+    }
+
     #[test]
     fn row_adapter_from_row_works() {
         let a = MiniAdapter;
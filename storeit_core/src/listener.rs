@@ -0,0 +1,191 @@
+//! Lifecycle hooks around persistence.
+//!
+//! An [`EntityListener`] is either declared on the entity (`#[entity(listener = "...")]`,
+//! surfaced through [`Listenable::listener`]) or registered on a backend repository with
+//! `with_listener`. Backends run the entity's listener first, then the registered ones in
+//! registration order, through [`Listeners`]. The first `Err` aborts the operation before any
+//! SQL is sent (pre-hooks) or is returned in place of the result (post-hooks).
+//!
+//! Hooks are synchronous and run on the calling task; push slow work such as publishing
+//! domain events onto a channel.
+
+use crate::{Identifiable, RepoResult};
+use std::sync::Arc;
+
+/// Callbacks around the persistence of `T`. Every hook defaults to doing nothing.
+pub trait EntityListener<T: Identifiable>: Send + Sync {
+    /// Before `insert` binds the entity; may rewrite it (e.g. normalize an email).
+    fn pre_insert(&self, _entity: &mut T) -> RepoResult<()> {
+        Ok(())
+    }
+
+    /// After `insert`, with the row as read back from the database.
+    fn post_insert(&self, _entity: &T) -> RepoResult<()> {
+        Ok(())
+    }
+
+    /// Before `update` binds the entity; the returned entity includes these changes.
+    fn pre_update(&self, _entity: &mut T) -> RepoResult<()> {
+        Ok(())
+    }
+
+    /// After an entity is mapped from a row by any finder, criteria, page or keyset query.
+    fn post_load(&self, _entity: &mut T) -> RepoResult<()> {
+        Ok(())
+    }
+
+    /// Before `delete_by_id` (or `purge_by_id`). `delete_where` deletes without loading
+    /// rows and does not call it.
+    fn pre_delete(&self, _id: &T::Key) -> RepoResult<()> {
+        Ok(())
+    }
+}
+
+/// Entities whose listener the backends look up. `#[derive(Entity)]` implements it, with the
+/// type named by `#[entity(listener = "...")]` if there is one; hand-written entities can use
+/// an empty impl.
+pub trait Listenable: Identifiable + Sized {
+    /// The listener declared on the entity. Backends run it before the listeners registered
+    /// on a repository.
+    fn listener() -> Option<&'static dyn EntityListener<Self>> {
+        None
+    }
+}
+
+/// The listeners registered on one repository, plus the entity's own.
+pub struct Listeners<T: Identifiable> {
+    registered: Vec<Arc<dyn EntityListener<T>>>,
+}
+
+impl<T: Identifiable> Default for Listeners<T> {
+    fn default() -> Self {
+        Self {
+            registered: Vec::new(),
+        }
+    }
+}
+
+impl<T: Identifiable> Clone for Listeners<T> {
+    fn clone(&self) -> Self {
+        Self {
+            registered: self.registered.clone(),
+        }
+    }
+}
+
+impl<T: Identifiable> Listeners<T> {
+    /// Register `listener` after the ones already present.
+    pub fn push(&mut self, listener: Arc<dyn EntityListener<T>>) {
+        self.registered.push(listener);
+    }
+}
+
+impl<T: Listenable + 'static> Listeners<T> {
+    fn each(&self, mut f: impl FnMut(&dyn EntityListener<T>) -> RepoResult<()>) -> RepoResult<()> {
+        if let Some(own) = T::listener() {
+            f(own)?;
+        }
+        self.registered.iter().try_for_each(|l| f(l.as_ref()))
+    }
+
+    pub fn pre_insert(&self, entity: &mut T) -> RepoResult<()> {
+        self.each(|l| l.pre_insert(entity))
+    }
+
+    pub fn post_insert(&self, entity: &T) -> RepoResult<()> {
+        self.each(|l| l.post_insert(entity))
+    }
+
+    pub fn pre_update(&self, entity: &mut T) -> RepoResult<()> {
+        self.each(|l| l.pre_update(entity))
+    }
+
+    pub fn post_load(&self, entity: &mut T) -> RepoResult<()> {
+        self.each(|l| l.post_load(entity))
+    }
+
+    /// Map `entity` through [`EntityListener::post_load`].
+    pub fn loaded(&self, mut entity: T) -> RepoResult<T> {
+        self.post_load(&mut entity)?;
+        Ok(entity)
+    }
+
+    /// Pass the inserted `entity` through [`EntityListener::post_insert`].
+    pub fn inserted(&self, entity: T) -> RepoResult<T> {
+        self.post_insert(&entity)?;
+        Ok(entity)
+    }
+
+    pub fn pre_delete(&self, id: &T::Key) -> RepoResult<()> {
+        self.each(|l| l.pre_delete(id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RepoError;
+    use std::sync::Mutex;
+
+    #[derive(Debug)]
+    struct Account {
+        id: i64,
+        email: String,
+    }
+
+    struct Lowercase;
+    impl EntityListener<Account> for Lowercase {
+        fn pre_insert(&self, entity: &mut Account) -> RepoResult<()> {
+            entity.email = entity.email.to_lowercase();
+            Ok(())
+        }
+    }
+
+    impl Identifiable for Account {
+        type Key = i64;
+        const ID_COLUMN: &'static str = "id";
+        fn id(&self) -> Option<i64> {
+            Some(self.id)
+        }
+    }
+
+    impl Listenable for Account {
+        fn listener() -> Option<&'static dyn EntityListener<Self>> {
+            Some(&Lowercase)
+        }
+    }
+
+    #[derive(Default)]
+    struct Recorder(Mutex<Vec<String>>);
+    impl EntityListener<Account> for Recorder {
+        fn pre_insert(&self, entity: &mut Account) -> RepoResult<()> {
+            self.0.lock().unwrap().push(entity.email.clone());
+            Ok(())
+        }
+        fn pre_delete(&self, id: &i64) -> RepoResult<()> {
+            if *id == 0 {
+                return Err(RepoError::backend(std::io::Error::new(
+                    std::io::ErrorKind::PermissionDenied,
+                    "account 0 is protected",
+                )));
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn entity_listener_runs_before_registered_ones() {
+        let recorder = Arc::new(Recorder::default());
+        let mut listeners = Listeners::default();
+        listeners.push(recorder.clone());
+        let mut account = Account {
+            id: 1,
+            email: "Ann@Example.com".into(),
+        };
+        listeners.pre_insert(&mut account).unwrap();
+        assert_eq!(account.email, "ann@example.com");
+        assert_eq!(*recorder.0.lock().unwrap(), vec!["ann@example.com"]);
+        assert!(listeners.pre_delete(&0).is_err());
+        assert!(listeners.pre_delete(&1).is_ok());
+    }
+}
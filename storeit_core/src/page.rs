@@ -0,0 +1,454 @@
+//! Page requests and results for the paged repository methods.
+//!
+//! A [`Pageable`] selects a zero-based page of `size` rows with an optional sort order; backends
+//! answer it with one `LIMIT/OFFSET` query plus a `COUNT(*)` and return a [`Page`].
+//!
+//! Keyset pagination instead continues from a [`Cursor`]: the sort-key values of the row a page
+//! ended (or started) at. It costs the same for every page and is stable under concurrent inserts;
+//! backends return a [`CursorPage`] whose cursors encode to opaque URL-safe tokens.
+
+use crate::{ParamValue, RepoError, RepoResult};
+use base64::engine::{general_purpose::URL_SAFE_NO_PAD, Engine as _};
+
+/// Sort direction of a single [`Order`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Direction {
+    #[default]
+    Asc,
+    Desc,
+}
+
+/// Ordering by one column. The column must be one of the entity's `SELECT_COLUMNS`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Order {
+    pub column: String,
+    pub direction: Direction,
+}
+
+impl Order {
+    pub fn asc(column: impl Into<String>) -> Self {
+        Order {
+            column: column.into(),
+            direction: Direction::Asc,
+        }
+    }
+
+    pub fn desc(column: impl Into<String>) -> Self {
+        Order {
+            column: column.into(),
+            direction: Direction::Desc,
+        }
+    }
+}
+
+/// A sort specification: orders applied in sequence, the first being the primary key.
+///
+/// ```
+/// use storeit_core::{Pageable, Sort};
+///
+/// let p = Pageable::new(2, 20).with_sort(Sort::desc("created").then_asc("id"));
+/// assert_eq!(p.sort.len(), 2);
+/// assert_eq!(p.offset(), Some(40));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Sort {
+    pub orders: Vec<Order>,
+}
+
+impl Sort {
+    pub fn asc(column: impl Into<String>) -> Self {
+        Sort {
+            orders: vec![Order::asc(column)],
+        }
+    }
+
+    pub fn desc(column: impl Into<String>) -> Self {
+        Sort {
+            orders: vec![Order::desc(column)],
+        }
+    }
+
+    pub fn then_asc(mut self, column: impl Into<String>) -> Self {
+        self.orders.push(Order::asc(column));
+        self
+    }
+
+    pub fn then_desc(mut self, column: impl Into<String>) -> Self {
+        self.orders.push(Order::desc(column));
+        self
+    }
+}
+
+impl From<Sort> for Vec<Order> {
+    fn from(sort: Sort) -> Self {
+        sort.orders
+    }
+}
+
+/// A request for page `page` (zero-based) of `size` rows, sorted by `sort`.
+///
+/// Without a sort the database may return rows in any order, so pages are only stable when
+/// the sort ends with a unique column such as the id.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pageable {
+    pub page: usize,
+    pub size: usize,
+    pub sort: Vec<Order>,
+}
+
+impl Pageable {
+    pub fn new(page: usize, size: usize) -> Self {
+        Pageable {
+            page,
+            size,
+            sort: Vec::new(),
+        }
+    }
+
+    pub fn with_sort(mut self, sort: impl Into<Vec<Order>>) -> Self {
+        self.sort = sort.into();
+        self
+    }
+
+    /// Number of rows skipped before this page, or `None` if it overflows.
+    pub fn offset(&self) -> Option<usize> {
+        self.page.checked_mul(self.size)
+    }
+}
+
+/// One page of results together with the totals of the whole result set.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// Zero-based page number this page answers.
+    pub page: usize,
+    /// Requested page size.
+    pub size: usize,
+    /// Number of rows matching the query across all pages.
+    pub total: u64,
+    pub total_pages: u64,
+    pub has_next: bool,
+}
+
+impl<T> Page<T> {
+    /// Assemble a page from its rows and the total row count of the query.
+    pub fn new(items: Vec<T>, pageable: &Pageable, total: u64) -> Self {
+        let size = pageable.size as u64;
+        let total_pages = total
+            .checked_div(size)
+            .map_or(0, |full| full + u64::from(total % size != 0));
+        Page {
+            items,
+            page: pageable.page,
+            size: pageable.size,
+            total,
+            total_pages,
+            has_next: (pageable.page as u64).saturating_add(1) < total_pages,
+        }
+    }
+
+    /// Convert the items, keeping the page metadata.
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> Page<U> {
+        Page {
+            items: self.items.into_iter().map(f).collect(),
+            page: self.page,
+            size: self.size,
+            total: self.total,
+            total_pages: self.total_pages,
+            has_next: self.has_next,
+        }
+    }
+}
+
+/// Position in a keyset-paginated result: the sort-key values of a boundary row.
+///
+/// Cursors are produced by `Repository::find_page_after` and are opaque to callers; hand them to
+/// clients with [`Cursor::encode`] (or `to_string()`) and read them back with [`Cursor::decode`]
+/// (or `parse()`). A cursor records the sort columns it was made for, and backends reject it for
+/// a different sort.
+#[derive(Debug, Clone)]
+pub struct Cursor {
+    before: bool,
+    columns: Vec<String>,
+    values: Vec<ParamValue>,
+}
+
+impl Cursor {
+    /// A cursor selecting the rows after the row whose keys are `values`.
+    pub fn after(columns: Vec<String>, values: Vec<ParamValue>) -> Self {
+        Cursor {
+            before: false,
+            columns,
+            values,
+        }
+    }
+
+    /// A cursor selecting the rows before the row whose keys are `values`.
+    pub fn before(columns: Vec<String>, values: Vec<ParamValue>) -> Self {
+        Cursor {
+            before: true,
+            columns,
+            values,
+        }
+    }
+
+    /// Whether this cursor pages backwards (towards the start of the ordering).
+    pub fn is_before(&self) -> bool {
+        self.before
+    }
+
+    /// The key columns, in sort order, ending with the id tie-breaker.
+    pub fn columns(&self) -> &[String] {
+        &self.columns
+    }
+
+    /// The boundary row's values for [`columns`](Self::columns).
+    pub fn values(&self) -> &[ParamValue] {
+        &self.values
+    }
+
+    /// Encode as an opaque, URL-safe token.
+    pub fn encode(&self) -> String {
+        let mut buf = vec![if self.before { b'b' } else { b'a' }];
+        for (column, value) in self.columns.iter().zip(&self.values) {
+            put_field(&mut buf, column.as_bytes());
+            let (tag, body) = encode_value(value);
+            buf.push(tag);
+            put_field(&mut buf, &body);
+        }
+        URL_SAFE_NO_PAD.encode(buf)
+    }
+
+    /// Decode a token produced by [`encode`](Self::encode).
+    pub fn decode(token: &str) -> RepoResult<Self> {
+        let buf = URL_SAFE_NO_PAD.decode(token).map_err(|_| malformed())?;
+        let (&dir, mut rest) = buf.split_first().ok_or_else(malformed)?;
+        let before = match dir {
+            b'a' => false,
+            b'b' => true,
+            _ => return Err(malformed()),
+        };
+        let mut columns = Vec::new();
+        let mut values = Vec::new();
+        while !rest.is_empty() {
+            let column = take_field(&mut rest)?;
+            let (&tag, tail) = rest.split_first().ok_or_else(malformed)?;
+            rest = tail;
+            let body = take_field(&mut rest)?;
+            columns.push(String::from_utf8(column.to_vec()).map_err(|_| malformed())?);
+            values.push(decode_value(tag, body).ok_or_else(malformed)?);
+        }
+        Ok(Cursor {
+            before,
+            columns,
+            values,
+        })
+    }
+}
+
+impl std::fmt::Display for Cursor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.encode())
+    }
+}
+
+impl std::str::FromStr for Cursor {
+    type Err = RepoError;
+
+    fn from_str(s: &str) -> RepoResult<Self> {
+        Cursor::decode(s)
+    }
+}
+
+/// One keyset page: the items plus cursors for the neighbouring pages, if there are any.
+#[derive(Debug, Clone)]
+pub struct CursorPage<T> {
+    pub items: Vec<T>,
+    /// Continues after the last item; `None` on the last page.
+    pub next: Option<Cursor>,
+    /// Continues before the first item; `None` on the first page.
+    pub prev: Option<Cursor>,
+}
+
+impl<T> CursorPage<T> {
+    /// Assemble a page fetched for `request`. `first_key`/`last_key` are the key values of the
+    /// first and last item, and `has_more` says whether the query found rows beyond the page
+    /// in the direction it was paging.
+    pub fn new(
+        items: Vec<T>,
+        columns: Vec<String>,
+        first_key: Option<Vec<ParamValue>>,
+        last_key: Option<Vec<ParamValue>>,
+        request: Option<&Cursor>,
+        has_more: bool,
+    ) -> Self {
+        let backward = request.is_some_and(Cursor::is_before);
+        let (more_next, more_prev) = if backward {
+            (true, has_more)
+        } else {
+            (has_more, request.is_some())
+        };
+        CursorPage {
+            next: last_key
+                .filter(|_| more_next)
+                .map(|k| Cursor::after(columns.clone(), k)),
+            prev: first_key
+                .filter(|_| more_prev)
+                .map(|k| Cursor::before(columns, k)),
+            items,
+        }
+    }
+}
+
+fn malformed() -> RepoError {
+    RepoError::backend(std::io::Error::new(
+        std::io::ErrorKind::InvalidInput,
+        "malformed pagination cursor",
+    ))
+}
+
+// Fields are length-prefixed (`<len>:<bytes>`) so strings and bytes need no escaping.
+fn put_field(buf: &mut Vec<u8>, bytes: &[u8]) {
+    buf.extend_from_slice(bytes.len().to_string().as_bytes());
+    buf.push(b':');
+    buf.extend_from_slice(bytes);
+}
+
+fn take_field<'a>(rest: &mut &'a [u8]) -> RepoResult<&'a [u8]> {
+    let colon = rest.iter().position(|&b| b == b':').ok_or_else(malformed)?;
+    let len: usize = std::str::from_utf8(&rest[..colon])
+        .ok()
+        .and_then(|n| n.parse().ok())
+        .ok_or_else(malformed)?;
+    // A forged length must not overflow the end offset.
+    let end = (colon + 1).checked_add(len).ok_or_else(malformed)?;
+    let body = rest.get(colon + 1..end).ok_or_else(malformed)?;
+    *rest = &rest[end..];
+    Ok(body)
+}
+
+fn encode_value(value: &ParamValue) -> (u8, Vec<u8>) {
+    let text = |t: String| t.into_bytes();
+    match value {
+        ParamValue::String(s) => (b's', text(s.clone())),
+        ParamValue::I32(i) => (b'i', text(i.to_string())),
+        ParamValue::I64(i) => (b'l', text(i.to_string())),
+        ParamValue::F64(f) => (b'f', text(f.to_bits().to_string())),
+        ParamValue::Bool(b) => (b'b', text(u8::from(*b).to_string())),
+        ParamValue::Bytes(b) => (b'x', b.clone()),
+        ParamValue::Uuid(u) => (b'u', u.to_vec()),
+        ParamValue::Timestamp(t) => (b't', text(t.to_string())),
+        ParamValue::Date(d) => (b'd', text(d.to_string())),
+        ParamValue::Time(t) => (b'm', text(t.to_string())),
+        ParamValue::Decimal(d) => (b'c', text(d.clone())),
+        ParamValue::Json(j) => (b'j', text(j.clone())),
+        ParamValue::Null => (b'n', Vec::new()),
+    }
+}
+
+fn decode_value(tag: u8, body: &[u8]) -> Option<ParamValue> {
+    let text = || std::str::from_utf8(body).ok().map(str::to_string);
+    let num = || std::str::from_utf8(body).ok();
+    Some(match tag {
+        b's' => ParamValue::String(text()?),
+        b'i' => ParamValue::I32(num()?.parse().ok()?),
+        b'l' => ParamValue::I64(num()?.parse().ok()?),
+        b'f' => ParamValue::F64(f64::from_bits(num()?.parse().ok()?)),
+        b'b' => ParamValue::Bool(num()? == "1"),
+        b'x' => ParamValue::Bytes(body.to_vec()),
+        b'u' => ParamValue::Uuid(body.try_into().ok()?),
+        b't' => ParamValue::Timestamp(num()?.parse().ok()?),
+        b'd' => ParamValue::Date(num()?.parse().ok()?),
+        b'm' => ParamValue::Time(num()?.parse().ok()?),
+        b'c' => ParamValue::Decimal(text()?),
+        b'j' => ParamValue::Json(text()?),
+        b'n' => ParamValue::Null,
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn page_totals_and_has_next() {
+        let p = Page::new(vec![1, 2], &Pageable::new(0, 2), 5);
+        assert_eq!((p.total_pages, p.has_next), (3, true));
+        let last = Page::new(vec![5], &Pageable::new(2, 2), 5);
+        assert_eq!((last.total_pages, last.has_next), (3, false));
+        let past_end = Page::<i32>::new(vec![], &Pageable::new(9, 2), 5);
+        assert!(!past_end.has_next);
+        let empty = Page::<i32>::new(vec![], &Pageable::new(0, 10), 0);
+        assert_eq!((empty.total_pages, empty.has_next), (0, false));
+        assert_eq!(p.map(|i| i * 10).items, vec![10, 20]);
+    }
+
+    #[test]
+    fn pageable_offset_and_sort() {
+        assert_eq!(Pageable::new(3, 25).offset(), Some(75));
+        assert_eq!(Pageable::new(usize::MAX, 2).offset(), None);
+        let p = Pageable::new(0, 10).with_sort(Sort::asc("name").then_desc("id"));
+        assert_eq!(p.sort, vec![Order::asc("name"), Order::desc("id")]);
+    }
+
+    #[test]
+    fn cursor_tokens_roundtrip_every_variant() {
+        let values = vec![
+            ParamValue::String("a:b,\nc".into()),
+            ParamValue::I32(-3),
+            ParamValue::I64(1 << 40),
+            ParamValue::F64(-0.1),
+            ParamValue::Bool(true),
+            ParamValue::Bytes(vec![0, 255, 58]),
+            ParamValue::Uuid([7; 16]),
+            ParamValue::Timestamp(-1),
+            ParamValue::Date(19_723),
+            ParamValue::Time(3_600_000_000),
+            ParamValue::Decimal("1.50".into()),
+            ParamValue::Json("{}".into()),
+            ParamValue::Null,
+        ];
+        let columns: Vec<String> = (0..values.len()).map(|i| format!("c{i}")).collect();
+        let cursor = Cursor::before(columns.clone(), values.clone());
+        let token = cursor.to_string();
+        assert!(token
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_'));
+
+        let back: Cursor = token.parse().expect("decode");
+        assert!(back.is_before());
+        assert_eq!(back.columns(), columns.as_slice());
+        assert_eq!(format!("{:?}", back.values()), format!("{:?}", values));
+
+        for bad in ["", "Zm9v", "eA", "!!", "YTE6"] {
+            assert!(Cursor::decode(bad).is_err(), "accepted {bad:?}");
+        }
+        // A field length near usize::MAX is rejected instead of overflowing.
+        let forged = URL_SAFE_NO_PAD.encode(format!("a{}:c", usize::MAX));
+        assert!(Cursor::decode(&forged).is_err());
+    }
+
+    #[test]
+    fn cursor_page_links_neighbours() {
+        let cols = vec!["id".to_string()];
+        let key = |i: i64| Some(vec![ParamValue::I64(i)]);
+
+        let first = CursorPage::new(vec![1, 2], cols.clone(), key(1), key(2), None, true);
+        assert!(first.prev.is_none());
+        let next = first.next.expect("next");
+        assert!(!next.is_before());
+
+        let middle = CursorPage::new(vec![3, 4], cols.clone(), key(3), key(4), Some(&next), false);
+        assert!(middle.next.is_none());
+        assert!(middle.prev.as_ref().is_some_and(Cursor::is_before));
+
+        let back = middle.prev.unwrap();
+        let start = CursorPage::new(vec![1, 2], cols.clone(), key(1), key(2), Some(&back), false);
+        assert!(start.prev.is_none());
+        assert!(start.next.is_some());
+
+        let empty = CursorPage::<i32>::new(vec![], cols, None, None, Some(&next), false);
+        assert!(empty.next.is_none() && empty.prev.is_none());
+    }
+}
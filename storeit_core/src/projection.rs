@@ -0,0 +1,69 @@
+//! Read models selecting a subset of an entity's columns.
+//!
+//! `#[derive(Projection)] #[projection(of = User)]` on a smaller struct implements
+//! [`Projection`] for it. The derive checks at compile time, through [`has_column`], that every
+//! field maps to one of the entity's `SELECT_COLUMNS`, and generates a row adapter reading only
+//! [`Projection::COLUMNS`]. Backend repositories use it for `find_by_id_as::<P>()` and
+//! `find_by_field_as::<P>()`.
+
+use crate::Fetchable;
+
+/// A struct filled from some of the columns of [`Projection::Entity`]'s table.
+pub trait Projection: Sized {
+    /// The entity whose table and filters (soft delete, tenant) the projection reads through.
+    type Entity: Fetchable;
+    /// The selected columns, in field order.
+    const COLUMNS: &'static [&'static str];
+    /// The row adapter for backend row type `R`.
+    type Adapter<R>;
+
+    fn adapter<R>() -> Self::Adapter<R>;
+}
+
+/// Whether `columns` contains `name`; usable in constants.
+pub const fn has_column(columns: &[&str], name: &str) -> bool {
+    let mut i = 0;
+    while i < columns.len() {
+        let (a, b) = (columns[i].as_bytes(), name.as_bytes());
+        if a.len() == b.len() {
+            let mut j = 0;
+            while j < a.len() && a[j] == b[j] {
+                j += 1;
+            }
+            if j == a.len() {
+                return true;
+            }
+        }
+        i += 1;
+    }
+    false
+}
+
+/// Whether `columns` contains every name in `names`; usable in constants.
+pub const fn has_columns(columns: &[&str], names: &[&str]) -> bool {
+    let mut i = 0;
+    while i < names.len() {
+        if !has_column(columns, names[i]) {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn has_column_matches_whole_names() {
+        const COLS: &[&str] = &["id", "name", "email_address"];
+        const _: () = assert!(has_column(COLS, "name"));
+        assert!(has_column(COLS, "email_address"));
+        assert!(!has_column(COLS, "email"));
+        assert!(!has_column(COLS, "names"));
+        assert!(!has_column(&[], "id"));
+        assert!(has_columns(COLS, &["name", "id"]));
+        assert!(!has_columns(COLS, &["name", "email"]));
+    }
+}
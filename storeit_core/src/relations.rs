@@ -0,0 +1,207 @@
+//! Relationships between entities.
+//!
+//! A foreign-key field marked `#[fetch(belongs_to = "Customer")]` makes the derive implement
+//! [`BelongsTo<Customer>`] for its entity. [`Relations`], implemented for every
+//! [`Repository`], then loads the related rows: `load_parent` on the parent's repository,
+//! `load_children`/`load_children_for` on the child's. `load_children_for` fetches the children
+//! of many parents with one `IN (...)` query instead of one query per parent.
+
+use crate::{Fetchable, Identifiable, ParamValue, RepoResult, Repository, ToParamValue};
+use async_trait::async_trait;
+use std::collections::hash_map::{Entry, HashMap};
+use std::hash::Hash;
+
+/// A many-to-one reference from `Self` to `P` through the foreign-key column
+/// [`FOREIGN_KEY`](BelongsTo::FOREIGN_KEY), which holds `P`'s key.
+pub trait BelongsTo<P: Identifiable>: Fetchable {
+    /// The column of `Self::TABLE` referencing the parent's key.
+    const FOREIGN_KEY: &'static str;
+
+    /// The referenced parent key; `None` when an optional reference is unset.
+    fn parent_key(&self) -> Option<P::Key>;
+}
+
+/// Association loading on top of [`Repository`]; see the module documentation.
+#[async_trait]
+pub trait Relations<T: Identifiable>: Repository<T> {
+    /// The parent `child` refers to, found in this (the parent's) repository.
+    async fn load_parent<C>(&self, child: &C) -> RepoResult<Option<T>>
+    where
+        C: BelongsTo<T> + Sync,
+        T::Key: Send + Sync,
+    {
+        match child.parent_key() {
+            Some(key) => self.find_by_id(&key).await,
+            None => Ok(None),
+        }
+    }
+
+    /// The children of `parent`, found in this (the children's) repository.
+    async fn load_children<P>(&self, parent: &P) -> RepoResult<Vec<T>>
+    where
+        T: BelongsTo<P>,
+        P: Identifiable + Sync,
+        P::Key: ToParamValue,
+    {
+        let Some(key) = parent.id().map(|k| k.to_param_value()) else {
+            return Ok(Vec::new());
+        };
+        self.find_by_field(T::FOREIGN_KEY, key).await
+    }
+
+    /// The children of every parent in `parents`, grouped by parent key, loaded with a single
+    /// [`find_by_field_in`](Repository::find_by_field_in) query. Every keyed parent has an
+    /// entry, empty when it has no children.
+    async fn load_children_for<P>(&self, parents: &[P]) -> RepoResult<HashMap<P::Key, Vec<T>>>
+    where
+        T: BelongsTo<P> + Send,
+        P: Identifiable + Sync,
+        P::Key: ToParamValue + Hash + Eq + Send,
+    {
+        let mut grouped: HashMap<P::Key, Vec<T>> = HashMap::new();
+        let mut keys: Vec<ParamValue> = Vec::new();
+        for key in parents.iter().filter_map(Identifiable::id) {
+            if let Entry::Vacant(slot) = grouped.entry(key) {
+                keys.push(slot.key().to_param_value());
+                slot.insert(Vec::new());
+            }
+        }
+        for child in self.find_by_field_in(T::FOREIGN_KEY, keys).await? {
+            if let Some(children) = child.parent_key().and_then(|k| grouped.get_mut(&k)) {
+                children.push(child);
+            }
+        }
+        Ok(grouped)
+    }
+}
+
+impl<T: Identifiable, R: Repository<T> + ?Sized> Relations<T> for R {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{unsupported, Criteria, RepoError};
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct Order {
+        id: i64,
+    }
+    impl Identifiable for Order {
+        type Key = i64;
+        const ID_COLUMN: &'static str = "id";
+        fn id(&self) -> Option<i64> {
+            Some(self.id)
+        }
+    }
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct LineItem {
+        id: i64,
+        order_id: i64,
+    }
+    impl Identifiable for LineItem {
+        type Key = i64;
+        const ID_COLUMN: &'static str = "id";
+        fn id(&self) -> Option<i64> {
+            Some(self.id)
+        }
+    }
+    impl Fetchable for LineItem {
+        const TABLE: &'static str = "line_items";
+        const SELECT_COLUMNS: &'static [&'static str] = &["id", "order_id"];
+        const FINDABLE_COLUMNS: &'static [(&'static str, &'static str)] = &[];
+    }
+    impl BelongsTo<Order> for LineItem {
+        const FOREIGN_KEY: &'static str = "order_id";
+        fn parent_key(&self) -> Option<i64> {
+            Some(self.order_id)
+        }
+    }
+
+    // Serves `find_where(In)` from a fixed list and records every criteria it was asked for.
+    struct Items(Vec<LineItem>, std::sync::Mutex<Vec<String>>);
+
+    #[async_trait]
+    impl Repository<LineItem> for Items {
+        async fn find_by_id(&self, _id: &i64) -> RepoResult<Option<LineItem>> {
+            Err(unsupported("find_by_id"))
+        }
+        async fn find_by_field(&self, _f: &str, _v: ParamValue) -> RepoResult<Vec<LineItem>> {
+            Err(unsupported("find_by_field"))
+        }
+        async fn insert(&self, _e: &LineItem) -> RepoResult<LineItem> {
+            Err(unsupported("insert"))
+        }
+        async fn update(&self, _e: &LineItem) -> RepoResult<LineItem> {
+            Err(unsupported("update"))
+        }
+        async fn delete_by_id(&self, _id: &i64) -> RepoResult<bool> {
+            Err(unsupported("delete_by_id"))
+        }
+        async fn find_where(&self, criteria: &Criteria) -> RepoResult<Vec<LineItem>> {
+            self.1.lock().unwrap().push(format!("{criteria:?}"));
+            let Criteria::In(col, values) = criteria else {
+                return Err(RepoError::mapping(std::io::Error::new(
+                    std::io::ErrorKind::InvalidInput,
+                    "expected IN",
+                )));
+            };
+            assert_eq!(col, "order_id");
+            Ok(self
+                .0
+                .iter()
+                .filter(|i| {
+                    values
+                        .iter()
+                        .any(|v| matches!(v, ParamValue::I64(k) if *k == i.order_id))
+                })
+                .cloned()
+                .collect())
+        }
+    }
+
+    #[test]
+    fn children_are_loaded_in_one_query_and_grouped() {
+        let items = Items(
+            vec![
+                LineItem {
+                    id: 1,
+                    order_id: 10,
+                },
+                LineItem {
+                    id: 2,
+                    order_id: 20,
+                },
+                LineItem {
+                    id: 3,
+                    order_id: 10,
+                },
+                LineItem {
+                    id: 4,
+                    order_id: 30,
+                },
+            ],
+            Default::default(),
+        );
+        let orders = [
+            Order { id: 10 },
+            Order { id: 20 },
+            Order { id: 10 },
+            Order { id: 40 },
+        ];
+        let grouped = futures::executor::block_on(items.load_children_for(&orders)).unwrap();
+        assert_eq!(items.1.lock().unwrap().len(), 1);
+        assert_eq!(grouped.len(), 3);
+        assert_eq!(
+            grouped[&10].iter().map(|i| i.id).collect::<Vec<_>>(),
+            [1, 3]
+        );
+        assert_eq!(grouped[&20].len(), 1);
+        assert!(grouped[&40].is_empty());
+
+        let none: [Order; 0] = [];
+        let empty = futures::executor::block_on(items.load_children_for(&none)).unwrap();
+        assert!(empty.is_empty());
+        assert_eq!(items.1.lock().unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,560 @@
+//! Table definitions derived from entity metadata.
+//!
+//! `#[derive(Entity)]` implements [`Schema`] from the field types: `Option` fields are
+//! nullable, the `#[fetch(id)]` fields form the primary key, and `#[fetch(unique)]`,
+//! `#[fetch(index)]` and `#[fetch(sql_type = "...")]` refine single columns. The SQL builder
+//! renders a [`TableDef`] as `CREATE TABLE`/`CREATE INDEX` statements per dialect
+//! (`storeit_sql_builder::create_table`), which is enough to bootstrap test and development
+//! databases.
+//!
+//! Field types the derive does not know map to [`SqlType::Text`]; use `sql_type` for others,
+//! e.g. `#[fetch(sql_type = "INTEGER")]` on a `#[db_enum(integer)]` enum.
+//!
+//! The same definition validates a live database: backends read the table's columns
+//! (`validate_schema`) and [`check`] compares them with the entity, producing a
+//! [`SchemaReport`] a service can refuse to start on.
+
+use crate::{Fetchable, Identifiable, RepoError, RepoResult};
+use std::fmt;
+
+/// A portable column type, rendered per dialect by the SQL builder.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SqlType {
+    /// 32-bit integer (`i32`).
+    Integer,
+    /// 64-bit integer (`i64`).
+    BigInt,
+    /// Double-precision float (`f64`).
+    Double,
+    Boolean,
+    Text,
+    /// Binary data (`Vec<u8>`).
+    Bytes,
+    Uuid,
+    /// Timestamp without time zone (`SystemTime`, `NaiveDateTime`).
+    Timestamp,
+    /// Timestamp with time zone (`DateTime<Tz>`).
+    TimestampTz,
+    Date,
+    Time,
+    Decimal,
+    /// JSON document (`serde_json::Value`, `#[fetch(json)]` fields).
+    Json,
+    /// A type name emitted verbatim for every dialect (`#[fetch(sql_type = "...")]`).
+    Custom(String),
+}
+
+/// One column of a [`TableDef`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColumnDef {
+    pub name: String,
+    pub sql_type: SqlType,
+    pub nullable: bool,
+    /// `#[fetch(unique)]`: a `UNIQUE` constraint on this column alone.
+    pub unique: bool,
+    /// `#[fetch(index)]`: a non-unique index on this column alone.
+    pub indexed: bool,
+}
+
+impl ColumnDef {
+    /// A `NOT NULL` column without constraints or index.
+    pub fn new(name: impl Into<String>, sql_type: SqlType) -> Self {
+        Self {
+            name: name.into(),
+            sql_type,
+            nullable: false,
+            unique: false,
+            indexed: false,
+        }
+    }
+}
+
+/// The table an entity is stored in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableDef {
+    pub name: String,
+    /// Columns in `Fetchable::SELECT_COLUMNS` order, plus a soft-delete column that no field
+    /// maps to.
+    pub columns: Vec<ColumnDef>,
+    /// `Identifiable::ID_COLUMNS`.
+    pub primary_key: Vec<String>,
+    /// `Identifiable::ID_GENERATED`: the database assigns the (single) key.
+    pub key_generated: bool,
+}
+
+impl TableDef {
+    /// The column named `name`, if any.
+    pub fn column(&self, name: &str) -> Option<&ColumnDef> {
+        self.columns.iter().find(|c| c.name == name)
+    }
+}
+
+/// Entities whose table can be described as a [`TableDef`]; implemented by
+/// `#[derive(Entity)]`.
+pub trait Schema: Fetchable + Identifiable {
+    fn table_def() -> TableDef;
+}
+
+/// A column of a live table, as introspected by a backend.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LiveColumn {
+    pub name: String,
+    pub nullable: bool,
+    /// The database fills the column when an INSERT omits it (a default or generated key).
+    pub has_default: bool,
+    pub primary_key: bool,
+}
+
+/// An index of a live table, as introspected by a backend.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LiveIndex {
+    pub name: String,
+    /// The indexed columns in index order.
+    pub columns: Vec<String>,
+    pub unique: bool,
+}
+
+impl LiveIndex {
+    /// Group `(index, unique, column)` rows, ordered by index and then column position, into
+    /// indexes.
+    pub fn group(rows: impl IntoIterator<Item = (String, bool, String)>) -> Vec<LiveIndex> {
+        let mut indexes: Vec<LiveIndex> = Vec::new();
+        for (name, unique, column) in rows {
+            match indexes.last_mut() {
+                Some(last) if last.name == name => last.columns.push(column),
+                _ => indexes.push(LiveIndex {
+                    name,
+                    columns: vec![column],
+                    unique,
+                }),
+            }
+        }
+        indexes
+    }
+}
+
+/// One difference between an entity and its live table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SchemaIssue {
+    /// The table does not exist.
+    MissingTable,
+    /// The entity maps a column the table does not have.
+    MissingColumn(String),
+    /// The entity's `Option`-ness disagrees with the column: `expected_nullable` columns
+    /// reject `None` on write, the others fail to map NULLs on read.
+    Nullability {
+        column: String,
+        expected_nullable: bool,
+    },
+    /// The primary key columns differ from `Identifiable::ID_COLUMNS`.
+    PrimaryKey {
+        expected: Vec<String>,
+        actual: Vec<String>,
+    },
+    /// A NOT NULL column without a default that the entity never writes, so inserts fail.
+    UnmappedRequiredColumn(String),
+}
+
+impl fmt::Display for SchemaIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchemaIssue::MissingTable => write!(f, "table does not exist"),
+            SchemaIssue::MissingColumn(c) => write!(f, "column `{}` does not exist", c),
+            SchemaIssue::Nullability {
+                column,
+                expected_nullable: true,
+            } => write!(
+                f,
+                "column `{}` is NOT NULL but the field is optional",
+                column
+            ),
+            SchemaIssue::Nullability { column, .. } => {
+                write!(
+                    f,
+                    "column `{}` is nullable but the field is not optional",
+                    column
+                )
+            }
+            SchemaIssue::PrimaryKey { expected, actual } => write!(
+                f,
+                "primary key is ({}), expected ({})",
+                actual.join(", "),
+                expected.join(", ")
+            ),
+            SchemaIssue::UnmappedRequiredColumn(c) => write!(
+                f,
+                "column `{}` is NOT NULL without a default but not mapped",
+                c
+            ),
+        }
+    }
+}
+
+/// The result of validating one entity against its live table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaReport {
+    pub table: String,
+    pub issues: Vec<SchemaIssue>,
+}
+
+impl SchemaReport {
+    /// Whether the entity and the table agree.
+    pub fn is_ok(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// `Ok(())` when the entity and the table agree, otherwise a `RepoError::Mapping`
+    /// wrapping this report.
+    pub fn into_result(self) -> RepoResult<()> {
+        if self.is_ok() {
+            Ok(())
+        } else {
+            Err(RepoError::mapping(self))
+        }
+    }
+}
+
+impl fmt::Display for SchemaReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_ok() {
+            return write!(f, "table `{}` matches its entity", self.table);
+        }
+        write!(f, "table `{}` does not match its entity: ", self.table)?;
+        for (i, issue) in self.issues.iter().enumerate() {
+            if i > 0 {
+                write!(f, "; ")?;
+            }
+            write!(f, "{}", issue)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for SchemaReport {}
+
+/// Compare `expected` with the `live` columns of its table; no live columns means the table
+/// does not exist. Names compare case-insensitively, as unquoted SQL identifiers do, and
+/// primary keys compare as sets. Column types are not compared: drivers report them too
+/// differently, and the row mapping already converts between compatible types.
+pub fn check(expected: &TableDef, live: &[LiveColumn]) -> SchemaReport {
+    let mut issues = Vec::new();
+    if live.is_empty() {
+        issues.push(SchemaIssue::MissingTable);
+        return SchemaReport {
+            table: expected.name.clone(),
+            issues,
+        };
+    }
+    let find = |name: &str| live.iter().find(|c| c.name.eq_ignore_ascii_case(name));
+    for column in &expected.columns {
+        match find(&column.name) {
+            None => issues.push(SchemaIssue::MissingColumn(column.name.clone())),
+            Some(l) if l.nullable != column.nullable && !l.primary_key => {
+                issues.push(SchemaIssue::Nullability {
+                    column: column.name.clone(),
+                    expected_nullable: column.nullable,
+                })
+            }
+            Some(_) => {}
+        }
+    }
+    let mut expected_key: Vec<String> = expected
+        .primary_key
+        .iter()
+        .map(|c| c.to_ascii_lowercase())
+        .collect();
+    let mut actual_key: Vec<String> = live
+        .iter()
+        .filter(|c| c.primary_key)
+        .map(|c| c.name.to_ascii_lowercase())
+        .collect();
+    expected_key.sort();
+    actual_key.sort();
+    if expected_key != actual_key {
+        issues.push(SchemaIssue::PrimaryKey {
+            expected: expected.primary_key.clone(),
+            actual: live
+                .iter()
+                .filter(|c| c.primary_key)
+                .map(|c| c.name.clone())
+                .collect(),
+        });
+    }
+    for column in live {
+        let mapped = expected
+            .columns
+            .iter()
+            .any(|c| c.name.eq_ignore_ascii_case(&column.name));
+        if !mapped && !column.nullable && !column.has_default {
+            issues.push(SchemaIssue::UnmappedRequiredColumn(column.name.clone()));
+        }
+    }
+    SchemaReport {
+        table: expected.name.clone(),
+        issues,
+    }
+}
+
+/// One change bringing a live table in line with its entity; see [`diff`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SchemaChange {
+    /// Create the missing table with its indexes.
+    CreateTable,
+    /// Add the entity column of this name.
+    AddColumn(String),
+    /// Drop a column the entity does not map.
+    DropColumn(String),
+    /// Make the column nullable or NOT NULL, as the entity's field is optional or not.
+    SetNullable { column: String, nullable: bool },
+    /// Index a `#[fetch(index)]` column, or enforce a `#[fetch(unique)]` one.
+    CreateIndex { column: String, unique: bool },
+}
+
+/// The changes turning the live table (its `columns` and `indexes`) into `expected`, for a
+/// proposed migration: additions and nullability changes in column order, then drops, then
+/// indexes. Names compare case-insensitively. Primary keys and column types are left alone,
+/// as in [`check`]. A unique column counts as indexed when a unique index covers exactly
+/// that column; an indexed column when some index starts with it.
+pub fn diff(
+    expected: &TableDef,
+    columns: &[LiveColumn],
+    indexes: &[LiveIndex],
+) -> Vec<SchemaChange> {
+    if columns.is_empty() {
+        return vec![SchemaChange::CreateTable];
+    }
+    let same = |a: &str, b: &str| a.eq_ignore_ascii_case(b);
+    let mut changes = Vec::new();
+    let mut new_indexes = Vec::new();
+    for column in &expected.columns {
+        let live = columns.iter().find(|c| same(&c.name, &column.name));
+        match live {
+            None => changes.push(SchemaChange::AddColumn(column.name.clone())),
+            Some(l) if l.nullable != column.nullable && !l.primary_key => {
+                changes.push(SchemaChange::SetNullable {
+                    column: column.name.clone(),
+                    nullable: column.nullable,
+                })
+            }
+            Some(_) => {}
+        }
+        let keyed = live.is_some_and(|l| l.primary_key);
+        let unique_index = indexes.iter().any(|i| {
+            i.unique && matches!(i.columns.as_slice(), [only] if same(only, &column.name))
+        });
+        let leading_index = indexes
+            .iter()
+            .any(|i| i.columns.first().is_some_and(|c| same(c, &column.name)));
+        if column.unique && !keyed && !unique_index {
+            new_indexes.push(SchemaChange::CreateIndex {
+                column: column.name.clone(),
+                unique: true,
+            });
+        } else if column.indexed && !column.unique && !keyed && !leading_index {
+            new_indexes.push(SchemaChange::CreateIndex {
+                column: column.name.clone(),
+                unique: false,
+            });
+        }
+    }
+    for column in columns {
+        let mapped = expected.columns.iter().any(|c| same(&c.name, &column.name));
+        if !mapped && !column.primary_key {
+            changes.push(SchemaChange::DropColumn(column.name.clone()));
+        }
+    }
+    changes.extend(new_indexes);
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn users() -> TableDef {
+        TableDef {
+            name: "users".into(),
+            columns: vec![
+                ColumnDef::new("id", SqlType::BigInt),
+                ColumnDef::new("email", SqlType::Text),
+                ColumnDef {
+                    nullable: true,
+                    ..ColumnDef::new("nickname", SqlType::Text)
+                },
+            ],
+            primary_key: vec!["id".into()],
+            key_generated: true,
+        }
+    }
+
+    fn live(name: &str, nullable: bool) -> LiveColumn {
+        LiveColumn {
+            name: name.into(),
+            nullable,
+            has_default: false,
+            primary_key: false,
+        }
+    }
+
+    #[test]
+    fn matching_tables_have_no_issues() {
+        let id = LiveColumn {
+            primary_key: true,
+            has_default: true,
+            ..live("ID", true)
+        };
+        let report = check(
+            &users(),
+            &[
+                id,
+                live("email", false),
+                live("nickname", true),
+                live("note", true),
+            ],
+        );
+        assert!(report.is_ok(), "{}", report);
+        assert!(report.into_result().is_ok());
+    }
+
+    #[test]
+    fn live_indexes_group_rows_in_order() {
+        let rows = [
+            ("idx_a".to_string(), false, "a".to_string()),
+            ("idx_a".to_string(), false, "b".to_string()),
+            ("uq_c".to_string(), true, "c".to_string()),
+        ];
+        assert_eq!(
+            LiveIndex::group(rows),
+            vec![
+                LiveIndex {
+                    name: "idx_a".into(),
+                    columns: vec!["a".into(), "b".into()],
+                    unique: false,
+                },
+                LiveIndex {
+                    name: "uq_c".into(),
+                    columns: vec!["c".into()],
+                    unique: true,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn diff_proposes_columns_nullability_and_indexes() {
+        let mut expected = users();
+        expected.columns[1].unique = true;
+        expected.columns.push(ColumnDef {
+            indexed: true,
+            ..ColumnDef::new("age", SqlType::Integer)
+        });
+        let id = LiveColumn {
+            primary_key: true,
+            ..live("id", false)
+        };
+        let columns = [
+            id,
+            live("EMAIL", false),
+            live("nickname", false),
+            live("legacy", true),
+        ];
+        assert_eq!(
+            diff(&expected, &columns, &[]),
+            vec![
+                SchemaChange::SetNullable {
+                    column: "nickname".into(),
+                    nullable: true,
+                },
+                SchemaChange::AddColumn("age".into()),
+                SchemaChange::DropColumn("legacy".into()),
+                SchemaChange::CreateIndex {
+                    column: "email".into(),
+                    unique: true,
+                },
+                SchemaChange::CreateIndex {
+                    column: "age".into(),
+                    unique: false,
+                },
+            ]
+        );
+
+        // Existing indexes satisfy the entity, whatever their names.
+        let indexes = [
+            LiveIndex {
+                name: "users_email_key".into(),
+                columns: vec!["email".into()],
+                unique: true,
+            },
+            LiveIndex {
+                name: "by_age".into(),
+                columns: vec!["age".into(), "email".into()],
+                unique: false,
+            },
+        ];
+        let mut columns = columns.to_vec();
+        columns[2].nullable = true;
+        columns.pop();
+        columns.push(live("age", false));
+        assert!(diff(&expected, &columns, &indexes).is_empty());
+        assert_eq!(diff(&expected, &[], &[]), vec![SchemaChange::CreateTable]);
+    }
+
+    #[test]
+    fn drift_is_reported_per_column() {
+        let report = check(
+            &users(),
+            &[
+                live("id", false),
+                live("email", true),
+                live("nickname", false),
+                live("tenant", false),
+            ],
+        );
+        assert_eq!(
+            report.issues,
+            vec![
+                SchemaIssue::Nullability {
+                    column: "email".into(),
+                    expected_nullable: false
+                },
+                SchemaIssue::Nullability {
+                    column: "nickname".into(),
+                    expected_nullable: true
+                },
+                SchemaIssue::PrimaryKey {
+                    expected: vec!["id".into()],
+                    actual: vec![]
+                },
+                SchemaIssue::UnmappedRequiredColumn("tenant".into()),
+            ]
+        );
+        assert!(report
+            .to_string()
+            .starts_with("table `users` does not match its entity: column `email` is nullable"));
+        assert!(matches!(
+            report.into_result(),
+            Err(RepoError::Mapping { .. })
+        ));
+
+        let missing = check(&users(), &[]);
+        assert_eq!(missing.issues, vec![SchemaIssue::MissingTable]);
+        let renamed = check(
+            &users(),
+            &[
+                LiveColumn {
+                    primary_key: true,
+                    ..live("id", false)
+                },
+                live("mail", false),
+                live("nickname", true),
+            ],
+        );
+        assert_eq!(
+            renamed.issues,
+            vec![
+                SchemaIssue::MissingColumn("email".into()),
+                SchemaIssue::UnmappedRequiredColumn("mail".into()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,154 @@
+//! Task-local tenant scope for shared-schema multi-tenancy.
+//!
+//! Entities with a `TENANT_COLUMN` (`#[entity(tenant = "tenant_id")]`) can only be accessed
+//! inside [`scope`], which every backend reads the same way it reads its transaction
+//! task-locals: inserts stamp the tenant column and every SELECT/UPDATE/DELETE built by
+//! `storeit_sql_builder` carries `AND tenant_id = <ph>`. Under [`bypass`] backends use
+//! statements built without that condition, so cross-tenant maintenance code sees every row. Anywhere else, and
+//! in a scope whose tenant is NULL, the operation fails with [`RepoError::MissingTenant`].
+//!
+//! Like all task-locals, the scope does not follow `tokio::spawn`; wrap spawned futures in
+//! their own `scope`.
+
+use crate::{Fetchable, Insertable, ParamValue, RepoError, RepoResult, ToParamValue};
+use std::future::Future;
+
+/// The tenant context of the current task.
+#[derive(Debug, Clone)]
+pub enum TenantScope {
+    /// Only rows of this tenant are visible, and inserts are stamped with it.
+    Tenant(ParamValue),
+    /// Tenant filtering is explicitly switched off.
+    Bypass,
+}
+
+tokio::task_local! {
+    static TENANT: TenantScope;
+}
+
+/// Run `fut` with `tenant` as the current tenant. A NULL tenant (e.g. `None`) is not a
+/// tenant: operations on tenant-scoped entities inside it fail with
+/// [`RepoError::MissingTenant`].
+///
+/// ```
+/// # async fn demo() {
+/// let tenant = storeit_core::tenant::scope("acme", async {
+///     storeit_core::tenant::current()
+/// })
+/// .await;
+/// assert!(matches!(tenant, Some(storeit_core::tenant::TenantScope::Tenant(_))));
+/// # }
+/// ```
+pub async fn scope<F: Future>(tenant: impl ToParamValue, fut: F) -> F::Output {
+    TENANT
+        .scope(TenantScope::Tenant(tenant.to_param_value()), fut)
+        .await
+}
+
+/// Run `fut` without tenant filtering, e.g. for migrations or cross-tenant reports.
+pub async fn bypass<F: Future>(fut: F) -> F::Output {
+    TENANT.scope(TenantScope::Bypass, fut).await
+}
+
+/// The tenant context of the current task, if any.
+pub fn current() -> Option<TenantScope> {
+    TENANT.try_with(Clone::clone).ok()
+}
+
+/// Whether the current task runs under [`bypass`]; backends then use statements built
+/// without the tenant condition.
+pub fn is_bypassed() -> bool {
+    matches!(current(), Some(TenantScope::Bypass))
+}
+
+/// The bind values for the tenant condition of `E`'s statements: the scoped tenant for
+/// entities with a tenant column, none without one or under [`bypass`].
+pub fn filter_values<E: Fetchable>() -> RepoResult<Vec<ParamValue>> {
+    if E::TENANT_COLUMN.is_none() {
+        return Ok(Vec::new());
+    }
+    match current() {
+        Some(TenantScope::Tenant(ParamValue::Null)) | None => {
+            Err(RepoError::MissingTenant { table: E::TABLE })
+        }
+        Some(TenantScope::Tenant(t)) => Ok(vec![t]),
+        Some(TenantScope::Bypass) => Ok(Vec::new()),
+    }
+}
+
+/// Overwrite the tenant column in `values` (ordered like `INSERT_COLUMNS`) with the scoped
+/// tenant. Under [`bypass`] the entity's own value is kept.
+pub fn stamp_insert<E: Fetchable + Insertable>(values: &mut [ParamValue]) -> RepoResult<()> {
+    let Some(col) = E::TENANT_COLUMN else {
+        return Ok(());
+    };
+    match current() {
+        Some(TenantScope::Tenant(ParamValue::Null)) | None => {
+            Err(RepoError::MissingTenant { table: E::TABLE })
+        }
+        Some(TenantScope::Tenant(t)) => {
+            if let Some(i) = E::INSERT_COLUMNS.iter().position(|c| *c == col) {
+                if let Some(slot) = values.get_mut(i) {
+                    *slot = t;
+                }
+            }
+            Ok(())
+        }
+        Some(TenantScope::Bypass) => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::executor::block_on;
+
+    struct Note;
+    impl Fetchable for Note {
+        const TABLE: &'static str = "notes";
+        const SELECT_COLUMNS: &'static [&'static str] = &["id", "tenant_id", "body"];
+        const FINDABLE_COLUMNS: &'static [(&'static str, &'static str)] = &[];
+        const TENANT_COLUMN: Option<&'static str> = Some("tenant_id");
+    }
+    impl Insertable for Note {
+        const INSERT_COLUMNS: &'static [&'static str] = &["tenant_id", "body"];
+        fn insert_values(&self) -> Vec<ParamValue> {
+            vec![ParamValue::Null, ParamValue::String("b".into())]
+        }
+    }
+
+    #[test]
+    fn filter_values_follow_the_scope() {
+        assert!(matches!(
+            filter_values::<Note>(),
+            Err(RepoError::MissingTenant { table: "notes" })
+        ));
+        let scoped = block_on(scope(7i64, async { filter_values::<Note>() })).unwrap();
+        assert!(matches!(scoped.as_slice(), [ParamValue::I64(7)]));
+        let bypassed = block_on(bypass(async { filter_values::<Note>() })).unwrap();
+        assert!(bypassed.is_empty());
+        assert!(block_on(bypass(async { is_bypassed() })));
+        // A NULL tenant is no tenant rather than every tenant.
+        assert!(matches!(
+            block_on(scope(None::<String>, async { filter_values::<Note>() })),
+            Err(RepoError::MissingTenant { table: "notes" })
+        ));
+        // Nested scopes win.
+        let inner = block_on(scope(1i64, scope(2i64, async { filter_values::<Note>() })));
+        assert!(matches!(inner.unwrap().as_slice(), [ParamValue::I64(2)]));
+    }
+
+    #[test]
+    fn stamp_insert_overwrites_the_tenant_column() {
+        let mut values = Note.insert_values();
+        assert!(stamp_insert::<Note>(&mut values).is_err());
+        block_on(scope("acme", async { stamp_insert::<Note>(&mut values) })).unwrap();
+        assert!(matches!(&values[0], ParamValue::String(t) if t == "acme"));
+        block_on(bypass(async { stamp_insert::<Note>(&mut values) })).unwrap();
+        assert!(matches!(&values[0], ParamValue::String(t) if t == "acme"));
+        assert!(block_on(scope(None::<String>, async {
+            stamp_insert::<Note>(&mut values)
+        }))
+        .is_err());
+    }
+}
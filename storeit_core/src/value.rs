@@ -0,0 +1,320 @@
+//! Calendar arithmetic and canonical text forms for [`ParamValue`] variants.
+//!
+//! Backends use these when a database has no native column type for a value (e.g. SQLite
+//! stores dates as text). The text forms sort lexicographically in time order, so range
+//! queries over them behave like they do over native columns.
+//!
+//! The `*_from_column` helpers go the other way: they accept every representation a backend
+//! may read for a logical type (a native variant or its text form) and are the building
+//! blocks of [`FromColumn`](crate::FromColumn) impls.
+
+use crate::{ParamValue, RepoError, RepoResult};
+
+const MICROS_PER_DAY: i64 = 86_400_000_000;
+
+/// Converts days since 1970-01-01 into a proleptic Gregorian `(year, month, day)`.
+pub fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    // Howard Hinnant's `civil_from_days`, with eras of 400 years starting on March 1st.
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// Converts a proleptic Gregorian date into days since 1970-01-01.
+pub fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y.rem_euclid(400);
+    let m = i64::from(month);
+    let doy = (153 * (if m > 2 { m - 3 } else { m + 9 }) + 2) / 5 + i64::from(day) - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// Splits microseconds since the Unix epoch into days since 1970-01-01 and microseconds
+/// since midnight.
+pub fn split_timestamp(micros: i64) -> (i64, i64) {
+    (
+        micros.div_euclid(MICROS_PER_DAY),
+        micros.rem_euclid(MICROS_PER_DAY),
+    )
+}
+
+/// Splits microseconds since midnight into `(hour, minute, second, microsecond)`.
+pub fn time_parts(micros: i64) -> (u32, u32, u32, u32) {
+    let secs = micros.div_euclid(1_000_000);
+    (
+        (secs / 3_600) as u32,
+        (secs / 60 % 60) as u32,
+        (secs % 60) as u32,
+        micros.rem_euclid(1_000_000) as u32,
+    )
+}
+
+/// Hyphenated lowercase form, e.g. `67e55044-10b1-426f-9247-bb680e5fe0c8`.
+pub fn format_uuid(bytes: &[u8; 16]) -> String {
+    let mut out = String::with_capacity(36);
+    for (i, b) in bytes.iter().enumerate() {
+        if matches!(i, 4 | 6 | 8 | 10) {
+            out.push('-');
+        }
+        out.push_str(&format!("{:02x}", b));
+    }
+    out
+}
+
+/// `YYYY-MM-DD`.
+pub fn format_date(days: i32) -> String {
+    let (y, m, d) = civil_from_days(i64::from(days));
+    format!("{:04}-{:02}-{:02}", y, m, d)
+}
+
+/// `HH:MM:SS`, followed by `.ffffff` when there is a fractional part.
+pub fn format_time(micros: i64) -> String {
+    let (h, m, s, us) = time_parts(micros);
+    if us == 0 {
+        format!("{:02}:{:02}:{:02}", h, m, s)
+    } else {
+        format!("{:02}:{:02}:{:02}.{:06}", h, m, s, us)
+    }
+}
+
+/// `YYYY-MM-DD HH:MM:SS[.ffffff]` in UTC, the form SQLite's date functions understand.
+pub fn format_timestamp(micros: i64) -> String {
+    let (days, time) = split_timestamp(micros);
+    format!("{} {}", format_date(days as i32), format_time(time))
+}
+
+/// Parses the hyphenated (or plain 32 hex digit) form of a UUID.
+pub fn parse_uuid(s: &str) -> Option<[u8; 16]> {
+    let hex: Vec<u8> = s.bytes().filter(|b| *b != b'-').collect();
+    if hex.len() != 32 {
+        return None;
+    }
+    let mut out = [0u8; 16];
+    for (i, pair) in hex.chunks(2).enumerate() {
+        let pair = std::str::from_utf8(pair).ok()?;
+        out[i] = u8::from_str_radix(pair, 16).ok()?;
+    }
+    Some(out)
+}
+
+/// Parses `YYYY-MM-DD` into days since 1970-01-01.
+pub fn parse_date(s: &str) -> Option<i32> {
+    let mut parts = s.splitn(3, '-');
+    let (y, m, d) = (parts.next()?, parts.next()?, parts.next()?);
+    let (y, m, d): (i64, u32, u32) = (y.parse().ok()?, m.parse().ok()?, d.parse().ok()?);
+    if !(1..=12).contains(&m) || !(1..=31).contains(&d) {
+        return None;
+    }
+    i32::try_from(days_from_civil(y, m, d)).ok()
+}
+
+/// Parses `HH:MM[:SS[.fraction]]` into microseconds since midnight.
+pub fn parse_time(s: &str) -> Option<i64> {
+    let (hms, frac) = match s.split_once('.') {
+        Some((hms, frac)) => (hms, frac),
+        None => (s, ""),
+    };
+    let mut parts = hms.split(':');
+    let h: i64 = parts.next()?.parse().ok()?;
+    let m: i64 = parts.next()?.parse().ok()?;
+    let sec: i64 = parts.next().map_or(Some(0), |p| p.parse().ok())?;
+    if parts.next().is_some() || !frac.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    // Truncate or right-pad the fraction to microseconds.
+    let micros = format!("{:0<6}", &frac[..frac.len().min(6)])
+        .parse::<i64>()
+        .ok()?;
+    Some(((h * 60 + m) * 60 + sec) * 1_000_000 + micros)
+}
+
+/// Parses `YYYY-MM-DD[ T]HH:MM:SS[.fraction]` with an optional `Z` or `±HH[:MM]` offset
+/// into microseconds since the Unix epoch. A missing offset means UTC.
+pub fn parse_timestamp(s: &str) -> Option<i64> {
+    let s = s.trim();
+    if s.len() < 10 {
+        return None;
+    }
+    let (date, rest) = s.split_at(10);
+    let days = i64::from(parse_date(date)?);
+    let rest = rest.trim_start_matches([' ', 'T']);
+    if rest.is_empty() {
+        return Some(days * MICROS_PER_DAY);
+    }
+    let (time, offset_secs) = if let Some(t) = rest.strip_suffix('Z') {
+        (t, 0)
+    } else if let Some(pos) = rest.rfind(['+', '-']) {
+        let (t, off) = rest.split_at(pos);
+        let sign = if off.starts_with('-') { -1 } else { 1 };
+        let off = off[1..].replace(':', "");
+        let (oh, om) = off.split_at(off.len().min(2));
+        let oh: i64 = oh.parse().ok()?;
+        let om: i64 = if om.is_empty() { 0 } else { om.parse().ok()? };
+        (t, sign * (oh * 3_600 + om * 60))
+    } else {
+        (rest, 0)
+    };
+    Some(days * MICROS_PER_DAY + parse_time(time.trim_end())? - offset_secs * 1_000_000)
+}
+
+pub(crate) fn unexpected(expected: &str, got: &ParamValue) -> RepoError {
+    RepoError::mapping(std::io::Error::new(
+        std::io::ErrorKind::InvalidData,
+        format!("expected a {} column, got {:?}", expected, got),
+    ))
+}
+
+#[cfg(feature = "chrono")]
+pub(crate) fn out_of_range(what: &str, ty: &str) -> RepoError {
+    RepoError::mapping(std::io::Error::new(
+        std::io::ErrorKind::InvalidData,
+        format!("{} out of range for {}", what, ty),
+    ))
+}
+
+/// Text content: strings, decimals, JSON, UTF-8 bytes and (formatted) UUIDs.
+pub fn text_from_column(value: ParamValue) -> RepoResult<String> {
+    match value {
+        ParamValue::String(s) | ParamValue::Decimal(s) | ParamValue::Json(s) => Ok(s),
+        ParamValue::Uuid(u) => Ok(format_uuid(&u)),
+        ParamValue::Bytes(b) => String::from_utf8(b).map_err(RepoError::mapping),
+        other => Err(unexpected("text", &other)),
+    }
+}
+
+/// Binary content: bytes or the UTF-8 bytes of a string.
+pub fn bytes_from_column(value: ParamValue) -> RepoResult<Vec<u8>> {
+    match value {
+        ParamValue::Bytes(b) => Ok(b),
+        ParamValue::String(s) => Ok(s.into_bytes()),
+        other => Err(unexpected("binary", &other)),
+    }
+}
+
+/// A UUID: native, 16 raw bytes, or its text form.
+pub fn uuid_from_column(value: ParamValue) -> RepoResult<[u8; 16]> {
+    match value {
+        ParamValue::Uuid(u) => Ok(u),
+        ParamValue::Bytes(b) if b.len() == 16 => Ok(b.try_into().expect("length checked")),
+        ParamValue::String(ref s) => parse_uuid(s).ok_or_else(|| unexpected("UUID", &value)),
+        other => Err(unexpected("UUID", &other)),
+    }
+}
+
+/// Microseconds since the Unix epoch: native timestamps, dates (at midnight) or their text form.
+pub fn timestamp_from_column(value: ParamValue) -> RepoResult<i64> {
+    match value {
+        ParamValue::Timestamp(t) => Ok(t),
+        ParamValue::Date(d) => Ok(i64::from(d) * MICROS_PER_DAY),
+        ParamValue::String(ref s) => {
+            parse_timestamp(s).ok_or_else(|| unexpected("timestamp", &value))
+        }
+        other => Err(unexpected("timestamp", &other)),
+    }
+}
+
+/// Days since 1970-01-01: native dates, the date part of a timestamp, or the text form.
+pub fn date_from_column(value: ParamValue) -> RepoResult<i32> {
+    match value {
+        ParamValue::Date(d) => Ok(d),
+        ParamValue::Timestamp(t) => Ok(split_timestamp(t).0 as i32),
+        ParamValue::String(ref s) => parse_date(s).ok_or_else(|| unexpected("date", &value)),
+        other => Err(unexpected("date", &other)),
+    }
+}
+
+/// Microseconds since midnight: native times or the text form.
+pub fn time_from_column(value: ParamValue) -> RepoResult<i64> {
+    match value {
+        ParamValue::Time(t) => Ok(t),
+        ParamValue::String(ref s) => parse_time(s).ok_or_else(|| unexpected("time", &value)),
+        other => Err(unexpected("time", &other)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn civil_conversions_roundtrip() {
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(civil_from_days(-1), (1969, 12, 31));
+        assert_eq!(civil_from_days(10_957), (2000, 1, 1));
+        assert_eq!(civil_from_days(11_016), (2000, 2, 29));
+        for days in [-719_468, -1, 0, 59, 10_957, 19_723, 2_932_896] {
+            let (y, m, d) = civil_from_days(days);
+            assert_eq!(days_from_civil(y, m, d), days);
+        }
+    }
+
+    #[test]
+    fn text_forms() {
+        let mut bytes = [0u8; 16];
+        bytes[0] = 0x67;
+        bytes[15] = 0xc8;
+        assert_eq!(format_uuid(&bytes), "67000000-0000-0000-0000-0000000000c8");
+        assert_eq!(format_date(19_723), "2024-01-01");
+        assert_eq!(format_time(3_723_000_000), "01:02:03");
+        assert_eq!(format_time(3_723_000_042), "01:02:03.000042");
+        assert_eq!(format_timestamp(-1_000_000), "1969-12-31 23:59:59");
+        assert_eq!(
+            format_timestamp(1_704_067_200_500_000),
+            "2024-01-01 00:00:00.500000"
+        );
+    }
+
+    #[test]
+    fn text_forms_parse_back() {
+        let u = [0xab; 16];
+        assert_eq!(parse_uuid(&format_uuid(&u)), Some(u));
+        assert_eq!(parse_uuid("not-a-uuid"), None);
+        assert_eq!(parse_date("2024-01-01"), Some(19_723));
+        assert_eq!(parse_date("2024-13-01"), None);
+        assert_eq!(parse_time("01:02:03.000042"), Some(3_723_000_042));
+        assert_eq!(parse_time("01:02:03.5"), Some(3_723_500_000));
+        assert_eq!(parse_time("01:02"), Some(3_720_000_000));
+        for t in [-1_000_000, 0, 1_704_067_200_500_000] {
+            assert_eq!(parse_timestamp(&format_timestamp(t)), Some(t));
+        }
+        assert_eq!(
+            parse_timestamp("2024-01-01T01:00:00+01:00"),
+            Some(1_704_067_200_000_000)
+        );
+        assert_eq!(
+            parse_timestamp("2024-01-01T00:00:00Z"),
+            Some(1_704_067_200_000_000)
+        );
+        assert_eq!(parse_timestamp("2024-01-01"), Some(1_704_067_200_000_000));
+    }
+
+    #[test]
+    fn column_helpers_accept_native_and_text_forms() {
+        assert_eq!(
+            timestamp_from_column(ParamValue::String("1970-01-02 00:00:00".into())).unwrap(),
+            MICROS_PER_DAY
+        );
+        assert_eq!(date_from_column(ParamValue::Timestamp(-1)).unwrap(), -1);
+        assert_eq!(
+            uuid_from_column(ParamValue::Bytes(vec![1; 16])).unwrap(),
+            [1; 16]
+        );
+        assert_eq!(
+            text_from_column(ParamValue::Bytes(b"abc".to_vec())).unwrap(),
+            "abc"
+        );
+        assert!(matches!(
+            time_from_column(ParamValue::Bool(true)),
+            Err(RepoError::Mapping { .. })
+        ));
+    }
+}
@@ -24,6 +24,8 @@ fn main() -> Result<(), storeit_core::RepoError> {
             self.id
         }
     }
+
+    impl storeit_core::Listenable for User {}
     impl storeit_core::Insertable for User {
         const INSERT_COLUMNS: &'static [&'static str] = &["email"];
         fn insert_values(&self) -> Vec<storeit_core::ParamValue> {
@@ -48,6 +48,91 @@ mod backend {
         static SP_DEPTH: RefCell<usize>;
     }
 
+    /// Translate a libsql error into a structured [`RepoError`](storeit_core::RepoError).
+    ///
+    /// Local SQLite failures carry an extended result code which drives the classification;
+    /// remote (Hrana) failures only carry the SQLite message, so those are classified by text.
+    /// Anything unrecognized stays an opaque `RepoError::Backend`.
+    pub fn map_libsql_error(e: libsql::Error) -> storeit_core::RepoError {
+        let (code, message) = match &e {
+            libsql::Error::SqliteFailure(code, msg) => (*code, msg.clone()),
+            libsql::Error::RemoteSqliteFailure(_, extended, msg) => (*extended, msg.clone()),
+            libsql::Error::Hrana(_) => {
+                let msg = e.to_string();
+                (code_from_message(&msg), msg)
+            }
+            libsql::Error::ConnectionFailed(_) => {
+                return storeit_core::RepoError::ConnectionLost {
+                    source: Box::new(e),
+                }
+            }
+            _ => return storeit_core::RepoError::backend(e),
+        };
+        classify_sqlite(code, &message, Box::new(e))
+    }
+
+    // SQLite (extended) result codes, see https://www.sqlite.org/rescode.html
+    const SQLITE_BUSY: i32 = 5;
+    const SQLITE_LOCKED: i32 = 6;
+    const SQLITE_READONLY: i32 = 8;
+    const SQLITE_BUSY_SNAPSHOT: i32 = 517;
+    const SQLITE_CONSTRAINT_FOREIGNKEY: i32 = 787;
+    const SQLITE_CONSTRAINT_NOTNULL: i32 = 1299;
+    const SQLITE_CONSTRAINT_PRIMARYKEY: i32 = 1555;
+    const SQLITE_CONSTRAINT_UNIQUE: i32 = 2067;
+
+    fn code_from_message(msg: &str) -> i32 {
+        if msg.contains("UNIQUE constraint failed") {
+            SQLITE_CONSTRAINT_UNIQUE
+        } else if msg.contains("FOREIGN KEY constraint failed") {
+            SQLITE_CONSTRAINT_FOREIGNKEY
+        } else if msg.contains("NOT NULL constraint failed") {
+            SQLITE_CONSTRAINT_NOTNULL
+        } else if msg.contains("database is locked") {
+            SQLITE_BUSY
+        } else if msg.contains("readonly database") {
+            SQLITE_READONLY
+        } else {
+            0
+        }
+    }
+
+    /// SQLite reports the offending columns after the colon, e.g.
+    /// "UNIQUE constraint failed: users.email".
+    fn constraint_detail(msg: &str) -> Option<String> {
+        msg.split_once("failed: ")
+            .map(|(_, detail)| detail.trim().trim_end_matches('`').to_string())
+            .filter(|d| !d.is_empty())
+    }
+
+    fn classify_sqlite(
+        code: i32,
+        message: &str,
+        source: Box<dyn std::error::Error + Send + Sync>,
+    ) -> storeit_core::RepoError {
+        use storeit_core::RepoError;
+        match code {
+            SQLITE_CONSTRAINT_UNIQUE | SQLITE_CONSTRAINT_PRIMARYKEY => RepoError::UniqueViolation {
+                constraint: constraint_detail(message),
+                source,
+            },
+            SQLITE_CONSTRAINT_FOREIGNKEY => RepoError::ForeignKeyViolation {
+                constraint: constraint_detail(message),
+                source,
+            },
+            SQLITE_CONSTRAINT_NOTNULL => RepoError::NotNullViolation {
+                column: constraint_detail(message),
+                source,
+            },
+            SQLITE_BUSY_SNAPSHOT => RepoError::SerializationFailure { source },
+            c if c & 0xff == SQLITE_BUSY || c & 0xff == SQLITE_LOCKED => {
+                RepoError::Timeout { source }
+            }
+            c if c & 0xff == SQLITE_READONLY => RepoError::ReadOnlyViolation { source },
+            _ => RepoError::Backend { source },
+        }
+    }
+
     fn begin_sql(isolation: Isolation) -> &'static str {
         match isolation {
             Isolation::Default | Isolation::ReadCommitted => "BEGIN DEFERRED",
@@ -70,6 +155,11 @@ mod backend {
             Self { db }
         }
 
+        /// The database this manager opens its connections on.
+        pub fn database(&self) -> &Arc<Database> {
+            &self.db
+        }
+
         /// Vend a repository bound to the current transaction connection if available,
         /// otherwise a regular repository against the manager's database.
         pub async fn repository<T, A>(
@@ -90,6 +180,22 @@ mod backend {
                 None => LibsqlRepository::new(self.db.clone(), adapter),
             })
         }
+
+        /// Compare `T` with its live table (`PRAGMA table_info`); see `storeit_core::schema`.
+        pub async fn validate_schema<T: Schema>(&self) -> storeit_core::RepoResult<SchemaReport> {
+            let conn = self.db.connect().map_err(map_libsql_error)?;
+            let live = live_columns(&conn, T::TABLE).await?;
+            Ok(schema::check(&T::table_def(), &live))
+        }
+
+        /// The changes that would bring `T`'s live table in line with it, for
+        /// `storeit_sql_builder::migration_script`; see `storeit_core::schema::diff`.
+        pub async fn schema_diff<T: Schema>(&self) -> storeit_core::RepoResult<Vec<SchemaChange>> {
+            let conn = self.db.connect().map_err(map_libsql_error)?;
+            let columns = live_columns(&conn, T::TABLE).await?;
+            let indexes = live_indexes(&conn, T::TABLE).await?;
+            Ok(schema::diff(&T::table_def(), &columns, &indexes))
+        }
     }
 
     #[async_trait::async_trait]
@@ -130,9 +236,7 @@ mod backend {
                 let conn = if active {
                     TX_STACK.with(|cell| cell.borrow().last().cloned().expect("stack non-empty"))
                 } else {
-                    self.db
-                        .connect()
-                        .map_err(storeit_core::RepoError::backend)?
+                    self.db.connect().map_err(map_libsql_error)?
                 };
 
                 if !active {
@@ -146,7 +250,7 @@ mod backend {
                         .ok();
                     conn.execute(begin_sql(def.isolation), ())
                         .await
-                        .map_err(storeit_core::RepoError::backend)?;
+                        .map_err(map_libsql_error)?;
                     TX_STACK.with(|cell| cell.borrow_mut().push(conn.clone()));
                     SP_DEPTH.with(|d| *d.borrow_mut() = 0);
                     created_tx = true;
@@ -169,13 +273,11 @@ mod backend {
 
                 if created_tx {
                     if result.is_ok() {
-                        conn.execute("COMMIT", ())
-                            .await
-                            .map_err(storeit_core::RepoError::backend)?;
+                        conn.execute("COMMIT", ()).await.map_err(map_libsql_error)?;
                     } else {
                         conn.execute("ROLLBACK", ())
                             .await
-                            .map_err(storeit_core::RepoError::backend)?;
+                            .map_err(map_libsql_error)?;
                     }
                     if def.read_only {
                         conn.execute("PRAGMA query_only = OFF", ()).await.ok();
@@ -222,14 +324,99 @@ mod backend {
         }
     }
     use async_trait::async_trait;
-    use libsql::{params, Database, Row, Value};
+    use libsql::{Database, Row, Value};
     use std::collections::HashMap;
     use std::marker::PhantomData;
-    use std::sync::Mutex;
+    use std::sync::{Mutex, OnceLock};
+    use storeit_core::schema::{self, LiveColumn, LiveIndex, SchemaChange, SchemaReport};
     use storeit_core::{
-        Fetchable, Identifiable, Insertable, ParamValue, RepoError, RepoResult, Repository,
-        RowAdapter, Updatable,
+        Criteria, Cursor, CursorPage, EntityListener, Fetchable, Identifiable, Insertable,
+        KeyValues, Listenable, Listeners, Order, Page, Pageable, ParamValue, Projection, RepoError,
+        RepoResult, Repository, RowAdapter, Schema, ToParamValue, Updatable,
     };
+    use storeit_sql_builder::Dialect;
+
+    // Read the columns of `table` for `storeit_core::schema::check`; none when it does not exist.
+    async fn live_columns(conn: &libsql::Connection, table: &str) -> RepoResult<Vec<LiveColumn>> {
+        let mut rows = conn
+            .query(
+                "SELECT name, \"notnull\", dflt_value IS NOT NULL, pk FROM pragma_table_info(?1)",
+                [table],
+            )
+            .await
+            .map_err(map_libsql_error)?;
+        let mut columns = Vec::new();
+        while let Some(row) = rows.next().await.map_err(map_libsql_error)? {
+            let primary_key = row.get::<i64>(3).map_err(map_libsql_error)? > 0;
+            columns.push(LiveColumn {
+                name: row.get::<String>(0).map_err(map_libsql_error)?,
+                // An INTEGER PRIMARY KEY aliases the rowid: reported nullable, but never NULL.
+                nullable: row.get::<i64>(1).map_err(map_libsql_error)? == 0 && !primary_key,
+                has_default: row.get::<i64>(2).map_err(map_libsql_error)? != 0,
+                primary_key,
+            });
+        }
+        Ok(columns)
+    }
+
+    // The indexes of `table`, including those backing UNIQUE constraints.
+    async fn live_indexes(conn: &libsql::Connection, table: &str) -> RepoResult<Vec<LiveIndex>> {
+        let mut rows = conn
+            .query(
+                "SELECT il.name, il.\"unique\", ii.name FROM pragma_index_list(?1) AS il, \
+                 pragma_index_info(il.name) AS ii ORDER BY il.name, ii.seqno",
+                [table],
+            )
+            .await
+            .map_err(map_libsql_error)?;
+        let mut entries = Vec::new();
+        while let Some(row) = rows.next().await.map_err(map_libsql_error)? {
+            entries.push((
+                row.get::<String>(0).map_err(map_libsql_error)?,
+                row.get::<i64>(1).map_err(map_libsql_error)? != 0,
+                row.get::<String>(2).map_err(map_libsql_error)?,
+            ));
+        }
+        Ok(LiveIndex::group(entries))
+    }
+
+    // Run a `SELECT COUNT(*) ...` statement and read its single value.
+    async fn query_count(
+        conn: &libsql::Connection,
+        sql: &str,
+        params: Vec<Value>,
+    ) -> RepoResult<u64> {
+        let mut rows = conn.query(sql, params).await.map_err(map_libsql_error)?;
+        let count: i64 = match rows.next().await.map_err(map_libsql_error)? {
+            Some(row) => row.get(0).map_err(map_libsql_error)?,
+            None => 0,
+        };
+        Ok(count as u64)
+    }
+
+    // Bind parameters for a (possibly composite) key, in `ID_COLUMNS` order.
+    fn key_params<K: KeyValues>(key: &K) -> Vec<Value> {
+        key.key_values().into_iter().map(to_libsql_value).collect()
+    }
+
+    // Bind values for the tenant condition ending every statement of a tenant-scoped entity.
+    fn tenant_params<T: Fetchable>() -> RepoResult<Vec<Value>> {
+        Ok(storeit_core::tenant::filter_values::<T>()?
+            .into_iter()
+            .map(to_libsql_value)
+            .collect())
+    }
+
+    // `key_params` followed by `tenant_params`, for the by-id statements.
+    fn scoped_key_params<T>(key: &T::Key) -> RepoResult<Vec<Value>>
+    where
+        T: Fetchable + Identifiable,
+        T::Key: KeyValues,
+    {
+        let mut params = key_params(key);
+        params.extend(tenant_params::<T>()?);
+        Ok(params)
+    }
 
     // Helper function to convert ParamValue to libsql::Value.
     fn to_libsql_value(p: ParamValue) -> Value {
@@ -239,14 +426,43 @@ mod backend {
             ParamValue::I64(i) => i.into(),
             ParamValue::F64(f) => f.into(),
             ParamValue::Bool(b) => (b as i64).into(), // SQLite bools are 0/1
+            ParamValue::Bytes(b) => Value::Blob(b),
+            // SQLite has no date/time, UUID, decimal or JSON storage classes; use the
+            // canonical text forms, which its date and JSON functions understand.
+            ParamValue::Uuid(u) => storeit_core::value::format_uuid(&u).into(),
+            ParamValue::Timestamp(t) => storeit_core::value::format_timestamp(t).into(),
+            ParamValue::Date(d) => storeit_core::value::format_date(d).into(),
+            ParamValue::Time(t) => storeit_core::value::format_time(t).into(),
+            ParamValue::Decimal(d) => d.into(),
+            ParamValue::Json(j) => j.into(),
             ParamValue::Null => Value::Null,
         }
     }
 
+    /// Read column `idx` of `row` as a [`ParamValue`] following SQLite's storage classes
+    /// (INTEGER, REAL, TEXT, BLOB). Backs `FromColumn`-based entity mapping.
+    pub fn column_value(row: &Row, idx: usize) -> RepoResult<ParamValue> {
+        Ok(
+            match row.get_value(idx as i32).map_err(RepoError::mapping)? {
+                Value::Null => ParamValue::Null,
+                Value::Integer(i) => ParamValue::I64(i),
+                Value::Real(f) => ParamValue::F64(f),
+                Value::Text(s) => ParamValue::String(s),
+                Value::Blob(b) => ParamValue::Bytes(b),
+            },
+        )
+    }
+
     /// A fully asynchronous, `libsql`-backed repository.
     struct RepoSql<T> {
+        dialect: Dialect,
+        /// Whether the statements carry the tenant condition (see `statements()`).
+        tenant: bool,
         select_by_id: String,
+        select_by_id_with_deleted: String,
         delete_by_id: String,
+        soft_delete_by_id: Option<String>,
+        restore_by_id: Option<String>,
         insert: String,
         update_by_id: String,
         find_by_field_cache: Mutex<HashMap<String, String>>,
@@ -257,14 +473,35 @@ mod backend {
     where
         T: Fetchable + Identifiable + Insertable + Updatable,
     {
-        fn new() -> Self {
-            let select_by_id = storeit_sql_builder::select_by_id::<T>(T::ID_COLUMN);
-            let delete_by_id = storeit_sql_builder::delete_by_id::<T>(T::ID_COLUMN);
-            let insert = storeit_sql_builder::insert::<T>(T::ID_COLUMN);
-            let update_by_id = storeit_sql_builder::update_by_id::<T>(T::ID_COLUMN);
+        fn new(tenant: bool) -> Self {
+            let dialect = Dialect::Sqlite;
+            let select_by_id =
+                storeit_sql_builder::select_by_id::<T>(dialect, tenant, T::ID_COLUMNS);
+            let delete_by_id =
+                storeit_sql_builder::delete_by_id::<T>(dialect, tenant, T::ID_COLUMNS);
+            let insert = storeit_sql_builder::insert::<T>(dialect, T::ID_COLUMN);
+            let update_by_id =
+                storeit_sql_builder::update_by_id::<T>(dialect, tenant, T::ID_COLUMNS);
             Self {
+                dialect,
+                tenant,
                 select_by_id,
+                select_by_id_with_deleted: storeit_sql_builder::select_by_id_with_deleted::<T>(
+                    dialect,
+                    tenant,
+                    T::ID_COLUMNS,
+                ),
                 delete_by_id,
+                soft_delete_by_id: storeit_sql_builder::soft_delete_by_id::<T>(
+                    dialect,
+                    tenant,
+                    T::ID_COLUMNS,
+                ),
+                restore_by_id: storeit_sql_builder::restore_by_id::<T>(
+                    dialect,
+                    tenant,
+                    T::ID_COLUMNS,
+                ),
                 insert,
                 update_by_id,
                 find_by_field_cache: Mutex::new(HashMap::new()),
@@ -280,7 +517,7 @@ mod backend {
             if let Some(s) = guard.get(field) {
                 return s.clone();
             }
-            let built = storeit_sql_builder::select_by_field::<T>(field);
+            let built = storeit_sql_builder::select_by_field::<T>(self.dialect, self.tenant, field);
             guard.insert(field.to_string(), built.clone());
             built
         }
@@ -298,9 +535,28 @@ mod backend {
         conn: Option<libsql::Connection>,
         adapter: A,
         sql: RepoSql<T>,
+        /// `sql` without the tenant condition, for `tenant::bypass`; built on first use.
+        sql_bypass: OnceLock<RepoSql<T>>,
+        listeners: Listeners<T>,
         _marker: PhantomData<T>,
     }
 
+    impl<T, A> LibsqlRepository<T, A>
+    where
+        T: Fetchable + Identifiable + Insertable + Updatable + 'static,
+        A: RowAdapter<T> + Send + Sync + 'static,
+    {
+        // The cached statements for the current tenant context. Both sets are built with an
+        // explicit tenant flag, so where the repository was constructed does not matter.
+        fn statements(&self) -> &RepoSql<T> {
+            if T::TENANT_COLUMN.is_some() && storeit_core::tenant::is_bypassed() {
+                self.sql_bypass.get_or_init(|| RepoSql::new(false))
+            } else {
+                &self.sql
+            }
+        }
+    }
+
     impl<T, A> LibsqlRepository<T, A>
     where
         T: Identifiable + 'static,
@@ -311,12 +567,14 @@ mod backend {
         where
             T: Fetchable + Identifiable + Insertable + Updatable,
         {
-            let sql = RepoSql::<T>::new();
+            let sql = RepoSql::<T>::new(true);
             Self {
                 db,
                 conn: None,
                 adapter,
                 sql,
+                sql_bypass: OnceLock::new(),
+                listeners: Listeners::default(),
                 _marker: PhantomData,
             }
         }
@@ -327,12 +585,14 @@ mod backend {
         where
             T: Fetchable + Identifiable + Insertable + Updatable,
         {
-            let sql = RepoSql::<T>::new();
+            let sql = RepoSql::<T>::new(true);
             Self {
                 db,
                 conn: Some(conn),
                 adapter,
                 sql,
+                sql_bypass: OnceLock::new(),
+                listeners: Listeners::default(),
                 _marker: PhantomData,
             }
         }
@@ -348,45 +608,243 @@ mod backend {
         {
             // Database::open is deprecated upstream; keep a narrow allow here until Builder migration
             #[allow(deprecated)]
-            let db = Arc::new(Database::open(database_url).map_err(RepoError::backend)?);
+            let db = Arc::new(Database::open(database_url).map_err(map_libsql_error)?);
             Ok(Self::new(db, adapter))
         }
     }
 
+    // Lifecycle listeners (see `storeit_core::listener`).
+    impl<T, A> LibsqlRepository<T, A>
+    where
+        T: Fetchable
+            + Identifiable
+            + Insertable
+            + Updatable
+            + Listenable
+            + Send
+            + Sync
+            + Clone
+            + 'static,
+        A: RowAdapter<T, Row = Row> + Send + Sync + 'static,
+        T::Key: Clone + Send + Sync + 'static + KeyValues,
+    {
+        /// Register a listener that runs after the entity's own and earlier registrations.
+        pub fn with_listener(mut self, listener: impl EntityListener<T> + 'static) -> Self {
+            self.listeners.push(Arc::new(listener));
+            self
+        }
+
+        // Prefer an active transaction-bound connection if present in task-local storage.
+        fn connection(&self) -> RepoResult<libsql::Connection> {
+            if let Ok(Some(tx_conn)) = TX_STACK.try_with(|cell| cell.borrow().last().cloned()) {
+                Ok(tx_conn)
+            } else if let Some(c) = &self.conn {
+                Ok(c.clone())
+            } else {
+                self.db.connect().map_err(map_libsql_error)
+            }
+        }
+
+        // Map a row to an entity and run the `post_load` hooks on it.
+        fn load(&self, row: &Row) -> RepoResult<T> {
+            self.listeners.loaded(self.adapter.from_row(row)?)
+        }
+    }
+
+    // Explicit access to soft-deleted rows (see `Fetchable::SOFT_DELETE_COLUMN`).
+    impl<T, A> LibsqlRepository<T, A>
+    where
+        T: Fetchable
+            + Identifiable
+            + Insertable
+            + Updatable
+            + Listenable
+            + Send
+            + Sync
+            + Clone
+            + 'static,
+        A: RowAdapter<T, Row = Row> + Send + Sync + 'static,
+        T::Key: Clone + Send + Sync + 'static + KeyValues,
+    {
+        /// Find an entity by id whether or not it is soft-deleted.
+        pub async fn find_with_deleted(&self, id: &T::Key) -> RepoResult<Option<T>> {
+            let __start = Instant::now();
+            let conn = self.connection()?;
+            let mut rows = conn
+                .query(
+                    &self.statements().select_by_id_with_deleted,
+                    scoped_key_params::<T>(id)?,
+                )
+                .await
+                .map_err(map_libsql_error)?;
+            let found = match rows.next().await.map_err(map_libsql_error)? {
+                Some(row) => Some(self.load(&row)?),
+                None => None,
+            };
+            obs_record(
+                "find_with_deleted",
+                T::TABLE,
+                __start,
+                found.is_some() as usize,
+                true,
+            );
+            Ok(found)
+        }
+
+        /// Clear the soft-delete column of a deleted row. Returns whether a row was restored.
+        /// Fails for entities without a soft-delete column.
+        pub async fn restore_by_id(&self, id: &T::Key) -> RepoResult<bool> {
+            let __start = Instant::now();
+            let sql = self.statements().restore_by_id.as_ref().ok_or_else(|| {
+                RepoError::backend(std::io::Error::new(
+                    std::io::ErrorKind::InvalidInput,
+                    format!("{} has no soft-delete column", T::TABLE),
+                ))
+            })?;
+            let n = self
+                .connection()?
+                .execute(sql, scoped_key_params::<T>(id)?)
+                .await
+                .map_err(map_libsql_error)?;
+            obs_record("restore_by_id", T::TABLE, __start, n as usize, true);
+            Ok(n > 0)
+        }
+
+        /// Physically delete a row, soft-deleted or not. Returns whether a row was removed.
+        pub async fn purge_by_id(&self, id: &T::Key) -> RepoResult<bool> {
+            self.listeners.pre_delete(id)?;
+            let __start = Instant::now();
+            let n = self
+                .connection()?
+                .execute(&self.statements().delete_by_id, scoped_key_params::<T>(id)?)
+                .await
+                .map_err(map_libsql_error)?;
+            obs_record("purge_by_id", T::TABLE, __start, n as usize, true);
+            Ok(n > 0)
+        }
+    }
+
+    // Projections (see `storeit_core::Projection`): only the projected columns are selected.
+    impl<T, A> LibsqlRepository<T, A>
+    where
+        T: Fetchable
+            + Identifiable
+            + Insertable
+            + Updatable
+            + Listenable
+            + Send
+            + Sync
+            + Clone
+            + 'static,
+        A: RowAdapter<T, Row = Row> + Send + Sync + 'static,
+        T::Key: Clone + Send + Sync + 'static + KeyValues,
+    {
+        /// Find the projection `P` of the entity with the given id.
+        pub async fn find_by_id_as<P>(&self, id: &T::Key) -> RepoResult<Option<P>>
+        where
+            P: Projection<Entity = T>,
+            P::Adapter<Row>: RowAdapter<P, Row = Row>,
+        {
+            let __start = Instant::now();
+            let sql = storeit_sql_builder::select_columns_by_id::<T>(
+                self.statements().dialect,
+                self.statements().tenant,
+                P::COLUMNS,
+                T::ID_COLUMNS,
+            );
+            let mut rows = self
+                .connection()?
+                .query(&sql, scoped_key_params::<T>(id)?)
+                .await
+                .map_err(map_libsql_error)?;
+            let found = match rows.next().await.map_err(map_libsql_error)? {
+                Some(row) => Some(P::adapter::<Row>().from_row(&row)?),
+                None => None,
+            };
+            obs_record(
+                "find_by_id_as",
+                T::TABLE,
+                __start,
+                found.is_some() as usize,
+                true,
+            );
+            Ok(found)
+        }
+
+        /// Find the projections `P` of the entities whose `field_name` column equals `value`.
+        pub async fn find_by_field_as<P>(
+            &self,
+            field_name: &str,
+            value: ParamValue,
+        ) -> RepoResult<Vec<P>>
+        where
+            P: Projection<Entity = T>,
+            P::Adapter<Row>: RowAdapter<P, Row = Row>,
+        {
+            let __start = Instant::now();
+            let sql = storeit_sql_builder::select_columns_by_field::<T>(
+                self.statements().dialect,
+                self.statements().tenant,
+                P::COLUMNS,
+                field_name,
+            );
+            let mut values = vec![to_libsql_value(value)];
+            values.extend(tenant_params::<T>()?);
+            let mut rows = self
+                .connection()?
+                .query(&sql, values)
+                .await
+                .map_err(map_libsql_error)?;
+
+            let adapter = P::adapter::<Row>();
+            let mut found = Vec::new();
+            while let Some(row) = rows.next().await.map_err(map_libsql_error)? {
+                found.push(adapter.from_row(&row)?);
+            }
+            obs_record("find_by_field_as", T::TABLE, __start, found.len(), true);
+            Ok(found)
+        }
+    }
+
+    // Schema validation (see `storeit_core::schema`).
+    impl<T, A> LibsqlRepository<T, A>
+    where
+        T: Schema + Insertable + Updatable + Listenable + Send + Sync + Clone + 'static,
+        A: RowAdapter<T, Row = Row> + Send + Sync + 'static,
+        T::Key: Clone + Send + Sync + 'static + KeyValues,
+    {
+        /// Compare `T` with its live table (`PRAGMA table_info`); see `storeit_core::schema`.
+        pub async fn validate_schema(&self) -> RepoResult<SchemaReport> {
+            let live = live_columns(&self.connection()?, T::TABLE).await?;
+            Ok(schema::check(&T::table_def(), &live))
+        }
+    }
+
     #[async_trait]
     impl<T, A> Repository<T> for LibsqlRepository<T, A>
     where
-        T: Fetchable + Identifiable + Insertable + Updatable + Send + Sync + Clone + 'static,
-        A: RowAdapter<T, Row = Row> + Send + Sync + 'static,
-        T::Key: Clone
+        T: Fetchable
+            + Identifiable
+            + Insertable
+            + Updatable
+            + Listenable
             + Send
             + Sync
-            + 'static
-            + Default
-            + PartialEq
-            + Into<libsql::Value>
-            + serde::Serialize
-            + serde::de::DeserializeOwned,
+            + Clone
+            + 'static,
+        A: RowAdapter<T, Row = Row> + Send + Sync + 'static,
+        T::Key: Clone + Send + Sync + 'static + KeyValues,
     {
         async fn find_by_id(&self, id: &T::Key) -> RepoResult<Option<T>> {
             let __start = Instant::now();
-            // Prefer an active transaction-bound connection if present in task-local storage.
-            let conn = if let Ok(Some(tx_conn)) =
-                TX_STACK.try_with(|cell| cell.borrow().last().cloned())
-            {
-                tx_conn
-            } else if let Some(c) = &self.conn {
-                c.clone()
-            } else {
-                self.db.connect().map_err(RepoError::backend)?
-            };
+            let conn = self.connection()?;
             let mut rows = conn
-                .query(&self.sql.select_by_id, params!(id.clone()))
+                .query(&self.statements().select_by_id, scoped_key_params::<T>(id)?)
                 .await
-                .map_err(RepoError::backend)?;
+                .map_err(map_libsql_error)?;
 
             if let Ok(Some(row)) = rows.next().await {
-                let entity = self.adapter.from_row(&row)?;
+                let entity = self.load(&row)?;
                 obs_record("find_by_id", T::TABLE, __start, 1, true);
                 Ok(Some(entity))
             } else {
@@ -397,183 +855,383 @@ mod backend {
 
         async fn find_by_field(&self, field_name: &str, value: ParamValue) -> RepoResult<Vec<T>> {
             let __start = Instant::now();
-            let sql = self.sql.get_select_by_field(field_name);
-            let value_param = to_libsql_value(value);
-            // Prefer an active transaction-bound connection if present in task-local storage.
-            let conn = if let Ok(Some(tx_conn)) =
-                TX_STACK.try_with(|cell| cell.borrow().last().cloned())
-            {
-                tx_conn
-            } else if let Some(c) = &self.conn {
-                c.clone()
-            } else {
-                self.db.connect().map_err(RepoError::backend)?
-            };
-            let mut rows = conn
-                .query(&sql, params!(value_param))
-                .await
-                .map_err(RepoError::backend)?;
+            let sql = self.statements().get_select_by_field(field_name);
+            let mut values = vec![to_libsql_value(value)];
+            values.extend(tenant_params::<T>()?);
+            let conn = self.connection()?;
+            let mut rows = conn.query(&sql, values).await.map_err(map_libsql_error)?;
 
             let mut entities = Vec::new();
             while let Ok(Some(row)) = rows.next().await {
-                entities.push(self.adapter.from_row(&row)?);
+                entities.push(self.load(&row)?);
             }
             let len = entities.len();
             obs_record("find_by_field", T::TABLE, __start, len, true);
             Ok(entities)
         }
 
+        async fn find_by_field_in(
+            &self,
+            field_name: &str,
+            values: Vec<ParamValue>,
+        ) -> RepoResult<Vec<T>> {
+            if values.is_empty() {
+                return Ok(Vec::new());
+            }
+            let __start = Instant::now();
+            let sql = storeit_sql_builder::select_by_in::<T>(
+                self.statements().dialect,
+                self.statements().tenant,
+                field_name,
+                values.len(),
+            );
+            let mut params: Vec<Value> = values.into_iter().map(to_libsql_value).collect();
+            params.extend(tenant_params::<T>()?);
+            let mut rows = self
+                .connection()?
+                .query(&sql, params)
+                .await
+                .map_err(map_libsql_error)?;
+
+            let mut entities = Vec::new();
+            while let Some(row) = rows.next().await.map_err(map_libsql_error)? {
+                entities.push(self.load(&row)?);
+            }
+            obs_record("find_by_field_in", T::TABLE, __start, entities.len(), true);
+            Ok(entities)
+        }
+
         async fn insert(&self, entity: &T) -> RepoResult<T> {
+            // Listeners may rewrite the entity before it is bound.
+            let mut entity = entity.clone();
+            self.listeners.pre_insert(&mut entity)?;
+            let entity = &entity;
             let __start = Instant::now();
-            let values: Vec<Value> = entity
-                .insert_values()
-                .into_iter()
-                .map(to_libsql_value)
-                .collect();
-            // Prefer an active transaction-bound connection if present in task-local storage.
-            let conn = if let Ok(Some(tx_conn)) =
-                TX_STACK.try_with(|cell| cell.borrow().last().cloned())
-            {
-                tx_conn
-            } else if let Some(c) = &self.conn {
-                c.clone()
+            let mut params = entity.insert_values();
+            storeit_core::tenant::stamp_insert::<T>(&mut params)?;
+            let values: Vec<Value> = params.into_iter().map(to_libsql_value).collect();
+            let conn = self.connection()?;
+            // Caller-supplied keys are inserted and used for the read-back; otherwise the
+            // database generates the key.
+            let supplied_key = if T::ID_GENERATED {
+                None
             } else {
-                self.db.connect().map_err(RepoError::backend)?
+                Some(key_params(&entity.id().ok_or_else(|| {
+                    RepoError::backend(std::io::Error::new(
+                        std::io::ErrorKind::InvalidInput,
+                        "client-assigned key must be set before insert",
+                    ))
+                })?))
             };
+
             #[cfg(feature = "libsql_returning")]
-            {
+            let generated_id: i64 = {
                 // Use INSERT ... RETURNING to obtain the new id
                 let mut rows = conn
-                    .query(&self.sql.insert, values)
+                    .query(&self.statements().insert, values)
                     .await
-                    .map_err(RepoError::backend)?;
+                    .map_err(map_libsql_error)?;
                 let row = rows
                     .next()
                     .await
-                    .map_err(RepoError::backend)?
+                    .map_err(map_libsql_error)?
                     .ok_or_else(|| {
                         RepoError::backend(std::io::Error::new(
                             std::io::ErrorKind::Other,
                             "no row returned from INSERT ... RETURNING",
                         ))
                     })?;
-                let ret_id: i64 = row.get(0).map_err(RepoError::backend)?;
-                let new_key: T::Key = serde_json::from_value(serde_json::Value::from(ret_id))
-                    .map_err(RepoError::backend)?;
-                // Fetch using the same connection to avoid any visibility issues
-                let mut rows2 = conn
-                    .query(&self.sql.select_by_id, params!(new_key.clone().into()))
-                    .await
-                    .map_err(RepoError::backend)?;
-                if let Ok(Some(row2)) = rows2.next().await {
-                    let out = self.adapter.from_row(&row2);
-                    if out.is_ok() {
-                        obs_record("insert", T::TABLE, __start, 1, true);
-                    } else {
-                        obs_record("insert", T::TABLE, __start, 0, false);
-                    }
-                    return out;
+                if T::ID_GENERATED {
+                    row.get(0).map_err(map_libsql_error)?
                 } else {
-                    obs_record("insert", T::TABLE, __start, 0, false);
-                    return Err(RepoError::backend(std::io::Error::new(
-                        std::io::ErrorKind::Other,
-                        "Failed to fetch entity after insert",
-                    )));
+                    0
                 }
-            }
+            };
 
             #[cfg(not(feature = "libsql_returning"))]
-            {
-                conn.execute(&self.sql.insert, values)
+            let generated_id: i64 = {
+                conn.execute(&self.statements().insert, values)
                     .await
-                    .map_err(RepoError::backend)?;
+                    .map_err(map_libsql_error)?;
+                conn.last_insert_rowid()
+            };
 
-                let new_id = conn.last_insert_rowid();
-                let new_key: T::Key = serde_json::from_value(serde_json::Value::from(new_id))
-                    .map_err(RepoError::backend)?;
+            let mut read_back = supplied_key.unwrap_or_else(|| vec![Value::Integer(generated_id)]);
+            read_back.extend(tenant_params::<T>()?);
 
-                // Fetch using the same connection to avoid any visibility issues
-                let mut rows2 = conn
-                    .query(&self.sql.select_by_id, params!(new_key.clone().into()))
-                    .await
-                    .map_err(RepoError::backend)?;
-                if let Ok(Some(row2)) = rows2.next().await {
-                    let out = self.adapter.from_row(&row2);
-                    if out.is_ok() {
-                        obs_record("insert", T::TABLE, __start, 1, true);
-                    } else {
-                        obs_record("insert", T::TABLE, __start, 0, false);
-                    }
-                    out
+            // Fetch using the same connection to avoid any visibility issues
+            let mut rows2 = conn
+                .query(&self.statements().select_by_id, read_back)
+                .await
+                .map_err(map_libsql_error)?;
+            if let Ok(Some(row2)) = rows2.next().await {
+                let out = self
+                    .adapter
+                    .from_row(&row2)
+                    .and_then(|e| self.listeners.inserted(e));
+                if out.is_ok() {
+                    obs_record("insert", T::TABLE, __start, 1, true);
                 } else {
                     obs_record("insert", T::TABLE, __start, 0, false);
-                    return Err(RepoError::backend(std::io::Error::new(
-                        std::io::ErrorKind::Other,
-                        "Failed to fetch entity after insert",
-                    )));
                 }
+                out
+            } else {
+                obs_record("insert", T::TABLE, __start, 0, false);
+                Err(RepoError::backend(std::io::Error::new(
+                    std::io::ErrorKind::Other,
+                    "Failed to fetch entity after insert",
+                )))
             }
         }
 
         async fn update(&self, entity: &T) -> RepoResult<T> {
+            let mut entity = entity.clone();
+            self.listeners.pre_update(&mut entity)?;
+            let entity = &entity;
             let __start = Instant::now();
-            let values: Vec<Value> = entity
-                .update_values()
-                .into_iter()
-                .map(to_libsql_value)
-                .collect();
-            // Prefer an active transaction-bound connection if present in task-local storage.
-            let conn = if let Ok(Some(tx_conn)) =
-                TX_STACK.try_with(|cell| cell.borrow().last().cloned())
-            {
-                tx_conn
-            } else if let Some(c) = &self.conn {
-                c.clone()
-            } else {
-                self.db.connect().map_err(RepoError::backend)?
-            };
-            conn.execute(&self.sql.update_by_id, values)
+            let now = storeit_core::clock::now();
+            let mut values: Vec<Value> =
+                storeit_core::clock::with_now(now, || entity.update_values())
+                    .into_iter()
+                    .map(to_libsql_value)
+                    .collect();
+            values.extend(tenant_params::<T>()?);
+            let conn = self.connection()?;
+            let n = conn
+                .execute(&self.statements().update_by_id, values)
                 .await
-                .map_err(RepoError::backend)?;
-            obs_record("update", T::TABLE, __start, 1, true);
-            Ok(entity.clone())
+                .map_err(map_libsql_error)?;
+            // A versioned update that matched nothing lost a race with another writer.
+            if T::VERSION_COLUMN.is_some() && n == 0 {
+                obs_record("update", T::TABLE, __start, 0, false);
+                return Err(RepoError::OptimisticLock { table: T::TABLE });
+            }
+            // Otherwise, for a tenant-scoped entity, the row belongs to another tenant (or is gone).
+            if T::TENANT_COLUMN.is_some() && n == 0 {
+                obs_record("update", T::TABLE, __start, 0, false);
+                return Err(RepoError::NotFound);
+            }
+            obs_record("update", T::TABLE, __start, n as usize, true);
+            let mut updated = entity.clone();
+            updated.bump_version();
+            updated.touch(now);
+            Ok(updated)
         }
 
         async fn delete_by_id(&self, id: &T::Key) -> RepoResult<bool> {
+            self.listeners.pre_delete(id)?;
             let __start = Instant::now();
-            // Prefer an active transaction-bound connection if present in task-local storage.
-            let conn = if let Ok(Some(tx_conn)) =
-                TX_STACK.try_with(|cell| cell.borrow().last().cloned())
-            {
-                tx_conn
-            } else if let Some(c) = &self.conn {
-                c.clone()
+            let conn = self.connection()?;
+            // Soft-deleting entities only stamp their deletion column.
+            let n = if let Some(sql) = &self.statements().soft_delete_by_id {
+                let mut params = vec![to_libsql_value(storeit_core::clock::now().to_param_value())];
+                params.extend(scoped_key_params::<T>(id)?);
+                conn.execute(sql, params).await
             } else {
-                self.db.connect().map_err(RepoError::backend)?
-            };
-            let n = conn
-                .execute(&self.sql.delete_by_id, params!(id.clone()))
-                .await
-                .map_err(RepoError::backend)?;
+                conn.execute(&self.statements().delete_by_id, scoped_key_params::<T>(id)?)
+                    .await
+            }
+            .map_err(map_libsql_error)?;
             let ok = n > 0;
             obs_record("delete_by_id", T::TABLE, __start, n as usize, true);
             Ok(ok)
         }
+
+        async fn find_where(&self, criteria: &Criteria) -> RepoResult<Vec<T>> {
+            let __start = Instant::now();
+            let (sql, params) = storeit_sql_builder::select_by_criteria::<T>(
+                self.statements().dialect,
+                self.statements().tenant,
+                criteria,
+            )?;
+            let mut values: Vec<Value> = params.into_iter().map(to_libsql_value).collect();
+            values.extend(tenant_params::<T>()?);
+            let conn = self.connection()?;
+            let mut rows = conn.query(&sql, values).await.map_err(map_libsql_error)?;
+
+            let mut entities = Vec::new();
+            while let Some(row) = rows.next().await.map_err(map_libsql_error)? {
+                entities.push(self.load(&row)?);
+            }
+            obs_record("find_where", T::TABLE, __start, entities.len(), true);
+            Ok(entities)
+        }
+
+        async fn count_where(&self, criteria: &Criteria) -> RepoResult<u64> {
+            let __start = Instant::now();
+            let (sql, params) = storeit_sql_builder::count_by_criteria::<T>(
+                self.statements().dialect,
+                self.statements().tenant,
+                criteria,
+            )?;
+            let mut values: Vec<Value> = params.into_iter().map(to_libsql_value).collect();
+            values.extend(tenant_params::<T>()?);
+            let conn = self.connection()?;
+            let count = query_count(&conn, &sql, values).await?;
+            obs_record("count_where", T::TABLE, __start, 1, true);
+            Ok(count)
+        }
+
+        async fn exists_where(&self, criteria: &Criteria) -> RepoResult<bool> {
+            let __start = Instant::now();
+            let (sql, params) = storeit_sql_builder::exists_by_criteria::<T>(
+                self.statements().dialect,
+                self.statements().tenant,
+                criteria,
+            )?;
+            let mut values: Vec<Value> = params.into_iter().map(to_libsql_value).collect();
+            values.extend(tenant_params::<T>()?);
+            let conn = self.connection()?;
+            let mut rows = conn.query(&sql, values).await.map_err(map_libsql_error)?;
+            let found = rows.next().await.map_err(map_libsql_error)?.is_some();
+            obs_record("exists_where", T::TABLE, __start, found as usize, true);
+            Ok(found)
+        }
+
+        async fn delete_where(&self, criteria: &Criteria) -> RepoResult<u64> {
+            let __start = Instant::now();
+            let (sql, params) = storeit_sql_builder::delete_by_criteria::<T>(
+                self.statements().dialect,
+                self.statements().tenant,
+                criteria,
+            )?;
+            let mut values: Vec<Value> = params.into_iter().map(to_libsql_value).collect();
+            values.extend(tenant_params::<T>()?);
+            let conn = self.connection()?;
+            let n = conn.execute(&sql, values).await.map_err(map_libsql_error)?;
+            obs_record("delete_where", T::TABLE, __start, n as usize, true);
+            Ok(n)
+        }
+
+        async fn find_page(&self, pageable: &Pageable) -> RepoResult<Page<T>> {
+            let __start = Instant::now();
+            let sql = storeit_sql_builder::select_page::<T>(
+                self.statements().dialect,
+                self.statements().tenant,
+                pageable,
+            )?;
+            let count_sql = storeit_sql_builder::select_count_all::<T>(
+                self.statements().dialect,
+                self.statements().tenant,
+            );
+            let tenant = tenant_params::<T>()?;
+            let conn = self.connection()?;
+            let total = query_count(&conn, &count_sql, tenant.clone()).await?;
+            let mut items = Vec::new();
+            if (pageable.page.saturating_mul(pageable.size) as u64) < total {
+                let mut rows = conn.query(&sql, tenant).await.map_err(map_libsql_error)?;
+                while let Some(row) = rows.next().await.map_err(map_libsql_error)? {
+                    items.push(self.load(&row)?);
+                }
+            }
+            obs_record("find_page", T::TABLE, __start, items.len(), true);
+            Ok(Page::new(items, pageable, total))
+        }
+
+        async fn find_page_by_field(
+            &self,
+            field_name: &str,
+            value: ParamValue,
+            pageable: &Pageable,
+        ) -> RepoResult<Page<T>> {
+            let __start = Instant::now();
+            let sql = storeit_sql_builder::select_page_by_field::<T>(
+                self.statements().dialect,
+                self.statements().tenant,
+                field_name,
+                pageable,
+            )?;
+            let count_sql = storeit_sql_builder::select_count_by_field::<T>(
+                self.statements().dialect,
+                self.statements().tenant,
+                field_name,
+            );
+            let mut values = vec![to_libsql_value(value)];
+            values.extend(tenant_params::<T>()?);
+            let conn = self.connection()?;
+            let total = query_count(&conn, &count_sql, values.clone()).await?;
+            let mut items = Vec::new();
+            if (pageable.page.saturating_mul(pageable.size) as u64) < total {
+                let mut rows = conn.query(&sql, values).await.map_err(map_libsql_error)?;
+                while let Some(row) = rows.next().await.map_err(map_libsql_error)? {
+                    items.push(self.load(&row)?);
+                }
+            }
+            obs_record("find_page_by_field", T::TABLE, __start, items.len(), true);
+            Ok(Page::new(items, pageable, total))
+        }
+
+        async fn find_page_after(
+            &self,
+            cursor: Option<Cursor>,
+            limit: usize,
+            order: &[Order],
+        ) -> RepoResult<CursorPage<T>> {
+            let __start = Instant::now();
+            let orders = storeit_sql_builder::keyset_orders::<T>(order, T::ID_COLUMNS)?;
+            let (sql, params) = storeit_sql_builder::keyset_select::<T>(
+                self.statements().dialect,
+                self.statements().tenant,
+                &orders,
+                cursor.as_ref(),
+                limit,
+            )?;
+            let mut values: Vec<Value> = params.into_iter().map(to_libsql_value).collect();
+            values.extend(tenant_params::<T>()?);
+            let conn = self.connection()?;
+            let key_idx: Vec<usize> = orders
+                .iter()
+                .filter_map(|o| T::SELECT_COLUMNS.iter().position(|c| *c == o.column))
+                .collect();
+            // Map while iterating: a libsql row is only readable until the cursor advances.
+            let mut rows = conn.query(&sql, values).await.map_err(map_libsql_error)?;
+            let mut items = Vec::new();
+            let mut keys = Vec::new();
+            while let Some(row) = rows.next().await.map_err(map_libsql_error)? {
+                keys.push(
+                    key_idx
+                        .iter()
+                        .map(|&i| column_value(&row, i))
+                        .collect::<RepoResult<Vec<_>>>()?,
+                );
+                items.push(self.load(&row)?);
+            }
+            let has_more = items.len() > limit;
+            items.truncate(limit);
+            keys.truncate(limit);
+            if cursor.as_ref().is_some_and(Cursor::is_before) {
+                items.reverse();
+                keys.reverse();
+            }
+            let first_key = keys.first().cloned();
+            let last_key = keys.pop();
+            obs_record("find_page_after", T::TABLE, __start, items.len(), true);
+            Ok(CursorPage::new(
+                items,
+                orders.into_iter().map(|o| o.column).collect(),
+                first_key,
+                last_key,
+                cursor.as_ref(),
+                has_more,
+            ))
+        }
     }
 }
 
 #[cfg(feature = "libsql-backend")]
-pub use backend::{LibsqlRepository, LibsqlTransactionManager};
+pub use backend::{column_value, map_libsql_error, LibsqlRepository, LibsqlTransactionManager};
 
 #[cfg(all(test, feature = "libsql-backend"))]
 mod tests {
-    use super::backend::{LibsqlRepository, LibsqlTransactionManager};
+    use super::backend::{
+        column_value, map_libsql_error, LibsqlRepository, LibsqlTransactionManager,
+    };
     use libsql::Database;
     use std::sync::{Arc, OnceLock};
     use storeit_core::transactions::{
         Isolation, Propagation, TransactionDefinition, TransactionManager,
     };
-    use storeit_core::{Repository, RowAdapter};
+    use storeit_core::{Repository, RowAdapter, ToParamValue};
     use tokio::sync::Mutex as AsyncMutex;
 
     #[derive(Clone, Debug, PartialEq)]
@@ -599,6 +1257,8 @@ mod tests {
             self.id
         }
     }
+
+    impl storeit_core::Listenable for U {}
     impl storeit_core::Insertable for U {
         const INSERT_COLUMNS: &'static [&'static str] = &["email", "active"];
         fn insert_values(&self) -> Vec<storeit_core::ParamValue> {
@@ -619,6 +1279,25 @@ mod tests {
         }
     }
 
+    impl storeit_core::Schema for U {
+        fn table_def() -> storeit_core::schema::TableDef {
+            use storeit_core::schema::{ColumnDef, SqlType, TableDef};
+            TableDef {
+                name: "users".into(),
+                columns: vec![
+                    ColumnDef::new("id", SqlType::BigInt),
+                    ColumnDef {
+                        unique: true,
+                        ..ColumnDef::new("email", SqlType::Text)
+                    },
+                    ColumnDef::new("active", SqlType::Boolean),
+                ],
+                primary_key: vec!["id".into()],
+                key_generated: true,
+            }
+        }
+    }
+
     struct A;
     impl RowAdapter<U> for A {
         type Row = libsql::Row;
@@ -660,6 +1339,98 @@ mod tests {
         // _guard dropped here at end of function scope
     }
 
+    #[tokio::test]
+    async fn validate_schema_reports_drift() {
+        use storeit_core::schema::SchemaIssue;
+        let db = setup_db().await;
+        let tm = LibsqlTransactionManager::from_arc(db.clone());
+        assert!(tm.validate_schema::<U>().await.unwrap().is_ok());
+
+        let conn = db.connect().unwrap();
+        conn.execute("ALTER TABLE users RENAME COLUMN email TO mail", ())
+            .await
+            .unwrap();
+        let repo: LibsqlRepository<U, A> = LibsqlRepository::new(db.clone(), A);
+        let report = repo.validate_schema().await.unwrap();
+        assert_eq!(
+            report.issues,
+            vec![
+                SchemaIssue::MissingColumn("email".into()),
+                SchemaIssue::UnmappedRequiredColumn("mail".into()),
+            ]
+        );
+        assert!(report.into_result().is_err());
+
+        conn.execute("DROP TABLE users", ()).await.unwrap();
+        let report = tm.validate_schema::<U>().await.unwrap();
+        assert_eq!(report.issues, vec![SchemaIssue::MissingTable]);
+    }
+
+    #[tokio::test]
+    async fn schema_diff_script_repairs_drift() {
+        use storeit_core::schema::SchemaChange;
+        use storeit_sql_builder::{migration_script, Dialect};
+        let db = setup_db().await;
+        let tm = LibsqlTransactionManager::from_arc(db.clone());
+        assert!(tm.schema_diff::<U>().await.unwrap().is_empty());
+
+        let conn = db.connect().unwrap();
+        // A populated table, referenced by a cascading foreign key that is enforced.
+        conn.execute_batch(
+            "DROP TABLE users; \
+             CREATE TABLE users (id INTEGER PRIMARY KEY AUTOINCREMENT, email TEXT NOT NULL, legacy TEXT); \
+             CREATE TABLE sessions (id INTEGER PRIMARY KEY, user_id INTEGER NOT NULL REFERENCES users (id) ON DELETE CASCADE); \
+             INSERT INTO users (email, legacy) VALUES ('a@x', 'old'); \
+             INSERT INTO sessions (user_id) VALUES (1); \
+             PRAGMA foreign_keys=ON;",
+        )
+        .await
+        .unwrap();
+        let changes = tm.schema_diff::<U>().await.unwrap();
+        assert_eq!(
+            changes,
+            vec![
+                SchemaChange::AddColumn("active".into()),
+                SchemaChange::DropColumn("legacy".into()),
+                SchemaChange::CreateIndex {
+                    column: "email".into(),
+                    unique: true,
+                },
+            ]
+        );
+        conn.execute_batch(&migration_script(
+            Dialect::Sqlite,
+            &<U as storeit_core::Schema>::table_def(),
+            &changes,
+        ))
+        .await
+        .unwrap();
+        assert!(tm.schema_diff::<U>().await.unwrap().is_empty());
+        assert!(tm.validate_schema::<U>().await.unwrap().is_ok());
+        // The rebuild kept the row, gave the new NOT NULL column its placeholder and did not
+        // cascade to the referencing rows.
+        let mut rows = conn
+            .query(
+                "SELECT id, email, active, (SELECT COUNT(*) FROM sessions) FROM users",
+                (),
+            )
+            .await
+            .unwrap();
+        let row = rows.next().await.unwrap().expect("row kept");
+        assert_eq!(row.get::<i64>(0).unwrap(), 1);
+        assert_eq!(row.get::<String>(1).unwrap(), "a@x");
+        assert_eq!(row.get::<i64>(2).unwrap(), 0);
+        assert_eq!(row.get::<i64>(3).unwrap(), 1);
+        assert!(rows.next().await.unwrap().is_none());
+        conn.execute("DROP TABLE sessions", ()).await.unwrap();
+
+        conn.execute("DROP TABLE users", ()).await.unwrap();
+        assert_eq!(
+            tm.schema_diff::<U>().await.unwrap(),
+            vec![SchemaChange::CreateTable]
+        );
+    }
+
     #[tokio::test]
     async fn find_by_field_with_unknown_column_surfaces_query_error() {
         let db = setup_db().await;
@@ -683,6 +1454,619 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn criteria_queries_filter_count_and_delete() {
+        use storeit_core::Criteria;
+        let db = setup_db().await;
+        let repo: LibsqlRepository<U, A> = LibsqlRepository::new(db.clone(), A);
+        for (email, active) in [("a@x", true), ("b@x", false), ("c@y", true)] {
+            repo.insert(&U {
+                id: None,
+                email: email.into(),
+                active,
+            })
+            .await
+            .expect("insert");
+        }
+
+        let active_x = Criteria::eq("active", true).and(Criteria::like("email", "%@x"));
+        let found = repo.find_where(&active_x).await.expect("find_where");
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].email, "a@x");
+
+        let either = Criteria::in_list("email", ["a@x", "c@y"]).or(!Criteria::eq("active", true));
+        assert_eq!(repo.count_where(&either).await.expect("count"), 3);
+        assert!(repo.exists_where(&active_x).await.expect("exists"));
+        assert!(!repo
+            .exists_where(&Criteria::in_list("email", Vec::<String>::new()))
+            .await
+            .expect("exists empty"));
+
+        assert_eq!(
+            repo.delete_where(&Criteria::eq("active", true))
+                .await
+                .expect("delete_where"),
+            2
+        );
+        assert_eq!(repo.count_where(&Criteria::all()).await.expect("count"), 1);
+    }
+
+    #[tokio::test]
+    async fn find_page_sorts_limits_and_counts() {
+        use storeit_core::{Pageable, Sort};
+        let db = setup_db().await;
+        let repo: LibsqlRepository<U, A> = LibsqlRepository::new(db.clone(), A);
+        for i in 0..5 {
+            repo.insert(&U {
+                id: None,
+                email: format!("p{i}@x"),
+                active: i % 2 == 0,
+            })
+            .await
+            .expect("insert");
+        }
+
+        let by_email_desc = Pageable::new(1, 2).with_sort(Sort::desc("email"));
+        let page = repo.find_page(&by_email_desc).await.expect("find_page");
+        let emails: Vec<_> = page.items.iter().map(|u| u.email.as_str()).collect();
+        assert_eq!(emails, ["p2@x", "p1@x"]);
+        assert_eq!((page.total, page.total_pages, page.has_next), (5, 3, true));
+
+        let active = repo
+            .find_page_by_field(
+                "active",
+                storeit_core::ParamValue::Bool(true),
+                &Pageable::new(1, 2).with_sort(Sort::asc("id")),
+            )
+            .await
+            .expect("find_page_by_field");
+        assert_eq!(active.items.len(), 1);
+        assert_eq!(active.items[0].email, "p4@x");
+        assert_eq!((active.total, active.has_next), (3, false));
+
+        let past_end = repo
+            .find_page(&Pageable::new(10, 2))
+            .await
+            .expect("past end");
+        assert!(past_end.items.is_empty());
+        assert_eq!(past_end.total, 5);
+
+        assert!(repo
+            .find_page(&Pageable::new(0, 2).with_sort(Sort::asc("nope")))
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn find_page_after_walks_forward_and_back_with_tokens() {
+        use storeit_core::{Cursor, Order};
+        let db = setup_db().await;
+        let repo: LibsqlRepository<U, A> = LibsqlRepository::new(db.clone(), A);
+        // Two rows share `active`, so the id tie-breaker decides their order.
+        for (email, active) in [("a@x", true), ("b@x", false), ("c@x", true), ("d@x", false)] {
+            repo.insert(&U {
+                id: None,
+                email: email.into(),
+                active,
+            })
+            .await
+            .expect("insert");
+        }
+        let order = [Order::desc("active")];
+        let emails = |items: &[U]| items.iter().map(|u| u.email.clone()).collect::<Vec<_>>();
+
+        let first = repo.find_page_after(None, 3, &order).await.expect("first");
+        assert_eq!(emails(&first.items), ["a@x", "c@x", "b@x"]);
+        assert!(first.prev.is_none());
+        let token = first.next.expect("next").encode();
+
+        let cursor: Cursor = token.parse().expect("decode");
+        let second = repo
+            .find_page_after(Some(cursor), 3, &order)
+            .await
+            .expect("second");
+        assert_eq!(emails(&second.items), ["d@x"]);
+        assert!(second.next.is_none());
+
+        let back = repo
+            .find_page_after(second.prev, 2, &order)
+            .await
+            .expect("back");
+        assert_eq!(emails(&back.items), ["c@x", "b@x"]);
+        assert!(back.prev.is_some() && back.next.is_some());
+
+        let stale = Cursor::decode(&token).unwrap();
+        assert!(repo
+            .find_page_after(Some(stale), 3, &[Order::asc("email")])
+            .await
+            .is_err());
+    }
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct Doc {
+        id: Option<i64>,
+        title: String,
+        version: i64,
+        updated_at: std::time::SystemTime,
+    }
+
+    impl storeit_core::Fetchable for Doc {
+        const TABLE: &'static str = "docs";
+        const SELECT_COLUMNS: &'static [&'static str] = &["id", "title", "version", "updated_at"];
+        const FINDABLE_COLUMNS: &'static [(&'static str, &'static str)] = &[("title", "TEXT")];
+    }
+    impl storeit_core::Identifiable for Doc {
+        type Key = i64;
+        const ID_COLUMN: &'static str = "id";
+        fn id(&self) -> Option<Self::Key> {
+            self.id
+        }
+    }
+
+    impl storeit_core::Listenable for Doc {}
+    impl storeit_core::Insertable for Doc {
+        const INSERT_COLUMNS: &'static [&'static str] = &["title", "version", "updated_at"];
+        fn insert_values(&self) -> Vec<storeit_core::ParamValue> {
+            vec![
+                storeit_core::ParamValue::String(self.title.clone()),
+                storeit_core::ParamValue::I64(self.version),
+                storeit_core::clock::now().to_param_value(),
+            ]
+        }
+    }
+    impl storeit_core::Updatable for Doc {
+        const UPDATE_COLUMNS: &'static [&'static str] = &["title", "updated_at"];
+        const VERSION_COLUMN: Option<&'static str> = Some("version");
+        fn update_values(&self) -> Vec<storeit_core::ParamValue> {
+            vec![
+                storeit_core::ParamValue::String(self.title.clone()),
+                storeit_core::clock::now().to_param_value(),
+                storeit_core::ParamValue::I64(self.id.unwrap_or_default()),
+                storeit_core::ParamValue::I64(self.version),
+            ]
+        }
+        fn bump_version(&mut self) {
+            self.version += 1;
+        }
+        fn touch(&mut self, now: std::time::SystemTime) {
+            self.updated_at = now;
+        }
+    }
+
+    struct DocAdapter;
+    impl RowAdapter<Doc> for DocAdapter {
+        type Row = libsql::Row;
+        fn from_row(&self, row: &Self::Row) -> storeit_core::RepoResult<Doc> {
+            Ok(Doc {
+                id: Some(row.get(0).map_err(storeit_core::RepoError::mapping)?),
+                title: row.get(1).map_err(storeit_core::RepoError::mapping)?,
+                version: row.get(2).map_err(storeit_core::RepoError::mapping)?,
+                updated_at: storeit_core::FromColumn::from_column(column_value(row, 3)?)?,
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn versioned_update_bumps_version_stamps_updated_at_and_rejects_stale_writes() {
+        use std::time::{Duration, UNIX_EPOCH};
+        use storeit_core::clock::{self, Clock, FixedClock};
+        use storeit_core::RepoError;
+        let clock = FixedClock::new(UNIX_EPOCH + Duration::from_secs(1_700_000_000));
+        clock::set_clock(std::sync::Arc::new(clock.clone()));
+        let db = setup_db().await;
+        db.connect()
+            .expect("connect")
+            .execute(
+                "CREATE TABLE docs (id INTEGER PRIMARY KEY AUTOINCREMENT, title TEXT NOT NULL, version INTEGER NOT NULL, updated_at TEXT NOT NULL)",
+                (),
+            )
+            .await
+            .expect("create docs");
+        let repo: LibsqlRepository<Doc, DocAdapter> = LibsqlRepository::new(db.clone(), DocAdapter);
+
+        let doc = repo
+            .insert(&Doc {
+                id: None,
+                title: "draft".into(),
+                version: 0,
+                updated_at: UNIX_EPOCH,
+            })
+            .await
+            .expect("insert");
+        assert_eq!(doc.updated_at, clock.now());
+        let stale = doc.clone();
+        clock.advance(Duration::from_millis(1_500));
+
+        let updated = repo
+            .update(&Doc {
+                title: "final".into(),
+                ..doc
+            })
+            .await
+            .expect("update");
+        assert_eq!(updated.version, 1);
+        assert_eq!(updated.updated_at, clock.now());
+        let stored = repo
+            .find_by_id(&updated.id.unwrap())
+            .await
+            .expect("find")
+            .expect("row");
+        assert_eq!(stored, updated);
+
+        let err = repo.update(&stale).await.expect_err("stale update");
+        assert!(matches!(err, RepoError::OptimisticLock { table: "docs" }));
+
+        repo.delete_by_id(&updated.id.unwrap())
+            .await
+            .expect("delete");
+        assert!(matches!(
+            repo.update(&updated).await,
+            Err(RepoError::OptimisticLock { .. })
+        ));
+        clock::reset_clock();
+    }
+
+    #[test]
+    fn map_libsql_error_classifies_extended_codes() {
+        use storeit_core::RepoError;
+        let unique = map_libsql_error(libsql::Error::SqliteFailure(
+            2067,
+            "UNIQUE constraint failed: users.email".into(),
+        ));
+        match unique {
+            RepoError::UniqueViolation { constraint, .. } => {
+                assert_eq!(constraint.as_deref(), Some("users.email"))
+            }
+            other => panic!("unexpected: {:?}", other),
+        }
+        assert!(matches!(
+            map_libsql_error(libsql::Error::SqliteFailure(
+                1299,
+                "NOT NULL constraint failed: users.email".into()
+            )),
+            RepoError::NotNullViolation { column: Some(c), .. } if c == "users.email"
+        ));
+        assert!(matches!(
+            map_libsql_error(libsql::Error::SqliteFailure(
+                787,
+                "FOREIGN KEY constraint failed".into()
+            )),
+            RepoError::ForeignKeyViolation {
+                constraint: None,
+                ..
+            }
+        ));
+        assert!(matches!(
+            map_libsql_error(libsql::Error::SqliteFailure(5, "database is locked".into())),
+            RepoError::Timeout { .. }
+        ));
+        assert!(matches!(
+            map_libsql_error(libsql::Error::SqliteFailure(
+                517,
+                "database is locked".into()
+            )),
+            RepoError::SerializationFailure { .. }
+        ));
+        assert!(matches!(
+            map_libsql_error(libsql::Error::SqliteFailure(
+                8,
+                "attempt to write a readonly database".into()
+            )),
+            RepoError::ReadOnlyViolation { .. }
+        ));
+        assert!(matches!(
+            map_libsql_error(libsql::Error::RemoteSqliteFailure(
+                19,
+                2067,
+                "UNIQUE constraint failed: users.email".into()
+            )),
+            RepoError::UniqueViolation { .. }
+        ));
+        assert!(matches!(
+            map_libsql_error(libsql::Error::ConnectionFailed("gone".into())),
+            RepoError::ConnectionLost { .. }
+        ));
+        assert!(matches!(
+            map_libsql_error(libsql::Error::SqliteFailure(1, "no such column: x".into())),
+            RepoError::Backend { .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn duplicate_insert_surfaces_unique_violation() {
+        let db = setup_db().await;
+        let repo: LibsqlRepository<U, A> = LibsqlRepository::new(db.clone(), A);
+        let u = U {
+            id: None,
+            email: "dup@x".into(),
+            active: true,
+        };
+        repo.insert(&u).await.expect("first insert");
+        let err = repo.insert(&u).await.expect_err("duplicate email");
+        match err {
+            storeit_core::RepoError::UniqueViolation { constraint, .. } => {
+                assert_eq!(constraint.as_deref(), Some("users.email"))
+            }
+            other => panic!("expected UniqueViolation, got {:?}", other),
+        }
+    }
+
+    // Association entity with a two-column primary key.
+    #[derive(Clone, Debug, PartialEq)]
+    struct UserRole {
+        user_id: i64,
+        role_id: i64,
+        granted_by: String,
+    }
+    impl storeit_core::Fetchable for UserRole {
+        const TABLE: &'static str = "user_roles";
+        const SELECT_COLUMNS: &'static [&'static str] = &["user_id", "role_id", "granted_by"];
+        const FINDABLE_COLUMNS: &'static [(&'static str, &'static str)] =
+            &[("granted_by", "String")];
+    }
+    impl storeit_core::Identifiable for UserRole {
+        type Key = (i64, i64);
+        const ID_COLUMN: &'static str = "user_id";
+        const ID_COLUMNS: &'static [&'static str] = &["user_id", "role_id"];
+        fn id(&self) -> Option<Self::Key> {
+            Some((self.user_id, self.role_id))
+        }
+    }
+
+    impl storeit_core::Listenable for UserRole {}
+    impl storeit_core::Insertable for UserRole {
+        const INSERT_COLUMNS: &'static [&'static str] = &["user_id", "role_id", "granted_by"];
+        fn insert_values(&self) -> Vec<storeit_core::ParamValue> {
+            vec![
+                storeit_core::ParamValue::I64(self.user_id),
+                storeit_core::ParamValue::I64(self.role_id),
+                storeit_core::ParamValue::String(self.granted_by.clone()),
+            ]
+        }
+    }
+    impl storeit_core::Updatable for UserRole {
+        const UPDATE_COLUMNS: &'static [&'static str] = &["granted_by"];
+        fn update_values(&self) -> Vec<storeit_core::ParamValue> {
+            vec![
+                storeit_core::ParamValue::String(self.granted_by.clone()),
+                storeit_core::ParamValue::I64(self.user_id),
+                storeit_core::ParamValue::I64(self.role_id),
+            ]
+        }
+    }
+    struct UserRoleAdapter;
+    impl RowAdapter<UserRole> for UserRoleAdapter {
+        type Row = libsql::Row;
+        fn from_row(&self, row: &Self::Row) -> storeit_core::RepoResult<UserRole> {
+            Ok(UserRole {
+                user_id: row.get(0).map_err(storeit_core::RepoError::mapping)?,
+                role_id: row.get(1).map_err(storeit_core::RepoError::mapping)?,
+                granted_by: row.get(2).map_err(storeit_core::RepoError::mapping)?,
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn composite_key_crud_roundtrip() {
+        let db = setup_db().await;
+        db.connect()
+            .unwrap()
+            .execute(
+                "CREATE TABLE IF NOT EXISTS user_roles (user_id INTEGER NOT NULL, role_id INTEGER NOT NULL, granted_by TEXT NOT NULL, PRIMARY KEY (user_id, role_id));",
+                (),
+            )
+            .await
+            .expect("create user_roles");
+        let repo: LibsqlRepository<UserRole, UserRoleAdapter> =
+            LibsqlRepository::new(db.clone(), UserRoleAdapter);
+
+        let mut ur = UserRole {
+            user_id: 1,
+            role_id: 2,
+            granted_by: "root".into(),
+        };
+        let created = repo.insert(&ur).await.expect("insert");
+        assert_eq!(created, ur);
+        // A different role for the same user is a different row.
+        repo.insert(&UserRole {
+            role_id: 3,
+            ..ur.clone()
+        })
+        .await
+        .expect("insert second role");
+
+        ur.granted_by = "admin".into();
+        repo.update(&ur).await.expect("update");
+        let fetched = repo.find_by_id(&(1, 2)).await.expect("find").unwrap();
+        assert_eq!(fetched.granted_by, "admin");
+        let other = repo.find_by_id(&(1, 3)).await.expect("find").unwrap();
+        assert_eq!(other.granted_by, "root");
+
+        assert!(repo.delete_by_id(&(1, 2)).await.expect("delete"));
+        assert!(repo.find_by_id(&(1, 2)).await.unwrap().is_none());
+        assert!(repo.find_by_id(&(1, 3)).await.unwrap().is_some());
+    }
+
+    // Entity with a client-assigned, typed text key.
+    #[derive(Clone, Debug, PartialEq)]
+    struct ApiKeyId(String);
+    impl storeit_core::ToParamValue for ApiKeyId {
+        fn to_param_value(&self) -> storeit_core::ParamValue {
+            storeit_core::ParamValue::String(self.0.clone())
+        }
+    }
+    #[derive(Clone, Debug, PartialEq)]
+    struct ApiKey {
+        id: ApiKeyId,
+        label: String,
+    }
+    impl storeit_core::Fetchable for ApiKey {
+        const TABLE: &'static str = "api_keys";
+        const SELECT_COLUMNS: &'static [&'static str] = &["id", "label"];
+        const FINDABLE_COLUMNS: &'static [(&'static str, &'static str)] = &[("label", "String")];
+    }
+    impl storeit_core::Identifiable for ApiKey {
+        type Key = ApiKeyId;
+        const ID_COLUMN: &'static str = "id";
+        const ID_GENERATED: bool = false;
+        fn id(&self) -> Option<Self::Key> {
+            Some(self.id.clone())
+        }
+    }
+
+    impl storeit_core::Listenable for ApiKey {}
+    impl storeit_core::Insertable for ApiKey {
+        const INSERT_COLUMNS: &'static [&'static str] = &["id", "label"];
+        fn insert_values(&self) -> Vec<storeit_core::ParamValue> {
+            vec![
+                storeit_core::ParamValue::String(self.id.0.clone()),
+                storeit_core::ParamValue::String(self.label.clone()),
+            ]
+        }
+    }
+    impl storeit_core::Updatable for ApiKey {
+        const UPDATE_COLUMNS: &'static [&'static str] = &["label"];
+        fn update_values(&self) -> Vec<storeit_core::ParamValue> {
+            vec![
+                storeit_core::ParamValue::String(self.label.clone()),
+                storeit_core::ParamValue::String(self.id.0.clone()),
+            ]
+        }
+    }
+    struct ApiKeyAdapter;
+    impl RowAdapter<ApiKey> for ApiKeyAdapter {
+        type Row = libsql::Row;
+        fn from_row(&self, row: &Self::Row) -> storeit_core::RepoResult<ApiKey> {
+            Ok(ApiKey {
+                id: ApiKeyId(row.get(0).map_err(storeit_core::RepoError::mapping)?),
+                label: row.get(1).map_err(storeit_core::RepoError::mapping)?,
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn client_assigned_text_key_roundtrip() {
+        let db = setup_db().await;
+        db.connect()
+            .unwrap()
+            .execute(
+                "CREATE TABLE IF NOT EXISTS api_keys (id TEXT PRIMARY KEY NOT NULL, label TEXT NOT NULL);",
+                (),
+            )
+            .await
+            .expect("create api_keys");
+        let repo: LibsqlRepository<ApiKey, ApiKeyAdapter> =
+            LibsqlRepository::new(db.clone(), ApiKeyAdapter);
+
+        let key = ApiKey {
+            id: ApiKeyId("0b8f5c1e-7d3a-4f0e-9a55-3c2e1d4b6a70".into()),
+            label: "ci".into(),
+        };
+        let created = repo.insert(&key).await.expect("insert");
+        assert_eq!(created, key);
+        let fetched = repo.find_by_id(&key.id).await.expect("find").unwrap();
+        assert_eq!(fetched.label, "ci");
+        assert!(repo.delete_by_id(&key.id).await.expect("delete"));
+        assert!(repo.find_by_id(&key.id).await.unwrap().is_none());
+    }
+
+    // Entity exercising the text and blob bindings of the native ParamValue variants.
+    #[derive(Clone, Debug, PartialEq)]
+    struct Event {
+        id: Option<i64>,
+        day: i32,
+        payload: Vec<u8>,
+    }
+    impl storeit_core::Fetchable for Event {
+        const TABLE: &'static str = "events";
+        const SELECT_COLUMNS: &'static [&'static str] = &["id", "day", "payload"];
+        const FINDABLE_COLUMNS: &'static [(&'static str, &'static str)] = &[("day", "NaiveDate")];
+    }
+    impl storeit_core::Identifiable for Event {
+        type Key = i64;
+        const ID_COLUMN: &'static str = "id";
+        fn id(&self) -> Option<Self::Key> {
+            self.id
+        }
+    }
+
+    impl storeit_core::Listenable for Event {}
+    impl storeit_core::Insertable for Event {
+        const INSERT_COLUMNS: &'static [&'static str] = &["day", "payload"];
+        fn insert_values(&self) -> Vec<storeit_core::ParamValue> {
+            vec![
+                storeit_core::ParamValue::Date(self.day),
+                storeit_core::ParamValue::Bytes(self.payload.clone()),
+            ]
+        }
+    }
+    impl storeit_core::Updatable for Event {
+        const UPDATE_COLUMNS: &'static [&'static str] = &["day", "payload"];
+        fn update_values(&self) -> Vec<storeit_core::ParamValue> {
+            let mut v = storeit_core::Insertable::insert_values(self);
+            v.push(storeit_core::ParamValue::I64(self.id.unwrap_or_default()));
+            v
+        }
+    }
+    struct EventAdapter;
+    impl RowAdapter<Event> for EventAdapter {
+        type Row = libsql::Row;
+        fn from_row(&self, row: &Self::Row) -> storeit_core::RepoResult<Event> {
+            let day: String = row.get(1).map_err(storeit_core::RepoError::mapping)?;
+            let (y, m, d) = (&day[0..4], &day[5..7], &day[8..10]);
+            Ok(Event {
+                id: row.get(0).map_err(storeit_core::RepoError::mapping)?,
+                day: storeit_core::value::days_from_civil(
+                    y.parse().unwrap(),
+                    m.parse().unwrap(),
+                    d.parse().unwrap(),
+                ) as i32,
+                payload: row.get(2).map_err(storeit_core::RepoError::mapping)?,
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn native_values_bind_as_sqlite_text_and_blob() {
+        let db = setup_db().await;
+        let conn = db.connect().unwrap();
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS events (id INTEGER PRIMARY KEY AUTOINCREMENT, day TEXT NOT NULL, payload BLOB NOT NULL);",
+            (),
+        )
+        .await
+        .expect("create events");
+        let repo: LibsqlRepository<Event, EventAdapter> =
+            LibsqlRepository::new(db.clone(), EventAdapter);
+
+        // 2023-12-31 and 2024-01-01
+        for day in [19_722, 19_723] {
+            let created = repo
+                .insert(&Event {
+                    id: None,
+                    day,
+                    payload: vec![0, 1, 255],
+                })
+                .await
+                .expect("insert");
+            assert_eq!(created.day, day);
+            assert_eq!(created.payload, [0, 1, 255]);
+        }
+        let found = repo
+            .find_by_field("day", storeit_core::ParamValue::Date(19_723))
+            .await
+            .expect("find");
+        assert_eq!(found.len(), 1);
+
+        // Dates are stored as YYYY-MM-DD, so text comparison is chronological.
+        let mut rows = conn
+            .query("SELECT count(*) FROM events WHERE day < '2024-01-01'", ())
+            .await
+            .unwrap();
+        let before: i64 = rows.next().await.unwrap().unwrap().get(0).unwrap();
+        assert_eq!(before, 1);
+    }
+
     // Adapter that intentionally requests a missing column index to force a mapping error
     struct BadAdapter;
     impl RowAdapter<U> for BadAdapter {
@@ -798,7 +2182,11 @@ mod tests {
                         })
                         .await
                         .expect_err("write should fail in read-only tx");
-                    drop(err); // just ensure error surfaced
+                    assert!(
+                        matches!(err, storeit_core::RepoError::ReadOnlyViolation { .. }),
+                        "unexpected error: {:?}",
+                        err
+                    );
                     Ok::<_, storeit_core::RepoError>(())
                 }
             })
@@ -2,7 +2,7 @@
 #![allow(unused_imports)]
 
 use storeit_core::{Repository, RowAdapter};
-use tests_common::{migrations, User};
+use tests_common::{migrations, Membership, Note, User};
 
 #[cfg(feature = "libsql-backend")]
 struct MyAdapter;
@@ -24,6 +24,16 @@ impl RowAdapter<User> for MyAdapter {
     }
 }
 
+#[cfg(feature = "libsql-backend")]
+async fn apply_migrations(db: &std::sync::Arc<libsql::Database>) {
+    let mgr = storeit_libsql::LibsqlTransactionManager::new(db.clone());
+    storeit_migrate::Migrator::new(migrations::LIBSQL)
+        .expect("migrations")
+        .migrate(&mgr)
+        .await
+        .expect("apply migrations");
+}
+
 #[cfg(feature = "libsql-backend")]
 #[tokio::test]
 #[ignore = "Excluded from default runs to keep coverage fast and deterministic; run with -- --ignored to execute"]
@@ -35,15 +45,14 @@ async fn libsql_crud_and_find_by_field_in_memory() -> storeit_core::RepoResult<(
     #[allow(deprecated)]
     let db =
         Database::open("file::memory:?cache=shared").map_err(storeit_core::RepoError::backend)?;
+    let db = std::sync::Arc::new(db);
+    // The shared in-memory database lives as long as one of its connections does.
+    let _keep_alive = db.connect().map_err(storeit_core::RepoError::backend)?;
     // Run migrations
-    let conn = db.connect().map_err(storeit_core::RepoError::backend)?;
-    conn.execute(migrations::LIBSQL_USERS_SQL, ())
-        .await
-        .expect("apply migrations");
+    apply_migrations(&db).await;
 
     // Build repository
-    let repo: LibsqlRepository<User, MyAdapter> =
-        LibsqlRepository::new(std::sync::Arc::new(db), MyAdapter);
+    let repo: LibsqlRepository<User, MyAdapter> = LibsqlRepository::new(db, MyAdapter);
 
     // Insert
     let u = User {
@@ -80,3 +89,97 @@ async fn libsql_crud_and_find_by_field_in_memory() -> storeit_core::RepoResult<(
 
     Ok(())
 }
+
+#[cfg(feature = "libsql-backend")]
+struct MembershipAdapter;
+
+#[cfg(feature = "libsql-backend")]
+impl RowAdapter<Membership> for MembershipAdapter {
+    type Row = libsql::Row;
+
+    fn from_row(&self, row: &Self::Row) -> storeit_core::RepoResult<Membership> {
+        Ok(Membership {
+            id: Some(row.get(0).map_err(storeit_core::RepoError::mapping)?),
+            user_id: row.get(1).map_err(storeit_core::RepoError::mapping)?,
+            role: row.get(2).map_err(storeit_core::RepoError::mapping)?,
+        })
+    }
+}
+
+#[cfg(feature = "libsql-backend")]
+struct NoteAdapter;
+
+#[cfg(feature = "libsql-backend")]
+impl RowAdapter<Note> for NoteAdapter {
+    type Row = libsql::Row;
+
+    fn from_row(&self, row: &Self::Row) -> storeit_core::RepoResult<Note> {
+        Ok(Note {
+            id: Some(row.get(0).map_err(storeit_core::RepoError::mapping)?),
+            tenant_id: row.get(1).map_err(storeit_core::RepoError::mapping)?,
+            body: row.get(2).map_err(storeit_core::RepoError::mapping)?,
+        })
+    }
+}
+
+#[cfg(feature = "libsql-backend")]
+struct LibsqlFactory {
+    db: std::sync::Arc<libsql::Database>,
+}
+
+#[cfg(feature = "libsql-backend")]
+#[async_trait::async_trait]
+impl tests_common::RepoFactory for LibsqlFactory {
+    async fn new_user_repo(
+        &self,
+    ) -> storeit_core::RepoResult<Box<dyn Repository<User> + Send + Sync>> {
+        let repo = storeit_libsql::LibsqlRepository::new(self.db.clone(), MyAdapter);
+        Ok(Box::new(repo))
+    }
+}
+
+#[cfg(feature = "libsql-backend")]
+#[async_trait::async_trait]
+impl tests_common::MembershipRepoFactory for LibsqlFactory {
+    async fn new_membership_repo(
+        &self,
+    ) -> storeit_core::RepoResult<Box<dyn Repository<Membership> + Send + Sync>> {
+        let repo = storeit_libsql::LibsqlRepository::new(self.db.clone(), MembershipAdapter);
+        Ok(Box::new(repo))
+    }
+}
+
+#[cfg(feature = "libsql-backend")]
+#[async_trait::async_trait]
+impl tests_common::NoteRepoFactory for LibsqlFactory {
+    async fn new_note_repo(
+        &self,
+    ) -> storeit_core::RepoResult<Box<dyn Repository<Note> + Send + Sync>> {
+        let repo = storeit_libsql::LibsqlRepository::new(self.db.clone(), NoteAdapter);
+        Ok(Box::new(repo))
+    }
+}
+
+#[cfg(feature = "libsql-backend")]
+#[tokio::test]
+#[ignore = "Excluded from default runs to keep coverage fast and deterministic; run with -- --ignored to execute"]
+async fn libsql_generic_suite_file_db() -> storeit_core::RepoResult<()> {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let path = dir.path().join("generic_suite.sqlite3");
+    #[allow(deprecated)]
+    let db = libsql::Database::open(format!("file:{}?mode=rwc", path.display()))
+        .map_err(storeit_core::RepoError::backend)?;
+    let db = std::sync::Arc::new(db);
+    apply_migrations(&db).await;
+
+    let mgr = storeit_libsql::LibsqlTransactionManager::new(db.clone());
+    let factory = LibsqlFactory { db };
+    tests_common::test_crud_roundtrip(&factory).await?;
+    tests_common::test_find_by_field(&factory).await?;
+    tests_common::test_unique_violation(&factory).await?;
+    tests_common::test_foreign_key_violation(&factory).await?;
+    tests_common::test_not_null_violation(&factory).await?;
+    tests_common::test_read_only_violation(&factory, &mgr).await?;
+    tests_common::test_tenant_isolation(&factory).await?;
+    Ok(())
+}
@@ -1,11 +1,18 @@
 //! Procedural macros for the `storeit-rs` repository library.
 //!
-//! This crate provides two main macros:
+//! This crate provides five main macros:
 //! - `#[derive(Entity)]`: A derive macro that inspects a struct and generates all the
 //!   necessary metadata and a default `RowAdapter` implementation for it to be used
 //!   in a repository.
+//! - `#[derive(Projection)]`: A derive macro for read models selecting a subset of an
+//!   entity's columns, with their own `RowAdapter`.
+//! - `#[derive(Embeddable)]`: A derive macro for value objects that entities store as a group
+//!   of prefixed columns via `#[fetch(flatten, prefix = "...")]`.
+//! - `#[derive(DbEnum)]`: A derive macro mapping fieldless enums to text or integer columns.
 //! - `#[repository(...)]`: An attribute macro that generates a complete, asynchronous
 //!   repository module for an entity.
+//!
+//! It also implements `storeit_migrate::embed_migrations!`.
 
 use proc_macro::TokenStream;
 use quote::{quote, ToTokens};
@@ -75,7 +82,32 @@ struct FieldMetadata {
     ty_str: String,
     column_name: String,
     is_id: bool,
+    /// `false` for `#[fetch(id, generated = false)]`: the caller supplies the key.
+    id_generated: bool,
     is_skipped: bool,
+    /// `#[fetch(version)]`: the optimistic-lock version column.
+    is_version: bool,
+    /// `#[fetch(created_at)]`: set from the clock on insert, never updated.
+    is_created_at: bool,
+    /// `#[fetch(updated_at)]`: set from the clock on insert and on every update.
+    is_updated_at: bool,
+    /// `#[fetch(with = "path::module")]`: a module providing `to_param_value(&T) -> ParamValue`
+    /// and `from_column(ParamValue) -> RepoResult<T>` for this field. `#[fetch(json)]` is
+    /// shorthand for `with = "::storeit::json"`.
+    with: Option<syn::Path>,
+    /// `#[fetch(belongs_to = "Parent")]`: a foreign key holding `Parent`'s key.
+    belongs_to: Option<Type>,
+    /// `#[fetch(flatten, prefix = "billing_")]`: an `Embeddable` field whose columns are
+    /// spliced into the entity's with the given prefix (empty by default).
+    flatten: Option<String>,
+    /// `#[fetch(json)]`: stored as a JSON document.
+    is_json: bool,
+    /// `#[fetch(unique)]`: a unique column in the generated DDL.
+    is_unique: bool,
+    /// `#[fetch(index)]`: an indexed column in the generated DDL.
+    is_indexed: bool,
+    /// `#[fetch(sql_type = "...")]`: the column type emitted verbatim in the generated DDL.
+    sql_type: Option<String>,
 }
 
 /// Parses all named fields from a `DeriveInput` struct.
@@ -97,7 +129,19 @@ fn parse_field_metadata(input: &DeriveInput) -> Vec<FieldMetadata> {
             let ty_str = ty.to_token_stream().to_string().replace(' ', "");
             let mut column_name = ident.to_string();
             let mut is_id = false;
+            let mut id_generated = true;
             let mut is_skipped = false;
+            let mut is_version = false;
+            let mut is_created_at = false;
+            let mut is_updated_at = false;
+            let mut with = None;
+            let mut belongs_to = None;
+            let mut json = false;
+            let mut is_unique = false;
+            let mut is_indexed = false;
+            let mut sql_type = None;
+            let mut flatten = false;
+            let mut prefix = None;
 
             for attr in &field.attrs {
                 if attr.path().is_ident("fetch") {
@@ -114,8 +158,66 @@ fn parse_field_metadata(input: &DeriveInput) -> Vec<FieldMetadata> {
                                 column_name = s.value();
                             } else if meta.path.is_ident("id") {
                                 is_id = true;
+                            } else if meta.path.is_ident("generated") {
+                                let value = meta
+                                    .value()
+                                    .expect("Invalid #[fetch(generated = ...)] syntax");
+                                let b: syn::LitBool = value
+                                    .parse()
+                                    .expect("Invalid #[fetch(generated = ...)] value; expected true or false");
+                                id_generated = b.value;
                             } else if meta.path.is_ident("skip") {
                                 is_skipped = true;
+                            } else if meta.path.is_ident("version") {
+                                is_version = true;
+                            } else if meta.path.is_ident("created_at") {
+                                is_created_at = true;
+                            } else if meta.path.is_ident("updated_at") {
+                                is_updated_at = true;
+                            } else if meta.path.is_ident("with") {
+                                let value = meta
+                                    .value()
+                                    .expect("Invalid #[fetch(with = \"...\")] syntax");
+                                let s: LitStr = value
+                                    .parse()
+                                    .expect("Invalid #[fetch(with = \"...\")] value");
+                                with = Some(s.parse::<syn::Path>().expect(
+                                    "Invalid #[fetch(with = \"...\")] value; expected a module path",
+                                ));
+                            } else if meta.path.is_ident("json") {
+                                json = true;
+                            } else if meta.path.is_ident("unique") {
+                                is_unique = true;
+                            } else if meta.path.is_ident("index") {
+                                is_indexed = true;
+                            } else if meta.path.is_ident("sql_type") {
+                                let value = meta
+                                    .value()
+                                    .expect("Invalid #[fetch(sql_type = \"...\")] syntax");
+                                let s: LitStr = value
+                                    .parse()
+                                    .expect("Invalid #[fetch(sql_type = \"...\")] value");
+                                sql_type = Some(s.value());
+                            } else if meta.path.is_ident("flatten") {
+                                flatten = true;
+                            } else if meta.path.is_ident("prefix") {
+                                let value = meta
+                                    .value()
+                                    .expect("Invalid #[fetch(prefix = \"...\")] syntax");
+                                let s: LitStr = value
+                                    .parse()
+                                    .expect("Invalid #[fetch(prefix = \"...\")] value");
+                                prefix = Some(s.value());
+                            } else if meta.path.is_ident("belongs_to") {
+                                let value = meta
+                                    .value()
+                                    .expect("Invalid #[fetch(belongs_to = \"...\")] syntax");
+                                let s: LitStr = value
+                                    .parse()
+                                    .expect("Invalid #[fetch(belongs_to = \"...\")] value");
+                                belongs_to = Some(s.parse::<Type>().expect(
+                                    "Invalid #[fetch(belongs_to = \"...\")] value; expected a type path",
+                                ));
                             }
                             Ok(())
                         })
@@ -123,41 +225,348 @@ fn parse_field_metadata(input: &DeriveInput) -> Vec<FieldMetadata> {
                     }
                 }
             }
+            if json {
+                if with.is_some() {
+                    panic!("#[fetch(json)] on `{}` cannot be combined with #[fetch(with = \"...\")].", ident);
+                }
+                with = Some(syn::parse_quote!(::storeit::json));
+            }
+            if prefix.is_some() && !flatten {
+                panic!("#[fetch(prefix = \"...\")] on `{}` requires #[fetch(flatten)].", ident);
+            }
+            let flatten = flatten.then(|| prefix.unwrap_or_default());
             FieldMetadata {
                 ident,
                 ty,
                 ty_str,
                 column_name,
                 is_id,
+                id_generated,
                 is_skipped,
+                is_version,
+                is_created_at,
+                is_updated_at,
+                with,
+                belongs_to,
+                flatten,
+                is_json: json,
+                is_unique,
+                is_indexed,
+                sql_type,
             }
         })
         .collect()
 }
 
+/// The field marked by `is_marked`, if any; panics when several fields carry `#[fetch(attr)]`.
+fn single_marked<'a>(
+    fields: &'a [FieldMetadata],
+    is_marked: impl Fn(&FieldMetadata) -> bool,
+    attr: &str,
+) -> Option<&'a FieldMetadata> {
+    let mut marked = fields.iter().filter(|f| is_marked(f));
+    let first = marked.next();
+    if marked.next().is_some() {
+        panic!("Only one field can be marked with #[fetch({})].", attr);
+    }
+    first
+}
+
 // --- `Entity` derive macro ---
 
+/// The prefixed columns of a flattened field as a `&'static [&'static str]` expression,
+/// spliced together at compile time by the `storeit::embed` const functions.
+fn embedded_columns(field: &FieldMetadata, prefix: &str) -> proc_macro2::TokenStream {
+    let ty = &field.ty;
+    quote! {{
+        const COLUMNS: &[&str] = <#ty as ::storeit::Embeddable>::COLUMNS;
+        const BYTES: [u8; ::storeit::embed::prefixed_len(#prefix, COLUMNS)] =
+            ::storeit::embed::prefixed_bytes(#prefix, COLUMNS);
+        const NAMES: [&str; COLUMNS.len()] =
+            ::storeit::embed::prefixed_columns(&BYTES, #prefix, COLUMNS);
+        &NAMES
+    }}
+}
+
+/// The columns of `fields` as a `&'static [&'static str]` expression; a plain array literal
+/// unless a field is flattened.
+fn column_list(fields: &[&FieldMetadata]) -> proc_macro2::TokenStream {
+    if fields.iter().all(|f| f.flatten.is_none()) {
+        let columns = fields.iter().map(|f| &f.column_name);
+        return quote! { &[#(#columns),*] };
+    }
+    let parts = fields.iter().map(|f| match &f.flatten {
+        Some(prefix) => embedded_columns(f, prefix),
+        None => {
+            let column = &f.column_name;
+            quote! { &[#column] }
+        }
+    });
+    quote! {{
+        const PARTS: &[&[&str]] = &[#(#parts),*];
+        const ALL: [&str; ::storeit::embed::concat_len(PARTS)] = ::storeit::embed::concat(PARTS);
+        &ALL
+    }}
+}
+
+/// Rejects flattened fields combined with per-column options, and prefixes that would not
+/// form valid column names.
+fn validate_flattened(fields: &[FieldMetadata]) {
+    for f in fields {
+        let Some(prefix) = &f.flatten else { continue };
+        if f.is_id
+            || f.is_version
+            || f.is_created_at
+            || f.is_updated_at
+            || f.with.is_some()
+            || f.belongs_to.is_some()
+            || f.is_unique
+            || f.is_indexed
+            || f.sql_type.is_some()
+        {
+            panic!(
+                "#[fetch(flatten)] field `{}` cannot also be an id, version, audit, `with`, belongs_to, unique, index or sql_type field.",
+                f.ident
+            );
+        }
+        if !prefix
+            .chars()
+            .all(|c| c == '_' || c.is_ascii_alphanumeric())
+        {
+            panic!(
+                "Invalid prefix `{}` on `{}`. Use ASCII letters, digits, or `_`.",
+                prefix, f.ident
+            );
+        }
+    }
+}
+
+/// The last path segment of `ty` (`DateTime` for `chrono::DateTime<Utc>`). Well-known types
+/// are recognized by this exact name, so a user type such as `OrderUuid` is not mistaken for one.
+fn type_name(ty: &Type) -> Option<String> {
+    match ty {
+        Type::Path(p) if p.qself.is_none() => p.path.segments.last().map(|s| s.ident.to_string()),
+        _ => None,
+    }
+}
+
+/// The portable `::storeit::schema::SqlType` of a field type (the inner type of an `Option`).
+/// Types the derive does not map natively are assumed to be stored as text.
+fn sql_type_of(ty: &Type) -> proc_macro2::TokenStream {
+    let variant = match ty.to_token_stream().to_string().replace(' ', "").as_str() {
+        "Vec<u8>" => quote! { Bytes },
+        "serde_json::Value" => quote! { Json },
+        _ => match type_name(ty).as_deref() {
+            Some("i32") => quote! { Integer },
+            Some("i64") => quote! { BigInt },
+            Some("f64") => quote! { Double },
+            Some("bool") => quote! { Boolean },
+            Some("SystemTime" | "NaiveDateTime") => quote! { Timestamp },
+            Some("DateTime") => quote! { TimestampTz },
+            Some("NaiveDate") => quote! { Date },
+            Some("NaiveTime") => quote! { Time },
+            Some("Decimal") => quote! { Decimal },
+            Some("Uuid") => quote! { Uuid },
+            _ => quote! { Text },
+        },
+    };
+    quote! { ::storeit::schema::SqlType::#variant }
+}
+
+/// Statements pushing the `::storeit::schema::ColumnDef`s of `fields` onto `columns`, in
+/// [`column_list`] order. Key columns are never nullable.
+fn column_defs(fields: &[&FieldMetadata]) -> proc_macro2::TokenStream {
+    let pushes = fields.iter().map(|f| {
+        if let Some(prefix) = &f.flatten {
+            let ty = &f.ty;
+            return quote! {
+                columns.extend(
+                    <#ty as ::storeit::Embeddable>::column_defs()
+                        .into_iter()
+                        .map(|c| ::storeit::schema::ColumnDef { name: format!("{}{}", #prefix, c.name), ..c }),
+                );
+            };
+        }
+        let name = &f.column_name;
+        let inner = get_option_inner(&f.ty).unwrap_or(&f.ty);
+        let sql_type = match &f.sql_type {
+            Some(custom) => quote! { ::storeit::schema::SqlType::Custom(#custom.to_string()) },
+            None if f.is_json => quote! { ::storeit::schema::SqlType::Json },
+            None => sql_type_of(inner),
+        };
+        let nullable = is_option(&f.ty) && !f.is_id;
+        let unique = f.is_unique;
+        let indexed = f.is_indexed;
+        quote! {
+            columns.push(::storeit::schema::ColumnDef {
+                name: #name.to_string(),
+                sql_type: #sql_type,
+                nullable: #nullable,
+                unique: #unique,
+                indexed: #indexed,
+            });
+        }
+    });
+    quote! { #(#pushes)* }
+}
+
+/// The `Vec<ParamValue>` of `fields`, one value per column of [`column_list`].
+fn values_expr(fields: &[&FieldMetadata]) -> proc_macro2::TokenStream {
+    if fields.iter().all(|f| f.flatten.is_none()) {
+        let values = fields.iter().map(|f| field_to_param_value(f));
+        return quote! { vec![#(#values),*] };
+    }
+    // Runs of plain fields become `vec![..]` segments between the flattened fields' values.
+    let mut segments = Vec::new();
+    let mut run = Vec::new();
+    for f in fields {
+        if f.flatten.is_some() {
+            if !run.is_empty() {
+                segments.push(quote! { vec![#(#run),*] });
+                run.clear();
+            }
+            let ident = &f.ident;
+            segments.push(quote! { ::storeit::Embeddable::to_values(&self.#ident) });
+        } else {
+            run.push(field_to_param_value(f));
+        }
+    }
+    if !run.is_empty() {
+        segments.push(quote! { vec![#(#run),*] });
+    }
+    quote! { [#(#segments),*].concat() }
+}
+
+/// The `ParamValue` expression binding `field` of `self`. Audit fields bind the `__now`
+/// reading taken at the start of the values function.
+fn field_to_param_value(field: &FieldMetadata) -> proc_macro2::TokenStream {
+    let ident = &field.ident;
+
+    if field.is_created_at || field.is_updated_at {
+        return quote! { ::storeit::ToParamValue::to_param_value(&__now) };
+    }
+    if let Some(with) = &field.with {
+        return quote! { #with::to_param_value(&self.#ident) };
+    }
+    quote! { ::storeit::ToParamValue::to_param_value(&self.#ident) }
+}
+
+/// Per-field initializers for a generated `RowAdapter`, using index-based getters on
+/// `::storeit::row::RowRead`. Skipped fields are not selected, so the column index only
+/// advances for mapped fields. A flattened field reads its `Embeddable::COLUMNS`, whose count
+/// is only known to the compiler, so later indices add it to the literal.
+fn row_field_inits(fields: &[FieldMetadata]) -> Vec<proc_macro2::TokenStream> {
+    let mut col_idx = 0usize;
+    let mut embedded_lens: Vec<proc_macro2::TokenStream> = Vec::new();
+    fields
+        .iter()
+        .map(|f| {
+            let ident = &f.ident;
+            if f.is_skipped {
+                return quote! { #ident: ::core::default::Default::default() };
+            }
+            let lit = proc_macro2::Literal::usize_unsuffixed(col_idx);
+            let idx_lit = quote! { #lit #(+ #embedded_lens)* };
+            if f.flatten.is_some() {
+                let ty = &f.ty;
+                let len = quote! { <#ty as ::storeit::Embeddable>::COLUMNS.len() };
+                let init = quote! {
+                    #ident: <#ty as ::storeit::Embeddable>::from_values(
+                        (0..#len)
+                            .map(|i| row.get_value(#idx_lit + i))
+                            .collect::<::storeit::RepoResult<Vec<_>>>()?,
+                    )?
+                };
+                embedded_lens.push(len);
+                return init;
+            }
+            col_idx += 1;
+            let get_value = quote! { row.get_value(#idx_lit)? };
+
+            if let Some(with) = &f.with {
+                return quote! { #ident: #with::from_column(#get_value)? };
+            }
+
+            // The primitives use the backend's typed getters; everything else (chrono, uuid,
+            // decimals, newtypes, enums) maps through `FromColumn`.
+            let get = match get_option_inner(&f.ty) {
+                Some(inner) => match inner.to_token_stream().to_string().as_str() {
+                    "String" => quote! { row.get_opt_string(#idx_lit)? },
+                    "i32" => quote! { row.get_opt_i32(#idx_lit)? },
+                    "i64" => quote! { row.get_opt_i64(#idx_lit)? },
+                    "f64" => quote! { row.get_opt_f64(#idx_lit)? },
+                    "bool" => quote! { row.get_opt_bool(#idx_lit)? },
+                    _ => quote! { ::storeit::FromColumn::from_column(#get_value)? },
+                },
+                None => match f.ty_str.as_str() {
+                    "String" => quote! { row.get_string(#idx_lit)? },
+                    "i32" => quote! { row.get_i32(#idx_lit)? },
+                    "i64" => quote! { row.get_i64(#idx_lit)? },
+                    "f64" => quote! { row.get_f64(#idx_lit)? },
+                    "bool" => quote! { row.get_bool(#idx_lit)? },
+                    _ => quote! { ::storeit::FromColumn::from_column(#get_value)? },
+                },
+            };
+            quote! { #ident: #get }
+        })
+        .collect()
+}
+
+/// The `<Name>RowAdapter<R>` type and its `RowAdapter` impl over any `::storeit::row::RowRead`.
+fn row_adapter_impls(
+    struct_name: &Ident,
+    adapter_struct_name: &Ident,
+    field_inits: &[proc_macro2::TokenStream],
+) -> proc_macro2::TokenStream {
+    quote! {
+        #[derive(Debug, Clone, Copy, Default)]
+        pub struct #adapter_struct_name<R>(::core::marker::PhantomData<R>);
+
+        impl<R> #adapter_struct_name<R> {
+            pub fn new() -> Self { Self(::core::marker::PhantomData) }
+        }
+
+        impl<R> ::storeit::RowAdapter<#struct_name> for #adapter_struct_name<R>
+        where
+            R: ::storeit::row::RowRead,
+        {
+            type Row = R;
+            fn from_row(&self, row: &Self::Row) -> ::storeit::RepoResult<#struct_name> {
+                Ok(#struct_name { #(#field_inits),* })
+            }
+        }
+    }
+}
+
 #[proc_macro_derive(Entity, attributes(entity, fetch))]
 pub fn derive_entity(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
     let struct_name = &input.ident;
     let fields_metadata = parse_field_metadata(&input);
 
-    // --- Get table name ---
-    // Look for `#[entity(table = "...")]` first.
-    let table_name_override = input.attrs.iter().find_map(|attr| {
-        if attr.path().is_ident("entity") {
-            if let Ok(meta) = attr.meta.require_list() {
-                let parsed: Result<MetaNameValue, _> = syn::parse2(meta.tokens.clone());
-                if let Ok(MetaNameValue { path, value, .. }) = parsed {
-                    if path.is_ident("table") {
-                        return Some(value.value());
-                    }
-                }
-            }
-        }
-        None
-    });
+    // --- Get table name, soft-delete and tenant columns ---
+    // Look for `#[entity(table = "...", soft_delete = "...", tenant = "...")]` first.
+    let entity_options: Vec<MetaNameValue> = input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("entity"))
+        .filter_map(|attr| attr.meta.require_list().ok())
+        .filter_map(|meta| {
+            meta.parse_args_with(Punctuated::<MetaNameValue, Token![,]>::parse_terminated)
+                .ok()
+        })
+        .flatten()
+        .collect();
+    let entity_option = |name: &str| {
+        entity_options
+            .iter()
+            .find(|o| o.path.is_ident(name))
+            .map(|o| o.value.value())
+    };
+    let table_name_override = entity_option("table");
+    let soft_delete_column = entity_option("soft_delete");
+    let tenant_column = entity_option("tenant");
+    let listener_type = entity_option("listener");
 
     // If no override, deduce it from the struct name (`User` -> `users`).
     let table_name = table_name_override
@@ -185,13 +594,63 @@ pub fn derive_entity(input: TokenStream) -> TokenStream {
             panic!("Invalid column name `{}`. Use ASCII letters, digits, or `_`, starting with a letter or `_`. See docs/architecture.md (Entities) for guidance.", f.column_name);
         }
     }
+    validate_flattened(&fields_metadata);
+    if let Some(col) = &soft_delete_column {
+        if !is_valid_ident(col) {
+            panic!("Invalid soft_delete column `{}`. Use ASCII letters, digits, or `_`, starting with a letter or `_`.", col);
+        }
+        if fields_metadata.iter().any(|f| {
+            &f.column_name == col
+                && (f.is_id
+                    || f.is_version
+                    || f.is_created_at
+                    || f.is_updated_at
+                    || f.flatten.is_some())
+        }) {
+            panic!(
+                "The soft_delete column `{}` cannot be an id, version, audit or flattened field.",
+                col
+            );
+        }
+    }
+    // A field mapped to the soft-delete column is read like any other but only written
+    // by delete/restore, so a stale entity cannot undo a deletion on update.
+    let is_soft_delete_field =
+        |f: &FieldMetadata| soft_delete_column.as_deref() == Some(f.column_name.as_str());
+    let soft_delete_const = match &soft_delete_column {
+        Some(col) => quote! { const SOFT_DELETE_COLUMN: Option<&'static str> = Some(#col); },
+        None => quote! {},
+    };
+    // The tenant column must be a plain mapped field: inserts stamp it in place, and it is
+    // never updated, so an entity cannot be moved to another tenant.
+    if let Some(col) = &tenant_column {
+        let ok = fields_metadata.iter().any(|f| {
+            &f.column_name == col
+                && !(f.is_id
+                    || f.is_skipped
+                    || f.is_version
+                    || f.is_created_at
+                    || f.is_updated_at
+                    || f.flatten.is_some()
+                    || is_soft_delete_field(f))
+        });
+        if !ok {
+            panic!(
+                "The tenant column `{}` must map to a field that is not an id, skipped, version, audit, flattened or soft_delete field.",
+                col
+            );
+        }
+    }
+    let is_tenant_field =
+        |f: &FieldMetadata| tenant_column.as_deref() == Some(f.column_name.as_str());
+    let tenant_const = match &tenant_column {
+        Some(col) => quote! { const TENANT_COLUMN: Option<&'static str> = Some(#col); },
+        None => quote! {},
+    };
 
     // --- Implement `Fetchable` ---
-    let select_columns: Vec<_> = fields_metadata
-        .iter()
-        .filter(|f| !f.is_skipped)
-        .map(|f| &f.column_name)
-        .collect();
+    let select_fields: Vec<_> = fields_metadata.iter().filter(|f| !f.is_skipped).collect();
+    let select_columns = column_list(&select_fields);
     let findable_columns: Vec<_> = fields_metadata
         .iter()
         .filter(|f| {
@@ -209,315 +668,752 @@ pub fn derive_entity(input: TokenStream) -> TokenStream {
     let fetchable_impl = quote! {
         impl ::storeit::Fetchable for #struct_name {
             const TABLE: &'static str = #table_name;
-            const SELECT_COLUMNS: &'static [&'static str] = &[#(#select_columns),*];
+            const SELECT_COLUMNS: &'static [&'static str] = #select_columns;
             const FINDABLE_COLUMNS: &'static [(&'static str, &'static str)] = &[#(#findable_columns),*];
+            #soft_delete_const
+            #tenant_const
         }
     };
 
     // --- Implement `Identifiable` ---
-    // Validate exactly one #[fetch(id)]
-    let id_count = fields_metadata.iter().filter(|f| f.is_id).count();
-    if id_count == 0 {
+    // One #[fetch(id)] field gives a scalar key; several give a composite (tuple) key in field order.
+    let id_fields: Vec<&FieldMetadata> = fields_metadata.iter().filter(|f| f.is_id).collect();
+    if id_fields.is_empty() {
         panic!("A field must be marked with #[fetch(id)]. Hint: mark your primary key field like `#[fetch(id)]`. See docs/architecture.md (Entities) for details.");
-    } else if id_count > 1 {
-        panic!("Exactly one field must be marked with #[fetch(id)] (found {}). Remove extra #[fetch(id)] attributes. See docs/architecture.md (Entities).", id_count);
     }
+    let is_composite = id_fields.len() > 1;
+    // Composite keys are always supplied by the caller.
+    let id_generated = !is_composite && id_fields[0].id_generated;
 
-    let id_field = fields_metadata
+    let key_tys: Vec<&Type> = id_fields
         .iter()
-        .find(|f| f.is_id)
-        .expect("unreachable: validated id_count == 1");
-    let id_ident = &id_field.ident;
-    let id_ty = &id_field.ty;
-    let key_ty = get_option_inner(id_ty).unwrap_or(id_ty);
-    let id_column_name = &id_field.column_name;
-
-    let id_accessor = if is_option(id_ty) {
-        quote! { self.#id_ident.clone() }
+        .map(|f| get_option_inner(&f.ty).unwrap_or(&f.ty))
+        .collect();
+    let id_columns: Vec<&String> = id_fields.iter().map(|f| &f.column_name).collect();
+    let id_column_name = id_columns[0];
+
+    let (key_ty, id_accessor) = if is_composite {
+        let parts: Vec<_> = id_fields
+            .iter()
+            .map(|f| {
+                let ident = &f.ident;
+                if is_option(&f.ty) {
+                    quote! { self.#ident.clone()? }
+                } else {
+                    quote! { self.#ident.clone() }
+                }
+            })
+            .collect();
+        (quote! { (#(#key_tys),*) }, quote! { Some((#(#parts),*)) })
     } else {
-        quote! { Some(self.#id_ident.clone()) }
+        let id_ident = &id_fields[0].ident;
+        let key_ty = key_tys[0];
+        let accessor = if is_option(&id_fields[0].ty) {
+            quote! { self.#id_ident.clone() }
+        } else {
+            quote! { Some(self.#id_ident.clone()) }
+        };
+        (quote! { #key_ty }, accessor)
     };
 
+    // `#[entity(listener = "path::Type")]` names a `Default` type, built once on first use.
+    let listener_impl = listener_type.map(|ty| {
+        let ty: syn::Type = syn::parse_str(&ty)
+            .unwrap_or_else(|_| panic!("Invalid listener `{}`. Expected a type path.", ty));
+        quote! {
+            fn listener() -> Option<&'static dyn ::storeit::EntityListener<Self>> {
+                static LISTENER: ::std::sync::OnceLock<#ty> = ::std::sync::OnceLock::new();
+                Some(LISTENER.get_or_init(<#ty as ::core::default::Default>::default))
+            }
+        }
+    });
+
     let identifiable_impl = quote! {
         impl ::storeit::Identifiable for #struct_name {
             type Key = #key_ty;
             const ID_COLUMN: &'static str = #id_column_name;
+            const ID_COLUMNS: &'static [&'static str] = &[#(#id_columns),*];
+            const ID_GENERATED: bool = #id_generated;
             fn id(&self) -> Option<Self::Key> {
                 #id_accessor
             }
         }
-    };
-
-    // --- Implement `Insertable` and `Updatable` ---
-    let to_param_value = |field: &FieldMetadata| {
-        let ident = &field.ident;
-        let ty_str = &field.ty_str;
 
-        if is_option(&field.ty) {
-            return match ty_str.as_str() {
-                s if s.contains("String") => {
-                    quote! { self.#ident.as_ref().cloned().map(::storeit::ParamValue::String).unwrap_or(::storeit::ParamValue::Null) }
-                }
-                s if s.contains("i32") => {
-                    quote! { self.#ident.map_or(::storeit::ParamValue::Null, ::storeit::ParamValue::I32) }
-                }
-                s if s.contains("i64") => {
-                    quote! { self.#ident.map_or(::storeit::ParamValue::Null, ::storeit::ParamValue::I64) }
-                }
-                s if s.contains("f64") => {
-                    quote! { self.#ident.map_or(::storeit::ParamValue::Null, ::storeit::ParamValue::F64) }
-                }
-                s if s.contains("bool") => {
-                    quote! { self.#ident.map_or(::storeit::ParamValue::Null, ::storeit::ParamValue::Bool) }
-                }
-                s if s.contains("SystemTime") => {
-                    quote! { self.#ident.map_or(::storeit::ParamValue::Null, |st| ::storeit::ParamValue::I64(::chrono::DateTime::<::chrono::Utc>::from(st).timestamp_millis())) }
-                }
-                s if s.contains("NaiveDateTime") => {
-                    quote! { self.#ident.as_ref().map(|v| ::storeit::ParamValue::String(v.to_string())).unwrap_or(::storeit::ParamValue::Null) }
-                }
-                s if s.contains("NaiveDate") => {
-                    quote! { self.#ident.as_ref().map(|v| ::storeit::ParamValue::String(v.to_string())).unwrap_or(::storeit::ParamValue::Null) }
-                }
-                s if s.contains("rust_decimal::Decimal") || s.ends_with("::Decimal") || s == "Decimal" || s.contains("Decimal") => {
-                    quote! { self.#ident.as_ref().map(|v| ::storeit::ParamValue::String(v.to_string())).unwrap_or(::storeit::ParamValue::Null) }
-                }
-                s if s.contains("uuid::Uuid") || s.ends_with("::Uuid") || s == "Uuid" || s.contains("Uuid") => {
-                    quote! { self.#ident.as_ref().map(|v| ::storeit::ParamValue::String(v.to_string())).unwrap_or(::storeit::ParamValue::Null) }
-                }
-                _ => panic!("Unsupported Option type for ParamValue: {}. Hint: map this field to a supported type (String/i32/i64/f64/bool), or mark it with #[fetch(skip)] to exclude it from persistence.", ty_str),
-            };
+        impl ::storeit::Listenable for #struct_name {
+            #listener_impl
         }
+    };
 
-        match ty_str.as_str() {
-            "String" => quote! { ::storeit::ParamValue::String(self.#ident.clone()) },
-            "i32" => quote! { ::storeit::ParamValue::I32(self.#ident) },
-            "i64" => quote! { ::storeit::ParamValue::I64(self.#ident) },
-            "f64" => quote! { ::storeit::ParamValue::F64(self.#ident) },
-            "bool" => quote! { ::storeit::ParamValue::Bool(self.#ident) },
-            s if s.ends_with("SystemTime") => {
-                quote! { ::storeit::ParamValue::I64(::chrono::DateTime::<::chrono::Utc>::from(self.#ident).timestamp_millis()) }
-            }
-            s if s.ends_with("NaiveDateTime") => {
-                // Use Display to format to a portable string (e.g., "YYYY-MM-DD HH:MM:SS").
-                quote! { ::storeit::ParamValue::String(self.#ident.to_string()) }
-            }
-            s if s.ends_with("NaiveDate") => {
-                // Format as YYYY-MM-DD.
-                quote! { ::storeit::ParamValue::String(self.#ident.to_string()) }
-            }
-            s if s.ends_with("rust_decimal::Decimal") || s.ends_with("Decimal") => {
-                // Decimal string representation.
-                quote! { ::storeit::ParamValue::String(self.#ident.to_string()) }
-            }
-            s if s.ends_with("uuid::Uuid") || s.ends_with("Uuid") => {
-                // Standard hyphenated UUID string.
-                quote! { ::storeit::ParamValue::String(self.#ident.to_string()) }
+    // `#[fetch(belongs_to = "Parent")]` fields implement `BelongsTo<Parent>`, so each parent
+    // type can be referenced by one field only.
+    let mut parents = std::collections::HashSet::new();
+    let belongs_to_impls = fields_metadata.iter().filter_map(|f| {
+        let parent = f.belongs_to.as_ref()?;
+        if !parents.insert(parent.to_token_stream().to_string()) {
+            panic!(
+                "#[fetch(belongs_to)] on `{}`: `{}` is already referenced by another field.",
+                f.ident,
+                parent.to_token_stream()
+            );
+        }
+        if f.is_skipped || f.is_id {
+            panic!(
+                "#[fetch(belongs_to)] field `{}` must be a mapped, non-id column.",
+                f.ident
+            );
+        }
+        let ident = &f.ident;
+        let column = &f.column_name;
+        let accessor = if get_option_inner(&f.ty).is_some() {
+            quote! { self.#ident.clone() }
+        } else {
+            quote! { Some(self.#ident.clone()) }
+        };
+        Some(quote! {
+            impl ::storeit::BelongsTo<#parent> for #struct_name {
+                const FOREIGN_KEY: &'static str = #column;
+                fn parent_key(&self) -> Option<<#parent as ::storeit::Identifiable>::Key> {
+                    #accessor
+                }
             }
-            _ => panic!("Unsupported type for ParamValue: {}. Hint: map this field to a supported type (String/i32/i64/f64/bool) or mark it with #[fetch(skip)]. See docs/plan.md item 15 for portable types.", ty_str),
+        })
+    });
+    let belongs_to_impls = quote! { #(#belongs_to_impls)* };
+
+    // --- Implement `Insertable` and `Updatable` ---
+    // Audit fields take one reading of `::storeit::clock::now()` per values call.
+    let created_at_field = single_marked(&fields_metadata, |f| f.is_created_at, "created_at");
+    let updated_at_field = single_marked(&fields_metadata, |f| f.is_updated_at, "updated_at");
+    for (f, attr) in [
+        (created_at_field, "created_at"),
+        (updated_at_field, "updated_at"),
+    ] {
+        let Some(f) = f else { continue };
+        let inner = get_option_inner(&f.ty).unwrap_or(&f.ty);
+        let is_timestamp = matches!(
+            type_name(inner).as_deref(),
+            Some("SystemTime" | "NaiveDateTime" | "DateTime")
+        );
+        if f.is_id
+            || f.is_skipped
+            || f.is_version
+            || !is_timestamp
+            || f.is_created_at && f.is_updated_at
+        {
+            panic!(
+                "#[fetch({})] field `{}` must be a single non-id, non-skipped SystemTime, chrono::NaiveDateTime or chrono::DateTime (optionally in an Option).",
+                attr, f.ident
+            );
         }
+    }
+    let now_binding = if created_at_field.is_some() || updated_at_field.is_some() {
+        quote! { let __now = ::storeit::clock::now(); }
+    } else {
+        quote! {}
     };
 
+    // Generated ids are left to the database; caller-supplied (and composite) key columns
+    // are inserted like any other column.
     let insert_fields: Vec<_> = fields_metadata
         .iter()
-        .filter(|f| !f.is_id && !f.is_skipped)
+        .filter(|f| (!id_generated || !f.is_id) && !f.is_skipped && !is_soft_delete_field(f))
         .collect();
-    let insert_columns: Vec<_> = insert_fields.iter().map(|f| &f.column_name).collect();
-    let insert_values: Vec<_> = insert_fields.iter().map(|f| to_param_value(f)).collect();
+    let insert_columns = column_list(&insert_fields);
+    let insert_values = values_expr(&insert_fields);
 
     let insertable_impl = quote! {
         impl ::storeit::Insertable for #struct_name {
-            const INSERT_COLUMNS: &'static [&'static str] = &[#(#insert_columns),*];
+            const INSERT_COLUMNS: &'static [&'static str] = #insert_columns;
             fn insert_values(&self) -> Vec<::storeit::ParamValue> {
-                vec![#(#insert_values),*]
+                #now_binding
+                #insert_values
             }
         }
     };
 
+    // The version column is incremented by the UPDATE itself and compared in its WHERE clause.
+    let version_fields: Vec<&FieldMetadata> =
+        fields_metadata.iter().filter(|f| f.is_version).collect();
+    if version_fields.len() > 1 {
+        panic!("Only one field can be marked with #[fetch(version)].");
+    }
+    let version_field = version_fields.first().copied();
+    if let Some(v) = version_field {
+        if v.is_id || v.is_skipped || !matches!(v.ty_str.as_str(), "i32" | "i64") {
+            panic!(
+                "#[fetch(version)] field `{}` must be a non-id, non-skipped i32 or i64.",
+                v.ident
+            );
+        }
+    }
+
     let update_fields: Vec<_> = fields_metadata
         .iter()
-        .filter(|f| !f.is_id && !f.is_skipped)
+        .filter(|f| {
+            !f.is_id
+                && !f.is_skipped
+                && !f.is_version
+                && !f.is_created_at
+                && !is_soft_delete_field(f)
+                && !is_tenant_field(f)
+        })
         .collect();
-    let update_columns: Vec<_> = update_fields.iter().map(|f| &f.column_name).collect();
-    let mut update_values: Vec<_> = update_fields.iter().map(|f| to_param_value(f)).collect();
-    update_values.push(to_param_value(id_field));
+    let update_columns = column_list(&update_fields);
+    // Values bind the SET columns, then the key, then the expected version.
+    let mut update_value_fields = update_fields.clone();
+    update_value_fields.extend(id_fields.iter().copied());
+    update_value_fields.extend(version_field);
+    let update_values = values_expr(&update_value_fields);
+    let version_impl = version_field.map(|v| {
+        let column = &v.column_name;
+        let ident = &v.ident;
+        quote! {
+            const VERSION_COLUMN: Option<&'static str> = Some(#column);
+            fn bump_version(&mut self) {
+                self.#ident += 1;
+            }
+        }
+    });
+
+    let update_now_binding = if updated_at_field.is_some() {
+        now_binding.clone()
+    } else {
+        quote! {}
+    };
+    // The returned entity gets the time the UPDATE wrote, converted back to the field type.
+    let touch_impl = updated_at_field.map(|f| {
+        let ident = &f.ident;
+        let ty = get_option_inner(&f.ty).unwrap_or(&f.ty);
+        let convert = quote! {
+            <#ty as ::storeit::FromColumn>::from_column(::storeit::ToParamValue::to_param_value(&now))
+        };
+        let assign = if is_option(&f.ty) {
+            quote! { self.#ident = Some(value) }
+        } else {
+            quote! { self.#ident = value }
+        };
+        quote! {
+            fn touch(&mut self, now: ::std::time::SystemTime) {
+                if let Ok(value) = #convert {
+                    #assign;
+                }
+            }
+        }
+    });
 
     let updatable_impl = quote! {
         impl ::storeit::Updatable for #struct_name {
-            const UPDATE_COLUMNS: &'static [&'static str] = &[#(#update_columns),*];
+            const UPDATE_COLUMNS: &'static [&'static str] = #update_columns;
             fn update_values(&self) -> Vec<::storeit::ParamValue> {
-                vec![#(#update_values),*]
+                #update_now_binding
+                #update_values
             }
+            #version_impl
+            #touch_impl
         }
     };
 
     // --- Generate `RowAdapter` ---
     let adapter_struct_name = Ident::new(&format!("{}RowAdapter", struct_name), struct_name.span());
 
-    let _try_get_mappings: Vec<_> = fields_metadata
-        .iter()
-        .map(|f| {
-            if f.is_skipped {
-                let ident = &f.ident;
-                return quote! { #ident: ::core::default::Default::default() };
+    let field_inits = row_field_inits(&fields_metadata);
+
+    let row_adapter_impls = row_adapter_impls(struct_name, &adapter_struct_name, &field_inits);
+
+    // --- Implement `Schema` ---
+    // A soft-delete column without a field still needs a column to stamp.
+    let table_columns = column_defs(&select_fields);
+    let soft_delete_column_def = soft_delete_column
+        .as_ref()
+        .filter(|col| !select_fields.iter().any(|f| &&f.column_name == col))
+        .map(|col| {
+            quote! {
+                columns.push(::storeit::schema::ColumnDef {
+                    nullable: true,
+                    ..::storeit::schema::ColumnDef::new(#col, ::storeit::schema::SqlType::Timestamp)
+                });
             }
-            let ident = &f.ident;
-            let col_name_lit = LitStr::new(&f.column_name, ident.span());
-            let ty_str = &f.ty_str;
-            if ty_str.ends_with("SystemTime") {
-                quote! {
-                    #ident: {
-                        let val: ::chrono::DateTime<::chrono::Utc> = row
-                            .try_get(#col_name_lit)
-                            .map_err(|e| ::storeit::RepoError::mapping(e))?;
-                        val.into()
-                    }
+        });
+    let schema_impl = quote! {
+        impl ::storeit::schema::Schema for #struct_name {
+            fn table_def() -> ::storeit::schema::TableDef {
+                let mut columns = Vec::new();
+                #table_columns
+                #soft_delete_column_def
+                ::storeit::schema::TableDef {
+                    name: #table_name.to_string(),
+                    columns,
+                    primary_key: vec![#(#id_columns.to_string()),*],
+                    key_generated: #id_generated,
                 }
-            } else {
-                quote! { #ident: row
-                .try_get(#col_name_lit)
-                .map_err(|e| ::storeit::RepoError::mapping(e))? }
             }
-        })
-        .collect();
+        }
+    };
 
-    let _mysql_get_mappings: Vec<_> = fields_metadata
-        .iter()
-        .map(|f| {
-            if f.is_skipped {
-                let ident = &f.ident;
-                return quote! { #ident: ::core::default::Default::default() };
-            }
-            let ident = &f.ident;
-            let col_name_lit = LitStr::new(&f.column_name, ident.span());
-            let ty_str = &f.ty_str;
+    // --- Combine all generated code ---
+    let expanded = quote! {
+        #fetchable_impl
+        #identifiable_impl
+        #schema_impl
+        #belongs_to_impls
+        #insertable_impl
+        #updatable_impl
+        #row_adapter_impls
+    };
 
-            if ty_str.ends_with("SystemTime") {
-                quote! {
-                    #ident: {
-                        let naive_dt: ::chrono::NaiveDateTime = row.get(#col_name_lit)
-                            .ok_or_else(|| ::storeit::RepoError::mapping(std::io::Error::new(std::io::ErrorKind::Other, format!("missing required column {}.{} in row", #table_name, #col_name_lit))))?;
-                        ::chrono::DateTime::<::chrono::Utc>::from_naive_utc_and_offset(naive_dt, ::chrono::Utc).into()
-                    }
-                }
+    TokenStream::from(expanded)
+}
+
+// --- `Projection` derive macro ---
+
+/// Derives `storeit::Projection` for a read model over an entity's table, named with
+/// `#[projection(of = Entity)]`. Fields map to columns like entity fields (`#[fetch(column =
+/// "...")]`, `#[fetch(with = "...")]`, `#[fetch(skip)]`), and each column must be one of the
+/// entity's `SELECT_COLUMNS`, which is checked at compile time.
+#[proc_macro_derive(Projection, attributes(projection, fetch))]
+pub fn derive_projection(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let struct_name = &input.ident;
+    let fields_metadata = parse_field_metadata(&input);
+
+    let mut entity: Option<Type> = None;
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|a| a.path().is_ident("projection"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("of") {
+                entity = Some(meta.value()?.parse()?);
+                Ok(())
             } else {
-                quote! { #ident: row.get(#col_name_lit).ok_or_else(|| ::storeit::RepoError::mapping(std::io::Error::new(std::io::ErrorKind::Other, format!("missing required column {}.{} in row", #table_name, #col_name_lit))))? }
+                Err(meta.error("unsupported projection option; expected `of = Entity`"))
             }
         })
-        .collect();
+        .expect("Invalid #[projection(...)] attribute syntax");
+    }
+    let entity =
+        entity.expect("#[derive(Projection)] requires #[projection(of = Entity)] on the struct.");
+
+    validate_flattened(&fields_metadata);
+    let mapped: Vec<&FieldMetadata> = fields_metadata.iter().filter(|f| !f.is_skipped).collect();
+    let columns = column_list(&mapped);
+    let entity_name = entity.to_token_stream().to_string().replace(' ', "");
+    let checks = mapped.iter().map(|f| match &f.flatten {
+        Some(prefix) => {
+            let embedded = embedded_columns(f, prefix);
+            let message = format!(
+                "#[derive(Projection)] field `{}` of `{}`: not every embedded column is a column of `{}`",
+                f.ident, struct_name, entity_name
+            );
+            quote! {
+                assert!(
+                    ::storeit::projection::has_columns(<#entity as ::storeit::Fetchable>::SELECT_COLUMNS, #embedded),
+                    #message
+                );
+            }
+        }
+        None => {
+            let column = &f.column_name;
+            let message = format!(
+                "#[derive(Projection)] field `{}` of `{}`: column `{}` is not a column of `{}`",
+                f.ident, struct_name, column, entity_name
+            );
+            quote! {
+                assert!(
+                    ::storeit::projection::has_column(<#entity as ::storeit::Fetchable>::SELECT_COLUMNS, #column),
+                    #message
+                );
+            }
+        }
+    });
 
-    let _libsql_get_mappings: Vec<_> = fields_metadata
-        .iter()
-        .enumerate()
-        .map(|(i, f)| {
-            if f.is_skipped {
-                let ident = &f.ident;
-                return quote! { #ident: ::core::default::Default::default() };
+    let adapter_struct_name = Ident::new(&format!("{}RowAdapter", struct_name), struct_name.span());
+    let field_inits = row_field_inits(&fields_metadata);
+    let row_adapter_impls = row_adapter_impls(struct_name, &adapter_struct_name, &field_inits);
+
+    let expanded = quote! {
+        const _: () = { #(#checks)* };
+
+        impl ::storeit::Projection for #struct_name {
+            type Entity = #entity;
+            const COLUMNS: &'static [&'static str] = #columns;
+            type Adapter<R> = #adapter_struct_name<R>;
+            fn adapter<R>() -> Self::Adapter<R> {
+                #adapter_struct_name::new()
             }
-            let ident = &f.ident;
-            let col_index = i as i32;
-            let ty_str = &f.ty_str;
-            if ty_str.ends_with("SystemTime") {
-                quote! {
-                    #ident: {
-                        // Assumes the DB stores a unix timestamp (i64) and we convert it.
-                        let ts_seconds: i64 = row
-                            .get(#col_index)
-                            .map_err(|e| ::storeit::RepoError::mapping(e))?;
-                        ::chrono::DateTime::from_timestamp(ts_seconds, 0)
-                            .map(std::convert::Into::into)
-                            .ok_or_else(|| ::storeit::RepoError::mapping(std::io::Error::new(std::io::ErrorKind::Other, format!("Invalid timestamp in {}[{}]", #table_name, #col_index))))?
-                    }
-                }
-            } else {
-                quote! { #ident: row
-                    .get(#col_index)
-                    .map_err(|e| ::storeit::RepoError::mapping(e))? }
+        }
+
+        #row_adapter_impls
+    };
+
+    TokenStream::from(expanded)
+}
+
+// --- `Embeddable` derive macro ---
+
+/// Derives `storeit::Embeddable` for a value object stored as columns of the entities that
+/// flatten it (`#[fetch(flatten, prefix = "...")]`). Fields map to columns like entity fields;
+/// ids, versions, audit timestamps and relations belong on the entity.
+#[proc_macro_derive(Embeddable, attributes(fetch))]
+pub fn derive_embeddable(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let struct_name = &input.ident;
+    let fields_metadata = parse_field_metadata(&input);
+    validate_flattened(&fields_metadata);
+    if let Some(f) = fields_metadata.iter().find(|f| {
+        f.is_id || f.is_version || f.is_created_at || f.is_updated_at || f.belongs_to.is_some()
+    }) {
+        panic!(
+            "#[derive(Embeddable)] field `{}` cannot be an id, version, audit or belongs_to field.",
+            f.ident
+        );
+    }
+
+    let mapped: Vec<&FieldMetadata> = fields_metadata.iter().filter(|f| !f.is_skipped).collect();
+    let columns = column_list(&mapped);
+    let values = values_expr(&mapped);
+    let field_inits = row_field_inits(&fields_metadata);
+    let defs = column_defs(&mapped);
+
+    let expanded = quote! {
+        impl ::storeit::Embeddable for #struct_name {
+            const COLUMNS: &'static [&'static str] = #columns;
+            fn to_values(&self) -> Vec<::storeit::ParamValue> {
+                #values
             }
-        })
-        .collect();
+            fn from_values(values: Vec<::storeit::ParamValue>) -> ::storeit::RepoResult<Self> {
+                use ::storeit::row::RowRead as _;
+                let row: &[::storeit::ParamValue] = &values;
+                Ok(Self { #(#field_inits),* })
+            }
+            fn column_defs() -> Vec<::storeit::schema::ColumnDef> {
+                let mut columns = Vec::new();
+                #defs
+                columns
+            }
+        }
+    };
 
-    // Build per-field initializers using index-based getters on ::storeit::row::RowRead
-    let field_inits: Vec<proc_macro2::TokenStream> = fields_metadata
-        .iter()
-        .enumerate()
-        .map(|(i, f)| {
-            let ident = &f.ident;
-            let idx_lit = proc_macro2::Literal::usize_unsuffixed(i);
-            let get_string = quote! { row.get_string(#idx_lit)? };
-            let get_i32 = quote! { row.get_i32(#idx_lit)? };
-            let get_i64 = quote! { row.get_i64(#idx_lit)? };
-            let get_f64 = quote! { row.get_f64(#idx_lit)? };
-            let get_bool = quote! { row.get_bool(#idx_lit)? };
-
-            if is_option(&f.ty) {
-                if f.ty_str.contains("String") {
-                    let get = quote! { row.get_opt_string(#idx_lit)? };
-                    quote! { #ident: #get }
-                } else if f.ty_str.contains("i32") {
-                    let get = quote! { row.get_opt_i32(#idx_lit)? };
-                    quote! { #ident: #get }
-                } else if f.ty_str.contains("i64") {
-                    let get = quote! { row.get_opt_i64(#idx_lit)? };
-                    quote! { #ident: #get }
-                } else if f.ty_str.contains("f64") {
-                    let get = quote! { row.get_opt_f64(#idx_lit)? };
-                    quote! { #ident: #get }
-                } else if f.ty_str.contains("bool") {
-                    let get = quote! { row.get_opt_bool(#idx_lit)? };
-                    quote! { #ident: #get }
-                } else {
-                    let msg = format!("Unsupported Option field type '{}' in auto RowAdapter; provide an explicit adapter", f.ty_str);
-                    quote! { #ident: return Err(::storeit::RepoError::mapping(std::io::Error::new(std::io::ErrorKind::Other, #msg))) }
-                }
-            } else if f.ty_str == "String" {
-                quote! { #ident: #get_string }
-            } else if f.ty_str == "i32" {
-                quote! { #ident: #get_i32 }
-            } else if f.ty_str == "i64" {
-                quote! { #ident: #get_i64 }
-            } else if f.ty_str == "f64" {
-                quote! { #ident: #get_f64 }
-            } else if f.ty_str == "bool" {
-                quote! { #ident: #get_bool }
+    TokenStream::from(expanded)
+}
+
+// --- `DbEnum` derive macro ---
+
+/// Applies a `#[db_enum(rename_all = "...")]` rule to a variant name.
+fn rename_variant(name: &str, rule: &str) -> String {
+    match rule {
+        "lowercase" => name.to_lowercase(),
+        "UPPERCASE" => name.to_uppercase(),
+        "snake_case" => name.to_snake_case(),
+        "SCREAMING_SNAKE_CASE" => name.to_constant_case(),
+        "kebab-case" => name.to_kebab_case(),
+        "camelCase" => name.to_camel_case(),
+        "PascalCase" => name.to_pascal_case(),
+        other => panic!(
+            "Unsupported #[db_enum(rename_all = \"{}\")]. Use lowercase, UPPERCASE, snake_case, SCREAMING_SNAKE_CASE, kebab-case, camelCase or PascalCase.",
+            other
+        ),
+    }
+}
+
+/// Derives `ToParamValue` and `FromColumn` for a fieldless enum, so entity fields and
+/// `#[repository]` finders of that type map without an adapter.
+///
+/// Variants are stored as text by default: the variant name, transformed by
+/// `#[db_enum(rename_all = "snake_case")]` or replaced by `#[db_enum(rename = "...")]` on a
+/// variant. On Postgres the column may also be a native enum type with these labels.
+/// `#[db_enum(integer)]` stores the discriminants as `i32` instead.
+#[proc_macro_derive(DbEnum, attributes(db_enum))]
+pub fn derive_db_enum(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let enum_name = &input.ident;
+    let variants = match &input.data {
+        Data::Enum(e) => &e.variants,
+        _ => panic!("#[derive(DbEnum)] can only be used on enums."),
+    };
+
+    let mut integer = false;
+    let mut rename_all = None;
+    for attr in input.attrs.iter().filter(|a| a.path().is_ident("db_enum")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("integer") {
+                integer = true;
+            } else if meta.path.is_ident("rename_all") {
+                let s: LitStr = meta.value()?.parse()?;
+                rename_all = Some(s.value());
             } else {
-                let msg = format!("Unsupported field type '{}' in auto RowAdapter; please provide an explicit adapter", f.ty_str);
-                quote! { #ident: return Err(::storeit::RepoError::mapping(std::io::Error::new(std::io::ErrorKind::Other, #msg))) }
+                return Err(meta.error(
+                    "unsupported db_enum option; expected `integer` or `rename_all = \"...\"`",
+                ));
             }
+            Ok(())
         })
-        .collect();
-
-    let row_adapter_impls = quote! {
-        #[derive(Debug, Clone, Copy, Default)]
-        pub struct #adapter_struct_name<R>(::core::marker::PhantomData<R>);
+        .expect("Invalid #[db_enum(...)] attribute syntax");
+    }
+    if integer && rename_all.is_some() {
+        panic!("#[db_enum(integer)] enums are stored as numbers and cannot use rename_all.");
+    }
 
-        impl<R> #adapter_struct_name<R> {
-            pub fn new() -> Self { Self(::core::marker::PhantomData) }
+    let mut idents = Vec::new();
+    let mut texts = Vec::new();
+    let mut discriminants = Vec::new();
+    let mut next: i64 = 0;
+    for variant in variants {
+        if !matches!(variant.fields, Fields::Unit) {
+            panic!(
+                "#[derive(DbEnum)] variant `{}` must not have fields.",
+                variant.ident
+            );
         }
-
-        impl<R> ::storeit::RowAdapter<#struct_name> for #adapter_struct_name<R>
-        where
-            R: ::storeit::row::RowRead,
+        let mut text = match &rename_all {
+            Some(rule) => rename_variant(&variant.ident.to_string(), rule),
+            None => variant.ident.to_string(),
+        };
+        for attr in variant
+            .attrs
+            .iter()
+            .filter(|a| a.path().is_ident("db_enum"))
         {
-            type Row = R;
-            fn from_row(&self, row: &Self::Row) -> ::storeit::RepoResult<#struct_name> {
-                Ok(#struct_name { #(#field_inits),* })
-            }
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    let s: LitStr = meta.value()?.parse()?;
+                    text = s.value();
+                    Ok(())
+                } else {
+                    Err(meta
+                        .error("unsupported db_enum variant option; expected `rename = \"...\"`"))
+                }
+            })
+            .expect("Invalid #[db_enum(...)] variant attribute syntax");
         }
+        if let Some((_, expr)) = &variant.discriminant {
+            next = discriminant_value(expr).unwrap_or_else(|| {
+                panic!(
+                    "#[derive(DbEnum)] discriminant of `{}` must be an integer literal.",
+                    variant.ident
+                )
+            });
+        }
+        let value = i32::try_from(next).unwrap_or_else(|_| {
+            panic!(
+                "#[derive(DbEnum)] discriminant of `{}` does not fit in an i32.",
+                variant.ident
+            )
+        });
+        idents.push(&variant.ident);
+        texts.push(text);
+        discriminants.push(value);
+        next += 1;
+    }
+    let mut seen = std::collections::HashSet::new();
+    if let Some(dup) = texts.iter().find(|t| !seen.insert(t.as_str())) {
+        panic!(
+            "#[derive(DbEnum)] `{}` has two variants stored as `{}`.",
+            enum_name, dup
+        );
+    }
+
+    let name = enum_name.to_string();
+    let (to_value, from_value) = if integer {
+        (
+            quote! { ::storeit::ParamValue::I32(match self { #(Self::#idents => #discriminants),* }) },
+            quote! {
+                match <i32 as ::storeit::FromColumn>::from_column(value)? {
+                    #(#discriminants => Ok(Self::#idents),)*
+                    other => Err(::storeit::RepoError::mapping(::std::io::Error::new(
+                        ::std::io::ErrorKind::InvalidData,
+                        format!("unknown {} discriminant {}", #name, other),
+                    ))),
+                }
+            },
+        )
+    } else {
+        (
+            quote! { ::storeit::ParamValue::String(match self { #(Self::#idents => #texts),* }.to_string()) },
+            quote! {
+                match <String as ::storeit::FromColumn>::from_column(value)?.as_str() {
+                    #(#texts => Ok(Self::#idents),)*
+                    other => Err(::storeit::RepoError::mapping(::std::io::Error::new(
+                        ::std::io::ErrorKind::InvalidData,
+                        format!("unknown {} value `{}`", #name, other),
+                    ))),
+                }
+            },
+        )
     };
 
-    // --- Combine all generated code ---
     let expanded = quote! {
-        #fetchable_impl
-        #identifiable_impl
-        #insertable_impl
-        #updatable_impl
-        #row_adapter_impls
+        impl ::storeit::ToParamValue for #enum_name {
+            fn to_param_value(&self) -> ::storeit::ParamValue {
+                #to_value
+            }
+        }
+
+        impl ::storeit::FromColumn for #enum_name {
+            fn from_column(value: ::storeit::ParamValue) -> ::storeit::RepoResult<Self> {
+                #from_value
+            }
+        }
     };
 
     TokenStream::from(expanded)
 }
 
+/// The value of an integer literal discriminant, possibly negated.
+fn discriminant_value(expr: &syn::Expr) -> Option<i64> {
+    match expr {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Int(i),
+            ..
+        }) => i.base10_parse().ok(),
+        syn::Expr::Unary(syn::ExprUnary {
+            op: syn::UnOp::Neg(_),
+            expr,
+            ..
+        }) => discriminant_value(expr).map(|v| -v),
+        syn::Expr::Group(g) => discriminant_value(&g.expr),
+        _ => None,
+    }
+}
+
+// --- `embed_migrations!` (behind `storeit_migrate::embed_migrations!`) ---
+
+/// Input of `__embed_migrations!`: the `storeit_migrate` crate path and the directory.
+struct EmbedMigrationsArgs {
+    krate: syn::Path,
+    dir: LitStr,
+}
+
+impl Parse for EmbedMigrationsArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let krate = syn::Path::parse_mod_style(input)?;
+        input.parse::<Token![,]>()?;
+        let dir = input.parse()?;
+        Ok(Self { krate, dir })
+    }
+}
+
+/// One migration file name split into version, name and direction.
+struct MigrationFile {
+    version: i64,
+    name: String,
+    down: bool,
+}
+
+/// Parse `<version>_<name>.sql`, `<version>_<name>.up.sql` or `<version>_<name>.down.sql`.
+/// A leading `V` and any number of underscores after the version are accepted.
+fn parse_migration_file(file_name: &str) -> Option<MigrationFile> {
+    let stem = file_name.strip_suffix(".sql")?;
+    let (stem, down) = match stem.strip_suffix(".down") {
+        Some(stem) => (stem, true),
+        None => (stem.strip_suffix(".up").unwrap_or(stem), false),
+    };
+    let stem = stem.strip_prefix('V').unwrap_or(stem);
+    let digits = stem.bytes().take_while(u8::is_ascii_digit).count();
+    let version = stem[..digits].parse().ok()?;
+    let name = stem[digits..].trim_start_matches('_');
+    if name.is_empty() {
+        return None;
+    }
+    Some(MigrationFile {
+        version,
+        name: name.to_string(),
+        down,
+    })
+}
+
+fn embed_migrations(args: &EmbedMigrationsArgs) -> syn::Result<proc_macro2::TokenStream> {
+    let span = args.dir.span();
+    let root = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_else(|_| ".".into());
+    let dir = std::path::Path::new(&root).join(args.dir.value());
+    let entries = std::fs::read_dir(&dir).map_err(|e| {
+        syn::Error::new(
+            span,
+            format!("cannot read migrations in {}: {e}", dir.display()),
+        )
+    })?;
+    // version -> (name, up path, down path)
+    let mut found =
+        std::collections::BTreeMap::<i64, (String, Option<String>, Option<String>)>::new();
+    for entry in entries {
+        let path = entry
+            .map_err(|e| syn::Error::new(span, e.to_string()))?
+            .path();
+        let Some(file_name) = path.file_name().and_then(|n| n.to_str()) else {
+            continue;
+        };
+        if !file_name.ends_with(".sql") {
+            continue;
+        }
+        let file = parse_migration_file(file_name).ok_or_else(|| {
+            syn::Error::new(
+                span,
+                format!(
+                    "migration file `{file_name}` is not named `<version>_<name>[.up|.down].sql`"
+                ),
+            )
+        })?;
+        let slot = found
+            .entry(file.version)
+            .or_insert_with(|| (file.name.clone(), None, None));
+        if slot.0 != file.name {
+            return Err(syn::Error::new(
+                span,
+                format!(
+                    "migration version {} is used by `{}` and `{}`",
+                    file.version, slot.0, file.name
+                ),
+            ));
+        }
+        let target = if file.down { &mut slot.2 } else { &mut slot.1 };
+        if target.is_some() {
+            return Err(syn::Error::new(
+                span,
+                format!(
+                    "migration version {} has more than one {} file",
+                    file.version,
+                    if file.down { "down" } else { "up" }
+                ),
+            ));
+        }
+        *target = Some(path.display().to_string());
+    }
+    let krate = &args.krate;
+    let mut migrations = Vec::new();
+    for (version, (name, up, down)) in found {
+        let up = up.ok_or_else(|| {
+            syn::Error::new(
+                span,
+                format!("migration {version}_{name} has a down file but no up file"),
+            )
+        })?;
+        let down = match down {
+            Some(path) => quote! { ::core::option::Option::Some(::core::include_str!(#path)) },
+            None => quote! { ::core::option::Option::None },
+        };
+        migrations.push(quote! {
+            #krate::Migration {
+                version: #version,
+                name: #name,
+                up: ::core::include_str!(#up),
+                down: #down,
+            }
+        });
+    }
+    Ok(quote! { &[#(#migrations),*] })
+}
+
+/// Implementation of `storeit_migrate::embed_migrations!`, which passes its own crate path.
+#[doc(hidden)]
+#[proc_macro]
+pub fn __embed_migrations(input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(input as EmbedMigrationsArgs);
+    embed_migrations(&args)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
 // --- `repository` attribute macro ---
 
 /// Struct to parse a finder like `find_by_email: String`
@@ -648,8 +1544,13 @@ pub fn repository(attr: TokenStream, item: TokenStream) -> TokenStream {
                 quote! { ::libsql::Row },
                 quote! { ::storeit::row::LibRow },
             ),
+            "Memory" => (
+                quote! { ::storeit::backends::MemoryRepository },
+                quote! { ::storeit::row::MemRow },
+                quote! { ::storeit::row::MemRow },
+            ),
             other => panic!(
-            "Unsupported backend: `{}`. Supported backends are: TokioPostgres, MysqlAsync, Libsql",
+            "Unsupported backend: `{}`. Supported backends are: TokioPostgres, MysqlAsync, Libsql, Memory",
             other
         ),
         };
@@ -670,13 +1571,16 @@ pub fn repository(attr: TokenStream, item: TokenStream) -> TokenStream {
                 "i64" => quote! { ::storeit::ParamValue::I64(*value) },
                 "f64" => quote! { ::storeit::ParamValue::F64(*value) },
                 "bool" => quote! { ::storeit::ParamValue::Bool(*value) },
-                _ => {
+                "i8" | "i16" | "i128" | "isize" | "u8" | "u16" | "u32" | "u64" | "u128"
+                | "usize" | "f32" | "char" | "str" => {
                     let err_msg = format!(
-                        "Unsupported finder type: {}. Use String, i32, i64, f64, or bool.",
+                        "Unsupported finder type: {}. Use String, i32, i64, f64, bool, or a type implementing ToParamValue such as a #[derive(DbEnum)] enum.",
                         ty_string
                     );
                     quote! { compile_error!(#err_msg) }
                 }
+                // Other types (e.g. `#[derive(DbEnum)]` enums, id newtypes) bind through `ToParamValue`.
+                _ => quote! { ::storeit::ToParamValue::to_param_value(value) },
             };
 
             find_by_methods.push(quote! {
@@ -711,18 +1615,66 @@ pub fn repository(attr: TokenStream, item: TokenStream) -> TokenStream {
                     Ok(Self { inner })
                 }
 
+                pub fn new(backend_repo: #backend_repo_ty<#entity_ty, A>) -> Self {
+                    Self { inner: backend_repo }
+                }
+
+                /// Register a lifecycle listener on the underlying repository.
+                pub fn with_listener(self, listener: impl ::storeit::EntityListener<#entity_ty> + 'static) -> Self {
+                    Self { inner: self.inner.with_listener(listener) }
+                }
+
+                #(#find_by_methods)*
+
+                /// Find an entity by id whether or not it is soft-deleted.
+                pub async fn find_with_deleted(&self, id: &<#entity_ty as ::storeit::Identifiable>::Key) -> ::storeit::RepoResult<Option<#entity_ty>> {
+                    self.inner.find_with_deleted(id).await
+                }
+
+                /// Clear the soft-delete column of a deleted row.
+                pub async fn restore_by_id(&self, id: &<#entity_ty as ::storeit::Identifiable>::Key) -> ::storeit::RepoResult<bool> {
+                    self.inner.restore_by_id(id).await
+                }
+
+                /// Physically delete a row, soft-deleted or not.
+                pub async fn purge_by_id(&self, id: &<#entity_ty as ::storeit::Identifiable>::Key) -> ::storeit::RepoResult<bool> {
+                    self.inner.purge_by_id(id).await
+                }
+
+                /// Find the projection `P` of the entity with the given id.
+                pub async fn find_by_id_as<P>(&self, id: &<#entity_ty as ::storeit::Identifiable>::Key) -> ::storeit::RepoResult<Option<P>>
+                where
+                    P: ::storeit::Projection<Entity = #entity_ty>,
+                    P::Adapter<#backend_row_ty>: RowAdapter<P, Row = #backend_row_ty>,
+                {
+                    self.inner.find_by_id_as::<P>(id).await
+                }
+
+                /// Find the projections `P` of the entities whose `field_name` column equals `value`.
+                pub async fn find_by_field_as<P>(&self, field_name: &str, value: ::storeit::ParamValue) -> ::storeit::RepoResult<Vec<P>>
+                where
+                    P: ::storeit::Projection<Entity = #entity_ty>,
+                    P::Adapter<#backend_row_ty>: RowAdapter<P, Row = #backend_row_ty>,
+                {
+                    self.inner.find_by_field_as::<P>(field_name, value).await
+                }
+
+                /// Compare the entity with its live table; see `storeit::schema`.
+                pub async fn validate_schema(&self) -> ::storeit::RepoResult<::storeit::schema::SchemaReport> {
+                    self.inner.validate_schema().await
+                }
+            }
+
+            impl Repository<#_adapter_path_ts<#backend_row_alias>>
+            where
+                #backend_repo_ty<#entity_ty, #_adapter_path_ts<#backend_row_alias>>: ::storeit::Repository<#entity_ty>,
+            {
                 /// Convenience constructor that uses the auto-generated RowAdapter for this entity
                 /// and the appropriate backend row type.
                 pub async fn from_url(conn_str: &str) -> ::storeit::RepoResult<Self> {
                     let adapter = #_adapter_path_ts::<#backend_row_alias>::new();
                     Self::from_url_with_adapter(conn_str, adapter).await
                 }
-
-                pub fn new(backend_repo: #backend_repo_ty<#entity_ty, A>) -> Self {
-                    Self { inner: backend_repo }
-                }
-
-                #(#find_by_methods)*
             }
 
             // Note: We intentionally do not generate a convenience constructor that relies on
@@ -754,6 +1706,38 @@ pub fn repository(attr: TokenStream, item: TokenStream) -> TokenStream {
                 async fn delete_by_id(&self, id: &<#entity_ty as ::storeit::Identifiable>::Key) -> ::storeit::RepoResult<bool> {
                     self.inner.delete_by_id(id).await
                 }
+
+                async fn find_where(&self, criteria: &::storeit::Criteria) -> ::storeit::RepoResult<Vec<#entity_ty>> {
+                    self.inner.find_where(criteria).await
+                }
+
+                async fn find_by_field_in(&self, field_name: &str, values: Vec<::storeit::ParamValue>) -> ::storeit::RepoResult<Vec<#entity_ty>> {
+                    self.inner.find_by_field_in(field_name, values).await
+                }
+
+                async fn count_where(&self, criteria: &::storeit::Criteria) -> ::storeit::RepoResult<u64> {
+                    self.inner.count_where(criteria).await
+                }
+
+                async fn exists_where(&self, criteria: &::storeit::Criteria) -> ::storeit::RepoResult<bool> {
+                    self.inner.exists_where(criteria).await
+                }
+
+                async fn delete_where(&self, criteria: &::storeit::Criteria) -> ::storeit::RepoResult<u64> {
+                    self.inner.delete_where(criteria).await
+                }
+
+                async fn find_page(&self, pageable: &::storeit::Pageable) -> ::storeit::RepoResult<::storeit::Page<#entity_ty>> {
+                    self.inner.find_page(pageable).await
+                }
+
+                async fn find_page_by_field(&self, field_name: &str, value: ::storeit::ParamValue, pageable: &::storeit::Pageable) -> ::storeit::RepoResult<::storeit::Page<#entity_ty>> {
+                    self.inner.find_page_by_field(field_name, value, pageable).await
+                }
+
+                async fn find_page_after(&self, cursor: Option<::storeit::Cursor>, limit: usize, order: &[::storeit::Order]) -> ::storeit::RepoResult<::storeit::CursorPage<#entity_ty>> {
+                    self.inner.find_page_after(cursor, limit, order).await
+                }
             }
         }
     };
@@ -3,7 +3,6 @@
 fn ui_compile_fail() {
     let t = trybuild::TestCases::new();
     t.compile_fail("tests/ui/fail/entity_missing_id.rs");
-    t.compile_fail("tests/ui/fail/entity_duplicate_id.rs");
     t.compile_fail("tests/ui/fail/entity_invalid_meta.rs");
     t.compile_fail("tests/ui/fail/repository_invalid_finders_syntax.rs");
     t.compile_fail("tests/ui/fail/repository_unknown_list.rs");
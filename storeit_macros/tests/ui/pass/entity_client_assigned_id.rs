@@ -0,0 +1,30 @@
+use storeit_core::{Identifiable, Insertable, ParamValue, ToParamValue};
+use storeit_macros::Entity;
+
+#[derive(Clone, Debug, PartialEq)]
+struct TokenId(String);
+
+impl ToParamValue for TokenId {
+    fn to_param_value(&self) -> ParamValue {
+        ParamValue::String(self.0.clone())
+    }
+}
+
+#[derive(Entity)]
+struct Token {
+    #[fetch(id, generated = false)]
+    id: TokenId,
+    label: String,
+}
+
+fn main() {
+    // A client-assigned key is inserted like any other column.
+    assert!(!Token::ID_GENERATED);
+    assert_eq!(Token::INSERT_COLUMNS, &["id", "label"]);
+    let t = Token {
+        id: TokenId("t-1".into()),
+        label: "ci".into(),
+    };
+    assert_eq!(t.id(), Some(TokenId("t-1".into())));
+    assert_eq!(t.insert_values().len(), 2);
+}
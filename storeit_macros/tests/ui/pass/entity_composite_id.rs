@@ -0,0 +1,27 @@
+use storeit_core::{Identifiable, Insertable, Updatable};
+use storeit_macros::Entity;
+
+#[derive(Entity)]
+struct UserRole {
+    #[fetch(id)]
+    user_id: i64,
+    #[fetch(id)]
+    role_id: i64,
+    granted_by: String,
+}
+
+fn main() {
+    // Several #[fetch(id)] fields produce a tuple key over all id columns.
+    assert_eq!(UserRole::ID_COLUMN, "user_id");
+    assert_eq!(UserRole::ID_COLUMNS, &["user_id", "role_id"]);
+    assert_eq!(UserRole::INSERT_COLUMNS, &["user_id", "role_id", "granted_by"]);
+    assert_eq!(UserRole::UPDATE_COLUMNS, &["granted_by"]);
+    let ur = UserRole {
+        user_id: 1,
+        role_id: 2,
+        granted_by: "root".into(),
+    };
+    let key: (i64, i64) = ur.id().unwrap();
+    assert_eq!(key, (1, 2));
+    assert_eq!(ur.update_values().len(), 3);
+}
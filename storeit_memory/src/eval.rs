@@ -0,0 +1,370 @@
+//! Criteria evaluation and value ordering over stored rows.
+//!
+//! Conditions follow SQL's three-valued logic: a comparison involving NULL is unknown, `Not`
+//! keeps it unknown, and a row matches only when the whole condition is true. Values compare
+//! the way SQLite compares them: integers, floats, booleans and decimals by numeric value,
+//! text-like values (strings, JSON, UUIDs, dates and times in their canonical text forms) by
+//! their text, and values of different kinds not at all. Sorting puts NULLs first, then
+//! numbers, text and binary data.
+
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use storeit_core::{Criteria, Fetchable, ParamValue, RepoError, RepoResult};
+
+pub(crate) fn invalid_input(msg: String) -> RepoError {
+    RepoError::backend(std::io::Error::new(std::io::ErrorKind::InvalidInput, msg))
+}
+
+/// The column layout of `E`'s stored rows: `SELECT_COLUMNS`, then a soft-delete column that
+/// no field maps to. Rows are read through `E`'s row adapter by index, so the extra column is
+/// never seen by it.
+pub(crate) struct Columns {
+    table: &'static str,
+    names: Vec<&'static str>,
+}
+
+impl Columns {
+    pub(crate) fn of<E: Fetchable>() -> Self {
+        let mut names = E::SELECT_COLUMNS.to_vec();
+        if let Some(col) = E::SOFT_DELETE_COLUMN {
+            if !names.contains(&col) {
+                names.push(col);
+            }
+        }
+        Columns {
+            table: E::TABLE,
+            names,
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub(crate) fn index(&self, name: &str) -> RepoResult<usize> {
+        self.names
+            .iter()
+            .position(|c| *c == name)
+            .ok_or_else(|| invalid_input(format!("no such column: {}.{}", self.table, name)))
+    }
+
+    /// The value of `column` in `row`. A `column->key->...` reference reads the value at that
+    /// key path of a JSON column, NULL when the path does not exist.
+    pub(crate) fn value(&self, row: &[ParamValue], column: &str) -> RepoResult<ParamValue> {
+        let mut parts = column.split("->");
+        let base = parts.next().unwrap_or_default();
+        let value = row
+            .get(self.index(base)?)
+            .cloned()
+            .unwrap_or(ParamValue::Null);
+        let keys: Vec<&str> = parts.collect();
+        if keys.is_empty() {
+            return Ok(value);
+        }
+        let doc = match &value {
+            ParamValue::Null => return Ok(ParamValue::Null),
+            ParamValue::Json(s) | ParamValue::String(s) => {
+                serde_json::from_str::<serde_json::Value>(s).map_err(|e| {
+                    invalid_input(format!("{}.{} is not valid JSON: {}", self.table, base, e))
+                })?
+            }
+            _ => {
+                return Err(invalid_input(format!(
+                    "{}.{} is not a JSON column",
+                    self.table, base
+                )))
+            }
+        };
+        let found = keys.iter().try_fold(&doc, |node, key| node.get(*key));
+        Ok(match found {
+            None | Some(serde_json::Value::Null) => ParamValue::Null,
+            Some(serde_json::Value::Bool(b)) => ParamValue::Bool(*b),
+            Some(serde_json::Value::Number(n)) => match n.as_i64() {
+                Some(i) => ParamValue::I64(i),
+                None => n.as_f64().map_or(ParamValue::Null, ParamValue::F64),
+            },
+            Some(serde_json::Value::String(s)) => ParamValue::String(s.clone()),
+            // Objects and arrays compare as their JSON text, like SQLite's json_extract.
+            Some(other) => ParamValue::String(other.to_string()),
+        })
+    }
+}
+
+enum Number {
+    Int(i64),
+    Float(f64),
+}
+
+fn number(v: &ParamValue) -> Option<Number> {
+    match v {
+        ParamValue::I32(i) => Some(Number::Int(i64::from(*i))),
+        ParamValue::I64(i) => Some(Number::Int(*i)),
+        ParamValue::F64(f) => Some(Number::Float(*f)),
+        ParamValue::Bool(b) => Some(Number::Int(i64::from(*b))),
+        ParamValue::Decimal(d) => d
+            .parse()
+            .map(Number::Int)
+            .or_else(|_| d.parse().map(Number::Float))
+            .ok(),
+        _ => None,
+    }
+}
+
+fn text(v: &ParamValue) -> Option<Cow<'_, str>> {
+    use storeit_core::value::{format_date, format_time, format_timestamp, format_uuid};
+    match v {
+        ParamValue::String(s) | ParamValue::Json(s) => Some(Cow::Borrowed(s)),
+        ParamValue::Uuid(u) => Some(Cow::Owned(format_uuid(u))),
+        ParamValue::Timestamp(t) => Some(Cow::Owned(format_timestamp(*t))),
+        ParamValue::Date(d) => Some(Cow::Owned(format_date(*d))),
+        ParamValue::Time(t) => Some(Cow::Owned(format_time(*t))),
+        _ => None,
+    }
+}
+
+/// Compare two values as SQL does: `None` when either is NULL or they are not comparable.
+pub(crate) fn compare(a: &ParamValue, b: &ParamValue) -> Option<Ordering> {
+    match (a, b) {
+        (ParamValue::Null, _) | (_, ParamValue::Null) => None,
+        (ParamValue::Bytes(x), ParamValue::Bytes(y)) => Some(x.cmp(y)),
+        (ParamValue::Uuid(x), ParamValue::Uuid(y)) => Some(x.cmp(y)),
+        (ParamValue::Timestamp(x), ParamValue::Timestamp(y))
+        | (ParamValue::Time(x), ParamValue::Time(y)) => Some(x.cmp(y)),
+        (ParamValue::Date(x), ParamValue::Date(y)) => Some(x.cmp(y)),
+        _ => match (number(a), number(b)) {
+            (Some(Number::Int(x)), Some(Number::Int(y))) => Some(x.cmp(&y)),
+            (Some(x), Some(y)) => as_f64(x).partial_cmp(&as_f64(y)),
+            _ => match (text(a), text(b)) {
+                (Some(x), Some(y)) => Some(x.cmp(&y)),
+                _ => None,
+            },
+        },
+    }
+}
+
+fn as_f64(n: Number) -> f64 {
+    match n {
+        Number::Int(i) => i as f64,
+        Number::Float(f) => f,
+    }
+}
+
+// Storage class rank for ordering values that do not compare: NULL, numbers, text, bytes.
+fn rank(v: &ParamValue) -> u8 {
+    match v {
+        ParamValue::Null => 0,
+        ParamValue::Bytes(_) => 3,
+        _ if number(v).is_some() => 1,
+        _ => 2,
+    }
+}
+
+/// A total order for sorting and keys: [`compare`], with NULLs first and different kinds of
+/// values ordered by storage class.
+pub(crate) fn sort_cmp(a: &ParamValue, b: &ParamValue) -> Ordering {
+    compare(a, b).unwrap_or_else(|| rank(a).cmp(&rank(b)))
+}
+
+fn is_null(v: &ParamValue) -> bool {
+    matches!(v, ParamValue::Null)
+}
+
+// `value LIKE pattern` with `%` (any run) and `_` (one character), case-sensitive.
+fn like(value: &str, pattern: &str) -> bool {
+    let (v, p): (Vec<char>, Vec<char>) = (value.chars().collect(), pattern.chars().collect());
+    let (mut vi, mut pi) = (0, 0);
+    // The last `%` seen and the value position it currently absorbs up to.
+    let mut star: Option<(usize, usize)> = None;
+    while vi < v.len() {
+        if p.get(pi) == Some(&'%') {
+            star = Some((pi, vi));
+            pi += 1;
+        } else if p.get(pi).is_some_and(|&c| c == '_' || c == v[vi]) {
+            vi += 1;
+            pi += 1;
+        } else if let Some((sp, sv)) = star {
+            star = Some((sp, sv + 1));
+            pi = sp + 1;
+            vi = sv + 1;
+        } else {
+            return false;
+        }
+    }
+    p[pi..].iter().all(|&c| c == '%')
+}
+
+/// Evaluate `criteria` against `row`: `Some(true)`/`Some(false)`, or `None` when unknown.
+/// Every column is resolved, so an unknown column fails even where the result is decided.
+pub(crate) fn eval(
+    criteria: &Criteria,
+    cols: &Columns,
+    row: &[ParamValue],
+) -> RepoResult<Option<bool>> {
+    let cmp = |col: &str, v: &ParamValue, f: fn(Ordering) -> bool| -> RepoResult<Option<bool>> {
+        Ok(compare(&cols.value(row, col)?, v).map(f))
+    };
+    match criteria {
+        Criteria::Eq(c, ParamValue::Null) | Criteria::IsNull(c) => {
+            Ok(Some(is_null(&cols.value(row, c)?)))
+        }
+        Criteria::Ne(c, ParamValue::Null) => Ok(Some(!is_null(&cols.value(row, c)?))),
+        Criteria::Eq(c, v) => cmp(c, v, Ordering::is_eq),
+        Criteria::Ne(c, v) => cmp(c, v, Ordering::is_ne),
+        Criteria::Lt(c, v) => cmp(c, v, Ordering::is_lt),
+        Criteria::Le(c, v) => cmp(c, v, Ordering::is_le),
+        Criteria::Gt(c, v) => cmp(c, v, Ordering::is_gt),
+        Criteria::Ge(c, v) => cmp(c, v, Ordering::is_ge),
+        Criteria::Like(c, pattern) => {
+            let value = cols.value(row, c)?;
+            Ok(match (text(&value), text(pattern)) {
+                (Some(v), Some(p)) => Some(like(&v, &p)),
+                _ => None,
+            })
+        }
+        Criteria::In(c, values) => {
+            let value = cols.value(row, c)?;
+            let results: Vec<_> = values.iter().map(|v| compare(&value, v)).collect();
+            Ok(if results.contains(&Some(Ordering::Equal)) {
+                Some(true)
+            } else if results.iter().any(Option::is_none) {
+                None
+            } else {
+                Some(false)
+            })
+        }
+        Criteria::Between(c, low, high) => Ok(and([
+            cmp(c, low, Ordering::is_ge)?,
+            cmp(c, high, Ordering::is_le)?,
+        ])),
+        Criteria::And(parts) => Ok(and(parts
+            .iter()
+            .map(|p| eval(p, cols, row))
+            .collect::<RepoResult<Vec<_>>>()?)),
+        Criteria::Or(parts) => {
+            let results = parts
+                .iter()
+                .map(|p| eval(p, cols, row))
+                .collect::<RepoResult<Vec<_>>>()?;
+            Ok(if results.contains(&Some(true)) {
+                Some(true)
+            } else if results.contains(&None) {
+                None
+            } else {
+                Some(false)
+            })
+        }
+        Criteria::Not(inner) => Ok(eval(inner, cols, row)?.map(|b| !b)),
+    }
+}
+
+// Three-valued AND; an empty conjunction is true.
+fn and(results: impl IntoIterator<Item = Option<bool>>) -> Option<bool> {
+    let mut out = Some(true);
+    for r in results {
+        match r {
+            Some(false) => return Some(false),
+            None => out = None,
+            Some(true) => {}
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Doc;
+
+    impl Fetchable for Doc {
+        const TABLE: &'static str = "docs";
+        const SELECT_COLUMNS: &'static [&'static str] = &["id", "title", "score", "meta"];
+        const FINDABLE_COLUMNS: &'static [(&'static str, &'static str)] = &[];
+        const SOFT_DELETE_COLUMN: Option<&'static str> = Some("deleted_at");
+    }
+
+    fn row() -> Vec<ParamValue> {
+        vec![
+            ParamValue::I64(1),
+            ParamValue::String("Hello world".into()),
+            ParamValue::Null,
+            ParamValue::Json(r#"{"theme":"dark","size":12,"tags":["a"]}"#.into()),
+            ParamValue::Null,
+        ]
+    }
+
+    fn check(c: Criteria) -> Option<bool> {
+        eval(&c, &Columns::of::<Doc>(), &row()).expect("eval")
+    }
+
+    #[test]
+    fn comparisons_follow_three_valued_logic() {
+        assert_eq!(check(Criteria::eq("id", 1i32)), Some(true));
+        assert_eq!(check(Criteria::ge("id", 0.5)), Some(true));
+        assert_eq!(check(Criteria::eq("score", 3)), None);
+        assert_eq!(check(!Criteria::eq("score", 3)), None);
+        assert_eq!(check(Criteria::is_null("score")), Some(true));
+        assert_eq!(check(Criteria::is_null("deleted_at")), Some(true));
+        assert_eq!(
+            check(Criteria::Eq("score".into(), ParamValue::Null)),
+            Some(true)
+        );
+        assert_eq!(
+            check(Criteria::eq("score", 3).or(Criteria::eq("id", 1i64))),
+            Some(true)
+        );
+        assert_eq!(
+            check(Criteria::eq("score", 3).and(Criteria::eq("id", 2i64))),
+            Some(false)
+        );
+        assert_eq!(check(Criteria::in_list("id", [3i64, 1])), Some(true));
+        assert_eq!(
+            check(Criteria::in_list("id", Vec::<i64>::new())),
+            Some(false)
+        );
+        assert_eq!(check(Criteria::between("id", 1, 2)), Some(true));
+        assert_eq!(check(Criteria::all()), Some(true));
+        assert_eq!(check(Criteria::Or(Vec::new())), Some(false));
+        // Text and numbers do not compare.
+        assert_eq!(check(Criteria::eq("title", 1)), None);
+        assert!(eval(&Criteria::eq("nope", 1), &Columns::of::<Doc>(), &row()).is_err());
+    }
+
+    #[test]
+    fn like_and_json_paths() {
+        assert_eq!(check(Criteria::like("title", "Hello%")), Some(true));
+        assert_eq!(check(Criteria::like("title", "%o_w%")), Some(true));
+        assert_eq!(check(Criteria::like("title", "hello%")), Some(false));
+        assert_eq!(check(Criteria::like("title", "%world")), Some(true));
+        assert_eq!(check(Criteria::like("title", "%worl")), Some(false));
+        assert_eq!(check(Criteria::eq("meta->theme", "dark")), Some(true));
+        assert_eq!(check(Criteria::gt("meta->size", 10)), Some(true));
+        assert_eq!(check(Criteria::is_null("meta->missing")), Some(true));
+        assert_eq!(check(Criteria::eq("meta->tags", r#"["a"]"#)), Some(true));
+        assert!(eval(&Criteria::eq("id->x", 1), &Columns::of::<Doc>(), &row()).is_err());
+    }
+
+    #[test]
+    fn sort_order_puts_nulls_first_and_groups_kinds() {
+        let mut values = vec![
+            ParamValue::String("b".into()),
+            ParamValue::Bytes(vec![0]),
+            ParamValue::I64(2),
+            ParamValue::Null,
+            ParamValue::F64(1.5),
+            ParamValue::String("a".into()),
+        ];
+        values.sort_by(sort_cmp);
+        assert!(matches!(
+            values.as_slice(),
+            [
+                ParamValue::Null,
+                ParamValue::F64(_),
+                ParamValue::I64(2),
+                ParamValue::String(a),
+                ParamValue::String(_),
+                ParamValue::Bytes(_),
+            ] if a == "a"
+        ));
+    }
+}
@@ -0,0 +1,1170 @@
+#![forbid(unsafe_code)]
+//! In-memory backend for the storeit repository framework.
+//!
+//! [`MemoryRepository`] implements `Repository<T>` for any `#[derive(Entity)]` type over rows
+//! held in a [`MemoryDatabase`], so domain services can be unit tested with real behaviour and
+//! no database: generated keys come from a per-table sequence, `#[fetch(unique)]` columns and
+//! primary keys reject duplicates with `RepoError::UniqueViolation`, non-`Option` fields reject
+//! NULL, and soft deletes, tenant scopes, optimistic locking, listeners, criteria, paging and
+//! projections behave like the SQL backends.
+//!
+//! [`MemoryTransactionManager`] runs `TransactionManager::execute` callbacks on a snapshot of
+//! the database that is committed only when the callback succeeds. `Propagation::Nested`
+//! restores a savepoint when the nested callback fails, `Propagation::RequiresNew` commits
+//! independently of the surrounding transaction, and read-only transactions reject writes.
+//! A commit fails, applying nothing, when another transaction committed the same key or unique
+//! value, or changed the version of a row it wrote, since its snapshot. Isolation levels and
+//! timeouts are accepted and ignored.
+//!
+//! ```ignore
+//! use storeit::{repository, Entity};
+//!
+//! #[derive(Entity, Clone, Debug)]
+//! pub struct User {
+//!     #[fetch(id)]
+//!     pub id: Option<i64>,
+//!     #[fetch(unique)]
+//!     pub email: String,
+//! }
+//!
+//! // Requires the facade's `memory-backend` feature.
+//! #[repository(entity = User, backend = Memory, finders(find_by_email: String))]
+//! pub mod users {}
+//!
+//! // Repositories opened on the same `memory://<name>` URL share their tables.
+//! let repo = users::Repository::from_url("memory://users-test").await?;
+//! ```
+
+mod eval;
+mod store;
+
+pub use store::MemoryDatabase;
+
+use async_trait::async_trait;
+use eval::{compare, eval, invalid_input, sort_cmp, Columns};
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::marker::PhantomData;
+use std::sync::Arc;
+use store::{Constraints, Key, Row, TableMut, TX_STACK};
+use storeit_core::schema::{SchemaChange, SchemaReport, TableDef};
+use storeit_core::transactions::{
+    Propagation, TransactionContext, TransactionDefinition, TransactionManager,
+};
+use storeit_core::{
+    Criteria, Cursor, CursorPage, Direction, EntityListener, Fetchable, Identifiable, Insertable,
+    KeyValues, Listenable, Listeners, Order, Page, Pageable, ParamValue, Projection, RepoError,
+    RepoResult, Repository, RowAdapter, Schema, ToParamValue, Updatable,
+};
+
+/// A stored row handed to row adapters: the entity's values in `SELECT_COLUMNS` order (or a
+/// projection's values in `Projection::COLUMNS` order).
+#[derive(Debug, Clone)]
+pub struct MemoryRow(Vec<ParamValue>);
+
+impl MemoryRow {
+    pub fn new(values: Vec<ParamValue>) -> Self {
+        Self(values)
+    }
+
+    /// The column values, by index.
+    pub fn values(&self) -> &[ParamValue] {
+        &self.0
+    }
+}
+
+/// A `TransactionManager` over a [`MemoryDatabase`], with snapshot rollback.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryTransactionManager {
+    db: MemoryDatabase,
+}
+
+impl MemoryTransactionManager {
+    pub fn new(db: MemoryDatabase) -> Self {
+        Self { db }
+    }
+
+    /// The database this manager's transactions run on.
+    pub fn database(&self) -> &MemoryDatabase {
+        &self.db
+    }
+
+    /// Vend a repository on the manager's database. Inside `execute` it reads and writes the
+    /// running transaction's snapshot, like every repository on this database.
+    pub async fn repository<T, A>(
+        &self,
+        _ctx: TransactionContext<'_>,
+        adapter: A,
+    ) -> RepoResult<MemoryRepository<T, A>>
+    where
+        T: Identifiable + 'static,
+        A: RowAdapter<T, Row = MemoryRow> + Send + Sync + 'static,
+    {
+        Ok(MemoryRepository::new(self.db.clone(), adapter))
+    }
+
+    /// Tables are created from the entity on first use, so they always match it.
+    pub async fn validate_schema<T: Schema>(&self) -> RepoResult<SchemaReport> {
+        Ok(SchemaReport {
+            table: T::TABLE.to_string(),
+            issues: Vec::new(),
+        })
+    }
+
+    /// Always empty; see [`validate_schema`](Self::validate_schema).
+    pub async fn schema_diff<T: Schema>(&self) -> RepoResult<Vec<SchemaChange>> {
+        Ok(Vec::new())
+    }
+}
+
+#[async_trait]
+impl TransactionManager for MemoryTransactionManager {
+    async fn execute<'a, R, F, Fut>(&'a self, def: &TransactionDefinition, f: F) -> RepoResult<R>
+    where
+        F: FnOnce(TransactionContext<'a>) -> Fut + Send + 'a,
+        Fut: core::future::Future<Output = RepoResult<R>> + Send + 'a,
+        R: Send + 'a,
+    {
+        let fut = async {
+            let active = self.db.in_transaction();
+            match def.propagation {
+                Propagation::Never if active => {
+                    return Err(RepoError::backend(std::io::Error::new(
+                        std::io::ErrorKind::Other,
+                        "Transaction exists but Propagation::Never requested",
+                    )));
+                }
+                Propagation::Never | Propagation::Supports | Propagation::NotSupported
+                    if !active =>
+                {
+                    return f(TransactionContext::new()).await;
+                }
+                Propagation::Required | Propagation::Supports if active => {
+                    return f(TransactionContext::new()).await;
+                }
+                // Suspend the running transaction: the callback sees committed rows only.
+                Propagation::NotSupported => {
+                    return TX_STACK
+                        .scope(RefCell::new(Vec::new()), f(TransactionContext::new()))
+                        .await;
+                }
+                Propagation::Nested if active => {
+                    let savepoint =
+                        TX_STACK.with(|stack| stack.borrow().last().map(|f| f.savepoint()));
+                    let result = f(TransactionContext::new()).await;
+                    if let (Err(_), Some(savepoint)) = (&result, savepoint) {
+                        TX_STACK.with(|stack| {
+                            if let Some(frame) = stack.borrow_mut().last_mut() {
+                                frame.restore(savepoint);
+                            }
+                        });
+                    }
+                    return result;
+                }
+                // Required/Nested without a transaction, and RequiresNew: a new transaction.
+                _ => {}
+            }
+
+            TX_STACK.with(|stack| stack.borrow_mut().push(self.db.begin(def.read_only)));
+            let result = f(TransactionContext::new()).await;
+            let frame = TX_STACK.with(|stack| stack.borrow_mut().pop());
+            match (result, frame) {
+                (Ok(value), Some(frame)) => self.db.commit(frame).map(|()| value),
+                (result, _) => result,
+            }
+        };
+
+        // If the task-local TX_STACK isn't initialized for this task, set up its scope and run.
+        if TX_STACK.try_with(|_| ()).is_err() {
+            TX_STACK.scope(RefCell::new(Vec::new()), fut).await
+        } else {
+            fut.await
+        }
+    }
+}
+
+/// An in-memory repository for `T`, reading rows through `A`.
+pub struct MemoryRepository<T, A>
+where
+    T: Identifiable + 'static,
+    A: RowAdapter<T> + Send + Sync + 'static,
+{
+    db: MemoryDatabase,
+    adapter: A,
+    listeners: Listeners<T>,
+    _marker: PhantomData<T>,
+}
+
+impl<T, A> MemoryRepository<T, A>
+where
+    T: Identifiable + 'static,
+    A: RowAdapter<T, Row = MemoryRow> + Send + Sync + 'static,
+{
+    /// Creates a repository on `db`.
+    pub fn new(db: MemoryDatabase, adapter: A) -> Self {
+        Self {
+            db,
+            adapter,
+            listeners: Listeners::default(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Creates a repository on the database named by a `memory://<name>` URL (see
+    /// [`MemoryDatabase::from_url`]).
+    pub async fn from_url(
+        database_url: &str,
+        _id_column: &str, // Note: id_column is read from T::ID_COLUMN
+        adapter: A,
+    ) -> RepoResult<Self> {
+        Ok(Self::new(MemoryDatabase::from_url(database_url)?, adapter))
+    }
+
+    /// The database this repository stores its rows in.
+    pub fn database(&self) -> &MemoryDatabase {
+        &self.db
+    }
+}
+
+fn constraint(msg: String) -> Box<dyn std::error::Error + Send + Sync> {
+    Box::new(std::io::Error::new(std::io::ErrorKind::InvalidInput, msg))
+}
+
+// Whether `row` is of the tenant in `tenant` (see `storeit_core::tenant::filter_values`,
+// which is empty under `tenant::bypass`) and, when `live`, not soft-deleted.
+fn in_scope<T: Fetchable>(
+    cols: &Columns,
+    row: &[ParamValue],
+    tenant: &[ParamValue],
+    live: bool,
+) -> bool {
+    let column = |name| cols.index(name).ok().and_then(|i| row.get(i));
+    let alive = !live
+        || T::SOFT_DELETE_COLUMN
+            .and_then(column)
+            .map_or(true, |v| matches!(v, ParamValue::Null));
+    let own = match (T::TENANT_COLUMN, tenant.first()) {
+        (Some(col), Some(t)) => column(col)
+            .and_then(|v| compare(v, t))
+            .is_some_and(Ordering::is_eq),
+        _ => true,
+    };
+    alive && own
+}
+
+// Reject NULL in NOT NULL columns, and duplicates in `#[fetch(unique)]` columns among the
+// other rows of the table.
+fn check_constraints(
+    def: &TableDef,
+    cols: &Columns,
+    table: &TableMut<'_>,
+    key: &Key,
+    row: &[ParamValue],
+) -> RepoResult<()> {
+    for column in &def.columns {
+        let i = cols.index(&column.name)?;
+        let name = format!("{}.{}", def.name, column.name);
+        if matches!(row[i], ParamValue::Null) {
+            if !column.nullable {
+                return Err(RepoError::NotNullViolation {
+                    source: constraint(format!("NOT NULL constraint failed: {}", name)),
+                    column: Some(name),
+                });
+            }
+            continue;
+        }
+        let taken = || {
+            table
+                .rows()
+                .iter()
+                .any(|(k, r)| k != key && compare(&r[i], &row[i]) == Some(Ordering::Equal))
+        };
+        if column.unique && taken() {
+            return Err(RepoError::UniqueViolation {
+                source: constraint(format!("UNIQUE constraint failed: {}", name)),
+                constraint: Some(name),
+            });
+        }
+    }
+    Ok(())
+}
+
+// What a commit checks again for `T`'s rows.
+fn constraints<T: Schema + Updatable>() -> Constraints {
+    let cols = Columns::of::<T>();
+    Constraints {
+        key: T::ID_COLUMNS
+            .iter()
+            .map(|c| format!("{}.{}", T::TABLE, c))
+            .collect::<Vec<_>>()
+            .join(", "),
+        unique: T::table_def()
+            .columns
+            .iter()
+            .filter(|c| c.unique)
+            .filter_map(|c| {
+                Some((
+                    format!("{}.{}", T::TABLE, c.name),
+                    cols.index(&c.name).ok()?,
+                ))
+            })
+            .collect(),
+        version: T::VERSION_COLUMN.and_then(|c| cols.index(c).ok()),
+    }
+}
+
+// The version written by `version = version + 1`.
+fn increment(v: &ParamValue) -> ParamValue {
+    match v {
+        ParamValue::I32(i) => ParamValue::I32(i.wrapping_add(1)),
+        ParamValue::I64(i) => ParamValue::I64(i.wrapping_add(1)),
+        other => other.clone(),
+    }
+}
+
+// Sort `rows` by `orders` (validated column names), keeping key order among equal rows.
+fn sort_rows(cols: &Columns, rows: &mut [Row], orders: &[Order]) -> RepoResult<()> {
+    let keys = orders
+        .iter()
+        .map(|o| Ok((cols.index(&o.column)?, o.direction)))
+        .collect::<RepoResult<Vec<_>>>()?;
+    rows.sort_by(|a, b| {
+        keys.iter()
+            .map(|&(i, direction)| match direction {
+                Direction::Asc => sort_cmp(&a[i], &b[i]),
+                Direction::Desc => sort_cmp(&b[i], &a[i]),
+            })
+            .find(|o| o.is_ne())
+            .unwrap_or(Ordering::Equal)
+    });
+    Ok(())
+}
+
+// The validated sort and offset of a page request, as the SQL backends check them.
+fn page_offset<T: Fetchable>(pageable: &Pageable) -> RepoResult<usize> {
+    if pageable.size == 0 {
+        return Err(invalid_input("page size must be at least 1".to_string()));
+    }
+    let offset = pageable
+        .offset()
+        .ok_or_else(|| invalid_input("page offset overflows".to_string()))?;
+    storeit_sql_builder::order_by_list::<T>(&pageable.sort)?;
+    Ok(offset)
+}
+
+// Storage helpers shared by the repository methods.
+impl<T, A> MemoryRepository<T, A>
+where
+    T: Schema + Insertable + Updatable + Listenable + Send + Sync + Clone + 'static,
+    A: RowAdapter<T, Row = MemoryRow> + Send + Sync + 'static,
+    T::Key: Clone + Send + Sync + 'static + KeyValues,
+{
+    /// Register a listener that runs after the entity's own and earlier registrations.
+    pub fn with_listener(mut self, listener: impl EntityListener<T> + 'static) -> Self {
+        self.listeners.push(Arc::new(listener));
+        self
+    }
+
+    // Map a row to an entity and run the `post_load` hooks on it.
+    fn load(&self, row: Row) -> RepoResult<T> {
+        self.listeners
+            .loaded(self.adapter.from_row(&MemoryRow(row))?)
+    }
+
+    // Run `f` on the table for writing; a transaction's commit checks `T`'s constraints again.
+    fn write<R>(&self, f: impl FnOnce(&mut TableMut<'_>) -> RepoResult<R>) -> RepoResult<R> {
+        self.db.declare(T::TABLE, constraints::<T>);
+        self.db.with_table(T::TABLE, true, f)
+    }
+
+    // The rows of the current tenant matching `pred`, in key order; only live rows when `live`.
+    fn select(
+        &self,
+        live: bool,
+        pred: impl Fn(&Columns, &[ParamValue]) -> RepoResult<bool>,
+    ) -> RepoResult<Vec<Row>> {
+        let cols = Columns::of::<T>();
+        let tenant = storeit_core::tenant::filter_values::<T>()?;
+        self.db.with_table(T::TABLE, false, |table| {
+            let mut out = Vec::new();
+            for row in table.rows().values() {
+                if in_scope::<T>(&cols, row, &tenant, live) && pred(&cols, row)? {
+                    out.push(row.clone());
+                }
+            }
+            Ok(out)
+        })
+    }
+
+    // The live rows of the current tenant matching `criteria`.
+    fn select_where(&self, criteria: &Criteria) -> RepoResult<Vec<Row>> {
+        self.select(true, |cols, row| {
+            Ok(eval(criteria, cols, row)? == Some(true))
+        })
+    }
+
+    // The live rows whose `field` equals `value`; like `field = NULL` in SQL, NULL matches
+    // nothing.
+    fn select_by_field(&self, field: &str, value: &ParamValue) -> RepoResult<Vec<Row>> {
+        Columns::of::<T>().index(field)?;
+        if matches!(value, ParamValue::Null) {
+            return Ok(Vec::new());
+        }
+        self.select_where(&Criteria::Eq(field.to_string(), value.clone()))
+    }
+
+    // The row with key `id` of the current tenant; only a live one when `live`.
+    fn select_by_id(&self, id: &T::Key, live: bool) -> RepoResult<Option<Row>> {
+        let cols = Columns::of::<T>();
+        let tenant = storeit_core::tenant::filter_values::<T>()?;
+        let key = Key(id.key_values());
+        self.db.with_table(T::TABLE, false, |table| {
+            Ok(table
+                .get(&key)
+                .filter(|row| in_scope::<T>(&cols, row, &tenant, live))
+                .cloned())
+        })
+    }
+
+    // Apply `f` to the row with key `id` of the current tenant (live rows only when `live`)
+    // and store the result. Returns whether a row was changed.
+    fn modify_by_id(
+        &self,
+        id: &T::Key,
+        live: bool,
+        f: impl FnOnce(&Columns, &mut Row) -> RepoResult<bool>,
+    ) -> RepoResult<bool> {
+        let cols = Columns::of::<T>();
+        let tenant = storeit_core::tenant::filter_values::<T>()?;
+        let key = Key(id.key_values());
+        self.write(|table| {
+            let Some(mut row) = table
+                .get(&key)
+                .filter(|row| in_scope::<T>(&cols, row, &tenant, live))
+                .cloned()
+            else {
+                return Ok(false);
+            };
+            if !f(&cols, &mut row)? {
+                return Ok(false);
+            }
+            table.put(key, row);
+            Ok(true)
+        })
+    }
+
+    // Physically remove the row with key `id` of the current tenant.
+    fn remove_by_id(&self, id: &T::Key) -> RepoResult<bool> {
+        let cols = Columns::of::<T>();
+        let tenant = storeit_core::tenant::filter_values::<T>()?;
+        let key = Key(id.key_values());
+        self.write(|table| {
+            let found = table
+                .get(&key)
+                .is_some_and(|row| in_scope::<T>(&cols, row, &tenant, false));
+            if found {
+                table.remove(&key);
+            }
+            Ok(found)
+        })
+    }
+}
+
+// Explicit access to soft-deleted rows (see `Fetchable::SOFT_DELETE_COLUMN`).
+impl<T, A> MemoryRepository<T, A>
+where
+    T: Schema + Insertable + Updatable + Listenable + Send + Sync + Clone + 'static,
+    A: RowAdapter<T, Row = MemoryRow> + Send + Sync + 'static,
+    T::Key: Clone + Send + Sync + 'static + KeyValues,
+{
+    /// Find an entity by id whether or not it is soft-deleted.
+    pub async fn find_with_deleted(&self, id: &T::Key) -> RepoResult<Option<T>> {
+        self.select_by_id(id, false)?
+            .map(|row| self.load(row))
+            .transpose()
+    }
+
+    /// Clear the soft-delete column of a deleted row. Returns whether a row was restored.
+    /// Fails for entities without a soft-delete column.
+    pub async fn restore_by_id(&self, id: &T::Key) -> RepoResult<bool> {
+        let col = T::SOFT_DELETE_COLUMN
+            .ok_or_else(|| invalid_input(format!("{} has no soft-delete column", T::TABLE)))?;
+        self.modify_by_id(id, false, |cols, row| {
+            let i = cols.index(col)?;
+            if matches!(row[i], ParamValue::Null) {
+                return Ok(false);
+            }
+            row[i] = ParamValue::Null;
+            Ok(true)
+        })
+    }
+
+    /// Physically delete a row, soft-deleted or not. Returns whether a row was removed.
+    pub async fn purge_by_id(&self, id: &T::Key) -> RepoResult<bool> {
+        self.listeners.pre_delete(id)?;
+        self.remove_by_id(id)
+    }
+}
+
+// Projections (see `storeit_core::Projection`): rows hold only the projected columns.
+impl<T, A> MemoryRepository<T, A>
+where
+    T: Schema + Insertable + Updatable + Listenable + Send + Sync + Clone + 'static,
+    A: RowAdapter<T, Row = MemoryRow> + Send + Sync + 'static,
+    T::Key: Clone + Send + Sync + 'static + KeyValues,
+{
+    fn project<P>(row: &[ParamValue]) -> RepoResult<P>
+    where
+        P: Projection<Entity = T>,
+        P::Adapter<MemoryRow>: RowAdapter<P, Row = MemoryRow>,
+    {
+        let cols = Columns::of::<T>();
+        let values = P::COLUMNS
+            .iter()
+            .map(|c| cols.value(row, c))
+            .collect::<RepoResult<Vec<_>>>()?;
+        P::adapter::<MemoryRow>().from_row(&MemoryRow(values))
+    }
+
+    /// Find the projection `P` of the entity with the given id.
+    pub async fn find_by_id_as<P>(&self, id: &T::Key) -> RepoResult<Option<P>>
+    where
+        P: Projection<Entity = T>,
+        P::Adapter<MemoryRow>: RowAdapter<P, Row = MemoryRow>,
+    {
+        self.select_by_id(id, true)?
+            .map(|row| Self::project(&row))
+            .transpose()
+    }
+
+    /// Find the projections `P` of the entities whose `field_name` column equals `value`.
+    pub async fn find_by_field_as<P>(
+        &self,
+        field_name: &str,
+        value: ParamValue,
+    ) -> RepoResult<Vec<P>>
+    where
+        P: Projection<Entity = T>,
+        P::Adapter<MemoryRow>: RowAdapter<P, Row = MemoryRow>,
+    {
+        self.select_by_field(field_name, &value)?
+            .iter()
+            .map(|row| Self::project(row))
+            .collect()
+    }
+}
+
+// Schema validation (see `storeit_core::schema`).
+impl<T, A> MemoryRepository<T, A>
+where
+    T: Schema + Insertable + Updatable + Listenable + Send + Sync + Clone + 'static,
+    A: RowAdapter<T, Row = MemoryRow> + Send + Sync + 'static,
+    T::Key: Clone + Send + Sync + 'static + KeyValues,
+{
+    /// Tables are created from the entity on first use, so they always match it.
+    pub async fn validate_schema(&self) -> RepoResult<SchemaReport> {
+        Ok(SchemaReport {
+            table: T::TABLE.to_string(),
+            issues: Vec::new(),
+        })
+    }
+}
+
+#[async_trait]
+impl<T, A> Repository<T> for MemoryRepository<T, A>
+where
+    T: Schema + Insertable + Updatable + Listenable + Send + Sync + Clone + 'static,
+    A: RowAdapter<T, Row = MemoryRow> + Send + Sync + 'static,
+    T::Key: Clone + Send + Sync + 'static + KeyValues,
+{
+    async fn find_by_id(&self, id: &T::Key) -> RepoResult<Option<T>> {
+        self.select_by_id(id, true)?
+            .map(|row| self.load(row))
+            .transpose()
+    }
+
+    async fn find_by_field(&self, field_name: &str, value: ParamValue) -> RepoResult<Vec<T>> {
+        self.select_by_field(field_name, &value)?
+            .into_iter()
+            .map(|row| self.load(row))
+            .collect()
+    }
+
+    async fn insert(&self, entity: &T) -> RepoResult<T> {
+        // Listeners may rewrite the entity before it is stored.
+        let mut entity = entity.clone();
+        self.listeners.pre_insert(&mut entity)?;
+        let mut values = entity.insert_values();
+        storeit_core::tenant::stamp_insert::<T>(&mut values)?;
+
+        let cols = Columns::of::<T>();
+        let mut row = vec![ParamValue::Null; cols.len()];
+        for (col, value) in T::INSERT_COLUMNS.iter().zip(values) {
+            row[cols.index(col)?] = value;
+        }
+        if T::ID_GENERATED {
+            row[cols.index(T::ID_COLUMN)?] = ParamValue::I64(self.db.next_id(T::TABLE));
+        } else if entity.id().is_none() {
+            return Err(invalid_input(
+                "client-assigned key must be set before insert".to_string(),
+            ));
+        }
+        let key = Key(T::ID_COLUMNS
+            .iter()
+            .map(|c| Ok(row[cols.index(c)?].clone()))
+            .collect::<RepoResult<Vec<_>>>()?);
+
+        let def = T::table_def();
+        self.write(|table| {
+            if table.get(&key).is_some() {
+                let name = T::ID_COLUMNS
+                    .iter()
+                    .map(|c| format!("{}.{}", T::TABLE, c))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                return Err(RepoError::UniqueViolation {
+                    source: constraint(format!("UNIQUE constraint failed: {}", name)),
+                    constraint: Some(name),
+                });
+            }
+            check_constraints(&def, &cols, table, &key, &row)?;
+            table.put(key, row.clone());
+            Ok(())
+        })?;
+        let inserted = self.adapter.from_row(&MemoryRow(row))?;
+        self.listeners.inserted(inserted)
+    }
+
+    async fn update(&self, entity: &T) -> RepoResult<T> {
+        let mut entity = entity.clone();
+        self.listeners.pre_update(&mut entity)?;
+        let now = storeit_core::clock::now();
+        // UPDATE_COLUMNS values, then the key, then the expected version.
+        let mut values = storeit_core::clock::with_now(now, || entity.update_values());
+        let expected = T::VERSION_COLUMN.and_then(|_| values.pop());
+        values.truncate(T::UPDATE_COLUMNS.len());
+
+        let def = T::table_def();
+        let id = entity.id().ok_or_else(|| {
+            invalid_input(format!("cannot update a {} row without a key", T::TABLE))
+        })?;
+        let cols = Columns::of::<T>();
+        let tenant = storeit_core::tenant::filter_values::<T>()?;
+        let key = Key(id.key_values());
+        let n = self.write(|table| {
+            let current = table.get(&key).filter(|row| {
+                in_scope::<T>(&cols, row, &tenant, true)
+                    && match (T::VERSION_COLUMN, &expected) {
+                        (Some(col), Some(v)) => cols
+                            .index(col)
+                            .is_ok_and(|i| compare(&row[i], v) == Some(Ordering::Equal)),
+                        _ => true,
+                    }
+            });
+            let Some(mut row) = current.cloned() else {
+                return Ok(0);
+            };
+            for (col, value) in T::UPDATE_COLUMNS.iter().zip(values) {
+                row[cols.index(col)?] = value;
+            }
+            if let Some(col) = T::VERSION_COLUMN {
+                let i = cols.index(col)?;
+                row[i] = increment(&row[i]);
+            }
+            check_constraints(&def, &cols, table, &key, &row)?;
+            table.put(key, row);
+            Ok(1)
+        })?;
+        // A versioned update that matched nothing lost a race with another writer.
+        if T::VERSION_COLUMN.is_some() && n == 0 {
+            return Err(RepoError::OptimisticLock { table: T::TABLE });
+        }
+        // Otherwise, for a tenant-scoped entity, the row belongs to another tenant (or is gone).
+        if T::TENANT_COLUMN.is_some() && n == 0 {
+            return Err(RepoError::NotFound);
+        }
+        let mut updated = entity;
+        updated.bump_version();
+        updated.touch(now);
+        Ok(updated)
+    }
+
+    async fn delete_by_id(&self, id: &T::Key) -> RepoResult<bool> {
+        self.listeners.pre_delete(id)?;
+        // Soft-deleting entities only stamp their deletion column.
+        match T::SOFT_DELETE_COLUMN {
+            Some(col) => self.modify_by_id(id, true, |cols, row| {
+                row[cols.index(col)?] = storeit_core::clock::now().to_param_value();
+                Ok(true)
+            }),
+            None => self.remove_by_id(id),
+        }
+    }
+
+    async fn find_where(&self, criteria: &Criteria) -> RepoResult<Vec<T>> {
+        self.select_where(criteria)?
+            .into_iter()
+            .map(|row| self.load(row))
+            .collect()
+    }
+
+    async fn count_where(&self, criteria: &Criteria) -> RepoResult<u64> {
+        Ok(self.select_where(criteria)?.len() as u64)
+    }
+
+    async fn exists_where(&self, criteria: &Criteria) -> RepoResult<bool> {
+        Ok(!self.select_where(criteria)?.is_empty())
+    }
+
+    async fn delete_where(&self, criteria: &Criteria) -> RepoResult<u64> {
+        let cols = Columns::of::<T>();
+        let tenant = storeit_core::tenant::filter_values::<T>()?;
+        let deleted_at = storeit_core::clock::now().to_param_value();
+        self.write(|table| {
+            let mut matched = Vec::new();
+            for (key, row) in table.rows() {
+                if in_scope::<T>(&cols, row, &tenant, true)
+                    && eval(criteria, &cols, row)? == Some(true)
+                {
+                    matched.push(key.clone());
+                }
+            }
+            for key in &matched {
+                match T::SOFT_DELETE_COLUMN {
+                    Some(col) => {
+                        let mut row = table.get(key).cloned().unwrap_or_default();
+                        row[cols.index(col)?] = deleted_at.clone();
+                        table.put(key.clone(), row);
+                    }
+                    None => {
+                        table.remove(key);
+                    }
+                }
+            }
+            Ok(matched.len() as u64)
+        })
+    }
+
+    async fn find_page(&self, pageable: &Pageable) -> RepoResult<Page<T>> {
+        self.find_page_where(&Criteria::all(), pageable)
+    }
+
+    async fn find_page_by_field(
+        &self,
+        field_name: &str,
+        value: ParamValue,
+        pageable: &Pageable,
+    ) -> RepoResult<Page<T>> {
+        Columns::of::<T>().index(field_name)?;
+        if matches!(value, ParamValue::Null) {
+            page_offset::<T>(pageable)?;
+            return Ok(Page::new(Vec::new(), pageable, 0));
+        }
+        self.find_page_where(&Criteria::Eq(field_name.to_string(), value), pageable)
+    }
+
+    async fn find_page_after(
+        &self,
+        cursor: Option<Cursor>,
+        limit: usize,
+        order: &[Order],
+    ) -> RepoResult<CursorPage<T>> {
+        if limit == 0 {
+            return Err(invalid_input("page limit must be at least 1".to_string()));
+        }
+        let orders = storeit_sql_builder::keyset_orders::<T>(order, T::ID_COLUMNS)?;
+        let after = match &cursor {
+            Some(c) => storeit_sql_builder::keyset_criteria::<T>(&orders, c)?,
+            None => Criteria::all(),
+        };
+        let backward = cursor.as_ref().is_some_and(Cursor::is_before);
+        // Paging backwards reads the flipped order; the page is reversed below.
+        let scan: Vec<Order> = orders
+            .iter()
+            .map(|o| Order {
+                column: o.column.clone(),
+                direction: match (o.direction, backward) {
+                    (d, false) => d,
+                    (Direction::Asc, true) => Direction::Desc,
+                    (Direction::Desc, true) => Direction::Asc,
+                },
+            })
+            .collect();
+        let cols = Columns::of::<T>();
+        let mut rows = self.select_where(&after)?;
+        sort_rows(&cols, &mut rows, &scan)?;
+        let has_more = rows.len() > limit;
+        rows.truncate(limit);
+        if backward {
+            rows.reverse();
+        }
+        let key_of = |row: &Row| {
+            orders
+                .iter()
+                .map(|o| cols.value(row, &o.column))
+                .collect::<RepoResult<Vec<_>>>()
+        };
+        let first_key = rows.first().map(key_of).transpose()?;
+        let last_key = rows.last().map(key_of).transpose()?;
+        let items = rows
+            .into_iter()
+            .map(|row| self.load(row))
+            .collect::<RepoResult<Vec<_>>>()?;
+        Ok(CursorPage::new(
+            items,
+            orders.into_iter().map(|o| o.column).collect(),
+            first_key,
+            last_key,
+            cursor.as_ref(),
+            has_more,
+        ))
+    }
+}
+
+impl<T, A> MemoryRepository<T, A>
+where
+    T: Schema + Insertable + Updatable + Listenable + Send + Sync + Clone + 'static,
+    A: RowAdapter<T, Row = MemoryRow> + Send + Sync + 'static,
+    T::Key: Clone + Send + Sync + 'static + KeyValues,
+{
+    // One page of the live rows matching `criteria`, with their total count.
+    fn find_page_where(&self, criteria: &Criteria, pageable: &Pageable) -> RepoResult<Page<T>> {
+        let offset = page_offset::<T>(pageable)?;
+        let mut rows = self.select_where(criteria)?;
+        sort_rows(&Columns::of::<T>(), &mut rows, &pageable.sort)?;
+        let total = rows.len() as u64;
+        let items = rows
+            .into_iter()
+            .skip(offset)
+            .take(pageable.size)
+            .map(|row| self.load(row))
+            .collect::<RepoResult<Vec<_>>>()?;
+        Ok(Page::new(items, pageable, total))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use storeit_core::transactions::Isolation;
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct U {
+        id: Option<i64>,
+        email: String,
+        active: bool,
+    }
+
+    // Manually implement the core traits: the derive macro expands to `::storeit` paths.
+    impl Fetchable for U {
+        const TABLE: &'static str = "users";
+        const SELECT_COLUMNS: &'static [&'static str] = &["id", "email", "active"];
+        const FINDABLE_COLUMNS: &'static [(&'static str, &'static str)] =
+            &[("email", "TEXT"), ("active", "BOOLEAN")];
+    }
+    impl Identifiable for U {
+        type Key = i64;
+        const ID_COLUMN: &'static str = "id";
+        fn id(&self) -> Option<Self::Key> {
+            self.id
+        }
+    }
+
+    impl Listenable for U {}
+    impl Insertable for U {
+        const INSERT_COLUMNS: &'static [&'static str] = &["email", "active"];
+        fn insert_values(&self) -> Vec<ParamValue> {
+            vec![
+                ParamValue::String(self.email.clone()),
+                ParamValue::Bool(self.active),
+            ]
+        }
+    }
+    impl Updatable for U {
+        const UPDATE_COLUMNS: &'static [&'static str] = &["email", "active"];
+        fn update_values(&self) -> Vec<ParamValue> {
+            vec![
+                ParamValue::String(self.email.clone()),
+                ParamValue::Bool(self.active),
+                ParamValue::I64(self.id.unwrap_or_default()),
+            ]
+        }
+    }
+    impl Schema for U {
+        fn table_def() -> TableDef {
+            use storeit_core::schema::{ColumnDef, SqlType};
+            TableDef {
+                name: "users".into(),
+                columns: vec![
+                    ColumnDef::new("id", SqlType::BigInt),
+                    ColumnDef {
+                        unique: true,
+                        ..ColumnDef::new("email", SqlType::Text)
+                    },
+                    ColumnDef::new("active", SqlType::Boolean),
+                ],
+                primary_key: vec!["id".into()],
+                key_generated: true,
+            }
+        }
+    }
+
+    struct A;
+    impl RowAdapter<U> for A {
+        type Row = MemoryRow;
+        fn from_row(&self, row: &Self::Row) -> RepoResult<U> {
+            match row.values() {
+                [ParamValue::I64(id), ParamValue::String(email), ParamValue::Bool(active)] => {
+                    Ok(U {
+                        id: Some(*id),
+                        email: email.clone(),
+                        active: *active,
+                    })
+                }
+                other => Err(RepoError::mapping(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!("unexpected row {:?}", other),
+                ))),
+            }
+        }
+    }
+
+    fn user(email: &str) -> U {
+        U {
+            id: None,
+            email: email.into(),
+            active: true,
+        }
+    }
+
+    fn def(propagation: Propagation) -> TransactionDefinition {
+        TransactionDefinition {
+            propagation,
+            isolation: Isolation::Default,
+            read_only: false,
+            timeout: None,
+        }
+    }
+
+    fn boom() -> RepoError {
+        RepoError::backend(std::io::Error::new(std::io::ErrorKind::Other, "boom"))
+    }
+
+    async fn emails(db: &MemoryDatabase) -> Vec<String> {
+        let repo = MemoryRepository::new(db.clone(), A);
+        let mut all: Vec<String> = repo
+            .find_where(&Criteria::all())
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|u| u.email)
+            .collect();
+        all.sort();
+        all
+    }
+
+    #[tokio::test]
+    async fn crud_and_constraints() {
+        let repo = MemoryRepository::new(MemoryDatabase::new(), A);
+        let a = repo.insert(&user("a@x")).await.unwrap();
+        let b = repo.insert(&user("b@x")).await.unwrap();
+        assert_eq!((a.id, b.id), (Some(1), Some(2)));
+
+        let err = repo.insert(&user("a@x")).await.unwrap_err();
+        assert!(
+            matches!(&err, RepoError::UniqueViolation { constraint: Some(c), .. } if c == "users.email"),
+            "{:?}",
+            err
+        );
+        let err = repo
+            .update(&U {
+                email: "a@x".into(),
+                ..b.clone()
+            })
+            .await
+            .unwrap_err();
+        assert!(matches!(err, RepoError::UniqueViolation { .. }));
+
+        let renamed = repo
+            .update(&U {
+                active: false,
+                ..a.clone()
+            })
+            .await
+            .unwrap();
+        assert_eq!(repo.find_by_id(&1).await.unwrap(), Some(renamed));
+        let found = repo
+            .find_by_field("email", ParamValue::String("b@x".into()))
+            .await
+            .unwrap();
+        assert_eq!(found, vec![b]);
+        assert!(repo.find_by_field("nope", ParamValue::Null).await.is_err());
+
+        assert!(repo.delete_by_id(&1).await.unwrap());
+        assert!(!repo.delete_by_id(&1).await.unwrap());
+        assert_eq!(repo.count_where(&Criteria::all()).await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn rollback_discards_writes() {
+        let db = MemoryDatabase::new();
+        let mgr = MemoryTransactionManager::new(db.clone());
+        let res = mgr
+            .execute::<(), _, _>(&def(Propagation::Required), |_ctx| {
+                let db = db.clone();
+                async move {
+                    let repo = MemoryRepository::new(db, A);
+                    repo.insert(&user("gone@x")).await?;
+                    assert_eq!(repo.count_where(&Criteria::all()).await?, 1);
+                    Err(boom())
+                }
+            })
+            .await;
+        assert!(res.is_err());
+        assert!(emails(&db).await.is_empty());
+
+        mgr.execute(&def(Propagation::Required), |_ctx| {
+            let db = db.clone();
+            async move { MemoryRepository::new(db, A).insert(&user("kept@x")).await }
+        })
+        .await
+        .unwrap();
+        assert_eq!(emails(&db).await, vec!["kept@x"]);
+    }
+
+    #[tokio::test]
+    async fn nested_rolls_back_to_its_savepoint() {
+        let db = MemoryDatabase::new();
+        let mgr = MemoryTransactionManager::new(db.clone());
+        mgr.execute(&def(Propagation::Required), |_ctx| {
+            let (db, mgr) = (db.clone(), mgr.clone());
+            async move {
+                let repo = MemoryRepository::new(db.clone(), A);
+                repo.insert(&user("outer@x")).await?;
+                mgr.execute(&def(Propagation::Nested), |_ctx| {
+                    let db = db.clone();
+                    async move {
+                        MemoryRepository::new(db, A)
+                            .insert(&user("inner_ok@x"))
+                            .await
+                    }
+                })
+                .await?;
+                let inner = mgr
+                    .execute::<(), _, _>(&def(Propagation::Nested), |_ctx| {
+                        let db = db.clone();
+                        async move {
+                            MemoryRepository::new(db, A)
+                                .insert(&user("inner_fail@x"))
+                                .await?;
+                            Err(boom())
+                        }
+                    })
+                    .await;
+                assert!(inner.is_err());
+                Ok(())
+            }
+        })
+        .await
+        .unwrap();
+        assert_eq!(emails(&db).await, vec!["inner_ok@x", "outer@x"]);
+    }
+
+    #[tokio::test]
+    async fn requires_new_commits_independently() {
+        let db = MemoryDatabase::new();
+        let mgr = MemoryTransactionManager::new(db.clone());
+        let res = mgr
+            .execute::<(), _, _>(&def(Propagation::Required), |_ctx| {
+                let (db, mgr) = (db.clone(), mgr.clone());
+                async move {
+                    MemoryRepository::new(db.clone(), A)
+                        .insert(&user("outer@x"))
+                        .await?;
+                    mgr.execute(&def(Propagation::RequiresNew), |_ctx| {
+                        let db = db.clone();
+                        async move { MemoryRepository::new(db, A).insert(&user("audit@x")).await }
+                    })
+                    .await?;
+                    // The outer transaction does not see the independent commit.
+                    assert_eq!(
+                        MemoryRepository::new(db, A)
+                            .count_where(&Criteria::all())
+                            .await?,
+                        1
+                    );
+                    Err(boom())
+                }
+            })
+            .await;
+        assert!(res.is_err());
+        assert_eq!(emails(&db).await, vec!["audit@x"]);
+
+        let never = mgr
+            .execute(&def(Propagation::Required), |_ctx| {
+                let mgr = mgr.clone();
+                async move {
+                    mgr.execute(&def(Propagation::Never), |_ctx| async { Ok(()) })
+                        .await
+                }
+            })
+            .await;
+        assert!(never.is_err());
+    }
+
+    #[tokio::test]
+    async fn commit_rechecks_unique_values_against_concurrent_commits() {
+        let db = MemoryDatabase::new();
+        let mgr = MemoryTransactionManager::new(db.clone());
+        let res = mgr
+            .execute::<(), _, _>(&def(Propagation::Required), |_ctx| {
+                let (db, mgr) = (db.clone(), mgr.clone());
+                async move {
+                    // Unique in this transaction's snapshot...
+                    MemoryRepository::new(db.clone(), A)
+                        .insert(&user("dup@x"))
+                        .await?;
+                    // ...but another transaction commits the same email first.
+                    mgr.execute(&def(Propagation::RequiresNew), |_ctx| {
+                        let db = db.clone();
+                        async move { MemoryRepository::new(db, A).insert(&user("dup@x")).await }
+                    })
+                    .await?;
+                    MemoryRepository::new(db, A)
+                        .insert(&user("other@x"))
+                        .await?;
+                    Ok(())
+                }
+            })
+            .await;
+        assert!(
+            matches!(&res, Err(RepoError::UniqueViolation { constraint: Some(c), .. }) if c == "users.email"),
+            "{res:?}"
+        );
+        // The failed commit applied none of its rows.
+        assert_eq!(emails(&db).await, vec!["dup@x"]);
+    }
+
+    #[tokio::test]
+    async fn read_only_transactions_reject_writes() {
+        let db = MemoryDatabase::new();
+        let mgr = MemoryTransactionManager::new(db.clone());
+        let read_only = TransactionDefinition {
+            read_only: true,
+            ..def(Propagation::Required)
+        };
+        let err = mgr
+            .execute(&read_only, |_ctx| {
+                let db = db.clone();
+                async move {
+                    let repo = MemoryRepository::new(db, A);
+                    assert!(repo.find_by_id(&1).await?.is_none());
+                    repo.insert(&user("ro@x")).await
+                }
+            })
+            .await
+            .unwrap_err();
+        assert!(matches!(err, RepoError::ReadOnlyViolation { .. }));
+        assert!(emails(&db).await.is_empty());
+    }
+}
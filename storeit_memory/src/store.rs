@@ -0,0 +1,399 @@
+//! Table storage shared by repositories, and the task-local transaction frames over it.
+//!
+//! Committed rows live in a [`MemoryDatabase`]. A transaction works on a snapshot of every
+//! table (its frame) and records the keys it wrote; committing copies only those rows back, so
+//! transactions on other tasks that touched different rows do not overwrite each other.
+//! Constraints were checked against the snapshot only, so the commit checks the written rows
+//! again against what other tasks committed meanwhile, and applies nothing on a conflict.
+//! Sequences are shared and never rolled back, like database sequences.
+
+use crate::eval::{compare, sort_cmp};
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicUsize, Ordering as AtomicOrdering};
+use std::sync::{Arc, Mutex, OnceLock};
+use storeit_core::{ParamValue, RepoError, RepoResult};
+
+pub(crate) type Row = Vec<ParamValue>;
+
+/// A primary key: the row's values for `ID_COLUMNS`, ordered like sorted values.
+#[derive(Debug, Clone)]
+pub(crate) struct Key(pub(crate) Vec<ParamValue>);
+
+impl Ord for Key {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0
+            .iter()
+            .zip(&other.0)
+            .map(|(a, b)| sort_cmp(a, b))
+            .find(|o| o.is_ne())
+            .unwrap_or_else(|| self.0.len().cmp(&other.0.len()))
+    }
+}
+
+impl PartialOrd for Key {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Key {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+
+impl Eq for Key {}
+
+pub(crate) type Rows = BTreeMap<Key, Row>;
+// The keys a frame wrote, each with its row as of the snapshot (`None` if it did not exist).
+type Dirty = BTreeMap<(&'static str, Key), Option<Row>>;
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Tables(HashMap<&'static str, Rows>);
+
+#[derive(Debug, Default)]
+struct Shared {
+    tables: Tables,
+    sequences: HashMap<&'static str, i64>,
+    constraints: HashMap<&'static str, Constraints>,
+}
+
+/// The constraints a commit checks again for the rows of one table.
+#[derive(Debug, Clone)]
+pub(crate) struct Constraints {
+    /// The primary key's `table.column` list.
+    pub(crate) key: String,
+    /// The `table.column` name and row index of each unique column.
+    pub(crate) unique: Vec<(String, usize)>,
+    /// The row index of the `#[fetch(version)]` column.
+    pub(crate) version: Option<usize>,
+}
+
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+static NAMED: OnceLock<Mutex<HashMap<String, MemoryDatabase>>> = OnceLock::new();
+
+/// An in-memory database: tables of rows keyed by primary key, plus one sequence per table
+/// for generated keys. Tables are created on first use. Clones share the same data.
+#[derive(Debug, Clone)]
+pub struct MemoryDatabase {
+    id: usize,
+    shared: Arc<Mutex<Shared>>,
+}
+
+impl Default for MemoryDatabase {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MemoryDatabase {
+    /// A new, empty database.
+    pub fn new() -> Self {
+        Self {
+            id: NEXT_ID.fetch_add(1, AtomicOrdering::Relaxed),
+            shared: Arc::new(Mutex::new(Shared::default())),
+        }
+    }
+
+    /// The process-wide database registered under `name`, created on first use.
+    pub fn named(name: &str) -> Self {
+        let registry = NAMED.get_or_init(Default::default);
+        registry
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .entry(name.to_string())
+            .or_default()
+            .clone()
+    }
+
+    /// Resolve a `memory://<name>` URL to [`named`](Self::named)`(name)`; `memory://` without a
+    /// name opens a new, private database.
+    pub fn from_url(url: &str) -> RepoResult<Self> {
+        let name = url
+            .strip_prefix("memory://")
+            .or_else(|| url.strip_prefix("memory:"))
+            .ok_or_else(|| {
+                crate::eval::invalid_input(format!("`{}` is not a memory:// database URL", url))
+            })?;
+        Ok(if name.is_empty() {
+            Self::new()
+        } else {
+            Self::named(name)
+        })
+    }
+
+    /// Remove every row and reset the sequences.
+    pub fn clear(&self) {
+        let mut shared = self.lock();
+        shared.tables = Tables::default();
+        shared.sequences.clear();
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Shared> {
+        self.shared.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// The next generated key of `table`.
+    pub(crate) fn next_id(&self, table: &'static str) -> i64 {
+        let mut shared = self.lock();
+        let seq = shared.sequences.entry(table).or_insert(0);
+        *seq += 1;
+        *seq
+    }
+
+    /// Record the constraints of `table` for commits, once.
+    pub(crate) fn declare(&self, table: &'static str, constraints: impl FnOnce() -> Constraints) {
+        self.lock()
+            .constraints
+            .entry(table)
+            .or_insert_with(constraints);
+    }
+
+    /// Whether the innermost transaction of the current task runs on this database.
+    pub(crate) fn in_transaction(&self) -> bool {
+        TX_STACK
+            .try_with(|stack| stack.borrow().last().is_some_and(|f| f.db == self.id))
+            .unwrap_or(false)
+    }
+
+    /// Run `f` on `table` as the current task sees it: the snapshot of its transaction on this
+    /// database, else the committed rows. Writes in a read-only transaction fail.
+    pub(crate) fn with_table<R>(
+        &self,
+        table: &'static str,
+        write: bool,
+        f: impl FnOnce(&mut TableMut<'_>) -> RepoResult<R>,
+    ) -> RepoResult<R> {
+        if self.in_transaction() {
+            return TX_STACK.with(|stack| {
+                let mut stack = stack.borrow_mut();
+                let frame = stack.last_mut().expect("transaction frame");
+                if write && frame.read_only {
+                    return Err(RepoError::ReadOnlyViolation {
+                        source: Box::new(std::io::Error::new(
+                            std::io::ErrorKind::PermissionDenied,
+                            "write attempted in a read-only transaction",
+                        )),
+                    });
+                }
+                f(&mut TableMut {
+                    table,
+                    rows: frame.work.0.entry(table).or_default(),
+                    dirty: Some(&mut frame.dirty),
+                })
+            });
+        }
+        let mut shared = self.lock();
+        f(&mut TableMut {
+            table,
+            rows: shared.tables.0.entry(table).or_default(),
+            dirty: None,
+        })
+    }
+
+    /// A transaction frame over a snapshot of the committed rows.
+    pub(crate) fn begin(&self, read_only: bool) -> Frame {
+        Frame {
+            db: self.id,
+            read_only,
+            work: self.lock().tables.clone(),
+            dirty: Dirty::new(),
+        }
+    }
+
+    /// Copy the rows `frame` wrote (or removed) into the committed rows, unless another task
+    /// committed a conflicting write since the snapshot: a row under the same key, a changed
+    /// version of a row the frame also wrote, or a duplicate unique value. Then nothing is
+    /// copied and the error is what the write would have failed with.
+    pub(crate) fn commit(&self, frame: Frame) -> RepoResult<()> {
+        let mut shared = self.lock();
+        let Shared {
+            tables,
+            constraints,
+            ..
+        } = &mut *shared;
+        let mut staged: HashMap<&'static str, Rows> = HashMap::new();
+        for ((table, key), before) in &frame.dirty {
+            let committed = tables.0.get(table).and_then(|rows| rows.get(key));
+            let written = frame.work.0.get(table).and_then(|rows| rows.get(key));
+            if let (Some(rules), Some(_)) = (constraints.get(table), written) {
+                match (before, committed) {
+                    (None, Some(_)) => return Err(unique_violation(&rules.key)),
+                    // Another task updated or removed the versioned row first.
+                    (Some(before), committed) => {
+                        let changed = rules.version.is_some_and(|i| {
+                            committed.map_or(true, |row| {
+                                compare(&row[i], &before[i]) != Some(Ordering::Equal)
+                            })
+                        });
+                        if changed {
+                            return Err(RepoError::OptimisticLock { table });
+                        }
+                    }
+                    (None, None) => {}
+                }
+            }
+            let rows = staged
+                .entry(table)
+                .or_insert_with(|| tables.0.get(table).cloned().unwrap_or_default());
+            match written {
+                Some(row) => {
+                    rows.insert(key.clone(), row.clone());
+                }
+                None => {
+                    rows.remove(key);
+                }
+            }
+        }
+        for (table, key) in frame.dirty.keys() {
+            let (Some(rules), Some(rows)) = (constraints.get(table), staged.get(table)) else {
+                continue;
+            };
+            let Some(row) = rows.get(key) else {
+                continue;
+            };
+            for (name, i) in &rules.unique {
+                let taken = rows
+                    .iter()
+                    .any(|(k, r)| k != key && compare(&r[*i], &row[*i]) == Some(Ordering::Equal));
+                if taken {
+                    return Err(unique_violation(name));
+                }
+            }
+        }
+        tables.0.extend(staged);
+        Ok(())
+    }
+}
+
+fn unique_violation(name: &str) -> RepoError {
+    RepoError::UniqueViolation {
+        source: Box::new(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("UNIQUE constraint failed: {}", name),
+        )),
+        constraint: Some(name.to_string()),
+    }
+}
+
+/// One transaction of the current task.
+pub(crate) struct Frame {
+    db: usize,
+    read_only: bool,
+    work: Tables,
+    dirty: Dirty,
+}
+
+/// The state of a frame at a `Propagation::Nested` savepoint.
+pub(crate) struct Savepoint(Tables, Dirty);
+
+impl Frame {
+    pub(crate) fn savepoint(&self) -> Savepoint {
+        Savepoint(self.work.clone(), self.dirty.clone())
+    }
+
+    pub(crate) fn restore(&mut self, savepoint: Savepoint) {
+        self.work = savepoint.0;
+        self.dirty = savepoint.1;
+    }
+}
+
+// Task-local stack of open transactions, innermost last.
+tokio::task_local! {
+    pub(crate) static TX_STACK: RefCell<Vec<Frame>>;
+}
+
+/// Mutable access to one table; writes are recorded for the transaction's commit.
+pub(crate) struct TableMut<'a> {
+    table: &'static str,
+    rows: &'a mut Rows,
+    dirty: Option<&'a mut Dirty>,
+}
+
+impl TableMut<'_> {
+    pub(crate) fn rows(&self) -> &Rows {
+        self.rows
+    }
+
+    pub(crate) fn get(&self, key: &Key) -> Option<&Row> {
+        self.rows.get(key)
+    }
+
+    fn mark(&mut self, key: &Key) {
+        if let Some(dirty) = self.dirty.as_deref_mut() {
+            let before = self.rows.get(key).cloned();
+            dirty.entry((self.table, key.clone())).or_insert(before);
+        }
+    }
+
+    pub(crate) fn put(&mut self, key: Key, row: Row) {
+        self.mark(&key);
+        self.rows.insert(key, row);
+    }
+
+    pub(crate) fn remove(&mut self, key: &Key) -> Option<Row> {
+        self.mark(key);
+        self.rows.remove(key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(i: i64) -> Key {
+        Key(vec![ParamValue::I64(i)])
+    }
+
+    #[test]
+    fn keys_compare_across_integer_widths() {
+        assert_eq!(key(1), Key(vec![ParamValue::I32(1)]));
+        assert!(key(2) > key(1));
+        assert!(
+            Key(vec![ParamValue::I64(1), ParamValue::String("b".into())])
+                > Key(vec![ParamValue::I64(1), ParamValue::String("a".into())])
+        );
+    }
+
+    #[test]
+    fn commit_copies_only_the_rows_a_frame_wrote() {
+        let db = MemoryDatabase::new();
+        let mut frame = db.begin(false);
+        db.with_table("t", true, |t| {
+            t.put(key(1), vec![ParamValue::I64(1)]);
+            Ok(())
+        })
+        .unwrap();
+        // A concurrent writer on the committed rows.
+        frame.work.0.entry("t").or_default();
+        frame.dirty.insert(("t", key(2)), None);
+        frame
+            .work
+            .0
+            .get_mut("t")
+            .unwrap()
+            .insert(key(2), vec![ParamValue::I64(2)]);
+        db.commit(frame).unwrap();
+        let ids: Vec<_> = db
+            .with_table("t", false, |t| {
+                Ok(t.rows().keys().cloned().collect::<Vec<_>>())
+            })
+            .unwrap();
+        assert_eq!(ids, vec![key(1), key(2)]);
+    }
+
+    #[test]
+    fn named_databases_are_shared_and_sequences_count_per_table() {
+        let a = MemoryDatabase::from_url("memory://store-test").unwrap();
+        let b = MemoryDatabase::named("store-test");
+        assert_eq!(a.next_id("t"), 1);
+        assert_eq!(b.next_id("t"), 2);
+        assert_eq!(b.next_id("u"), 1);
+        assert_ne!(MemoryDatabase::from_url("memory://").unwrap().id, a.id);
+        assert!(MemoryDatabase::from_url("sqlite://x").is_err());
+        a.clear();
+        assert_eq!(b.next_id("t"), 1);
+    }
+}
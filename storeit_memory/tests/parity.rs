@@ -0,0 +1,46 @@
+//! The shared `tests_common` suite, run against the in-memory backend.
+use async_trait::async_trait;
+use storeit::row::MemRow;
+use storeit_core::Repository;
+use storeit_memory::{MemoryDatabase, MemoryRepository, MemoryTransactionManager};
+use tests_common::{Note, NoteRowAdapter, User, UserRowAdapter};
+
+struct MemoryFactory {
+    db: MemoryDatabase,
+}
+
+#[async_trait]
+impl tests_common::RepoFactory for MemoryFactory {
+    async fn new_user_repo(
+        &self,
+    ) -> storeit_core::RepoResult<Box<dyn Repository<User> + Send + Sync>> {
+        let repo = MemoryRepository::new(self.db.clone(), UserRowAdapter::<MemRow>::new());
+        Ok(Box::new(repo))
+    }
+}
+
+#[async_trait]
+impl tests_common::NoteRepoFactory for MemoryFactory {
+    async fn new_note_repo(
+        &self,
+    ) -> storeit_core::RepoResult<Box<dyn Repository<Note> + Send + Sync>> {
+        let repo = MemoryRepository::new(self.db.clone(), NoteRowAdapter::<MemRow>::new());
+        Ok(Box::new(repo))
+    }
+}
+
+#[tokio::test]
+async fn memory_parity_suite() -> storeit_core::RepoResult<()> {
+    let factory = MemoryFactory {
+        db: MemoryDatabase::new(),
+    };
+    tests_common::test_crud_roundtrip(&factory).await?;
+    tests_common::test_find_by_field(&factory).await?;
+    tests_common::test_unique_violation(&factory).await?;
+    // Foreign keys are not modelled, and `Membership::role` is optional to the entity, so
+    // only the read-only check applies.
+    let mgr = MemoryTransactionManager::new(factory.db.clone());
+    tests_common::test_read_only_violation(&factory, &mgr).await?;
+    tests_common::test_tenant_isolation(&factory).await?;
+    Ok(())
+}
@@ -0,0 +1,307 @@
+//! `#[repository(backend = Memory)]` over entities using soft deletes, optimistic locking,
+//! tenants, projections and paging.
+// Generated finders take `&String` for `String` finder arguments.
+#![allow(clippy::ptr_arg)]
+use storeit::*;
+
+#[derive(Entity, Clone, Debug, PartialEq)]
+#[entity(table = "customers", soft_delete = "deleted_at")]
+pub struct Customer {
+    #[fetch(id)]
+    pub id: Option<i64>,
+    #[fetch(unique)]
+    pub email: String,
+    pub score: i64,
+    pub nickname: Option<String>,
+    #[fetch(version)]
+    pub version: i64,
+    pub deleted_at: Option<std::time::SystemTime>,
+}
+
+#[derive(Projection, Debug, PartialEq)]
+#[projection(of = Customer)]
+pub struct Contact {
+    pub email: String,
+}
+
+#[repository(entity = Customer, backend = Memory, finders(find_by_email: String))]
+pub mod customers {}
+
+#[derive(Entity, Clone, Debug, PartialEq)]
+#[entity(table = "notes", tenant = "tenant_id")]
+pub struct Note {
+    #[fetch(id)]
+    pub id: Option<i64>,
+    pub tenant_id: String,
+    pub body: String,
+}
+
+#[repository(entity = Note, backend = Memory)]
+pub mod notes {}
+
+fn customer(email: &str, score: i64) -> Customer {
+    Customer {
+        id: None,
+        email: email.into(),
+        score,
+        nickname: None,
+        version: 0,
+        deleted_at: None,
+    }
+}
+
+#[tokio::test]
+async fn soft_deletes_versions_and_projections() {
+    let repo = customers::Repository::from_url("memory://customers-lifecycle")
+        .await
+        .expect("repo");
+    let a = repo.insert(&customer("a@x", 1)).await.expect("insert");
+    let id = a.id.unwrap();
+
+    let updated = repo
+        .update(&Customer {
+            score: 5,
+            ..a.clone()
+        })
+        .await
+        .expect("update");
+    assert_eq!(updated.version, 1);
+    // The stale copy lost the race.
+    let err = repo.update(&a).await.unwrap_err();
+    assert!(matches!(
+        err,
+        RepoError::OptimisticLock { table: "customers" }
+    ));
+
+    // Repositories on the same URL share the database.
+    let other = customers::Repository::from_url("memory://customers-lifecycle")
+        .await
+        .expect("repo");
+    assert_eq!(
+        other.find_by_email(&"a@x".to_string()).await.unwrap(),
+        vec![updated.clone()]
+    );
+    assert_eq!(
+        repo.find_by_id_as::<Contact>(&id).await.unwrap(),
+        Some(Contact {
+            email: "a@x".into()
+        })
+    );
+
+    assert!(repo.delete_by_id(&id).await.unwrap());
+    assert_eq!(repo.find_by_id(&id).await.unwrap(), None);
+    assert!(repo.find_with_deleted(&id).await.unwrap().is_some());
+    // Updates skip soft-deleted rows, so the versioned update finds nothing to change.
+    assert!(matches!(
+        repo.update(&Customer {
+            score: 9,
+            ..updated.clone()
+        })
+        .await
+        .unwrap_err(),
+        RepoError::OptimisticLock { table: "customers" }
+    ));
+    // The email stays taken while the row is only soft-deleted.
+    assert!(matches!(
+        repo.insert(&customer("a@x", 2)).await.unwrap_err(),
+        RepoError::UniqueViolation { .. }
+    ));
+    assert!(repo.restore_by_id(&id).await.unwrap());
+    assert_eq!(repo.find_by_id(&id).await.unwrap(), Some(updated));
+    assert!(repo.purge_by_id(&id).await.unwrap());
+    assert_eq!(repo.find_with_deleted(&id).await.unwrap(), None);
+}
+
+#[tokio::test]
+async fn concurrent_commits_of_a_versioned_row_conflict() {
+    use storeit::backends::{MemoryDatabase, MemoryTransactionManager};
+    use storeit::transactions::{TransactionDefinition, TransactionManager};
+
+    let repo = customers::Repository::from_url("memory://customers-conflict")
+        .await
+        .expect("repo");
+    let mgr = MemoryTransactionManager::new(MemoryDatabase::named("customers-conflict"));
+    let a = repo.insert(&customer("a@x", 1)).await.expect("insert");
+    let res = mgr
+        .execute(&TransactionDefinition::default(), |_ctx| {
+            let (repo, mgr, a) = (&repo, mgr.clone(), a.clone());
+            async move {
+                repo.update(&Customer {
+                    score: 2,
+                    ..a.clone()
+                })
+                .await?;
+                // Another transaction updates the same version and commits first.
+                let requires_new = TransactionDefinition {
+                    propagation: storeit::transactions::Propagation::RequiresNew,
+                    ..TransactionDefinition::default()
+                };
+                mgr.execute(&requires_new, |_ctx| async move {
+                    repo.update(&Customer { score: 3, ..a }).await
+                })
+                .await
+            }
+        })
+        .await;
+    assert!(matches!(
+        res,
+        Err(RepoError::OptimisticLock { table: "customers" })
+    ));
+    let stored = repo.find_by_id(&a.id.unwrap()).await.unwrap().unwrap();
+    assert_eq!((stored.score, stored.version), (3, 1));
+}
+
+#[tokio::test]
+async fn criteria_and_paging() {
+    let repo = customers::Repository::from_url("memory://")
+        .await
+        .expect("repo");
+    for (i, email) in ["a@x", "b@x", "c@x", "d@x", "e@x"].iter().enumerate() {
+        repo.insert(&customer(email, (i as i64) % 3))
+            .await
+            .expect("insert");
+    }
+
+    let low = Criteria::Lt("score".into(), ParamValue::I64(2));
+    assert_eq!(repo.count_where(&low).await.unwrap(), 4);
+    assert!(repo
+        .exists_where(&Criteria::IsNull("nickname".into()))
+        .await
+        .unwrap());
+    // NULL compares as unknown, so neither `=` nor `<>` matches it.
+    assert_eq!(
+        repo.count_where(&Criteria::Ne(
+            "nickname".into(),
+            ParamValue::String("x".into())
+        ))
+        .await
+        .unwrap(),
+        0
+    );
+
+    let page = repo
+        .find_page(&Pageable::new(1, 2).with_sort(Sort::desc("score").then_asc("email")))
+        .await
+        .unwrap();
+    assert_eq!(page.total, 5);
+    let emails: Vec<_> = page.items.iter().map(|c| c.email.as_str()).collect();
+    assert_eq!(emails, ["e@x", "a@x"]);
+    assert!(repo
+        .find_page(&Pageable::new(0, 2).with_sort(Sort::asc("nope")))
+        .await
+        .is_err());
+
+    let order = [Order::asc("score")];
+    let first = repo.find_page_after(None, 3, &order).await.unwrap();
+    let emails: Vec<_> = first.items.iter().map(|c| c.email.as_str()).collect();
+    assert_eq!(emails, ["a@x", "d@x", "b@x"]);
+    let second = repo
+        .find_page_after(first.next.clone(), 3, &order)
+        .await
+        .unwrap();
+    let emails: Vec<_> = second.items.iter().map(|c| c.email.as_str()).collect();
+    assert_eq!(emails, ["e@x", "c@x"]);
+    assert!(second.next.is_none());
+    let back = repo.find_page_after(second.prev, 3, &order).await.unwrap();
+    assert_eq!(back.items, first.items);
+
+    assert_eq!(repo.delete_where(&low).await.unwrap(), 4);
+    assert_eq!(repo.count_where(&Criteria::all()).await.unwrap(), 1);
+}
+
+#[tokio::test]
+async fn tenants_only_see_their_own_rows() {
+    // Built under bypass, the repository still scopes every later call.
+    let repo = tenant::bypass(notes::Repository::from_url("memory://"))
+        .await
+        .expect("repo");
+    let note = |body: &str| Note {
+        id: None,
+        tenant_id: String::new(),
+        body: body.into(),
+    };
+    let a = tenant::scope("a", repo.insert(&note("for a")))
+        .await
+        .expect("insert");
+    tenant::scope("b", repo.insert(&note("for b")))
+        .await
+        .expect("insert");
+    assert_eq!(a.tenant_id, "a");
+
+    let seen = tenant::scope("b", repo.find_where(&Criteria::all()))
+        .await
+        .unwrap();
+    assert_eq!(seen.len(), 1);
+    assert_eq!(seen[0].body, "for b");
+    assert_eq!(
+        tenant::scope("b", repo.find_by_id(&a.id.unwrap()))
+            .await
+            .unwrap(),
+        None
+    );
+    let stolen = Note {
+        body: "hijacked".into(),
+        ..a.clone()
+    };
+    assert!(matches!(
+        tenant::scope("b", repo.update(&stolen)).await.unwrap_err(),
+        RepoError::NotFound
+    ));
+    assert!(matches!(
+        repo.find_where(&Criteria::all()).await.unwrap_err(),
+        RepoError::MissingTenant { table: "notes" }
+    ));
+    assert!(matches!(
+        tenant::scope(None::<String>, repo.find_where(&Criteria::all()))
+            .await
+            .unwrap_err(),
+        RepoError::MissingTenant { table: "notes" }
+    ));
+    let all = tenant::bypass(repo.find_where(&Criteria::all()))
+        .await
+        .unwrap();
+    assert_eq!(all.len(), 2);
+}
+
+#[tokio::test]
+async fn nullable_sort_keys_page_through_every_row() {
+    let repo = customers::Repository::from_url("memory://")
+        .await
+        .expect("repo");
+    for (email, nickname) in [
+        ("a@x", Some("m")),
+        ("b@x", None),
+        ("c@x", Some("k")),
+        ("d@x", None),
+        ("e@x", Some("m")),
+    ] {
+        repo.insert(&Customer {
+            nickname: nickname.map(Into::into),
+            ..customer(email, 0)
+        })
+        .await
+        .expect("insert");
+    }
+    let emails = |items: &[Customer]| items.iter().map(|c| c.email.clone()).collect::<Vec<_>>();
+
+    // NULL sorts first ascending and last descending, as on the SQL backends.
+    for (order, expected) in [
+        (Order::asc("nickname"), ["b@x", "d@x", "c@x", "a@x", "e@x"]),
+        (Order::desc("nickname"), ["a@x", "e@x", "c@x", "b@x", "d@x"]),
+    ] {
+        let order = [order];
+        let mut page = repo.find_page_after(None, 2, &order).await.unwrap();
+        let mut forward = emails(&page.items);
+        while let Some(next) = page.next.clone() {
+            page = repo.find_page_after(Some(next), 2, &order).await.unwrap();
+            forward.extend(emails(&page.items));
+        }
+        assert_eq!(forward, expected);
+        let mut backward = emails(&page.items);
+        while let Some(prev) = page.prev.clone() {
+            page = repo.find_page_after(Some(prev), 2, &order).await.unwrap();
+            backward.splice(0..0, emails(&page.items));
+        }
+        assert_eq!(backward, expected);
+    }
+}
@@ -0,0 +1,596 @@
+#![forbid(unsafe_code)]
+//! Versioned SQL migrations for storeit-rs.
+//!
+//! Migrations are SQL files embedded at compile time with [`embed_migrations!`] and applied
+//! in version order by a [`Migrator`]. Applied versions are recorded with a checksum of their
+//! SQL in the `storeit_schema_history` table, so an edited migration is reported instead of
+//! silently diverging. Every run holds a lock for its whole duration (an advisory lock on
+//! Postgres, `GET_LOCK` on MySQL, `BEGIN EXCLUSIVE` on SQLite), so concurrently starting
+//! replicas apply each migration once.
+//!
+//! The backends run on the connections of the existing transaction managers; enable
+//! `libsql-backend`, `postgres-backend` or `mysql-async`:
+//!
+//! ```ignore
+//! use storeit_migrate::{embed_migrations, Migrator};
+//!
+//! // migrations/1_create_users.sql, migrations/1_create_users.down.sql, migrations/2_add_index.sql
+//! let migrator = Migrator::new(embed_migrations!("migrations"))?;
+//! let applied = migrator.migrate(&tx_manager).await?;
+//! ```
+
+use async_trait::async_trait;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use storeit_core::{RepoError, RepoResult};
+
+#[cfg(feature = "mysql-async")]
+pub mod mysql;
+#[cfg(feature = "postgres-backend")]
+pub mod postgres;
+#[cfg(feature = "libsql-backend")]
+pub mod sqlite;
+
+#[doc(hidden)]
+pub use storeit_macros::__embed_migrations;
+
+/// The table recording applied migrations.
+pub const HISTORY_TABLE: &str = "storeit_schema_history";
+
+/// Embed the migrations in a directory (relative to the crate's `Cargo.toml`) as a
+/// `&'static [Migration]` sorted by version.
+///
+/// Files are named `<version>_<name>.sql` (or `.up.sql`) with an optional
+/// `<version>_<name>.down.sql` undoing them; a `V` prefix is allowed (`V2__add_index.sql`).
+/// Adding a file does not trigger a rebuild by itself; touch the crate after adding one.
+#[macro_export]
+macro_rules! embed_migrations {
+    ($dir:literal) => {
+        $crate::__embed_migrations!($crate, $dir)
+    };
+}
+
+/// One versioned migration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Migration {
+    /// Applied in ascending order; unique within a [`Migrator`].
+    pub version: i64,
+    pub name: &'static str,
+    /// The SQL applying the migration; it may hold several statements.
+    pub up: &'static str,
+    /// The SQL undoing the migration, needed by [`Migrator::rollback_to`].
+    pub down: Option<&'static str>,
+}
+
+impl Migration {
+    /// FNV-1a hash of the `up` SQL, ignoring carriage returns so line endings do not matter.
+    pub fn checksum(&self) -> i64 {
+        let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+        for byte in self.up.bytes().filter(|b| *b != b'\r') {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+        hash as i64
+    }
+}
+
+/// A row of the history table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AppliedMigration {
+    pub version: i64,
+    pub name: String,
+    pub checksum: i64,
+}
+
+/// Why a migration run was refused or failed; surfaced as [`RepoError::Backend`].
+#[derive(Debug)]
+pub enum MigrationError {
+    /// Two migrations share a version.
+    DuplicateVersion(i64),
+    /// The history holds a version that is not among the migrations.
+    UnknownVersion(i64),
+    /// An applied migration's SQL changed since it ran.
+    ChecksumMismatch { version: i64, name: String },
+    /// A pending migration is older than the newest applied one.
+    OutOfOrder { version: i64, latest: i64 },
+    /// A migration to roll back has no down SQL.
+    MissingDown { version: i64, name: String },
+    /// The migration lock could not be taken.
+    Lock(String),
+    /// The SQL of a migration failed; it was rolled back where the database allows it.
+    Failed {
+        version: i64,
+        name: String,
+        source: RepoError,
+    },
+}
+
+impl fmt::Display for MigrationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MigrationError::DuplicateVersion(v) => write!(f, "duplicate migration version {v}"),
+            MigrationError::UnknownVersion(v) => {
+                write!(f, "applied migration {v} is not among the known migrations")
+            }
+            MigrationError::ChecksumMismatch { version, name } => write!(
+                f,
+                "migration {version}_{name} was changed after it was applied"
+            ),
+            MigrationError::OutOfOrder { version, latest } => write!(
+                f,
+                "migration {version} is pending but older than the applied migration {latest}"
+            ),
+            MigrationError::MissingDown { version, name } => {
+                write!(f, "migration {version}_{name} has no down SQL")
+            }
+            MigrationError::Lock(reason) => write!(f, "migration lock not acquired: {reason}"),
+            MigrationError::Failed {
+                version,
+                name,
+                source,
+            } => write!(f, "migration {version}_{name} failed: {source}"),
+        }
+    }
+}
+
+impl std::error::Error for MigrationError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            MigrationError::Failed { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// A database the migrations run against, such as a backend's transaction manager.
+#[async_trait]
+pub trait MigrationTarget: Send + Sync {
+    type Connection: MigrationConnection;
+
+    /// Open the dedicated connection a run uses from lock to unlock.
+    async fn connect(&self) -> RepoResult<Self::Connection>;
+}
+
+/// The operations a [`Migrator`] needs from one connection.
+#[async_trait]
+pub trait MigrationConnection: Send {
+    /// Block until this connection holds the migration lock.
+    async fn lock(&mut self) -> RepoResult<()>;
+    /// Release the lock; called after every locked run, successful or not.
+    async fn unlock(&mut self) -> RepoResult<()>;
+    /// Create the history table if it does not exist.
+    async fn ensure_history(&mut self) -> RepoResult<()>;
+    /// The history rows, in any order.
+    async fn history(&mut self) -> RepoResult<Vec<AppliedMigration>>;
+    /// Start the unit a single migration runs in.
+    async fn begin(&mut self) -> RepoResult<()>;
+    /// Finish the unit; when this fails the runner rolls the unit back.
+    async fn commit(&mut self) -> RepoResult<()>;
+    async fn rollback(&mut self) -> RepoResult<()>;
+    /// Run a script of one or more statements.
+    async fn execute(&mut self, sql: &str) -> RepoResult<()>;
+    /// Add `migration` to the history.
+    async fn record(&mut self, migration: &Migration) -> RepoResult<()>;
+    /// Remove `version` from the history.
+    async fn forget(&mut self, version: i64) -> RepoResult<()>;
+}
+
+/// Applies and rolls back a fixed set of migrations.
+#[derive(Debug, Clone)]
+pub struct Migrator {
+    migrations: Vec<Migration>,
+}
+
+impl Migrator {
+    /// Sort `migrations` by version; fails on duplicate versions.
+    pub fn new(migrations: &[Migration]) -> RepoResult<Self> {
+        let mut migrations = migrations.to_vec();
+        migrations.sort_by_key(|m| m.version);
+        if let Some(pair) = migrations.windows(2).find(|w| w[0].version == w[1].version) {
+            return Err(RepoError::backend(MigrationError::DuplicateVersion(
+                pair[0].version,
+            )));
+        }
+        Ok(Self { migrations })
+    }
+
+    /// The migrations in version order.
+    pub fn migrations(&self) -> &[Migration] {
+        &self.migrations
+    }
+
+    /// Apply every pending migration in version order, each in its own transaction, and
+    /// return their versions. The history is validated first (see [`Migrator::pending`]).
+    pub async fn migrate<T: MigrationTarget>(&self, target: &T) -> RepoResult<Vec<i64>> {
+        let mut conn = target.connect().await?;
+        conn.lock().await?;
+        let result = self.apply_pending(&mut conn).await;
+        unlocked(&mut conn, result).await
+    }
+
+    /// Undo the applied migrations newer than `version`, newest first, and return their
+    /// versions. Nothing runs unless all of them have down SQL.
+    pub async fn rollback_to<T: MigrationTarget>(
+        &self,
+        target: &T,
+        version: i64,
+    ) -> RepoResult<Vec<i64>> {
+        let mut conn = target.connect().await?;
+        conn.lock().await?;
+        let result = self.undo_newer(&mut conn, version).await;
+        unlocked(&mut conn, result).await
+    }
+
+    /// The migrations [`Migrator::migrate`] would apply. Fails if an applied migration is
+    /// unknown or was edited, or if a pending one is older than the newest applied one.
+    pub async fn pending<T: MigrationTarget>(&self, target: &T) -> RepoResult<Vec<Migration>> {
+        let mut conn = target.connect().await?;
+        conn.ensure_history().await?;
+        let history = conn.history().await?;
+        Ok(self.validate(&history)?.into_iter().copied().collect())
+    }
+
+    async fn apply_pending<C: MigrationConnection>(&self, conn: &mut C) -> RepoResult<Vec<i64>> {
+        conn.ensure_history().await?;
+        let history = conn.history().await?;
+        let mut applied = Vec::new();
+        for migration in self.validate(&history)? {
+            run(conn, migration, Direction::Up).await?;
+            applied.push(migration.version);
+        }
+        Ok(applied)
+    }
+
+    async fn undo_newer<C: MigrationConnection>(
+        &self,
+        conn: &mut C,
+        version: i64,
+    ) -> RepoResult<Vec<i64>> {
+        conn.ensure_history().await?;
+        let history = conn.history().await?;
+        self.validate(&history)?;
+        let mut undo: Vec<&Migration> = self
+            .migrations
+            .iter()
+            .filter(|m| m.version > version && history.iter().any(|h| h.version == m.version))
+            .collect();
+        undo.reverse();
+        if let Some(m) = undo.iter().find(|m| m.down.is_none()) {
+            return Err(RepoError::backend(MigrationError::MissingDown {
+                version: m.version,
+                name: m.name.to_string(),
+            }));
+        }
+        let mut undone = Vec::new();
+        for migration in undo {
+            run(conn, migration, Direction::Down).await?;
+            undone.push(migration.version);
+        }
+        Ok(undone)
+    }
+
+    // Check `history` against the migrations and return the pending ones in order.
+    fn validate(&self, history: &[AppliedMigration]) -> RepoResult<Vec<&Migration>> {
+        for row in history {
+            let migration = self
+                .migrations
+                .iter()
+                .find(|m| m.version == row.version)
+                .ok_or_else(|| RepoError::backend(MigrationError::UnknownVersion(row.version)))?;
+            if migration.checksum() != row.checksum {
+                return Err(RepoError::backend(MigrationError::ChecksumMismatch {
+                    version: row.version,
+                    name: migration.name.to_string(),
+                }));
+            }
+        }
+        let latest = history.iter().map(|h| h.version).max();
+        let pending: Vec<&Migration> = self
+            .migrations
+            .iter()
+            .filter(|m| !history.iter().any(|h| h.version == m.version))
+            .collect();
+        if let (Some(first), Some(latest)) = (pending.first(), latest) {
+            if first.version < latest {
+                return Err(RepoError::backend(MigrationError::OutOfOrder {
+                    version: first.version,
+                    latest,
+                }));
+            }
+        }
+        Ok(pending)
+    }
+}
+
+/// Write `up` as a new migration `<version>_<name>.sql` in `dir`, creating the directory if
+/// needed, and return its path. The version is one more than the highest version among the
+/// files already there. Used to save a proposed migration, e.g. the output of
+/// `storeit_sql_builder::migration_script`, for review.
+pub fn write_migration(dir: impl AsRef<Path>, name: &str, up: &str) -> std::io::Result<PathBuf> {
+    let dir = dir.as_ref();
+    std::fs::create_dir_all(dir)?;
+    let mut latest: i64 = 0;
+    for entry in std::fs::read_dir(dir)? {
+        let file_name = entry?.file_name();
+        let file_name = file_name.to_string_lossy();
+        let stem = file_name.strip_prefix('V').unwrap_or(&file_name);
+        let digits = stem.bytes().take_while(u8::is_ascii_digit).count();
+        if let Ok(version) = stem[..digits].parse::<i64>() {
+            latest = latest.max(version);
+        }
+    }
+    let path = dir.join(format!("{}_{}.sql", latest + 1, name));
+    std::fs::write(&path, up)?;
+    Ok(path)
+}
+
+#[derive(Clone, Copy)]
+enum Direction {
+    Up,
+    Down,
+}
+
+// Run one direction of `migration` and its history change in one unit, rolling back on failure.
+async fn run<C: MigrationConnection>(
+    conn: &mut C,
+    migration: &Migration,
+    direction: Direction,
+) -> RepoResult<()> {
+    conn.begin().await?;
+    let result = match direction {
+        Direction::Up => match conn.execute(migration.up).await {
+            Ok(()) => conn.record(migration).await,
+            Err(e) => Err(e),
+        },
+        Direction::Down => match conn.execute(migration.down.unwrap_or_default()).await {
+            Ok(()) => conn.forget(migration.version).await,
+            Err(e) => Err(e),
+        },
+    };
+    let result = match result {
+        Ok(()) => conn.commit().await,
+        Err(e) => Err(e),
+    };
+    if let Err(source) = result {
+        // The statement error is the one worth reporting.
+        let _ = conn.rollback().await;
+        return Err(RepoError::backend(MigrationError::Failed {
+            version: migration.version,
+            name: migration.name.to_string(),
+            source,
+        }));
+    }
+    Ok(())
+}
+
+// Release the lock after a run and report the run's error before the unlock's.
+async fn unlocked<C: MigrationConnection, R>(conn: &mut C, result: RepoResult<R>) -> RepoResult<R> {
+    let unlock = conn.unlock().await;
+    let value = result?;
+    unlock?;
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    // An in-memory history; a script containing "FAIL" fails.
+    #[derive(Default)]
+    struct Fake {
+        history: Vec<AppliedMigration>,
+        log: Vec<String>,
+        locked: bool,
+    }
+
+    #[derive(Clone, Default)]
+    struct FakeTarget(Arc<Mutex<Fake>>);
+
+    struct FakeConnection(Arc<Mutex<Fake>>);
+
+    #[async_trait]
+    impl MigrationTarget for FakeTarget {
+        type Connection = FakeConnection;
+        async fn connect(&self) -> RepoResult<FakeConnection> {
+            Ok(FakeConnection(self.0.clone()))
+        }
+    }
+
+    impl FakeConnection {
+        fn log(&self, entry: impl Into<String>) {
+            self.0.lock().unwrap().log.push(entry.into());
+        }
+    }
+
+    #[async_trait]
+    impl MigrationConnection for FakeConnection {
+        async fn lock(&mut self) -> RepoResult<()> {
+            self.0.lock().unwrap().locked = true;
+            Ok(())
+        }
+        async fn unlock(&mut self) -> RepoResult<()> {
+            self.0.lock().unwrap().locked = false;
+            Ok(())
+        }
+        async fn ensure_history(&mut self) -> RepoResult<()> {
+            Ok(())
+        }
+        async fn history(&mut self) -> RepoResult<Vec<AppliedMigration>> {
+            Ok(self.0.lock().unwrap().history.clone())
+        }
+        async fn begin(&mut self) -> RepoResult<()> {
+            self.log("begin");
+            Ok(())
+        }
+        async fn commit(&mut self) -> RepoResult<()> {
+            self.log("commit");
+            Ok(())
+        }
+        async fn rollback(&mut self) -> RepoResult<()> {
+            self.log("rollback");
+            Ok(())
+        }
+        async fn execute(&mut self, sql: &str) -> RepoResult<()> {
+            assert!(self.0.lock().unwrap().locked, "ran without the lock");
+            if sql.contains("FAIL") {
+                return Err(RepoError::backend(std::io::Error::new(
+                    std::io::ErrorKind::Other,
+                    "syntax error",
+                )));
+            }
+            self.log(sql);
+            Ok(())
+        }
+        async fn record(&mut self, migration: &Migration) -> RepoResult<()> {
+            self.0.lock().unwrap().history.push(AppliedMigration {
+                version: migration.version,
+                name: migration.name.to_string(),
+                checksum: migration.checksum(),
+            });
+            Ok(())
+        }
+        async fn forget(&mut self, version: i64) -> RepoResult<()> {
+            self.0
+                .lock()
+                .unwrap()
+                .history
+                .retain(|h| h.version != version);
+            Ok(())
+        }
+    }
+
+    const fn m(version: i64, up: &'static str, down: Option<&'static str>) -> Migration {
+        Migration {
+            version,
+            name: "m",
+            up,
+            down,
+        }
+    }
+
+    fn migration_error(e: RepoError) -> String {
+        match e {
+            RepoError::Backend { source } => source.to_string(),
+            other => panic!("unexpected error {other:?}"),
+        }
+    }
+
+    #[test]
+    fn checksum_ignores_line_endings() {
+        assert_eq!(
+            m(1, "a;\r\nb;", None).checksum(),
+            m(1, "a;\nb;", None).checksum()
+        );
+        assert_ne!(m(1, "a;", None).checksum(), m(1, "b;", None).checksum());
+    }
+
+    #[test]
+    fn new_sorts_and_rejects_duplicates() {
+        let migrator = Migrator::new(&[m(2, "b", None), m(1, "a", None)]).unwrap();
+        let versions: Vec<i64> = migrator.migrations().iter().map(|m| m.version).collect();
+        assert_eq!(versions, vec![1, 2]);
+        let err = Migrator::new(&[m(1, "a", None), m(1, "b", None)]).unwrap_err();
+        assert_eq!(migration_error(err), "duplicate migration version 1");
+    }
+
+    #[test]
+    fn write_migration_takes_the_next_version() {
+        let dir = tempfile::tempdir().unwrap();
+        let migrations = dir.path().join("migrations");
+        let first =
+            write_migration(&migrations, "create_users", "CREATE TABLE users (id INT);").unwrap();
+        assert_eq!(first, migrations.join("1_create_users.sql"));
+        std::fs::write(migrations.join("V7__add_email.down.sql"), "").unwrap();
+        let next =
+            write_migration(&migrations, "add_index", "CREATE INDEX i ON users (id);").unwrap();
+        assert_eq!(next, migrations.join("8_add_index.sql"));
+        assert_eq!(
+            std::fs::read_to_string(next).unwrap(),
+            "CREATE INDEX i ON users (id);"
+        );
+    }
+
+    #[tokio::test]
+    async fn migrate_applies_pending_once() {
+        let target = FakeTarget::default();
+        let migrator = Migrator::new(&[m(1, "a", None), m(2, "b", None)]).unwrap();
+        assert_eq!(migrator.pending(&target).await.unwrap().len(), 2);
+        assert_eq!(migrator.migrate(&target).await.unwrap(), vec![1, 2]);
+        assert_eq!(migrator.migrate(&target).await.unwrap(), Vec::<i64>::new());
+        let fake = target.0.lock().unwrap();
+        assert_eq!(fake.log, ["begin", "a", "commit", "begin", "b", "commit"]);
+        assert!(!fake.locked);
+    }
+
+    #[tokio::test]
+    async fn failing_migration_rolls_back_and_keeps_earlier_ones() {
+        let target = FakeTarget::default();
+        let migrator = Migrator::new(&[m(1, "a", None), m(2, "FAIL", None)]).unwrap();
+        let err = migrator.migrate(&target).await.unwrap_err();
+        assert_eq!(migration_error(err), "migration 2_m failed: backend error");
+        let fake = target.0.lock().unwrap();
+        assert_eq!(fake.history.len(), 1);
+        assert_eq!(fake.log.last().map(String::as_str), Some("rollback"));
+        assert!(!fake.locked);
+    }
+
+    #[tokio::test]
+    async fn history_is_validated() {
+        let target = FakeTarget::default();
+        Migrator::new(&[m(1, "a", None), m(3, "c", None)])
+            .unwrap()
+            .migrate(&target)
+            .await
+            .unwrap();
+
+        let edited = Migrator::new(&[m(1, "a2", None), m(3, "c", None)]).unwrap();
+        let err = edited.migrate(&target).await.unwrap_err();
+        assert_eq!(
+            migration_error(err),
+            "migration 1_m was changed after it was applied"
+        );
+        let unknown = Migrator::new(&[m(1, "a", None)]).unwrap();
+        let err = unknown.pending(&target).await.unwrap_err();
+        assert_eq!(
+            migration_error(err),
+            "applied migration 3 is not among the known migrations"
+        );
+        let out_of_order =
+            Migrator::new(&[m(1, "a", None), m(2, "b", None), m(3, "c", None)]).unwrap();
+        let err = out_of_order.migrate(&target).await.unwrap_err();
+        assert_eq!(
+            migration_error(err),
+            "migration 2 is pending but older than the applied migration 3"
+        );
+    }
+
+    #[tokio::test]
+    async fn rollback_runs_down_newest_first() {
+        let target = FakeTarget::default();
+        let migrator = Migrator::new(&[
+            m(1, "a", Some("undo a")),
+            m(2, "b", Some("undo b")),
+            m(3, "c", Some("undo c")),
+        ])
+        .unwrap();
+        migrator.migrate(&target).await.unwrap();
+        assert_eq!(migrator.rollback_to(&target, 1).await.unwrap(), vec![3, 2]);
+        let fake = target.0.lock().unwrap();
+        assert_eq!(fake.history.len(), 1);
+        assert_eq!(
+            &fake.log[9..],
+            ["begin", "undo c", "commit", "begin", "undo b", "commit"]
+        );
+    }
+
+    #[tokio::test]
+    async fn rollback_requires_every_down() {
+        let target = FakeTarget::default();
+        let migrator = Migrator::new(&[m(1, "a", None), m(2, "b", Some("undo b"))]).unwrap();
+        migrator.migrate(&target).await.unwrap();
+        let err = migrator.rollback_to(&target, 0).await.unwrap_err();
+        assert_eq!(migration_error(err), "migration 1_m has no down SQL");
+        assert_eq!(target.0.lock().unwrap().history.len(), 2);
+    }
+}
@@ -0,0 +1,114 @@
+//! Migrations on a [`MysqlAsyncTransactionManager`].
+//!
+//! A run holds the named lock [`LOCK_NAME`] (`GET_LOCK`, waiting indefinitely). Each
+//! migration runs in a transaction, but MySQL commits DDL implicitly: a migration failing
+//! halfway leaves its earlier statements applied and is not recorded.
+
+use crate::{AppliedMigration, Migration, MigrationConnection, MigrationError, MigrationTarget};
+use async_trait::async_trait;
+use mysql_async::prelude::*;
+use mysql_async::Conn;
+use storeit_core::{RepoError, RepoResult};
+use storeit_mysql_async::{map_mysql_error, MysqlAsyncTransactionManager};
+
+/// The `GET_LOCK` name serializing migration runs.
+pub const LOCK_NAME: &str = "storeit_schema_history";
+
+const HISTORY_DDL: &str = "CREATE TABLE IF NOT EXISTS storeit_schema_history (\
+     version BIGINT PRIMARY KEY, name VARCHAR(255) NOT NULL, checksum BIGINT NOT NULL, \
+     applied_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP)";
+
+/// A pooled connection of a [`MysqlAsyncTransactionManager`].
+pub struct MysqlMigrationConnection {
+    conn: Conn,
+}
+
+#[async_trait]
+impl MigrationTarget for MysqlAsyncTransactionManager {
+    type Connection = MysqlMigrationConnection;
+
+    async fn connect(&self) -> RepoResult<MysqlMigrationConnection> {
+        let conn = self.pool().get_conn().await.map_err(map_mysql_error)?;
+        Ok(MysqlMigrationConnection { conn })
+    }
+}
+
+#[async_trait]
+impl MigrationConnection for MysqlMigrationConnection {
+    async fn lock(&mut self) -> RepoResult<()> {
+        let acquired: Option<Option<i64>> = self
+            .conn
+            .exec_first("SELECT GET_LOCK(?, -1)", (LOCK_NAME,))
+            .await
+            .map_err(map_mysql_error)?;
+        match acquired.flatten() {
+            Some(1) => Ok(()),
+            other => Err(RepoError::backend(MigrationError::Lock(format!(
+                "GET_LOCK returned {other:?}"
+            )))),
+        }
+    }
+
+    async fn unlock(&mut self) -> RepoResult<()> {
+        self.conn
+            .exec_drop("SELECT RELEASE_LOCK(?)", (LOCK_NAME,))
+            .await
+            .map_err(map_mysql_error)
+    }
+
+    async fn ensure_history(&mut self) -> RepoResult<()> {
+        self.execute(HISTORY_DDL).await
+    }
+
+    async fn history(&mut self) -> RepoResult<Vec<AppliedMigration>> {
+        let rows: Vec<(i64, String, i64)> = self
+            .conn
+            .query("SELECT version, name, checksum FROM storeit_schema_history")
+            .await
+            .map_err(map_mysql_error)?;
+        Ok(rows
+            .into_iter()
+            .map(|(version, name, checksum)| AppliedMigration {
+                version,
+                name,
+                checksum,
+            })
+            .collect())
+    }
+
+    async fn begin(&mut self) -> RepoResult<()> {
+        self.execute("START TRANSACTION").await
+    }
+
+    async fn commit(&mut self) -> RepoResult<()> {
+        self.execute("COMMIT").await
+    }
+
+    async fn rollback(&mut self) -> RepoResult<()> {
+        self.execute("ROLLBACK").await
+    }
+
+    async fn execute(&mut self, sql: &str) -> RepoResult<()> {
+        self.conn.query_drop(sql).await.map_err(map_mysql_error)
+    }
+
+    async fn record(&mut self, migration: &Migration) -> RepoResult<()> {
+        self.conn
+            .exec_drop(
+                "INSERT INTO storeit_schema_history (version, name, checksum) VALUES (?, ?, ?)",
+                (migration.version, migration.name, migration.checksum()),
+            )
+            .await
+            .map_err(map_mysql_error)
+    }
+
+    async fn forget(&mut self, version: i64) -> RepoResult<()> {
+        self.conn
+            .exec_drop(
+                "DELETE FROM storeit_schema_history WHERE version = ?",
+                (version,),
+            )
+            .await
+            .map_err(map_mysql_error)
+    }
+}
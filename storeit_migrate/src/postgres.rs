@@ -0,0 +1,114 @@
+//! Migrations on a [`TokioPostgresTransactionManager`].
+//!
+//! A run holds a session-level advisory lock on [`LOCK_KEY`]; each migration runs in its own
+//! transaction, which also covers its DDL.
+
+use crate::{AppliedMigration, Migration, MigrationConnection, MigrationTarget};
+use async_trait::async_trait;
+use storeit_core::RepoResult;
+use storeit_tokio_postgres::{map_pg_error, TokioPostgresTransactionManager};
+use tokio_postgres::Client;
+
+/// The `pg_advisory_lock` key serializing migration runs (`"storeit!"` in ASCII).
+pub const LOCK_KEY: i64 = 0x7374_6f72_6569_7421;
+
+const HISTORY_DDL: &str = "CREATE TABLE IF NOT EXISTS storeit_schema_history (\
+     version BIGINT PRIMARY KEY, name TEXT NOT NULL, checksum BIGINT NOT NULL, \
+     applied_at TIMESTAMPTZ NOT NULL DEFAULT now())";
+
+/// A client of a [`TokioPostgresTransactionManager`].
+pub struct PostgresMigrationConnection {
+    client: Client,
+}
+
+#[async_trait]
+impl MigrationTarget for TokioPostgresTransactionManager {
+    type Connection = PostgresMigrationConnection;
+
+    async fn connect(&self) -> RepoResult<PostgresMigrationConnection> {
+        Ok(PostgresMigrationConnection {
+            client: TokioPostgresTransactionManager::connect(self).await?,
+        })
+    }
+}
+
+#[async_trait]
+impl MigrationConnection for PostgresMigrationConnection {
+    async fn lock(&mut self) -> RepoResult<()> {
+        self.client
+            .execute("SELECT pg_advisory_lock($1)", &[&LOCK_KEY])
+            .await
+            .map_err(map_pg_error)?;
+        Ok(())
+    }
+
+    async fn unlock(&mut self) -> RepoResult<()> {
+        self.client
+            .execute("SELECT pg_advisory_unlock($1)", &[&LOCK_KEY])
+            .await
+            .map_err(map_pg_error)?;
+        Ok(())
+    }
+
+    async fn ensure_history(&mut self) -> RepoResult<()> {
+        self.execute(HISTORY_DDL).await
+    }
+
+    async fn history(&mut self) -> RepoResult<Vec<AppliedMigration>> {
+        let rows = self
+            .client
+            .query(
+                "SELECT version, name, checksum FROM storeit_schema_history",
+                &[],
+            )
+            .await
+            .map_err(map_pg_error)?;
+        rows.iter()
+            .map(|row| {
+                Ok(AppliedMigration {
+                    version: row.try_get(0).map_err(map_pg_error)?,
+                    name: row.try_get(1).map_err(map_pg_error)?,
+                    checksum: row.try_get(2).map_err(map_pg_error)?,
+                })
+            })
+            .collect()
+    }
+
+    async fn begin(&mut self) -> RepoResult<()> {
+        self.execute("BEGIN").await
+    }
+
+    async fn commit(&mut self) -> RepoResult<()> {
+        self.execute("COMMIT").await
+    }
+
+    async fn rollback(&mut self) -> RepoResult<()> {
+        self.execute("ROLLBACK").await
+    }
+
+    async fn execute(&mut self, sql: &str) -> RepoResult<()> {
+        self.client.batch_execute(sql).await.map_err(map_pg_error)
+    }
+
+    async fn record(&mut self, migration: &Migration) -> RepoResult<()> {
+        self.client
+            .execute(
+                "INSERT INTO storeit_schema_history (version, name, checksum) VALUES ($1, $2, $3)",
+                &[&migration.version, &migration.name, &migration.checksum()],
+            )
+            .await
+            .map_err(map_pg_error)?;
+        Ok(())
+    }
+
+    async fn forget(&mut self, version: i64) -> RepoResult<()> {
+        self.client
+            .execute(
+                "DELETE FROM storeit_schema_history WHERE version = $1",
+                &[&version],
+            )
+            .await
+            .map_err(map_pg_error)?;
+        Ok(())
+    }
+}
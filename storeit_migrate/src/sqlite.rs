@@ -0,0 +1,160 @@
+//! Migrations on a [`LibsqlTransactionManager`].
+//!
+//! A run is one `BEGIN EXCLUSIVE` transaction, which is the lock; each migration runs in a
+//! savepoint inside it, so a failing migration is undone while earlier ones are committed.
+//! Waiting runs retry for up to [`BUSY_TIMEOUT`].
+//!
+//! `PRAGMA foreign_keys` cannot change inside a transaction, so a run switches foreign keys
+//! off before it begins: rebuilding a table (see `storeit_sql_builder::alter_table`) would
+//! otherwise cascade its `DROP TABLE` to the rows referencing it. Each migration is checked
+//! with `PRAGMA foreign_key_check` instead and fails if it left a dangling reference.
+
+use crate::{AppliedMigration, Migration, MigrationConnection, MigrationTarget};
+use async_trait::async_trait;
+use libsql::{Connection, Value};
+use std::time::Duration;
+use storeit_core::{RepoError, RepoResult};
+use storeit_libsql::{map_libsql_error, LibsqlTransactionManager};
+
+/// How long a run waits for another run's exclusive transaction.
+pub const BUSY_TIMEOUT: Duration = Duration::from_secs(60);
+
+const HISTORY_DDL: &str = "CREATE TABLE IF NOT EXISTS storeit_schema_history (\
+     version INTEGER PRIMARY KEY, name TEXT NOT NULL, checksum INTEGER NOT NULL, \
+     applied_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP)";
+
+/// A connection of a [`LibsqlTransactionManager`]'s database.
+pub struct LibsqlMigrationConnection {
+    conn: Connection,
+    /// Whether foreign keys were enforced before the run switched them off.
+    foreign_keys: bool,
+}
+
+#[async_trait]
+impl MigrationTarget for LibsqlTransactionManager {
+    type Connection = LibsqlMigrationConnection;
+
+    async fn connect(&self) -> RepoResult<LibsqlMigrationConnection> {
+        let conn = self.database().connect().map_err(map_libsql_error)?;
+        conn.busy_timeout(BUSY_TIMEOUT).map_err(map_libsql_error)?;
+        Ok(LibsqlMigrationConnection {
+            conn,
+            foreign_keys: false,
+        })
+    }
+}
+
+impl LibsqlMigrationConnection {
+    // The first column of the first row `pragma` returns, if any.
+    async fn pragma(&self, pragma: &str) -> RepoResult<Option<Value>> {
+        let mut rows = self
+            .conn
+            .query(pragma, ())
+            .await
+            .map_err(map_libsql_error)?;
+        match rows.next().await.map_err(map_libsql_error)? {
+            Some(row) => Ok(Some(row.get_value(0).map_err(map_libsql_error)?)),
+            None => Ok(None),
+        }
+    }
+}
+
+#[async_trait]
+impl MigrationConnection for LibsqlMigrationConnection {
+    async fn lock(&mut self) -> RepoResult<()> {
+        self.foreign_keys = matches!(
+            self.pragma("PRAGMA foreign_keys").await?,
+            Some(Value::Integer(1))
+        );
+        self.execute("PRAGMA foreign_keys=OFF; BEGIN EXCLUSIVE")
+            .await
+    }
+
+    async fn unlock(&mut self) -> RepoResult<()> {
+        self.execute("COMMIT").await?;
+        if self.foreign_keys {
+            self.execute("PRAGMA foreign_keys=ON").await?;
+        }
+        Ok(())
+    }
+
+    async fn ensure_history(&mut self) -> RepoResult<()> {
+        self.execute(HISTORY_DDL).await
+    }
+
+    async fn history(&mut self) -> RepoResult<Vec<AppliedMigration>> {
+        let mut rows = self
+            .conn
+            .query(
+                "SELECT version, name, checksum FROM storeit_schema_history",
+                (),
+            )
+            .await
+            .map_err(map_libsql_error)?;
+        let mut history = Vec::new();
+        while let Some(row) = rows.next().await.map_err(map_libsql_error)? {
+            history.push(AppliedMigration {
+                version: row.get(0).map_err(map_libsql_error)?,
+                name: row.get(1).map_err(map_libsql_error)?,
+                checksum: row.get(2).map_err(map_libsql_error)?,
+            });
+        }
+        Ok(history)
+    }
+
+    async fn begin(&mut self) -> RepoResult<()> {
+        self.execute("SAVEPOINT storeit_migration").await
+    }
+
+    async fn commit(&mut self) -> RepoResult<()> {
+        // Foreign keys are off for the run (see `lock`); check this migration's references.
+        if let Some(Value::Text(table)) = self.pragma("PRAGMA foreign_key_check").await? {
+            return Err(RepoError::ForeignKeyViolation {
+                source: Box::new(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!(
+                        "FOREIGN KEY constraint failed: rows of {} reference missing keys",
+                        table
+                    ),
+                )),
+                constraint: None,
+            });
+        }
+        self.execute("RELEASE storeit_migration").await
+    }
+
+    async fn rollback(&mut self) -> RepoResult<()> {
+        self.execute("ROLLBACK TO storeit_migration; RELEASE storeit_migration")
+            .await
+    }
+
+    async fn execute(&mut self, sql: &str) -> RepoResult<()> {
+        self.conn
+            .execute_batch(sql)
+            .await
+            .map_err(map_libsql_error)?;
+        Ok(())
+    }
+
+    async fn record(&mut self, migration: &Migration) -> RepoResult<()> {
+        self.conn
+            .execute(
+                "INSERT INTO storeit_schema_history (version, name, checksum) VALUES (?1, ?2, ?3)",
+                libsql::params![migration.version, migration.name, migration.checksum()],
+            )
+            .await
+            .map_err(map_libsql_error)?;
+        Ok(())
+    }
+
+    async fn forget(&mut self, version: i64) -> RepoResult<()> {
+        self.conn
+            .execute(
+                "DELETE FROM storeit_schema_history WHERE version = ?1",
+                libsql::params![version],
+            )
+            .await
+            .map_err(map_libsql_error)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,2 @@
+DROP INDEX idx_accounts_email;
+ALTER TABLE accounts DROP COLUMN nickname;
@@ -0,0 +1,2 @@
+ALTER TABLE accounts ADD COLUMN nickname TEXT;
+CREATE INDEX idx_accounts_email ON accounts (email);
@@ -0,0 +1,207 @@
+#![cfg(feature = "libsql-backend")]
+use std::sync::Arc;
+use storeit_core::{RepoError, RepoResult};
+use storeit_libsql::LibsqlTransactionManager;
+use storeit_migrate::{embed_migrations, Migration, MigrationError, Migrator};
+
+const MIGRATIONS: &[Migration] = embed_migrations!("tests/migrations/sqlite");
+
+fn manager(dir: &tempfile::TempDir) -> LibsqlTransactionManager {
+    let path = dir.path().join("migrate.sqlite3");
+    #[allow(deprecated)]
+    let db = libsql::Database::open(format!("file:{}?mode=rwc", path.display())).expect("open db");
+    LibsqlTransactionManager::new(Arc::new(db))
+}
+
+async fn columns(mgr: &LibsqlTransactionManager, table: &str) -> Vec<String> {
+    let conn = mgr.database().connect().unwrap();
+    let mut rows = conn
+        .query("SELECT name FROM pragma_table_info(?1)", [table])
+        .await
+        .unwrap();
+    let mut names = Vec::new();
+    while let Some(row) = rows.next().await.unwrap() {
+        names.push(row.get::<String>(0).unwrap());
+    }
+    names
+}
+
+#[test]
+fn embeds_the_directory_in_version_order() {
+    let summary: Vec<(i64, &str, bool)> = MIGRATIONS
+        .iter()
+        .map(|m| (m.version, m.name, m.down.is_some()))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![(1, "create_accounts", true), (2, "add_nickname", true)]
+    );
+    assert!(MIGRATIONS[1].up.contains("CREATE INDEX idx_accounts_email"));
+}
+
+#[tokio::test]
+async fn migrate_is_idempotent_and_rolls_back() -> RepoResult<()> {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let mgr = manager(&dir);
+    let migrator = Migrator::new(MIGRATIONS)?;
+
+    assert_eq!(migrator.pending(&mgr).await?.len(), 2);
+    assert_eq!(migrator.migrate(&mgr).await?, vec![1, 2]);
+    assert_eq!(columns(&mgr, "accounts").await, ["id", "email", "nickname"]);
+    assert_eq!(migrator.migrate(&mgr).await?, Vec::<i64>::new());
+    assert!(migrator.pending(&mgr).await?.is_empty());
+
+    assert_eq!(migrator.rollback_to(&mgr, 1).await?, vec![2]);
+    assert_eq!(columns(&mgr, "accounts").await, ["id", "email"]);
+    assert_eq!(migrator.migrate(&mgr).await?, vec![2]);
+    assert_eq!(migrator.rollback_to(&mgr, 0).await?, vec![2, 1]);
+    assert!(columns(&mgr, "accounts").await.is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn failing_migration_is_rolled_back() -> RepoResult<()> {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let mgr = manager(&dir);
+    let mut migrations = MIGRATIONS.to_vec();
+    migrations.push(Migration {
+        version: 3,
+        name: "broken",
+        up: "CREATE TABLE audit (id INTEGER); INSERT INTO missing VALUES (1);",
+        down: None,
+    });
+    let migrator = Migrator::new(&migrations)?;
+
+    let err = migrator.migrate(&mgr).await.unwrap_err();
+    let RepoError::Backend { source } = err else {
+        panic!("unexpected error {err:?}");
+    };
+    assert!(matches!(
+        source.downcast_ref::<MigrationError>(),
+        Some(MigrationError::Failed { version: 3, .. })
+    ));
+    assert!(columns(&mgr, "audit").await.is_empty());
+    assert_eq!(columns(&mgr, "accounts").await, ["id", "email", "nickname"]);
+    let pending = migrator.pending(&mgr).await?;
+    assert_eq!(pending.iter().map(|m| m.version).collect::<Vec<_>>(), [3]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn table_rebuilds_keep_rows_and_check_foreign_keys() -> RepoResult<()> {
+    use storeit_core::schema::{ColumnDef, SchemaChange, SqlType, TableDef};
+    use storeit_sql_builder::{migration_script, Dialect};
+
+    let dir = tempfile::tempdir().expect("temp dir");
+    let mgr = manager(&dir);
+    let teams = TableDef {
+        name: "teams".into(),
+        columns: vec![
+            ColumnDef::new("id", SqlType::BigInt),
+            ColumnDef::new("name", SqlType::Text),
+            ColumnDef::new("rank", SqlType::BigInt),
+        ],
+        primary_key: vec!["id".into()],
+        key_generated: false,
+    };
+    // A SQLite rebuild: `name` becomes NOT NULL and `rank` is a new NOT NULL column.
+    let rebuild = migration_script(
+        Dialect::Sqlite,
+        &teams,
+        &[
+            SchemaChange::SetNullable {
+                column: "name".into(),
+                nullable: false,
+            },
+            SchemaChange::AddColumn("rank".into()),
+        ],
+    );
+    let migrations = [
+        Migration {
+            version: 1,
+            name: "create_teams",
+            up: "CREATE TABLE teams (id INTEGER PRIMARY KEY, name TEXT); \
+                 CREATE TABLE players (id INTEGER PRIMARY KEY, \
+                   team_id INTEGER NOT NULL REFERENCES teams (id) ON DELETE CASCADE); \
+                 INSERT INTO teams (id, name) VALUES (1, 'a'); \
+                 INSERT INTO players (id, team_id) VALUES (1, 1);",
+            down: None,
+        },
+        Migration {
+            version: 2,
+            name: "rebuild_teams",
+            up: Box::leak(rebuild.into_boxed_str()),
+            down: None,
+        },
+        Migration {
+            version: 3,
+            name: "orphan_players",
+            up: "DELETE FROM teams;",
+            down: None,
+        },
+    ];
+    let migrator = Migrator::new(&migrations)?;
+    let err = migrator.migrate(&mgr).await.unwrap_err();
+    let RepoError::Backend { source } = err else {
+        panic!("unexpected error {err:?}");
+    };
+    let Some(MigrationError::Failed {
+        version, source, ..
+    }) = source.downcast_ref()
+    else {
+        panic!("unexpected error {source:?}");
+    };
+    assert_eq!(*version, 3);
+    assert!(matches!(source, RepoError::ForeignKeyViolation { .. }));
+
+    assert_eq!(columns(&mgr, "teams").await, ["id", "name", "rank"]);
+    let conn = mgr.database().connect().unwrap();
+    let mut rows = conn
+        .query(
+            "SELECT name, rank, (SELECT COUNT(*) FROM players) FROM teams",
+            (),
+        )
+        .await
+        .unwrap();
+    let row = rows.next().await.unwrap().expect("team kept");
+    assert_eq!(row.get::<String>(0).unwrap(), "a");
+    assert_eq!(row.get::<i64>(1).unwrap(), 0);
+    assert_eq!(row.get::<i64>(2).unwrap(), 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn edited_migration_is_refused() -> RepoResult<()> {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let mgr = manager(&dir);
+    Migrator::new(MIGRATIONS)?.migrate(&mgr).await?;
+
+    let mut edited = MIGRATIONS.to_vec();
+    edited[0].up = "CREATE TABLE accounts (id INTEGER PRIMARY KEY);";
+    let err = Migrator::new(&edited)?.migrate(&mgr).await.unwrap_err();
+    assert_eq!(
+        std::error::Error::source(&err).unwrap().to_string(),
+        "migration 1_create_accounts was changed after it was applied"
+    );
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn concurrent_runs_apply_each_migration_once() -> RepoResult<()> {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let mgr = Arc::new(manager(&dir));
+    let migrator = Arc::new(Migrator::new(MIGRATIONS)?);
+    let runs: Vec<_> = (0..4)
+        .map(|_| {
+            let (mgr, migrator) = (mgr.clone(), migrator.clone());
+            tokio::spawn(async move { migrator.migrate(&*mgr).await })
+        })
+        .collect();
+    let mut applied = Vec::new();
+    for run in runs {
+        applied.extend(run.await.expect("join")?);
+    }
+    applied.sort_unstable();
+    assert_eq!(applied, vec![1, 2]);
+    Ok(())
+}
@@ -7,18 +7,23 @@
 #[cfg(feature = "mysql-async")]
 mod backend {
     use async_trait::async_trait;
+    use mysql_async::consts::ColumnType;
     use mysql_async::{prelude::*, Conn, Params, Pool, Row, Value};
     use std::collections::HashMap;
     use std::marker::PhantomData;
     use std::sync::Arc;
     use std::sync::Mutex as StdMutex;
+    use std::sync::OnceLock;
+    use storeit_core::schema::{self, LiveColumn, LiveIndex, SchemaChange, SchemaReport};
     use storeit_core::transactions::{
         Isolation, Propagation, TransactionContext, TransactionDefinition, TransactionManager,
     };
     use storeit_core::{
-        Fetchable, Identifiable, Insertable, ParamValue, RepoError, RepoResult, Repository,
-        RowAdapter, Updatable,
+        Criteria, Cursor, CursorPage, EntityListener, Fetchable, Identifiable, Insertable,
+        KeyValues, Listenable, Listeners, Order, Page, Pageable, ParamValue, Projection, RepoError,
+        RepoResult, Repository, RowAdapter, Schema, ToParamValue, Updatable,
     };
+    use storeit_sql_builder::Dialect;
     use tokio::sync::Mutex;
 
     // Task-local storage for a transaction-bound connection and savepoint depth.
@@ -27,6 +32,92 @@ mod backend {
         static MY_SP_DEPTH: std::cell::RefCell<usize>;
     }
 
+    /// Translate a mysql_async error into a structured [`RepoError`] using the server error number.
+    ///
+    /// I/O failures and closed connections map to `ConnectionLost`; anything unrecognized
+    /// stays an opaque `RepoError::Backend`.
+    pub fn map_mysql_error(e: mysql_async::Error) -> RepoError {
+        match &e {
+            mysql_async::Error::Server(server) => {
+                let (code, message) = (server.code, server.message.clone());
+                classify_server_error(code, &message, Box::new(e))
+            }
+            mysql_async::Error::Io(_)
+            | mysql_async::Error::Driver(mysql_async::DriverError::ConnectionClosed) => {
+                RepoError::ConnectionLost {
+                    source: Box::new(e),
+                }
+            }
+            _ => RepoError::backend(e),
+        }
+    }
+
+    /// Return the text between `prefix` and the next `terminator` in `message`.
+    fn quoted_after(message: &str, prefix: &str, terminator: char) -> Option<String> {
+        let rest = &message[message.find(prefix)? + prefix.len()..];
+        rest.find(terminator).map(|end| rest[..end].to_string())
+    }
+
+    fn classify_server_error(
+        code: u16,
+        message: &str,
+        source: Box<dyn std::error::Error + Send + Sync>,
+    ) -> RepoError {
+        match code {
+            // ER_DUP_ENTRY, ER_DUP_ENTRY_WITH_KEY_NAME: "Duplicate entry 'x' for key 'users.email'"
+            1062 | 1586 => RepoError::UniqueViolation {
+                constraint: quoted_after(message, "for key '", '\''),
+                source,
+            },
+            // ER_ROW_IS_REFERENCED(_2), ER_NO_REFERENCED_ROW(_2): "... CONSTRAINT `fk_name` FOREIGN KEY ..."
+            1451 | 1452 | 1216 | 1217 => RepoError::ForeignKeyViolation {
+                constraint: quoted_after(message, "CONSTRAINT `", '`'),
+                source,
+            },
+            // ER_BAD_NULL_ERROR: "Column 'email' cannot be null"
+            1048 => RepoError::NotNullViolation {
+                column: quoted_after(message, "Column '", '\''),
+                source,
+            },
+            // ER_NO_DEFAULT_FOR_FIELD: "Field 'email' doesn't have a default value"
+            1364 => RepoError::NotNullViolation {
+                column: quoted_after(message, "Field '", '\''),
+                source,
+            },
+            // ER_LOCK_DEADLOCK
+            1213 => RepoError::Deadlock { source },
+            // ER_LOCK_WAIT_TIMEOUT, ER_QUERY_TIMEOUT (max_execution_time)
+            1205 | 3024 => RepoError::Timeout { source },
+            // ER_CANT_EXECUTE_IN_READ_ONLY_TRANSACTION, ER_OPTION_PREVENTS_STATEMENT (--read-only)
+            1792 | 1290 => RepoError::ReadOnlyViolation { source },
+            _ => RepoError::Backend { source },
+        }
+    }
+
+    // Bind parameters for a (possibly composite) key, in `ID_COLUMNS` order.
+    fn key_params<K: KeyValues>(key: &K) -> Vec<Value> {
+        key.key_values().into_iter().map(to_mysql_value).collect()
+    }
+
+    // `params` followed by the bind value of the tenant condition ending every statement of a
+    // tenant-scoped entity.
+    fn scoped_values<T: Fetchable>(params: Vec<ParamValue>) -> RepoResult<Vec<Value>> {
+        Ok(params
+            .into_iter()
+            .chain(storeit_core::tenant::filter_values::<T>()?)
+            .map(to_mysql_value)
+            .collect())
+    }
+
+    // `key_params` followed by the tenant bind value, for the by-id statements.
+    fn scoped_key_params<T>(key: &T::Key) -> RepoResult<Vec<Value>>
+    where
+        T: Fetchable + Identifiable,
+        T::Key: KeyValues,
+    {
+        scoped_values::<T>(key.key_values())
+    }
+
     // Helper to convert ParamValue to mysql_async::Value.
     fn to_mysql_value(p: ParamValue) -> Value {
         match p {
@@ -35,14 +126,137 @@ mod backend {
             ParamValue::I64(i) => Value::from(i),
             ParamValue::F64(f) => Value::from(f),
             ParamValue::Bool(b) => Value::from(b),
+            ParamValue::Bytes(b) => Value::Bytes(b),
+            // MySQL has no UUID type; bind the hyphenated form produced by UUID().
+            ParamValue::Uuid(u) => Value::from(storeit_core::value::format_uuid(&u)),
+            ParamValue::Timestamp(t) => {
+                let (days, time) = storeit_core::value::split_timestamp(t);
+                let (y, mo, d) = storeit_core::value::civil_from_days(days);
+                let (h, mi, s, us) = storeit_core::value::time_parts(time);
+                Value::Date(y as u16, mo as u8, d as u8, h as u8, mi as u8, s as u8, us)
+            }
+            ParamValue::Date(days) => {
+                let (y, mo, d) = storeit_core::value::civil_from_days(i64::from(days));
+                Value::Date(y as u16, mo as u8, d as u8, 0, 0, 0, 0)
+            }
+            ParamValue::Time(t) => {
+                let (h, mi, s, us) = storeit_core::value::time_parts(t);
+                Value::Time(false, h / 24, (h % 24) as u8, mi as u8, s as u8, us)
+            }
+            // DECIMAL and JSON travel as text on the MySQL wire protocol.
+            ParamValue::Decimal(d) => Value::Bytes(d.into_bytes()),
+            ParamValue::Json(j) => Value::Bytes(j.into_bytes()),
             ParamValue::Null => Value::NULL,
         }
     }
 
+    /// Read column `idx` of `row` as a [`ParamValue`], using the column metadata to tell
+    /// text from binary data and to parse text-protocol values. Backs `FromColumn`-based
+    /// entity mapping.
+    pub fn column_value(row: &Row, idx: usize) -> RepoResult<ParamValue> {
+        let (value, column) = row
+            .as_ref(idx)
+            .zip(row.columns_ref().get(idx))
+            .ok_or_else(|| {
+                RepoError::mapping(std::io::Error::new(
+                    std::io::ErrorKind::Other,
+                    format!("missing idx {}", idx),
+                ))
+            })?;
+        // Character set 63 is `binary` (BINARY/VARBINARY/BLOB columns).
+        decode_column(value, column.column_type(), column.character_set() == 63)
+    }
+
+    fn decode_column(value: &Value, ty: ColumnType, binary: bool) -> RepoResult<ParamValue> {
+        use storeit_core::value as v;
+        let text = || -> RepoResult<String> {
+            match value {
+                Value::Bytes(b) => String::from_utf8(b.clone()).map_err(RepoError::mapping),
+                other => Ok(other.as_sql(true).trim_matches('\'').to_string()),
+            }
+        };
+        let parsed = |kind: &str, parsed: Option<ParamValue>| {
+            parsed.ok_or_else(|| {
+                RepoError::mapping(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!("invalid {} value {:?}", kind, value),
+                ))
+            })
+        };
+        if let Value::NULL = value {
+            return Ok(ParamValue::Null);
+        }
+        Ok(match ty {
+            ColumnType::MYSQL_TYPE_TINY
+            | ColumnType::MYSQL_TYPE_SHORT
+            | ColumnType::MYSQL_TYPE_INT24
+            | ColumnType::MYSQL_TYPE_LONG
+            | ColumnType::MYSQL_TYPE_LONGLONG
+            | ColumnType::MYSQL_TYPE_YEAR => ParamValue::I64(
+                mysql_async::from_value_opt(value.clone()).map_err(RepoError::mapping)?,
+            ),
+            ColumnType::MYSQL_TYPE_FLOAT | ColumnType::MYSQL_TYPE_DOUBLE => ParamValue::F64(
+                mysql_async::from_value_opt(value.clone()).map_err(RepoError::mapping)?,
+            ),
+            ColumnType::MYSQL_TYPE_DECIMAL | ColumnType::MYSQL_TYPE_NEWDECIMAL => {
+                ParamValue::Decimal(text()?)
+            }
+            ColumnType::MYSQL_TYPE_JSON => ParamValue::Json(text()?),
+            ColumnType::MYSQL_TYPE_DATE | ColumnType::MYSQL_TYPE_NEWDATE => match value {
+                Value::Date(y, mo, d, ..) => ParamValue::Date(v::days_from_civil(
+                    i64::from(*y),
+                    u32::from(*mo),
+                    u32::from(*d),
+                ) as i32),
+                _ => parsed("DATE", v::parse_date(&text()?).map(ParamValue::Date))?,
+            },
+            ColumnType::MYSQL_TYPE_DATETIME
+            | ColumnType::MYSQL_TYPE_DATETIME2
+            | ColumnType::MYSQL_TYPE_TIMESTAMP
+            | ColumnType::MYSQL_TYPE_TIMESTAMP2 => match value {
+                Value::Date(y, mo, d, h, mi, s, us) => {
+                    let days = v::days_from_civil(i64::from(*y), u32::from(*mo), u32::from(*d));
+                    let secs = (i64::from(*h) * 60 + i64::from(*mi)) * 60 + i64::from(*s);
+                    ParamValue::Timestamp(days * 86_400_000_000 + secs * 1_000_000 + i64::from(*us))
+                }
+                _ => parsed(
+                    "DATETIME",
+                    v::parse_timestamp(&text()?).map(ParamValue::Timestamp),
+                )?,
+            },
+            ColumnType::MYSQL_TYPE_TIME | ColumnType::MYSQL_TYPE_TIME2 => match value {
+                Value::Time(neg, days, h, mi, s, us) => {
+                    let hours = i64::from(*days) * 24 + i64::from(*h);
+                    let micros = ((hours * 60 + i64::from(*mi)) * 60 + i64::from(*s)) * 1_000_000
+                        + i64::from(*us);
+                    ParamValue::Time(if *neg { -micros } else { micros })
+                }
+                _ => parsed("TIME", v::parse_time(&text()?).map(ParamValue::Time))?,
+            },
+            _ => match value {
+                Value::Bytes(b) if binary => ParamValue::Bytes(b.clone()),
+                Value::Bytes(b) => match String::from_utf8(b.clone()) {
+                    Ok(s) => ParamValue::String(s),
+                    Err(e) => ParamValue::Bytes(e.into_bytes()),
+                },
+                Value::Int(i) => ParamValue::I64(*i),
+                Value::Double(f) => ParamValue::F64(*f),
+                Value::Float(f) => ParamValue::F64(f64::from(*f)),
+                other => ParamValue::String(text().unwrap_or_else(|_| format!("{:?}", other))),
+            },
+        })
+    }
+
     /// A fully asynchronous, `mysql_async`-backed repository.
     struct RepoSql<T> {
+        dialect: Dialect,
+        /// Whether the statements carry the tenant condition (see `statements()`).
+        tenant: bool,
         select_by_id: String,
+        select_by_id_with_deleted: String,
         delete_by_id: String,
+        soft_delete_by_id: Option<String>,
+        restore_by_id: Option<String>,
         insert: String,
         update_by_id: String,
         find_by_field_cache: StdMutex<HashMap<String, String>>,
@@ -53,14 +267,35 @@ mod backend {
     where
         T: Fetchable + Identifiable + Insertable + Updatable,
     {
-        fn new() -> Self {
-            let select_by_id = storeit_sql_builder::select_by_id::<T>(T::ID_COLUMN);
-            let delete_by_id = storeit_sql_builder::delete_by_id::<T>(T::ID_COLUMN);
-            let insert = storeit_sql_builder::insert::<T>(T::ID_COLUMN);
-            let update_by_id = storeit_sql_builder::update_by_id::<T>(T::ID_COLUMN);
+        fn new(tenant: bool) -> Self {
+            let dialect = Dialect::MySql;
+            let select_by_id =
+                storeit_sql_builder::select_by_id::<T>(dialect, tenant, T::ID_COLUMNS);
+            let delete_by_id =
+                storeit_sql_builder::delete_by_id::<T>(dialect, tenant, T::ID_COLUMNS);
+            let insert = storeit_sql_builder::insert::<T>(dialect, T::ID_COLUMN);
+            let update_by_id =
+                storeit_sql_builder::update_by_id::<T>(dialect, tenant, T::ID_COLUMNS);
             Self {
+                dialect,
+                tenant,
                 select_by_id,
+                select_by_id_with_deleted: storeit_sql_builder::select_by_id_with_deleted::<T>(
+                    dialect,
+                    tenant,
+                    T::ID_COLUMNS,
+                ),
                 delete_by_id,
+                soft_delete_by_id: storeit_sql_builder::soft_delete_by_id::<T>(
+                    dialect,
+                    tenant,
+                    T::ID_COLUMNS,
+                ),
+                restore_by_id: storeit_sql_builder::restore_by_id::<T>(
+                    dialect,
+                    tenant,
+                    T::ID_COLUMNS,
+                ),
                 insert,
                 update_by_id,
                 find_by_field_cache: StdMutex::new(HashMap::new()),
@@ -76,7 +311,7 @@ mod backend {
             if let Some(s) = guard.get(field) {
                 return s.clone();
             }
-            let built = storeit_sql_builder::select_by_field::<T>(field);
+            let built = storeit_sql_builder::select_by_field::<T>(self.dialect, self.tenant, field);
             guard.insert(field.to_string(), built.clone());
             built
         }
@@ -90,25 +325,46 @@ mod backend {
         pool: Pool,
         adapter: A,
         sql: RepoSql<T>,
+        /// `sql` without the tenant condition, for `tenant::bypass`; built on first use.
+        sql_bypass: OnceLock<RepoSql<T>>,
+        listeners: Listeners<T>,
         _phantom: PhantomData<T>,
     }
 
+    impl<T, A> MysqlAsyncRepository<T, A>
+    where
+        T: Fetchable + Identifiable + Insertable + Updatable + 'static,
+        A: RowAdapter<T> + Send + Sync + 'static,
+    {
+        // The cached statements for the current tenant context. Both sets are built with an
+        // explicit tenant flag, so where the repository was constructed does not matter.
+        fn statements(&self) -> &RepoSql<T> {
+            if T::TENANT_COLUMN.is_some() && storeit_core::tenant::is_bypassed() {
+                self.sql_bypass.get_or_init(|| RepoSql::new(false))
+            } else {
+                &self.sql
+            }
+        }
+    }
+
     impl<T, A> MysqlAsyncRepository<T, A>
     where
         A: RowAdapter<T, Row = Row> + Send + Sync + 'static,
         T: Fetchable + Identifiable + Send + Sync + 'static,
-        T::Key: Clone + Into<Value> + Send + Sync,
+        T::Key: Clone + KeyValues + Send + Sync,
     {
         /// Creates a new repository from a mysql_async Pool.
         pub fn new(pool: Pool, adapter: A) -> Self
         where
             T: Insertable + Updatable,
         {
-            let sql = RepoSql::<T>::new();
+            let sql = RepoSql::<T>::new(true);
             Self {
                 pool,
                 adapter,
                 sql,
+                sql_bypass: OnceLock::new(),
+                listeners: Listeners::default(),
                 _phantom: PhantomData,
             }
         }
@@ -127,7 +383,7 @@ mod backend {
         }
 
         async fn get_conn(&self) -> RepoResult<Conn> {
-            self.pool.get_conn().await.map_err(RepoError::backend)
+            self.pool.get_conn().await.map_err(map_mysql_error)
         }
 
         // Note on transactions and repository reuse:
@@ -156,100 +412,344 @@ mod backend {
         }
     }
 
+    // Lifecycle listeners (see `storeit_core::listener`).
+    impl<T, A> MysqlAsyncRepository<T, A>
+    where
+        A: RowAdapter<T, Row = Row> + Send + Sync + 'static,
+        T: Fetchable
+            + Identifiable
+            + Insertable
+            + Updatable
+            + Listenable
+            + Send
+            + Sync
+            + Clone
+            + 'static,
+        T::Key: Clone + KeyValues + Send + Sync + 'static,
+    {
+        /// Register a listener that runs after the entity's own and earlier registrations.
+        pub fn with_listener(mut self, listener: impl EntityListener<T> + 'static) -> Self {
+            self.listeners.push(Arc::new(listener));
+            self
+        }
+
+        // Map a row to an entity and run the `post_load` hooks on it.
+        fn load(&self, row: &Row) -> RepoResult<T> {
+            self.listeners.loaded(self.adapter.from_row(row)?)
+        }
+    }
+
+    // Schema validation (see `storeit_core::schema`).
+    impl<T, A> MysqlAsyncRepository<T, A>
+    where
+        A: RowAdapter<T, Row = Row> + Send + Sync + 'static,
+        T: Schema + Insertable + Updatable + Listenable + Send + Sync + Clone + 'static,
+        T::Key: Clone + KeyValues + Send + Sync + 'static,
+    {
+        /// Compare `T` with its live table (`information_schema`); see `storeit_core::schema`.
+        pub async fn validate_schema(&self) -> RepoResult<SchemaReport> {
+            if let Ok(Some(arc)) = MY_TX_CONN.try_with(|c| c.borrow().as_ref().cloned()) {
+                let mut conn = arc.lock().await;
+                let live = live_columns(&mut conn, T::TABLE).await?;
+                return Ok(schema::check(&T::table_def(), &live));
+            }
+            let mut conn = self.get_conn().await?;
+            let live = live_columns(&mut conn, T::TABLE).await?;
+            Ok(schema::check(&T::table_def(), &live))
+        }
+    }
+
+    // Explicit access to soft-deleted rows (see `Fetchable::SOFT_DELETE_COLUMN`).
+    impl<T, A> MysqlAsyncRepository<T, A>
+    where
+        A: RowAdapter<T, Row = Row> + Send + Sync + 'static,
+        T: Fetchable
+            + Identifiable
+            + Insertable
+            + Updatable
+            + Listenable
+            + Send
+            + Sync
+            + Clone
+            + 'static,
+        T::Key: Clone + KeyValues + Send + Sync + 'static,
+    {
+        /// Find an entity by id whether or not it is soft-deleted.
+        pub async fn find_with_deleted(&self, id: &T::Key) -> RepoResult<Option<T>> {
+            let sql = self.statements().select_by_id_with_deleted.clone();
+            let id_vals = scoped_key_params::<T>(id)?;
+            let row_opt: Option<Row> =
+                if let Ok(Some(arc)) = MY_TX_CONN.try_with(|c| c.borrow().as_ref().cloned()) {
+                    let mut conn = arc.lock().await;
+                    conn.exec_first(sql, Params::Positional(id_vals))
+                        .await
+                        .map_err(map_mysql_error)?
+                } else {
+                    let mut conn = self.get_conn().await?;
+                    conn.exec_first(sql, Params::Positional(id_vals))
+                        .await
+                        .map_err(map_mysql_error)?
+                };
+            row_opt.map(|row| self.load(&row)).transpose()
+        }
+
+        /// Clear the soft-delete column of a deleted row. Returns whether a row was restored.
+        /// Fails for entities without a soft-delete column.
+        pub async fn restore_by_id(&self, id: &T::Key) -> RepoResult<bool> {
+            let sql = self.statements().restore_by_id.clone().ok_or_else(|| {
+                RepoError::backend(std::io::Error::new(
+                    std::io::ErrorKind::InvalidInput,
+                    format!("{} has no soft-delete column", T::TABLE),
+                ))
+            })?;
+            self.exec_affected(sql, scoped_key_params::<T>(id)?)
+                .await
+                .map(|n| n > 0)
+        }
+
+        /// Physically delete a row, soft-deleted or not. Returns whether a row was removed.
+        pub async fn purge_by_id(&self, id: &T::Key) -> RepoResult<bool> {
+            self.listeners.pre_delete(id)?;
+            self.exec_affected(
+                self.statements().delete_by_id.clone(),
+                scoped_key_params::<T>(id)?,
+            )
+            .await
+            .map(|n| n > 0)
+        }
+
+        async fn exec_affected(&self, sql: String, vals: Vec<Value>) -> RepoResult<u64> {
+            if let Ok(Some(arc)) = MY_TX_CONN.try_with(|c| c.borrow().as_ref().cloned()) {
+                let mut conn = arc.lock().await;
+                conn.exec_drop(sql, Params::Positional(vals))
+                    .await
+                    .map_err(map_mysql_error)?;
+                Ok(conn.affected_rows())
+            } else {
+                let mut conn = self.get_conn().await?;
+                conn.exec_drop(sql, Params::Positional(vals))
+                    .await
+                    .map_err(map_mysql_error)?;
+                Ok(conn.affected_rows())
+            }
+        }
+    }
+
+    // Projections (see `storeit_core::Projection`): only the projected columns are selected.
+    impl<T, A> MysqlAsyncRepository<T, A>
+    where
+        T: Fetchable
+            + Identifiable
+            + Insertable
+            + Updatable
+            + Listenable
+            + Send
+            + Sync
+            + Clone
+            + 'static,
+        A: RowAdapter<T, Row = Row> + Send + Sync + 'static,
+        T::Key: Clone + Send + Sync + 'static + KeyValues,
+    {
+        /// Find the projection `P` of the entity with the given id.
+        pub async fn find_by_id_as<P>(&self, id: &T::Key) -> RepoResult<Option<P>>
+        where
+            P: Projection<Entity = T>,
+            P::Adapter<Row>: RowAdapter<P, Row = Row>,
+        {
+            let sql = storeit_sql_builder::select_columns_by_id::<T>(
+                self.statements().dialect,
+                self.statements().tenant,
+                P::COLUMNS,
+                T::ID_COLUMNS,
+            );
+            let id_vals = scoped_key_params::<T>(id)?;
+            let row_opt: Option<Row> =
+                if let Ok(Some(arc)) = MY_TX_CONN.try_with(|c| c.borrow().as_ref().cloned()) {
+                    let mut conn = arc.lock().await;
+                    conn.exec_first(sql, Params::Positional(id_vals))
+                        .await
+                        .map_err(map_mysql_error)?
+                } else {
+                    let mut conn = self.get_conn().await?;
+                    conn.exec_first(sql, Params::Positional(id_vals))
+                        .await
+                        .map_err(map_mysql_error)?
+                };
+            row_opt
+                .map(|row| P::adapter::<Row>().from_row(&row))
+                .transpose()
+        }
+
+        /// Find the projections `P` of the entities whose `field_name` column equals `value`.
+        pub async fn find_by_field_as<P>(
+            &self,
+            field_name: &str,
+            value: ParamValue,
+        ) -> RepoResult<Vec<P>>
+        where
+            P: Projection<Entity = T>,
+            P::Adapter<Row>: RowAdapter<P, Row = Row>,
+        {
+            let sql = storeit_sql_builder::select_columns_by_field::<T>(
+                self.statements().dialect,
+                self.statements().tenant,
+                P::COLUMNS,
+                field_name,
+            );
+            let params = Params::Positional(scoped_values::<T>(vec![value])?);
+            let rows: Vec<Row> =
+                if let Ok(Some(arc)) = MY_TX_CONN.try_with(|c| c.borrow().as_ref().cloned()) {
+                    let mut conn = arc.lock().await;
+                    conn.exec(sql, params).await.map_err(map_mysql_error)?
+                } else {
+                    let mut conn = self.get_conn().await?;
+                    conn.exec(sql, params).await.map_err(map_mysql_error)?
+                };
+
+            let adapter = P::adapter::<Row>();
+            rows.iter().map(|row| adapter.from_row(row)).collect()
+        }
+    }
+
     #[async_trait]
     impl<T, A> Repository<T> for MysqlAsyncRepository<T, A>
     where
         A: RowAdapter<T, Row = Row> + Send + Sync + 'static,
-        T: Fetchable + Identifiable + Insertable + Updatable + Send + Sync + Clone + 'static,
-        T::Key: Clone
-            + Into<Value>
+        T: Fetchable
+            + Identifiable
+            + Insertable
+            + Updatable
+            + Listenable
             + Send
             + Sync
-            + 'static
-            + Default
-            + PartialEq
-            + serde::Serialize
-            + serde::de::DeserializeOwned,
+            + Clone
+            + 'static,
+        T::Key: Clone + KeyValues + Send + Sync + 'static,
     {
         async fn find_by_id(&self, id: &T::Key) -> RepoResult<Option<T>> {
-            let id_val: Value = id.clone().into();
+            let id_vals = scoped_key_params::<T>(id)?;
             let row_opt: Option<Row> =
                 if let Ok(Some(arc)) = MY_TX_CONN.try_with(|c| c.borrow().as_ref().cloned()) {
                     let mut conn = arc.lock().await;
                     conn.exec_first(
-                        self.sql.select_by_id.clone(),
-                        Params::Positional(vec![id_val]),
+                        self.statements().select_by_id.clone(),
+                        Params::Positional(id_vals),
                     )
                     .await
-                    .map_err(RepoError::backend)?
+                    .map_err(map_mysql_error)?
                 } else {
                     let mut conn = self.get_conn().await?;
                     conn.exec_first(
-                        self.sql.select_by_id.clone(),
-                        Params::Positional(vec![id_val]),
+                        self.statements().select_by_id.clone(),
+                        Params::Positional(id_vals),
                     )
                     .await
-                    .map_err(RepoError::backend)?
+                    .map_err(map_mysql_error)?
                 };
 
             let entity_opt = match row_opt {
-                Some(ref row) => Some(self.adapter.from_row(row)?),
+                Some(ref row) => Some(self.load(row)?),
                 None => None,
             };
             Ok(entity_opt)
         }
 
         async fn find_by_field(&self, field_name: &str, value: ParamValue) -> RepoResult<Vec<T>> {
-            let sql = self.sql.get_select_by_field(field_name);
-            let value_param = to_mysql_value(value);
+            let sql = self.statements().get_select_by_field(field_name);
+            let params = Params::Positional(scoped_values::<T>(vec![value])?);
             let rows: Vec<Row> =
                 if let Ok(Some(arc)) = MY_TX_CONN.try_with(|c| c.borrow().as_ref().cloned()) {
                     let mut conn = arc.lock().await;
-                    conn.exec(sql, Params::Positional(vec![value_param]))
-                        .await
-                        .map_err(RepoError::backend)?
+                    conn.exec(sql, params).await.map_err(map_mysql_error)?
                 } else {
                     let mut conn = self.get_conn().await?;
-                    conn.exec(sql, Params::Positional(vec![value_param]))
-                        .await
-                        .map_err(RepoError::backend)?
+                    conn.exec(sql, params).await.map_err(map_mysql_error)?
                 };
 
             rows.iter()
-                .map(|row| self.adapter.from_row(row))
+                .map(|row| self.load(row))
                 .collect::<RepoResult<Vec<T>>>()
         }
 
-        async fn insert(&self, entity: &T) -> RepoResult<T> {
-            let params = Params::Positional(
-                entity
-                    .insert_values()
-                    .into_iter()
-                    .map(to_mysql_value)
-                    .collect(),
+        async fn find_by_field_in(
+            &self,
+            field_name: &str,
+            values: Vec<ParamValue>,
+        ) -> RepoResult<Vec<T>> {
+            if values.is_empty() {
+                return Ok(Vec::new());
+            }
+            let sql = storeit_sql_builder::select_by_in::<T>(
+                self.statements().dialect,
+                self.statements().tenant,
+                field_name,
+                values.len(),
             );
+            let params = Params::Positional(scoped_values::<T>(values)?);
+            let rows: Vec<Row> =
+                if let Ok(Some(arc)) = MY_TX_CONN.try_with(|c| c.borrow().as_ref().cloned()) {
+                    let mut conn = arc.lock().await;
+                    conn.exec(sql, params).await.map_err(map_mysql_error)?
+                } else {
+                    let mut conn = self.get_conn().await?;
+                    conn.exec(sql, params).await.map_err(map_mysql_error)?
+                };
+
+            rows.iter()
+                .map(|row| self.load(row))
+                .collect::<RepoResult<Vec<T>>>()
+        }
+
+        async fn insert(&self, entity: &T) -> RepoResult<T> {
+            // Listeners may rewrite the entity before it is bound.
+            let mut entity = entity.clone();
+            self.listeners.pre_insert(&mut entity)?;
+            let entity = &entity;
+            let mut values = entity.insert_values();
+            storeit_core::tenant::stamp_insert::<T>(&mut values)?;
+            let params = Params::Positional(values.into_iter().map(to_mysql_value).collect());
+
+            // Caller-supplied keys are inserted and used for the read-back; otherwise the
+            // database generates the key.
+            let supplied_key = if T::ID_GENERATED {
+                None
+            } else {
+                Some(key_params(&entity.id().ok_or_else(|| {
+                    RepoError::backend(std::io::Error::new(
+                        std::io::ErrorKind::InvalidInput,
+                        "client-assigned key must be set before insert",
+                    ))
+                })?))
+            };
+            let tenant = scoped_values::<T>(Vec::new())?;
 
             // Execute the INSERT and capture the last_insert_id. Then fetch the row back
             // using the raw id value directly to avoid fragile generic conversions.
             if let Ok(Some(arc)) = MY_TX_CONN.try_with(|c| c.borrow().as_ref().cloned()) {
                 let mut conn = arc.lock().await;
                 let mut result = conn
-                    .exec_iter(self.sql.insert.clone(), params)
+                    .exec_iter(self.statements().insert.clone(), params)
                     .await
-                    .map_err(RepoError::backend)?;
+                    .map_err(map_mysql_error)?;
                 let new_id = result.last_insert_id().unwrap_or(0);
-                result.map(|_| ()).await.map_err(RepoError::backend)?;
+                result.map(|_| ()).await.map_err(map_mysql_error)?;
 
                 // Fetch back
                 let row: Option<Row> = conn
                     .exec_first(
-                        self.sql.select_by_id.clone(),
-                        Params::Positional(vec![Value::from(new_id)]),
+                        self.statements().select_by_id.clone(),
+                        Params::Positional(
+                            supplied_key
+                                .unwrap_or_else(|| vec![Value::from(new_id)])
+                                .into_iter()
+                                .chain(tenant)
+                                .collect::<Vec<_>>(),
+                        ),
                     )
                     .await
-                    .map_err(RepoError::backend)?;
+                    .map_err(map_mysql_error)?;
                 if let Some(row) = row {
-                    Ok(self.adapter.from_row(&row)?)
+                    self.listeners.inserted(self.adapter.from_row(&row)?)
                 } else {
                     Err(RepoError::backend(std::io::Error::new(
                         std::io::ErrorKind::Other,
@@ -259,22 +759,28 @@ mod backend {
             } else {
                 let mut conn = self.get_conn().await?;
                 let mut result = conn
-                    .exec_iter(self.sql.insert.clone(), params)
+                    .exec_iter(self.statements().insert.clone(), params)
                     .await
-                    .map_err(RepoError::backend)?;
+                    .map_err(map_mysql_error)?;
                 let new_id = result.last_insert_id().unwrap_or(0);
-                result.map(|_| ()).await.map_err(RepoError::backend)?;
+                result.map(|_| ()).await.map_err(map_mysql_error)?;
 
                 // Fetch back
                 let row: Option<Row> = conn
                     .exec_first(
-                        self.sql.select_by_id.clone(),
-                        Params::Positional(vec![Value::from(new_id)]),
+                        self.statements().select_by_id.clone(),
+                        Params::Positional(
+                            supplied_key
+                                .unwrap_or_else(|| vec![Value::from(new_id)])
+                                .into_iter()
+                                .chain(tenant)
+                                .collect::<Vec<_>>(),
+                        ),
                     )
                     .await
-                    .map_err(RepoError::backend)?;
+                    .map_err(map_mysql_error)?;
                 if let Some(row) = row {
-                    Ok(self.adapter.from_row(&row)?)
+                    self.listeners.inserted(self.adapter.from_row(&row)?)
                 } else {
                     Err(RepoError::backend(std::io::Error::new(
                         std::io::ErrorKind::Other,
@@ -285,54 +791,318 @@ mod backend {
         }
 
         async fn update(&self, entity: &T) -> RepoResult<T> {
-            let params = Params::Positional(
-                entity
-                    .update_values()
-                    .into_iter()
-                    .map(to_mysql_value)
-                    .collect(),
-            );
-            if let Ok(Some(arc)) = MY_TX_CONN.try_with(|c| c.borrow().as_ref().cloned()) {
-                let mut conn = arc.lock().await;
-                conn.exec_drop(self.sql.update_by_id.clone(), params)
-                    .await
-                    .map_err(RepoError::backend)?;
-            } else {
-                let mut conn = self.get_conn().await?;
-                conn.exec_drop(self.sql.update_by_id.clone(), params)
-                    .await
-                    .map_err(RepoError::backend)?;
-            }
+            let mut entity = entity.clone();
+            self.listeners.pre_update(&mut entity)?;
+            let entity = &entity;
+            let now = storeit_core::clock::now();
+            let params = Params::Positional(scoped_values::<T>(storeit_core::clock::with_now(
+                now,
+                || entity.update_values(),
+            ))?);
+            let affected =
+                if let Ok(Some(arc)) = MY_TX_CONN.try_with(|c| c.borrow().as_ref().cloned()) {
+                    let mut conn = arc.lock().await;
+                    conn.exec_drop(self.statements().update_by_id.clone(), params)
+                        .await
+                        .map_err(map_mysql_error)?;
+                    conn.affected_rows()
+                } else {
+                    let mut conn = self.get_conn().await?;
+                    conn.exec_drop(self.statements().update_by_id.clone(), params)
+                        .await
+                        .map_err(map_mysql_error)?;
+                    conn.affected_rows()
+                };
 
-            Ok(entity.clone())
+            // MySQL reports changed rows, but the version bump always changes a matched row.
+            if T::VERSION_COLUMN.is_some() && affected == 0 {
+                return Err(RepoError::OptimisticLock { table: T::TABLE });
+            }
+            // For a tenant-scoped entity the row may belong to another tenant (or be gone). Rewriting
+            // a row with its current values also reports 0, so look it up before failing.
+            if T::TENANT_COLUMN.is_some() && affected == 0 {
+                let key = entity.id().ok_or(RepoError::NotFound)?;
+                let sql = self.statements().select_by_id.clone();
+                let params = Params::Positional(scoped_key_params::<T>(&key)?);
+                let row: Option<Row> =
+                    if let Ok(Some(arc)) = MY_TX_CONN.try_with(|c| c.borrow().as_ref().cloned()) {
+                        let mut conn = arc.lock().await;
+                        conn.exec_first(sql, params)
+                            .await
+                            .map_err(map_mysql_error)?
+                    } else {
+                        let mut conn = self.get_conn().await?;
+                        conn.exec_first(sql, params)
+                            .await
+                            .map_err(map_mysql_error)?
+                    };
+                if row.is_none() {
+                    return Err(RepoError::NotFound);
+                }
+            }
+            let mut updated = entity.clone();
+            updated.bump_version();
+            updated.touch(now);
+            Ok(updated)
         }
 
         async fn delete_by_id(&self, id: &T::Key) -> RepoResult<bool> {
-            let id_val: Value = id.clone().into();
+            self.listeners.pre_delete(id)?;
+            // Soft-deleting entities only stamp their deletion column.
+            let (sql, id_vals) = match &self.statements().soft_delete_by_id {
+                Some(sql) => {
+                    let mut vals =
+                        vec![to_mysql_value(storeit_core::clock::now().to_param_value())];
+                    vals.extend(scoped_key_params::<T>(id)?);
+                    (sql.clone(), vals)
+                }
+                None => (
+                    self.statements().delete_by_id.clone(),
+                    scoped_key_params::<T>(id)?,
+                ),
+            };
             let affected =
                 if let Ok(Some(arc)) = MY_TX_CONN.try_with(|c| c.borrow().as_ref().cloned()) {
                     let mut conn = arc.lock().await;
                     let result = conn
-                        .exec_iter(
-                            self.sql.delete_by_id.clone(),
-                            Params::Positional(vec![id_val]),
-                        )
+                        .exec_iter(sql, Params::Positional(id_vals))
                         .await
-                        .map_err(RepoError::backend)?;
+                        .map_err(map_mysql_error)?;
                     result.affected_rows()
                 } else {
                     let mut conn = self.get_conn().await?;
                     let result = conn
-                        .exec_iter(
-                            self.sql.delete_by_id.clone(),
-                            Params::Positional(vec![id_val]),
-                        )
+                        .exec_iter(sql, Params::Positional(id_vals))
                         .await
-                        .map_err(RepoError::backend)?;
+                        .map_err(map_mysql_error)?;
                     result.affected_rows()
                 };
             Ok(affected > 0)
         }
+
+        async fn find_where(&self, criteria: &Criteria) -> RepoResult<Vec<T>> {
+            let (sql, params) = storeit_sql_builder::select_by_criteria::<T>(
+                self.statements().dialect,
+                self.statements().tenant,
+                criteria,
+            )?;
+            let params = Params::Positional(scoped_values::<T>(params)?);
+            let rows: Vec<Row> =
+                if let Ok(Some(arc)) = MY_TX_CONN.try_with(|c| c.borrow().as_ref().cloned()) {
+                    let mut conn = arc.lock().await;
+                    conn.exec(sql, params).await.map_err(map_mysql_error)?
+                } else {
+                    let mut conn = self.get_conn().await?;
+                    conn.exec(sql, params).await.map_err(map_mysql_error)?
+                };
+
+            rows.iter()
+                .map(|row| self.load(row))
+                .collect::<RepoResult<Vec<T>>>()
+        }
+
+        async fn count_where(&self, criteria: &Criteria) -> RepoResult<u64> {
+            let (sql, params) = storeit_sql_builder::count_by_criteria::<T>(
+                self.statements().dialect,
+                self.statements().tenant,
+                criteria,
+            )?;
+            let params = Params::Positional(scoped_values::<T>(params)?);
+            let count: Option<u64> =
+                if let Ok(Some(arc)) = MY_TX_CONN.try_with(|c| c.borrow().as_ref().cloned()) {
+                    let mut conn = arc.lock().await;
+                    conn.exec_first(sql, params)
+                        .await
+                        .map_err(map_mysql_error)?
+                } else {
+                    let mut conn = self.get_conn().await?;
+                    conn.exec_first(sql, params)
+                        .await
+                        .map_err(map_mysql_error)?
+                };
+            Ok(count.unwrap_or(0))
+        }
+
+        async fn exists_where(&self, criteria: &Criteria) -> RepoResult<bool> {
+            let (sql, params) = storeit_sql_builder::exists_by_criteria::<T>(
+                self.statements().dialect,
+                self.statements().tenant,
+                criteria,
+            )?;
+            let params = Params::Positional(scoped_values::<T>(params)?);
+            let row: Option<Row> =
+                if let Ok(Some(arc)) = MY_TX_CONN.try_with(|c| c.borrow().as_ref().cloned()) {
+                    let mut conn = arc.lock().await;
+                    conn.exec_first(sql, params)
+                        .await
+                        .map_err(map_mysql_error)?
+                } else {
+                    let mut conn = self.get_conn().await?;
+                    conn.exec_first(sql, params)
+                        .await
+                        .map_err(map_mysql_error)?
+                };
+            Ok(row.is_some())
+        }
+
+        async fn delete_where(&self, criteria: &Criteria) -> RepoResult<u64> {
+            let (sql, params) = storeit_sql_builder::delete_by_criteria::<T>(
+                self.statements().dialect,
+                self.statements().tenant,
+                criteria,
+            )?;
+            let params = Params::Positional(scoped_values::<T>(params)?);
+            let affected =
+                if let Ok(Some(arc)) = MY_TX_CONN.try_with(|c| c.borrow().as_ref().cloned()) {
+                    let mut conn = arc.lock().await;
+                    let result = conn.exec_iter(sql, params).await.map_err(map_mysql_error)?;
+                    result.affected_rows()
+                } else {
+                    let mut conn = self.get_conn().await?;
+                    let result = conn.exec_iter(sql, params).await.map_err(map_mysql_error)?;
+                    result.affected_rows()
+                };
+            Ok(affected)
+        }
+
+        async fn find_page(&self, pageable: &Pageable) -> RepoResult<Page<T>> {
+            let sql = storeit_sql_builder::select_page::<T>(
+                self.statements().dialect,
+                self.statements().tenant,
+                pageable,
+            )?;
+            let count_sql = storeit_sql_builder::select_count_all::<T>(
+                self.statements().dialect,
+                self.statements().tenant,
+            );
+            let params = scoped_values::<T>(Vec::new())?;
+            let (total, rows) =
+                if let Ok(Some(arc)) = MY_TX_CONN.try_with(|c| c.borrow().as_ref().cloned()) {
+                    let mut conn = arc.lock().await;
+                    page_rows(&mut conn, count_sql, sql, params, pageable).await?
+                } else {
+                    let mut conn = self.get_conn().await?;
+                    page_rows(&mut conn, count_sql, sql, params, pageable).await?
+                };
+
+            let items = rows
+                .iter()
+                .map(|row| self.load(row))
+                .collect::<RepoResult<Vec<T>>>()?;
+            Ok(Page::new(items, pageable, total))
+        }
+
+        async fn find_page_by_field(
+            &self,
+            field_name: &str,
+            value: ParamValue,
+            pageable: &Pageable,
+        ) -> RepoResult<Page<T>> {
+            let sql = storeit_sql_builder::select_page_by_field::<T>(
+                self.statements().dialect,
+                self.statements().tenant,
+                field_name,
+                pageable,
+            )?;
+            let count_sql = storeit_sql_builder::select_count_by_field::<T>(
+                self.statements().dialect,
+                self.statements().tenant,
+                field_name,
+            );
+            let params = scoped_values::<T>(vec![value])?;
+            let (total, rows) =
+                if let Ok(Some(arc)) = MY_TX_CONN.try_with(|c| c.borrow().as_ref().cloned()) {
+                    let mut conn = arc.lock().await;
+                    page_rows(&mut conn, count_sql, sql, params, pageable).await?
+                } else {
+                    let mut conn = self.get_conn().await?;
+                    page_rows(&mut conn, count_sql, sql, params, pageable).await?
+                };
+
+            let items = rows
+                .iter()
+                .map(|row| self.load(row))
+                .collect::<RepoResult<Vec<T>>>()?;
+            Ok(Page::new(items, pageable, total))
+        }
+
+        async fn find_page_after(
+            &self,
+            cursor: Option<Cursor>,
+            limit: usize,
+            order: &[Order],
+        ) -> RepoResult<CursorPage<T>> {
+            let orders = storeit_sql_builder::keyset_orders::<T>(order, T::ID_COLUMNS)?;
+            let (sql, params) = storeit_sql_builder::keyset_select::<T>(
+                self.statements().dialect,
+                self.statements().tenant,
+                &orders,
+                cursor.as_ref(),
+                limit,
+            )?;
+            let params = Params::Positional(scoped_values::<T>(params)?);
+            let mut rows: Vec<Row> =
+                if let Ok(Some(arc)) = MY_TX_CONN.try_with(|c| c.borrow().as_ref().cloned()) {
+                    let mut conn = arc.lock().await;
+                    conn.exec(sql, params).await.map_err(map_mysql_error)?
+                } else {
+                    let mut conn = self.get_conn().await?;
+                    conn.exec(sql, params).await.map_err(map_mysql_error)?
+                };
+            let has_more = rows.len() > limit;
+            rows.truncate(limit);
+            if cursor.as_ref().is_some_and(Cursor::is_before) {
+                rows.reverse();
+            }
+
+            let key_idx: Vec<usize> = orders
+                .iter()
+                .filter_map(|o| T::SELECT_COLUMNS.iter().position(|c| *c == o.column))
+                .collect();
+            let key_of = |row: &Row| {
+                key_idx
+                    .iter()
+                    .map(|&i| column_value(row, i))
+                    .collect::<RepoResult<Vec<_>>>()
+            };
+            let first_key = rows.first().map(key_of).transpose()?;
+            let last_key = rows.last().map(key_of).transpose()?;
+            let items = rows
+                .iter()
+                .map(|row| self.load(row))
+                .collect::<RepoResult<Vec<T>>>()?;
+            Ok(CursorPage::new(
+                items,
+                orders.into_iter().map(|o| o.column).collect(),
+                first_key,
+                last_key,
+                cursor.as_ref(),
+                has_more,
+            ))
+        }
+    }
+
+    // Run the COUNT query for a page, then its SELECT unless the page lies past the end.
+    async fn page_rows(
+        conn: &mut Conn,
+        count_sql: String,
+        sql: String,
+        params: Vec<Value>,
+        pageable: &Pageable,
+    ) -> RepoResult<(u64, Vec<Row>)> {
+        let total: Option<u64> = conn
+            .exec_first(count_sql, Params::Positional(params.clone()))
+            .await
+            .map_err(map_mysql_error)?;
+        let total = total.unwrap_or(0);
+        if (pageable.page.saturating_mul(pageable.size) as u64) >= total {
+            return Ok((total, Vec::new()));
+        }
+        let rows = conn
+            .exec(sql, Params::Positional(params))
+            .await
+            .map_err(map_mysql_error)?;
+        Ok((total, rows))
     }
 
     /// A concrete TransactionManager for mysql_async using a single connection per transaction.
@@ -346,6 +1116,11 @@ mod backend {
             Self { pool }
         }
 
+        /// The pool this manager takes its connections from.
+        pub fn pool(&self) -> &Pool {
+            &self.pool
+        }
+
         pub async fn repository<T, A>(
             &self,
             _ctx: TransactionContext<'_>,
@@ -354,7 +1129,7 @@ mod backend {
         where
             T: Fetchable + Identifiable + Insertable + Updatable + Send + Sync + 'static,
             A: RowAdapter<T, Row = Row> + Send + Sync + 'static,
-            T::Key: Clone + Into<Value> + Send + Sync,
+            T::Key: Clone + KeyValues + Send + Sync,
         {
             // If there is a transaction-bound connection in TLS, use it; otherwise create a non-tx repo.
             // We don't need to capture the connection explicitly: the repository will pick up
@@ -362,6 +1137,63 @@ mod backend {
             let repo = MysqlAsyncRepository::new(self.pool.clone(), adapter);
             Ok(repo)
         }
+
+        /// Compare `T` with its live table (`information_schema`); see `storeit_core::schema`.
+        pub async fn validate_schema<T: Schema>(&self) -> RepoResult<SchemaReport> {
+            let mut conn = self.pool.get_conn().await.map_err(map_mysql_error)?;
+            let live = live_columns(&mut conn, T::TABLE).await?;
+            Ok(schema::check(&T::table_def(), &live))
+        }
+
+        /// The changes that would bring `T`'s live table in line with it, for
+        /// `storeit_sql_builder::migration_script`; see `storeit_core::schema::diff`.
+        pub async fn schema_diff<T: Schema>(&self) -> RepoResult<Vec<SchemaChange>> {
+            let mut conn = self.pool.get_conn().await.map_err(map_mysql_error)?;
+            let columns = live_columns(&mut conn, T::TABLE).await?;
+            let indexes = live_indexes(&mut conn, T::TABLE).await?;
+            Ok(schema::diff(&T::table_def(), &columns, &indexes))
+        }
+    }
+
+    // The columns of `table` in the current database, for `storeit_core::schema::check`.
+    // An AUTO_INCREMENT column counts as having a default.
+    const LIVE_COLUMNS_SQL: &str = "SELECT COLUMN_NAME, IS_NULLABLE = 'YES', \
+         COLUMN_DEFAULT IS NOT NULL OR EXTRA LIKE '%auto_increment%', COLUMN_KEY = 'PRI' \
+         FROM information_schema.COLUMNS \
+         WHERE TABLE_SCHEMA = DATABASE() AND TABLE_NAME = ? \
+         ORDER BY ORDINAL_POSITION";
+
+    async fn live_columns(conn: &mut Conn, table: &str) -> RepoResult<Vec<LiveColumn>> {
+        let rows: Vec<(String, i64, i64, i64)> = conn
+            .exec(LIVE_COLUMNS_SQL, (table,))
+            .await
+            .map_err(map_mysql_error)?;
+        Ok(rows
+            .into_iter()
+            .map(|(name, nullable, has_default, primary_key)| LiveColumn {
+                name,
+                nullable: nullable != 0,
+                has_default: has_default != 0,
+                primary_key: primary_key != 0,
+            })
+            .collect())
+    }
+
+    // The indexes of `table`, including PRIMARY, with their columns in index order.
+    const LIVE_INDEXES_SQL: &str = "SELECT INDEX_NAME, NON_UNIQUE = 0, COLUMN_NAME \
+         FROM information_schema.STATISTICS \
+         WHERE TABLE_SCHEMA = DATABASE() AND TABLE_NAME = ? \
+         ORDER BY INDEX_NAME, SEQ_IN_INDEX";
+
+    async fn live_indexes(conn: &mut Conn, table: &str) -> RepoResult<Vec<LiveIndex>> {
+        let rows: Vec<(String, i64, String)> = conn
+            .exec(LIVE_INDEXES_SQL, (table,))
+            .await
+            .map_err(map_mysql_error)?;
+        Ok(LiveIndex::group(
+            rows.into_iter()
+                .map(|(name, unique, column)| (name, unique != 0, column)),
+        ))
     }
 
     #[async_trait]
@@ -398,7 +1230,7 @@ mod backend {
                 }
 
                 if !in_tx {
-                    let mut conn = self.pool.get_conn().await.map_err(RepoError::backend)?;
+                    let mut conn = self.pool.get_conn().await.map_err(map_mysql_error)?;
                     // Start transaction; attempt to set isolation/read-only/timeout best-effort
                     conn.query_drop("START TRANSACTION").await.ok();
                     match def.isolation {
@@ -510,9 +1342,124 @@ mod backend {
 
     #[cfg(test)]
     mod tests {
-        use super::to_mysql_value;
-        use mysql_async::Value;
-        use storeit_core::ParamValue;
+        use super::{decode_column, key_params, map_mysql_error, to_mysql_value};
+        use mysql_async::consts::ColumnType;
+        use mysql_async::{ServerError, Value};
+        use storeit_core::{ParamValue, RepoError};
+
+        fn server(code: u16, message: &str) -> mysql_async::Error {
+            mysql_async::Error::Server(ServerError {
+                code,
+                message: message.to_string(),
+                state: "HY000".to_string(),
+            })
+        }
+
+        #[test]
+        fn key_params_binds_each_key_component() {
+            assert_eq!(key_params(&7i64), vec![Value::Int(7)]);
+            assert_eq!(
+                key_params(&(1i64, String::from("admin"))),
+                vec![Value::Int(1), Value::Bytes(b"admin".to_vec())]
+            );
+        }
+
+        #[test]
+        fn map_mysql_error_classifies_server_codes() {
+            match map_mysql_error(server(1062, "Duplicate entry 'a@x' for key 'users.email'")) {
+                RepoError::UniqueViolation { constraint, .. } => {
+                    assert_eq!(constraint.as_deref(), Some("users.email"))
+                }
+                other => panic!("unexpected: {:?}", other),
+            }
+            match map_mysql_error(server(
+                1452,
+                "Cannot add or update a child row: a foreign key constraint fails (`db`.`posts`, CONSTRAINT `posts_user_fk` FOREIGN KEY (`user_id`) REFERENCES `users` (`id`))",
+            )) {
+                RepoError::ForeignKeyViolation { constraint, .. } => {
+                    assert_eq!(constraint.as_deref(), Some("posts_user_fk"))
+                }
+                other => panic!("unexpected: {:?}", other),
+            }
+            assert!(matches!(
+                map_mysql_error(server(1048, "Column 'email' cannot be null")),
+                RepoError::NotNullViolation { column: Some(c), .. } if c == "email"
+            ));
+            assert!(matches!(
+                map_mysql_error(server(1213, "Deadlock found when trying to get lock")),
+                RepoError::Deadlock { .. }
+            ));
+            assert!(matches!(
+                map_mysql_error(server(1205, "Lock wait timeout exceeded")),
+                RepoError::Timeout { .. }
+            ));
+            assert!(matches!(
+                map_mysql_error(server(
+                    1792,
+                    "Cannot execute statement in a READ ONLY transaction."
+                )),
+                RepoError::ReadOnlyViolation { .. }
+            ));
+            assert!(matches!(
+                map_mysql_error(mysql_async::Error::Driver(
+                    mysql_async::DriverError::ConnectionClosed
+                )),
+                RepoError::ConnectionLost { .. }
+            ));
+            assert!(matches!(
+                map_mysql_error(server(1054, "Unknown column 'x' in 'where clause'")),
+                RepoError::Backend { .. }
+            ));
+        }
+
+        #[test]
+        fn decode_column_uses_column_metadata() {
+            let bytes = |s: &str| Value::Bytes(s.as_bytes().to_vec());
+            // Text protocol returns everything as bytes.
+            assert!(matches!(
+                decode_column(&bytes("42"), ColumnType::MYSQL_TYPE_LONGLONG, false).unwrap(),
+                ParamValue::I64(42)
+            ));
+            assert!(matches!(
+                decode_column(&bytes("12.50"), ColumnType::MYSQL_TYPE_NEWDECIMAL, false).unwrap(),
+                ParamValue::Decimal(d) if d == "12.50"
+            ));
+            assert!(matches!(
+                decode_column(&bytes("2024-01-01"), ColumnType::MYSQL_TYPE_DATE, false).unwrap(),
+                ParamValue::Date(19_723)
+            ));
+            assert!(matches!(
+                decode_column(&bytes("\u{1}"), ColumnType::MYSQL_TYPE_BLOB, true).unwrap(),
+                ParamValue::Bytes(b) if b == [1]
+            ));
+            assert!(matches!(
+                decode_column(&bytes("x"), ColumnType::MYSQL_TYPE_VAR_STRING, false).unwrap(),
+                ParamValue::String(s) if s == "x"
+            ));
+            // Binary protocol values.
+            assert!(matches!(
+                decode_column(
+                    &Value::Date(2024, 1, 1, 0, 0, 1, 5),
+                    ColumnType::MYSQL_TYPE_DATETIME,
+                    false
+                )
+                .unwrap(),
+                ParamValue::Timestamp(1_704_067_201_000_005)
+            ));
+            assert!(matches!(
+                decode_column(
+                    &Value::Time(true, 1, 0, 0, 0, 0),
+                    ColumnType::MYSQL_TYPE_TIME,
+                    false
+                )
+                .unwrap(),
+                ParamValue::Time(-86_400_000_000)
+            ));
+            assert!(matches!(
+                decode_column(&Value::NULL, ColumnType::MYSQL_TYPE_LONG, false).unwrap(),
+                ParamValue::Null
+            ));
+        }
 
         #[test]
         fn to_mysql_value_maps_all_variants() {
@@ -541,6 +1488,42 @@ mod backend {
                 Value::Int(i) => assert_eq!(i, 1),
                 v => panic!("unexpected value for Bool(true): {:?}", v),
             }
+            // Bytes
+            match to_mysql_value(ParamValue::Bytes(vec![0, 255])) {
+                Value::Bytes(b) => assert_eq!(b, [0, 255]),
+                v => panic!("unexpected value for Bytes: {:?}", v),
+            }
+            // Uuid
+            match to_mysql_value(ParamValue::Uuid([0xab; 16])) {
+                Value::Bytes(b) => assert_eq!(b, b"abababab-abab-abab-abab-abababababab"),
+                v => panic!("unexpected value for Uuid: {:?}", v),
+            }
+            // Timestamp: 2024-01-01 00:00:00.5 UTC
+            match to_mysql_value(ParamValue::Timestamp(1_704_067_200_500_000)) {
+                Value::Date(y, mo, d, h, mi, s, us) => {
+                    assert_eq!((y, mo, d, h, mi, s, us), (2024, 1, 1, 0, 0, 0, 500_000))
+                }
+                v => panic!("unexpected value for Timestamp: {:?}", v),
+            }
+            // Date
+            match to_mysql_value(ParamValue::Date(19_723)) {
+                Value::Date(y, mo, d, 0, 0, 0, 0) => assert_eq!((y, mo, d), (2024, 1, 1)),
+                v => panic!("unexpected value for Date: {:?}", v),
+            }
+            // Time
+            match to_mysql_value(ParamValue::Time(3_723_000_004)) {
+                Value::Time(false, 0, h, mi, s, us) => assert_eq!((h, mi, s, us), (1, 2, 3, 4)),
+                v => panic!("unexpected value for Time: {:?}", v),
+            }
+            // Decimal / Json
+            match to_mysql_value(ParamValue::Decimal("-1.50".into())) {
+                Value::Bytes(b) => assert_eq!(b, b"-1.50"),
+                v => panic!("unexpected value for Decimal: {:?}", v),
+            }
+            match to_mysql_value(ParamValue::Json("{\"a\":1}".into())) {
+                Value::Bytes(b) => assert_eq!(b, b"{\"a\":1}"),
+                v => panic!("unexpected value for Json: {:?}", v),
+            }
             // Null
             match to_mysql_value(ParamValue::Null) {
                 Value::NULL => {}
@@ -551,4 +1534,6 @@ mod backend {
 }
 
 #[cfg(feature = "mysql-async")]
-pub use backend::{MysqlAsyncRepository, MysqlAsyncTransactionManager};
+pub use backend::{
+    column_value, map_mysql_error, MysqlAsyncRepository, MysqlAsyncTransactionManager,
+};
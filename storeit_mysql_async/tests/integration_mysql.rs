@@ -3,6 +3,7 @@
 use storeit_core::transactions::TransactionManager;
 use storeit_core::Identifiable;
 use storeit_core::{RepoError, RepoResult, Repository, RowAdapter};
+use storeit_migrate::{Migration, MigrationError, Migrator};
 use storeit_mysql_async::MysqlAsyncRepository;
 use testcontainers::runners::AsyncRunner;
 use testcontainers_modules::mariadb::Mariadb;
@@ -103,6 +104,50 @@ impl RowAdapter<tests_common::User> for MyAdapter {
     }
 }
 
+struct MembershipAdapter;
+impl RowAdapter<tests_common::Membership> for MembershipAdapter {
+    type Row = mysql_async::Row;
+    fn from_row(&self, row: &Self::Row) -> RepoResult<tests_common::Membership> {
+        let id: i64 = row.get("id").ok_or_else(|| {
+            RepoError::mapping(std::io::Error::new(std::io::ErrorKind::Other, "missing id"))
+        })?;
+        let user_id: i64 = row.get("user_id").ok_or_else(|| {
+            RepoError::mapping(std::io::Error::new(
+                std::io::ErrorKind::Other,
+                "missing user_id",
+            ))
+        })?;
+        Ok(tests_common::Membership {
+            id: Some(id),
+            user_id,
+            role: row.get("role").flatten(),
+        })
+    }
+}
+
+struct NoteAdapter;
+impl RowAdapter<tests_common::Note> for NoteAdapter {
+    type Row = mysql_async::Row;
+    fn from_row(&self, row: &Self::Row) -> RepoResult<tests_common::Note> {
+        fn column<V: mysql_async::prelude::FromValue>(
+            row: &mysql_async::Row,
+            name: &str,
+        ) -> RepoResult<V> {
+            row.get(name).ok_or_else(|| {
+                RepoError::mapping(std::io::Error::new(
+                    std::io::ErrorKind::Other,
+                    format!("missing {name}"),
+                ))
+            })
+        }
+        Ok(tests_common::Note {
+            id: Some(column(row, "id")?),
+            tenant_id: column(row, "tenant_id")?,
+            body: column(row, "body")?,
+        })
+    }
+}
+
 struct MyFactory {
     url: String,
 }
@@ -120,6 +165,36 @@ impl tests_common::RepoFactory for MyFactory {
     }
 }
 
+#[async_trait::async_trait]
+impl tests_common::MembershipRepoFactory for MyFactory {
+    async fn new_membership_repo(
+        &self,
+    ) -> RepoResult<Box<dyn Repository<tests_common::Membership> + Send + Sync>> {
+        let repo = MysqlAsyncRepository::<tests_common::Membership, MembershipAdapter>::from_url(
+            &self.url,
+            tests_common::Membership::ID_COLUMN,
+            MembershipAdapter,
+        )
+        .await?;
+        Ok(Box::new(repo))
+    }
+}
+
+#[async_trait::async_trait]
+impl tests_common::NoteRepoFactory for MyFactory {
+    async fn new_note_repo(
+        &self,
+    ) -> RepoResult<Box<dyn Repository<tests_common::Note> + Send + Sync>> {
+        let repo = MysqlAsyncRepository::<tests_common::Note, NoteAdapter>::from_url(
+            &self.url,
+            tests_common::Note::ID_COLUMN,
+            NoteAdapter,
+        )
+        .await?;
+        Ok(Box::new(repo))
+    }
+}
+
 async fn apply_migration(url: &str) -> RepoResult<()> {
     use mysql_async::prelude::*;
     let pool = mysql_async::Pool::new(url);
@@ -128,19 +203,11 @@ async fn apply_migration(url: &str) -> RepoResult<()> {
     conn.query_drop("SELECT 1")
         .await
         .map_err(RepoError::backend)?;
-    // Reduce metadata lock waits so we fail fast instead of hanging
-    let _ = conn.query_drop("SET SESSION lock_wait_timeout = 1").await;
-    eprintln!(
-        "[integration][mysql] applying migration SQL to url: {}",
-        url
-    );
-    eprintln!(
-        "[integration][mysql] migration SQL: {}",
-        tests_common::migrations::MYSQL_USERS_SQL.replace('\n', " ")
-    );
-    conn.query_drop(tests_common::migrations::MYSQL_USERS_SQL)
-        .await
-        .map_err(RepoError::backend)?;
+    eprintln!("[integration][mysql] applying migrations to url: {}", url);
+    let mgr = storeit_mysql_async::MysqlAsyncTransactionManager::new(pool.clone());
+    Migrator::new(tests_common::migrations::MYSQL)?
+        .migrate(&mgr)
+        .await?;
     eprintln!("[integration][mysql] migration applied successfully");
     // Disconnect best-effort with a short timeout to avoid hanging the migration call
     let _ = tokio::time::timeout(std::time::Duration::from_millis(200), pool.disconnect()).await;
@@ -320,6 +387,14 @@ async fn mysql_crud_and_find_by_field() -> RepoResult<()> {
     let factory = MyFactory { url: url.clone() };
     tests_common::test_crud_roundtrip(&factory).await?;
     tests_common::test_find_by_field(&factory).await?;
+    tests_common::test_unique_violation(&factory).await?;
+    tests_common::test_foreign_key_violation(&factory).await?;
+    tests_common::test_not_null_violation(&factory).await?;
+    let mgr = storeit_mysql_async::MysqlAsyncTransactionManager::new(mysql_async::Pool::new(
+        url.as_str(),
+    ));
+    tests_common::test_read_only_violation(&factory, &mgr).await?;
+    tests_common::test_tenant_isolation(&factory).await?;
 
     // Also verify delete_by_id returns false for non-existent id
     let repo = MysqlAsyncRepository::<tests_common::User, MyAdapter>::from_url(
@@ -796,3 +871,159 @@ async fn mysql_transaction_manager_propagation_never_errors_when_in_tx() -> Repo
     let _ = pool.disconnect().await;
     Ok(())
 }
+
+// Applied on top of the shared schema by the storeit_migrate tests, which roll them back
+// again so the shared database keeps the schema the other tests expect.
+const TEAMS: [Migration; 2] = [
+    Migration {
+        version: 101,
+        name: "create_teams",
+        up: "CREATE TABLE teams (id BIGINT AUTO_INCREMENT PRIMARY KEY, name VARCHAR(64) NOT NULL);\n\
+             CREATE INDEX idx_teams_name ON teams (name);\n\
+             INSERT INTO teams (name) VALUES ('core');",
+        down: Some("DROP TABLE teams;"),
+    },
+    Migration {
+        version: 102,
+        name: "add_rank",
+        up: "ALTER TABLE teams ADD COLUMN `rank` BIGINT NOT NULL DEFAULT 0;\n\
+             UPDATE teams SET `rank` = 1;",
+        down: Some("ALTER TABLE teams DROP COLUMN `rank`;"),
+    },
+];
+
+// How many migrations the shared schema has, and the newest of their versions.
+const SHARED_LEN: usize = tests_common::migrations::MYSQL.len();
+const SHARED: i64 = tests_common::migrations::MYSQL[SHARED_LEN - 1].version;
+
+fn with_teams() -> Vec<Migration> {
+    tests_common::migrations::MYSQL
+        .iter()
+        .copied()
+        .chain(TEAMS)
+        .collect()
+}
+
+async fn history(conn: &mut mysql_async::Conn) -> RepoResult<Vec<(i64, String, i64)>> {
+    conn.query("SELECT version, name, checksum FROM storeit_schema_history ORDER BY version")
+        .await
+        .map_err(RepoError::backend)
+}
+
+#[ignore]
+#[tokio::test(flavor = "multi_thread")]
+async fn mysql_migrator_applies_scripts_and_records_history() -> RepoResult<()> {
+    if !containers_usable() {
+        eprintln!("[integration] Skipping: Docker not available");
+        return Ok(());
+    }
+    let _lock = acquire_it_lock().await;
+    let url = shared_db_url().await;
+    let pool = mysql_async::Pool::new(url.as_str());
+    let mgr = storeit_mysql_async::MysqlAsyncTransactionManager::new(pool.clone());
+    let mut conn = pool.get_conn().await.map_err(RepoError::backend)?;
+    let migrations = with_teams();
+    let migrator = Migrator::new(&migrations)?;
+
+    // Every statement of the multi-statement scripts ran.
+    assert_eq!(migrator.migrate(&mgr).await?, vec![101, 102]);
+    let teams: Vec<(String, i64)> = conn
+        .query("SELECT name, `rank` FROM teams")
+        .await
+        .map_err(RepoError::backend)?;
+    assert_eq!(teams, vec![("core".to_string(), 1)]);
+    let expected: Vec<(i64, String, i64)> = migrations
+        .iter()
+        .map(|m| (m.version, m.name.to_string(), m.checksum()))
+        .collect();
+    assert_eq!(history(&mut conn).await?, expected);
+    assert!(migrator.migrate(&mgr).await?.is_empty());
+
+    // DDL commits implicitly on MySQL, so only the failed data change is rolled back; the
+    // migration is not recorded either way.
+    let mut broken = migrations.clone();
+    broken.push(Migration {
+        version: 103,
+        name: "broken",
+        up: "INSERT INTO teams (name) VALUES ('ops'); INSERT INTO missing VALUES (1);",
+        down: None,
+    });
+    let err = Migrator::new(&broken)?.migrate(&mgr).await.unwrap_err();
+    let RepoError::Backend { source } = err else {
+        panic!("unexpected error {err:?}");
+    };
+    assert!(matches!(
+        source.downcast_ref::<MigrationError>(),
+        Some(MigrationError::Failed { version: 103, .. })
+    ));
+    let count: Option<i64> = conn
+        .query_first("SELECT COUNT(*) FROM teams")
+        .await
+        .map_err(RepoError::backend)?;
+    assert_eq!(count, Some(1));
+    assert_eq!(history(&mut conn).await?, expected);
+
+    assert_eq!(migrator.rollback_to(&mgr, SHARED).await?, vec![102, 101]);
+    assert_eq!(history(&mut conn).await?, expected[..SHARED_LEN].to_vec());
+    Ok(())
+}
+
+#[ignore]
+#[tokio::test(flavor = "multi_thread")]
+async fn mysql_migrator_waits_for_the_named_lock() -> RepoResult<()> {
+    if !containers_usable() {
+        eprintln!("[integration] Skipping: Docker not available");
+        return Ok(());
+    }
+    let _lock = acquire_it_lock().await;
+    let url = shared_db_url().await;
+    let pool = mysql_async::Pool::new(url.as_str());
+    let mut holder = pool.get_conn().await.map_err(RepoError::backend)?;
+    let held: Option<i64> = holder
+        .exec_first(
+            "SELECT GET_LOCK(?, 5)",
+            (storeit_migrate::mysql::LOCK_NAME,),
+        )
+        .await
+        .map_err(RepoError::backend)?;
+    assert_eq!(held, Some(1));
+
+    let run = {
+        let pool = pool.clone();
+        tokio::spawn(async move {
+            let mgr = storeit_mysql_async::MysqlAsyncTransactionManager::new(pool);
+            Migrator::new(&with_teams())?.migrate(&mgr).await
+        })
+    };
+    tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+    assert!(
+        !run.is_finished(),
+        "migrate ran while another session held the lock"
+    );
+    assert_eq!(history(&mut holder).await?.len(), SHARED_LEN);
+
+    holder
+        .exec_drop(
+            "SELECT RELEASE_LOCK(?)",
+            (storeit_migrate::mysql::LOCK_NAME,),
+        )
+        .await
+        .map_err(RepoError::backend)?;
+    let applied = tokio::time::timeout(std::time::Duration::from_secs(30), run)
+        .await
+        .expect("migrate finishes once the lock is released")
+        .expect("migrate task")?;
+    assert_eq!(applied, vec![101, 102]);
+
+    // Concurrent runs apply each migration once.
+    let mgr = storeit_mysql_async::MysqlAsyncTransactionManager::new(pool.clone());
+    let migrator = Migrator::new(&with_teams())?;
+    assert_eq!(migrator.rollback_to(&mgr, SHARED).await?, vec![102, 101]);
+    let (a, b) = tokio::join!(migrator.migrate(&mgr), migrator.migrate(&mgr));
+    let mut applied = [a?, b?].concat();
+    applied.sort_unstable();
+    assert_eq!(applied, vec![101, 102]);
+    assert_eq!(history(&mut holder).await?.len(), SHARED_LEN + 2);
+    migrator.rollback_to(&mgr, SHARED).await?;
+    Ok(())
+}
@@ -2,13 +2,12 @@
 #![allow(unexpected_cfgs)]
 //! Minimal SQL builder helpers that leverage metadata from `#[derive(Fetchable)]`.
 //!
-//! Feature flags select placeholder style:
-//! - `tokio_postgres`: $1, $2, ...
-//! - `mysql_async`: ?
-//! - `rusqlite`: ?
-//! - `libsql`: ?
+//! Every builder that emits placeholders or dialect-specific syntax takes a [`Dialect`]:
+//! - `Dialect::Postgres`: $1, $2, ... and `RETURNING <id>` on inserts
+//! - `Dialect::MySql`: ?
+//! - `Dialect::Sqlite`: ? (plus `RETURNING <id>` on inserts with the `libsql_returning` feature)
 //!
-//! Default (no feature): ?
+//! The dialect is a runtime value, so statements for several backends can be built in one binary.
 
 /// Placeholder representation.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -17,12 +16,32 @@ pub enum Placeholder {
     Question, // ?
 }
 
-fn placeholder_style() -> Placeholder {
-    #[cfg(feature = "tokio_postgres")]
-    return Placeholder::Dollar;
+/// SQL dialect targeted by the builder functions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Dialect {
+    Postgres,
+    MySql,
+    Sqlite,
+}
+
+impl Dialect {
+    /// Placeholder style used by this dialect.
+    pub fn placeholder(self) -> Placeholder {
+        match self {
+            Dialect::Postgres => Placeholder::Dollar,
+            Dialect::MySql | Dialect::Sqlite => Placeholder::Question,
+        }
+    }
 
-    #[cfg(not(feature = "tokio_postgres"))]
-    return Placeholder::Question;
+    /// Whether `insert`/`insert_many` append `RETURNING <id>` for this dialect.
+    /// SQLite only does so when the `libsql_returning` feature is enabled.
+    pub fn returning_inserts(self) -> bool {
+        match self {
+            Dialect::Postgres => true,
+            Dialect::Sqlite => cfg!(feature = "libsql_returning"),
+            Dialect::MySql => false,
+        }
+    }
 }
 
 fn first_placeholder(ph: Placeholder) -> &'static str {
@@ -39,106 +58,341 @@ fn placeholder_n(ph: Placeholder, n: usize) -> String {
     }
 }
 
+/// Render `a = <ph> AND b = <ph>` for the given key columns, numbering from `first`.
+fn where_id(style: Placeholder, id_columns: &[&str], first: usize) -> String {
+    id_columns
+        .iter()
+        .enumerate()
+        .map(|(i, col)| format!("{} = {}", col, placeholder_n(style, first + i)))
+        .collect::<Vec<_>>()
+        .join(" AND ")
+}
+
+/// `<soft delete column> IS NULL` for entities with a `SOFT_DELETE_COLUMN`.
+fn live_condition<E: storeit_core::Fetchable>() -> Option<String> {
+    E::SOFT_DELETE_COLUMN.map(|col| format!("{col} IS NULL"))
+}
+
+/// Append ` AND <live condition>` to a statement that already has a WHERE clause.
+fn and_live<E: storeit_core::Fetchable>(mut sql: String) -> String {
+    if let Some(live) = live_condition::<E>() {
+        sql.push_str(" AND ");
+        sql.push_str(&live);
+    }
+    sql
+}
+
+/// `<tenant column> = <ph n>` for entities with a `TENANT_COLUMN` when `tenant` is set. Its
+/// value (from `storeit_core::tenant::filter_values`) binds after every other parameter.
+/// Statements for `tenant::bypass` are built with `tenant = false` and get no condition (and
+/// `filter_values` is empty there).
+fn tenant_condition<E: storeit_core::Fetchable>(
+    dialect: Dialect,
+    tenant: bool,
+    n: usize,
+) -> Option<String> {
+    if !tenant {
+        return None;
+    }
+    E::TENANT_COLUMN.map(|col| format!("{col} = {}", placeholder_n(dialect.placeholder(), n)))
+}
+
+/// Append ` AND <tenant condition>` to a statement that already has a WHERE clause.
+fn and_tenant<E: storeit_core::Fetchable>(
+    mut sql: String,
+    dialect: Dialect,
+    tenant: bool,
+    n: usize,
+) -> String {
+    if let Some(cond) = tenant_condition::<E>(dialect, tenant, n) {
+        sql.push_str(" AND ");
+        sql.push_str(&cond);
+    }
+    sql
+}
+
+/// The live and tenant conditions of `E`, in that order.
+fn scope_conditions<E: storeit_core::Fetchable>(
+    dialect: Dialect,
+    tenant: bool,
+    n: usize,
+) -> Vec<String> {
+    live_condition::<E>()
+        .into_iter()
+        .chain(tenant_condition::<E>(dialect, tenant, n))
+        .collect()
+}
+
+/// Append the live and tenant conditions to a statement without a WHERE clause.
+fn where_scoped<E: storeit_core::Fetchable>(
+    mut sql: String,
+    dialect: Dialect,
+    tenant: bool,
+    n: usize,
+) -> String {
+    let conds = scope_conditions::<E>(dialect, tenant, n);
+    if !conds.is_empty() {
+        sql.push_str(" WHERE ");
+        sql.push_str(&conds.join(" AND "));
+    }
+    sql
+}
+
+/// Render `criteria` restricted to live rows of the current tenant, numbering from `first`.
+/// The tenant placeholder, if any, comes last and gets no value in the returned params.
+fn scoped_criteria<E: storeit_core::Fetchable>(
+    dialect: Dialect,
+    tenant: bool,
+    criteria: &storeit_core::Criteria,
+    first: usize,
+) -> storeit_core::RepoResult<(String, Vec<storeit_core::ParamValue>)> {
+    check_criteria_columns::<E>(criteria)?;
+    let (cond, params) = render_criteria(dialect, &live_criteria::<E>(criteria), first);
+    let cond = match tenant_condition::<E>(dialect, tenant, first + params.len()) {
+        // The rendered criteria may be a bare `a OR b`.
+        Some(scope) => format!("({cond}) AND {scope}"),
+        None => cond,
+    };
+    Ok((cond, params))
+}
+
+/// Criteria columns are interpolated like ORDER BY columns, so each one (for a
+/// `column->key->...` path, its JSON column) must be among `E::SELECT_COLUMNS` or be the
+/// soft-delete column; others are rejected.
+fn check_criteria_columns<E: storeit_core::Fetchable>(
+    criteria: &storeit_core::Criteria,
+) -> storeit_core::RepoResult<()> {
+    use storeit_core::Criteria;
+
+    let column = match criteria {
+        Criteria::Eq(c, _)
+        | Criteria::Ne(c, _)
+        | Criteria::Lt(c, _)
+        | Criteria::Le(c, _)
+        | Criteria::Gt(c, _)
+        | Criteria::Ge(c, _)
+        | Criteria::Like(c, _)
+        | Criteria::In(c, _)
+        | Criteria::IsNull(c)
+        | Criteria::Between(c, _, _) => c,
+        Criteria::And(parts) | Criteria::Or(parts) => {
+            return parts.iter().try_for_each(check_criteria_columns::<E>);
+        }
+        Criteria::Not(inner) => return check_criteria_columns::<E>(inner),
+    };
+    let base = column.split("->").next().unwrap_or_default();
+    if E::SELECT_COLUMNS.contains(&base) || E::SOFT_DELETE_COLUMN == Some(base) {
+        Ok(())
+    } else {
+        Err(invalid_input(format!(
+            "cannot filter {} by unknown column `{}`",
+            E::TABLE,
+            column
+        )))
+    }
+}
+
+/// Restrict `criteria` to rows that are not soft-deleted.
+fn live_criteria<E: storeit_core::Fetchable>(
+    criteria: &storeit_core::Criteria,
+) -> std::borrow::Cow<'_, storeit_core::Criteria> {
+    use storeit_core::Criteria;
+    match E::SOFT_DELETE_COLUMN {
+        Some(col) => std::borrow::Cow::Owned(Criteria::And(vec![
+            criteria.clone(),
+            Criteria::IsNull(col.to_string()),
+        ])),
+        None => std::borrow::Cow::Borrowed(criteria),
+    }
+}
+
 /// Build a simple SELECT ... WHERE id = <ph> statement using metadata from `E`.
-pub fn select_by_id<E>(id_column: &str) -> String
+/// Composite keys render one `AND`-ed comparison per id column.
+/// Soft-deleted rows are excluded (see `Fetchable::SOFT_DELETE_COLUMN`).
+///
+/// With `tenant` set, this (like every builder taking a `tenant` flag) ends the WHERE clause
+/// with the tenant condition for entities with a `Fetchable::TENANT_COLUMN`; bind
+/// `storeit_core::tenant::filter_values` after the other parameters. Pass `false` for
+/// statements run under `storeit_core::tenant::bypass`.
+pub fn select_by_id<E>(dialect: Dialect, tenant: bool, id_columns: &[&str]) -> String
 where
     E: storeit_core::Fetchable,
 {
-    let cols = E::SELECT_COLUMNS.join(", ");
+    and_live::<E>(select_by_id_with_deleted::<E>(dialect, tenant, id_columns))
+}
+
+/// Like [`select_by_id`], but also returns a soft-deleted row.
+pub fn select_by_id_with_deleted<E>(dialect: Dialect, tenant: bool, id_columns: &[&str]) -> String
+where
+    E: storeit_core::Fetchable,
+{
+    select_columns_by_id_with_deleted::<E>(dialect, tenant, E::SELECT_COLUMNS, id_columns)
+}
+
+/// Like [`select_by_id`], but selecting only `columns` (see `storeit_core::Projection`).
+pub fn select_columns_by_id<E>(
+    dialect: Dialect,
+    tenant: bool,
+    columns: &[&str],
+    id_columns: &[&str],
+) -> String
+where
+    E: storeit_core::Fetchable,
+{
+    and_live::<E>(select_columns_by_id_with_deleted::<E>(
+        dialect, tenant, columns, id_columns,
+    ))
+}
+
+fn select_columns_by_id_with_deleted<E>(
+    dialect: Dialect,
+    tenant: bool,
+    columns: &[&str],
+    id_columns: &[&str],
+) -> String
+where
+    E: storeit_core::Fetchable,
+{
+    let cols = columns.join(", ");
     let table = E::TABLE;
-    let ph = first_placeholder(placeholder_style());
-    format!(
-        "SELECT {cols} FROM {table} WHERE {id} = {ph}",
+    let sql = format!(
+        "SELECT {cols} FROM {table} WHERE {cond}",
         cols = cols,
         table = table,
-        id = id_column,
-        ph = ph
-    )
+        cond = where_id(dialect.placeholder(), id_columns, 1)
+    );
+    and_tenant::<E>(sql, dialect, tenant, id_columns.len() + 1)
+}
+
+/// Build UPDATE <table> SET <soft delete column> = <ph> WHERE <id> = <ph> AND <column> IS NULL.
+/// The deletion time binds first, then the key components.
+/// Returns `None` when `E` has no `SOFT_DELETE_COLUMN`.
+pub fn soft_delete_by_id<E>(dialect: Dialect, tenant: bool, id_columns: &[&str]) -> Option<String>
+where
+    E: storeit_core::Fetchable,
+{
+    let col = E::SOFT_DELETE_COLUMN?;
+    let style = dialect.placeholder();
+    let sql = format!(
+        "UPDATE {table} SET {col} = {ph} WHERE {cond} AND {col} IS NULL",
+        table = E::TABLE,
+        ph = placeholder_n(style, 1),
+        cond = where_id(style, id_columns, 2)
+    );
+    Some(and_tenant::<E>(sql, dialect, tenant, id_columns.len() + 2))
+}
+
+/// Build UPDATE <table> SET <soft delete column> = NULL WHERE <id> = <ph> AND <column> IS NOT NULL.
+/// Returns `None` when `E` has no `SOFT_DELETE_COLUMN`.
+pub fn restore_by_id<E>(dialect: Dialect, tenant: bool, id_columns: &[&str]) -> Option<String>
+where
+    E: storeit_core::Fetchable,
+{
+    let col = E::SOFT_DELETE_COLUMN?;
+    let sql = format!(
+        "UPDATE {table} SET {col} = NULL WHERE {cond} AND {col} IS NOT NULL",
+        table = E::TABLE,
+        cond = where_id(dialect.placeholder(), id_columns, 1)
+    );
+    Some(and_tenant::<E>(sql, dialect, tenant, id_columns.len() + 1))
 }
 
 /// Build DELETE ... WHERE id = <ph>
-pub fn delete_by_id<E>(id_column: &str) -> String
+/// This always removes the row; see [`soft_delete_by_id`] for soft-deleting entities.
+pub fn delete_by_id<E>(dialect: Dialect, tenant: bool, id_columns: &[&str]) -> String
 where
     E: storeit_core::Fetchable,
 {
     let table = E::TABLE;
-    let ph = first_placeholder(placeholder_style());
-    format!(
-        "DELETE FROM {table} WHERE {id} = {ph}",
+    let sql = format!(
+        "DELETE FROM {table} WHERE {cond}",
         table = table,
-        id = id_column,
-        ph = ph
-    )
+        cond = where_id(dialect.placeholder(), id_columns, 1)
+    );
+    and_tenant::<E>(sql, dialect, tenant, id_columns.len() + 1)
 }
 
 /// Build INSERT INTO <table> (<cols>) VALUES (<placeholders>)
-/// When feature `tokio_postgres` or `libsql_returning` is enabled, this appends `RETURNING <id_column>`.
-pub fn insert<E>(id_column: &str) -> String
+/// When the dialect returns inserted ids (see [`Dialect::returning_inserts`]), this appends `RETURNING <id_column>`.
+pub fn insert<E>(dialect: Dialect, id_column: &str) -> String
 where
     E: storeit_core::Fetchable + storeit_core::Insertable,
 {
-    let cols = E::INSERT_COLUMNS;
-    let table = E::TABLE;
-    let style = placeholder_style();
-    let mut phs: Vec<String> = Vec::with_capacity(cols.len());
-    for i in 1..=cols.len() {
-        phs.push(placeholder_n(style, i));
-    }
-    let cols_csv = cols.join(", ");
-    let ph_csv = phs.join(", ");
-
-    // Two specialized branches to avoid unused_mut and unused variable warnings under -Dwarnings
-    #[cfg(any(feature = "tokio_postgres", feature = "libsql_returning"))]
-    {
-        let mut sql = format!(
-            "INSERT INTO {table} ({cols}) VALUES ({vals})",
-            table = table,
-            cols = cols_csv,
-            vals = ph_csv
-        );
+    let mut sql = insert_values_clause::<E>(dialect);
+    if dialect.returning_inserts() {
         sql.push_str(" RETURNING ");
         sql.push_str(id_column);
-        sql
     }
+    sql
+}
 
-    #[cfg(not(any(feature = "tokio_postgres", feature = "libsql_returning")))]
-    {
-        let _ = id_column; // silence unused parameter when not returning
-        format!(
-            "INSERT INTO {table} ({cols}) VALUES ({vals})",
-            table = table,
-            cols = cols_csv,
-            vals = ph_csv
-        )
-    }
+/// Build INSERT INTO <table> (<cols>) VALUES (<placeholders>) RETURNING <select cols>,
+/// so the stored row can be mapped without a follow-up SELECT.
+/// Only use this with dialects that support RETURNING (Postgres, SQLite 3.35+).
+pub fn insert_returning<E>(dialect: Dialect) -> String
+where
+    E: storeit_core::Fetchable + storeit_core::Insertable,
+{
+    let mut sql = insert_values_clause::<E>(dialect);
+    sql.push_str(" RETURNING ");
+    sql.push_str(&E::SELECT_COLUMNS.join(", "));
+    sql
+}
+
+fn insert_values_clause<E>(dialect: Dialect) -> String
+where
+    E: storeit_core::Fetchable + storeit_core::Insertable,
+{
+    let cols = E::INSERT_COLUMNS;
+    let style = dialect.placeholder();
+    let phs: Vec<String> = (1..=cols.len()).map(|i| placeholder_n(style, i)).collect();
+    format!(
+        "INSERT INTO {table} ({cols}) VALUES ({vals})",
+        table = E::TABLE,
+        cols = cols.join(", "),
+        vals = phs.join(", ")
+    )
 }
 
 /// Build UPDATE <table> SET <col1>=<ph1>, ... WHERE <id>=<phN>
-pub fn update_by_id<E>(id_column: &str) -> String
+/// Key parameters follow the SET parameters, in `id_columns` order.
+/// With a `VERSION_COLUMN` the statement also sets `version = version + 1` and appends
+/// `AND version = <ph>`, whose parameter comes last. With a `SOFT_DELETE_COLUMN` only live
+/// rows match.
+pub fn update_by_id<E>(dialect: Dialect, tenant: bool, id_columns: &[&str]) -> String
 where
     E: storeit_core::Fetchable + storeit_core::Updatable,
 {
     let cols = E::UPDATE_COLUMNS;
     let table = E::TABLE;
-    let style = placeholder_style();
+    let style = dialect.placeholder();
 
-    let mut assignments = Vec::with_capacity(cols.len());
+    let mut assignments = Vec::with_capacity(cols.len() + 1);
     for (i, col) in cols.iter().enumerate() {
         let ph = placeholder_n(style, i + 1);
         assignments.push(format!("{col} = {ph}", col = col, ph = ph));
     }
-    let where_ph = placeholder_n(style, cols.len() + 1);
+    let mut cond = where_id(style, id_columns, cols.len() + 1);
+    let mut next = cols.len() + id_columns.len() + 1;
+    if let Some(version) = E::VERSION_COLUMN {
+        assignments.push(format!("{version} = {version} + 1"));
+        let ph = placeholder_n(style, next);
+        cond.push_str(&format!(" AND {version} = {ph}"));
+        next += 1;
+    }
+    // Soft-deleted rows are not updated; restore them first.
+    let cond = and_tenant::<E>(and_live::<E>(cond), dialect, tenant, next);
 
     format!(
-        "UPDATE {table} SET {set_clause} WHERE {id} = {where_ph}",
+        "UPDATE {table} SET {set_clause} WHERE {cond}",
         table = table,
         set_clause = assignments.join(", "),
-        id = id_column,
-        where_ph = where_ph
+        cond = cond
     )
 }
 
 /// Build SELECT <cols> FROM <table>
+/// This is an unfiltered base: it includes soft-deleted rows and rows of every tenant.
 pub fn select_all<E>() -> String
 where
     E: storeit_core::Fetchable,
@@ -149,98 +403,118 @@ where
 }
 
 /// Build SELECT ... WHERE <field> = <ph>
-pub fn select_by_field<E>(field: &str) -> String
+pub fn select_by_field<E>(dialect: Dialect, tenant: bool, field: &str) -> String
 where
     E: storeit_core::Fetchable,
 {
-    let cols = E::SELECT_COLUMNS.join(", ");
+    select_columns_by_field::<E>(dialect, tenant, E::SELECT_COLUMNS, field)
+}
+
+/// Like [`select_by_field`], but selecting only `columns` (see `storeit_core::Projection`).
+pub fn select_columns_by_field<E>(
+    dialect: Dialect,
+    tenant: bool,
+    columns: &[&str],
+    field: &str,
+) -> String
+where
+    E: storeit_core::Fetchable,
+{
+    let cols = columns.join(", ");
     let table = E::TABLE;
-    let ph = first_placeholder(placeholder_style());
-    format!(
+    let ph = first_placeholder(dialect.placeholder());
+    let sql = and_live::<E>(format!(
         "SELECT {cols} FROM {table} WHERE {field} = {ph}",
         cols = cols,
         table = table,
         field = field,
         ph = ph
-    )
+    ));
+    and_tenant::<E>(sql, dialect, tenant, 2)
 }
 
 /// Build SELECT ... WHERE <field> IS NULL
-pub fn select_by_is_null<E>(field: &str) -> String
+pub fn select_by_is_null<E>(dialect: Dialect, tenant: bool, field: &str) -> String
 where
     E: storeit_core::Fetchable,
 {
     let cols = E::SELECT_COLUMNS.join(", ");
     let table = E::TABLE;
-    format!(
+    let sql = and_live::<E>(format!(
         "SELECT {cols} FROM {table} WHERE {field} IS NULL",
         cols = cols,
         table = table,
         field = field,
-    )
+    ));
+    and_tenant::<E>(sql, dialect, tenant, 1)
 }
 
 /// Build SELECT ... WHERE <field> IS NOT NULL
-pub fn select_by_is_not_null<E>(field: &str) -> String
+pub fn select_by_is_not_null<E>(dialect: Dialect, tenant: bool, field: &str) -> String
 where
     E: storeit_core::Fetchable,
 {
     let cols = E::SELECT_COLUMNS.join(", ");
     let table = E::TABLE;
-    format!(
+    let sql = and_live::<E>(format!(
         "SELECT {cols} FROM {table} WHERE {field} IS NOT NULL",
         cols = cols,
         table = table,
         field = field,
-    )
+    ));
+    and_tenant::<E>(sql, dialect, tenant, 1)
 }
 
 /// Build SELECT ... WHERE <field> IN (<ph1>, <ph2>, ...)
-pub fn select_by_in<E>(field: &str, count: usize) -> String
+pub fn select_by_in<E>(dialect: Dialect, tenant: bool, field: &str, count: usize) -> String
 where
     E: storeit_core::Fetchable,
 {
     let cols = E::SELECT_COLUMNS.join(", ");
     let table = E::TABLE;
-    let style = placeholder_style();
+    let style = dialect.placeholder();
     let mut phs: Vec<String> = Vec::with_capacity(count);
     for i in 1..=count {
         phs.push(placeholder_n(style, i));
     }
     let ph_csv = phs.join(", ");
-    format!(
+    let sql = and_live::<E>(format!(
         "SELECT {cols} FROM {table} WHERE {field} IN ({phs})",
         cols = cols,
         table = table,
         field = field,
         phs = ph_csv,
-    )
+    ));
+    and_tenant::<E>(sql, dialect, tenant, count + 1)
 }
 
 /// Build SELECT ... WHERE <field> NOT IN (<ph1>, <ph2>, ...)
-pub fn select_by_not_in<E>(field: &str, count: usize) -> String
+pub fn select_by_not_in<E>(dialect: Dialect, tenant: bool, field: &str, count: usize) -> String
 where
     E: storeit_core::Fetchable,
 {
     let cols = E::SELECT_COLUMNS.join(", ");
     let table = E::TABLE;
-    let style = placeholder_style();
+    let style = dialect.placeholder();
     let mut phs: Vec<String> = Vec::with_capacity(count);
     for i in 1..=count {
         phs.push(placeholder_n(style, i));
     }
     let ph_csv = phs.join(", ");
-    format!(
+    let sql = and_live::<E>(format!(
         "SELECT {cols} FROM {table} WHERE {field} NOT IN ({phs})",
         cols = cols,
         table = table,
         field = field,
         phs = ph_csv,
-    )
+    ));
+    and_tenant::<E>(sql, dialect, tenant, count + 1)
 }
 
 /// Build SELECT with optional ORDER BY, LIMIT, OFFSET
 pub fn select_with_pagination<E>(
+    dialect: Dialect,
+    tenant: bool,
     order_by: Option<&str>,
     limit: Option<usize>,
     offset: Option<usize>,
@@ -248,7 +522,20 @@ pub fn select_with_pagination<E>(
 where
     E: storeit_core::Fetchable,
 {
-    let mut sql = select_all::<E>();
+    paginate(
+        where_scoped::<E>(select_all::<E>(), dialect, tenant, 1),
+        order_by,
+        limit,
+        offset,
+    )
+}
+
+fn paginate(
+    mut sql: String,
+    order_by: Option<&str>,
+    limit: Option<usize>,
+    offset: Option<usize>,
+) -> String {
     if let Some(ob) = order_by {
         if !ob.trim().is_empty() {
             sql.push_str(" ORDER BY ");
@@ -266,41 +553,131 @@ where
     sql
 }
 
+/// Render `orders` as an ORDER BY list (`name ASC, id DESC`).
+/// Columns are interpolated, so each one must be among `E::SELECT_COLUMNS`; others are rejected.
+pub fn order_by_list<E>(orders: &[storeit_core::Order]) -> storeit_core::RepoResult<String>
+where
+    E: storeit_core::Fetchable,
+{
+    orders
+        .iter()
+        .map(|o| {
+            if !E::SELECT_COLUMNS.contains(&o.column.as_str()) {
+                return Err(invalid_input(format!(
+                    "cannot sort {} by unknown column `{}`",
+                    E::TABLE,
+                    o.column
+                )));
+            }
+            let dir = match o.direction {
+                storeit_core::Direction::Asc => "ASC",
+                storeit_core::Direction::Desc => "DESC",
+            };
+            Ok(format!("{} {}", o.column, dir))
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .map(|cols| cols.join(", "))
+}
+
+fn invalid_input(msg: String) -> storeit_core::RepoError {
+    storeit_core::RepoError::backend(std::io::Error::new(std::io::ErrorKind::InvalidInput, msg))
+}
+
+// Validated ORDER BY list and OFFSET for a page request.
+fn page_parts<E>(pageable: &storeit_core::Pageable) -> storeit_core::RepoResult<(String, usize)>
+where
+    E: storeit_core::Fetchable,
+{
+    if pageable.size == 0 {
+        return Err(invalid_input("page size must be at least 1".to_string()));
+    }
+    let offset = pageable
+        .offset()
+        .ok_or_else(|| invalid_input("page offset overflows".to_string()))?;
+    Ok((order_by_list::<E>(&pageable.sort)?, offset))
+}
+
+/// Build SELECT <cols> FROM <table> [ORDER BY ...] LIMIT <size> OFFSET <page * size>
+/// for a [`Pageable`](storeit_core::Pageable), validating its sort columns.
+pub fn select_page<E>(
+    dialect: Dialect,
+    tenant: bool,
+    pageable: &storeit_core::Pageable,
+) -> storeit_core::RepoResult<String>
+where
+    E: storeit_core::Fetchable,
+{
+    let (order_by, offset) = page_parts::<E>(pageable)?;
+    Ok(paginate(
+        where_scoped::<E>(select_all::<E>(), dialect, tenant, 1),
+        Some(&order_by),
+        Some(pageable.size),
+        Some(offset),
+    ))
+}
+
+/// Build SELECT ... WHERE <field> = <ph> [ORDER BY ...] LIMIT <size> OFFSET <page * size>.
+/// Pair it with [`select_count_by_field`] for the page total.
+pub fn select_page_by_field<E>(
+    dialect: Dialect,
+    tenant: bool,
+    field: &str,
+    pageable: &storeit_core::Pageable,
+) -> storeit_core::RepoResult<String>
+where
+    E: storeit_core::Fetchable,
+{
+    let (order_by, offset) = page_parts::<E>(pageable)?;
+    let mut sql = select_by_field::<E>(dialect, tenant, field);
+    if !order_by.is_empty() {
+        sql.push_str(" ORDER BY ");
+        sql.push_str(&order_by);
+    }
+    sql.push_str(&format!(" LIMIT {} OFFSET {}", pageable.size, offset));
+    Ok(sql)
+}
+
 /// Build SELECT COUNT(*) FROM <table>
-pub fn select_count_all<E>() -> String
+pub fn select_count_all<E>(dialect: Dialect, tenant: bool) -> String
 where
     E: storeit_core::Fetchable,
 {
     let table = E::TABLE;
-    format!("SELECT COUNT(*) FROM {table}", table = table)
+    where_scoped::<E>(
+        format!("SELECT COUNT(*) FROM {table}", table = table),
+        dialect,
+        tenant,
+        1,
+    )
 }
 
 /// Build SELECT COUNT(*) FROM <table> WHERE <field> = <ph>
-pub fn select_count_by_field<E>(field: &str) -> String
+pub fn select_count_by_field<E>(dialect: Dialect, tenant: bool, field: &str) -> String
 where
     E: storeit_core::Fetchable,
 {
     let table = E::TABLE;
-    let ph = first_placeholder(placeholder_style());
-    format!(
+    let ph = first_placeholder(dialect.placeholder());
+    let sql = and_live::<E>(format!(
         "SELECT COUNT(*) FROM {table} WHERE {field} = {ph}",
         table = table,
         field = field,
         ph = ph
-    )
+    ));
+    and_tenant::<E>(sql, dialect, tenant, 2)
 }
 
 /// Build INSERT INTO <table> (<cols>) VALUES rows*(<placeholders>)
 /// This generates multi-row VALUES with correct placeholder numbering for Postgres
-/// and '?' placeholders for other backends.
-pub fn insert_many<E>(rows: usize, id_column: &str) -> String
+/// and '?' placeholders for other dialects.
+pub fn insert_many<E>(dialect: Dialect, rows: usize, id_column: &str) -> String
 where
     E: storeit_core::Fetchable + storeit_core::Insertable,
 {
     assert!(rows >= 1, "rows must be >= 1");
     let cols = E::INSERT_COLUMNS;
     let table = E::TABLE;
-    let style = placeholder_style();
+    let style = dialect.placeholder();
 
     // One row placeholders
     let mut phs: Vec<String> = Vec::with_capacity(cols.len());
@@ -344,15 +721,7 @@ where
         }
     }
 
-    // Silence unused parameter when not returning on these features
-    #[cfg(not(any(feature = "tokio_postgres", feature = "libsql_returning")))]
-    {
-        let _ = id_column;
-    }
-
-    // Append RETURNING id_column when features that already do so are enabled
-    #[cfg(any(feature = "tokio_postgres", feature = "libsql_returning"))]
-    {
+    if dialect.returning_inserts() {
         sql.push_str(" RETURNING ");
         sql.push_str(id_column);
     }
@@ -363,14 +732,13 @@ where
 /// Build INSERT ... ON CONFLICT DO UPDATE (Postgres)
 /// Generates a statement inserting E::INSERT_COLUMNS and updating those same columns
 /// from EXCLUDED on conflict of `conflict_column`.
-#[cfg(feature = "tokio_postgres")]
 pub fn upsert_pg_on_conflict_do_update<E>(conflict_column: &str, id_column: &str) -> String
 where
     E: storeit_core::Fetchable + storeit_core::Insertable,
 {
     let cols = E::INSERT_COLUMNS;
     let table = E::TABLE;
-    let style = placeholder_style();
+    let style = Dialect::Postgres.placeholder();
     let mut phs: Vec<String> = Vec::with_capacity(cols.len());
     for i in 1..=cols.len() {
         phs.push(placeholder_n(style, i));
@@ -399,14 +767,13 @@ where
 }
 
 /// Build MySQL: INSERT ... ON DUPLICATE KEY UPDATE ...
-#[cfg(feature = "mysql_async")]
 pub fn upsert_mysql_on_duplicate_key_update<E>() -> String
 where
     E: storeit_core::Fetchable + storeit_core::Insertable,
 {
     let cols = E::INSERT_COLUMNS;
     let table = E::TABLE;
-    let style = placeholder_style();
+    let style = Dialect::MySql.placeholder();
     let mut phs: Vec<String> = Vec::with_capacity(cols.len());
     for i in 1..=cols.len() {
         phs.push(placeholder_n(style, i));
@@ -432,12 +799,13 @@ where
 /// Build WHERE clause for simple conjunction (AND) of equality comparisons.
 /// Returns ("WHERE <field1> = <ph> AND <field2> = <ph> ...", params_in_order)
 pub fn build_where_and(
+    dialect: Dialect,
     params: &[(&str, storeit_core::ParamValue)],
 ) -> (String, Vec<storeit_core::ParamValue>) {
     if params.is_empty() {
         return (String::new(), Vec::new());
     }
-    let ph_style = placeholder_style();
+    let ph_style = dialect.placeholder();
     let mut clauses: Vec<String> = Vec::with_capacity(params.len());
     let mut out_params: Vec<storeit_core::ParamValue> = Vec::with_capacity(params.len());
     for (i, (field, val)) in params.iter().enumerate() {
@@ -456,12 +824,13 @@ pub fn build_where_and(
 /// Each inner vector represents one group combined by AND; groups are then OR-ed together.
 /// Returns ("WHERE (a = ? AND b = ?) OR (c = ?)", params)
 pub fn build_where_or(
+    dialect: Dialect,
     groups: &[Vec<(&str, storeit_core::ParamValue)>],
 ) -> (String, Vec<storeit_core::ParamValue>) {
     if groups.is_empty() {
         return (String::new(), Vec::new());
     }
-    let ph_style = placeholder_style();
+    let ph_style = dialect.placeholder();
     let mut param_index = 1usize;
     let mut out_params: Vec<storeit_core::ParamValue> = Vec::new();
     let mut rendered_groups: Vec<String> = Vec::with_capacity(groups.len());
@@ -492,23 +861,12 @@ pub fn build_where_or(
     (sql, out_params)
 }
 
-/// Build SELECT <cols> FROM <table> WHERE <custom>
-pub fn select_where<E>(where_sql: &str) -> String
-where
-    E: storeit_core::Fetchable,
-{
-    let mut base = select_all::<E>();
-    if !where_sql.trim().is_empty() {
-        base.push(' ');
-        base.push_str(where_sql);
-    }
-    base
-}
-
 /// Keyset pagination helper over the id column. Returns (SQL, params).
 /// When `after` is Some(v): uses `WHERE id > v` (or `< v` when ascending=false) and orders accordingly.
 /// When `after` is None: omits the comparison and just orders/limits.
 pub fn keyset_by_id<E>(
+    dialect: Dialect,
+    tenant: bool,
     id_column: &str,
     after: Option<storeit_core::ParamValue>,
     limit: usize,
@@ -520,7 +878,7 @@ where
     let mut sql = String::new();
     // SELECT ... FROM ...
     sql.push_str(&select_all::<E>());
-    let ph_style = placeholder_style();
+    let ph_style = dialect.placeholder();
     let mut params: Vec<storeit_core::ParamValue> = Vec::new();
 
     if let Some(val) = after.clone() {
@@ -530,8 +888,11 @@ where
             Placeholder::Dollar => placeholder_n(ph_style, 1),
             Placeholder::Question => first_placeholder(ph_style).to_string(),
         };
-        sql.push_str(&format!(" WHERE {} {} {}", id_column, cmp, ph));
+        sql = and_live::<E>(format!("{} WHERE {} {} {}", sql, id_column, cmp, ph));
+        sql = and_tenant::<E>(sql, dialect, tenant, 2);
         params.push(val);
+    } else {
+        sql = where_scoped::<E>(sql, dialect, tenant, 1);
     }
 
     // ORDER BY and LIMIT
@@ -544,138 +905,1178 @@ where
     (sql, params)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use storeit_macros::Entity; // derive macro
-
-    #[derive(Entity)]
-
-    struct User {
-        #[fetch(id)]
-        id: i64,
-        email: String,
+/// Sort keys for keyset pagination: `order` followed by each id column it does not already
+/// contain (ascending) so the ordering is total. Every key must be among `E::SELECT_COLUMNS`.
+pub fn keyset_orders<E>(
+    order: &[storeit_core::Order],
+    id_columns: &[&str],
+) -> storeit_core::RepoResult<Vec<storeit_core::Order>>
+where
+    E: storeit_core::Fetchable,
+{
+    let mut orders = order.to_vec();
+    for id in id_columns {
+        if !orders.iter().any(|o| o.column == *id) {
+            orders.push(storeit_core::Order::asc(*id));
+        }
     }
+    order_by_list::<E>(&orders)?;
+    Ok(orders)
+}
 
-    #[test]
-    #[cfg(feature = "tokio_postgres")]
-    fn test_select_default_pg() {
-        let sql = select_by_id::<User>("id");
-        assert_eq!(sql, "SELECT id, email FROM users WHERE id = $1");
-    }
+/// Build a keyset page query over the sort keys from [`keyset_orders`]: rows strictly after
+/// (or, for a `before` cursor, strictly before) the cursor, `LIMIT limit + 1` so the caller
+/// can tell whether more rows follow. Paging backwards flips the ORDER BY; the caller
+/// reverses the returned rows. NULL sort keys come first in ascending order on every
+/// dialect (see [`keyset_criteria`]). Returns (SQL, params).
+pub fn keyset_select<E>(
+    dialect: Dialect,
+    tenant: bool,
+    orders: &[storeit_core::Order],
+    cursor: Option<&storeit_core::Cursor>,
+    limit: usize,
+) -> storeit_core::RepoResult<(String, Vec<storeit_core::ParamValue>)>
+where
+    E: storeit_core::Fetchable,
+{
+    use storeit_core::{Direction, Order};
 
-    #[cfg(not(feature = "tokio_postgres"))]
-    fn test_select_default_q() {
-        let sql = select_by_id::<User>("id");
-        assert_eq!(sql, "SELECT id, email FROM users WHERE id = ?");
+    if limit == 0 {
+        return Err(invalid_input("page limit must be at least 1".to_string()));
     }
+    let backward = cursor.is_some_and(storeit_core::Cursor::is_before);
+    let mut sql = select_all::<E>();
+    let mut params = Vec::new();
+    if let Some(c) = cursor {
+        let after = keyset_criteria::<E>(orders, c)?;
+        let (cond, values) = render_criteria(dialect, &after, 1);
+        let scope = scope_conditions::<E>(dialect, tenant, values.len() + 1);
+        sql.push_str(" WHERE ");
+        if scope.is_empty() {
+            sql.push_str(&cond);
+        } else {
+            sql.push_str(&format!("({}) AND {}", cond, scope.join(" AND ")));
+        }
+        params = values;
+    } else {
+        sql = where_scoped::<E>(sql, dialect, tenant, 1);
+    }
+    let flipped: Vec<Order> = orders
+        .iter()
+        .map(|o| Order {
+            column: o.column.clone(),
+            direction: match (o.direction, backward) {
+                (Direction::Asc, true) => Direction::Desc,
+                (Direction::Desc, true) => Direction::Asc,
+                (d, false) => d,
+            },
+        })
+        .collect();
+    order_by_list::<E>(&flipped)?;
+    // NULL sorts first ascending, as MySQL and SQLite do by default; Postgres is told so.
+    let keys: Vec<String> = flipped
+        .iter()
+        .map(|o| match (dialect, o.direction) {
+            (Dialect::Postgres, Direction::Asc) => format!("{} ASC NULLS FIRST", o.column),
+            (Dialect::Postgres, Direction::Desc) => format!("{} DESC NULLS LAST", o.column),
+            (_, Direction::Asc) => format!("{} ASC", o.column),
+            (_, Direction::Desc) => format!("{} DESC", o.column),
+        })
+        .collect();
+    sql.push_str(" ORDER BY ");
+    sql.push_str(&keys.join(", "));
+    sql.push_str(&format!(" LIMIT {}", limit.saturating_add(1)));
+    Ok((sql, params))
+}
 
-    #[test]
-    #[cfg(feature = "tokio_postgres")]
-    fn test_delete_default_pg() {
-        let sql = delete_by_id::<User>("id");
-        assert_eq!(sql, "DELETE FROM users WHERE id = $1");
-    }
+/// The rows strictly after (or, for a `before` cursor, strictly before) `cursor` in the sort
+/// keys from [`keyset_orders`]: (k0 > v0) OR (k0 = v0 AND k1 > v1) OR ..., with `<` for
+/// descending keys. NULL sorts before every other value, so NULL keys compare with
+/// `IS NULL`/`IS NOT NULL`. Fails when the cursor was created for a different sort.
+pub fn keyset_criteria<E>(
+    orders: &[storeit_core::Order],
+    cursor: &storeit_core::Cursor,
+) -> storeit_core::RepoResult<storeit_core::Criteria>
+where
+    E: storeit_core::Fetchable,
+{
+    use storeit_core::{Criteria, Direction, ParamValue};
 
-    #[cfg(not(feature = "tokio_postgres"))]
-    fn test_delete_default_q() {
-        let sql = delete_by_id::<User>("id");
-        assert_eq!(sql, "DELETE FROM users WHERE id = ?");
-    }
+    if cursor.columns().iter().ne(orders.iter().map(|o| &o.column))
+        || cursor.values().len() != orders.len()
+    {
+        return Err(invalid_input(format!(
+            "cursor was created for a different sort of {}",
+            E::TABLE
+        )));
+    }
+    let backward = cursor.is_before();
+    let branches = (0..orders.len())
+        .filter_map(|i| {
+            let mut parts: Vec<Criteria> = orders[..i]
+                .iter()
+                .zip(cursor.values())
+                .map(|(o, v)| match v {
+                    ParamValue::Null => Criteria::IsNull(o.column.clone()),
+                    v => Criteria::Eq(o.column.clone(), v.clone()),
+                })
+                .collect();
+            let (col, v) = (orders[i].column.clone(), cursor.values()[i].clone());
+            let ascending = (orders[i].direction == Direction::Asc) != backward;
+            parts.push(match (ascending, v) {
+                (true, ParamValue::Null) => Criteria::Not(Box::new(Criteria::IsNull(col))),
+                (true, v) => Criteria::Gt(col, v),
+                // Nothing sorts before NULL.
+                (false, ParamValue::Null) => return None,
+                (false, v) => {
+                    Criteria::Or(vec![Criteria::Lt(col.clone(), v), Criteria::IsNull(col)])
+                }
+            });
+            Some(if parts.len() == 1 {
+                parts.remove(0)
+            } else {
+                Criteria::And(parts)
+            })
+        })
+        .collect();
+    Ok(Criteria::Or(branches))
+}
 
-    #[test]
-    #[cfg(feature = "tokio_postgres")]
-    fn test_insert_default_pg() {
-        let sql = insert::<User>("id");
-        assert_eq!(sql, "INSERT INTO users (email) VALUES ($1) RETURNING id");
-    }
+/// Render a [`Criteria`](storeit_core::Criteria) tree as a SQL boolean expression.
+/// Dollar placeholders are numbered from `first`, so the expression can follow other
+/// parameters; the returned values are in placeholder order.
+/// Empty `In`/`Or` render as `1 = 0` and an empty `And` as `1 = 1`.
+/// Columns written as `column->key->...` address a value inside a JSON column; see
+/// [`json_path_expr`].
+pub fn render_criteria(
+    dialect: Dialect,
+    criteria: &storeit_core::Criteria,
+    first: usize,
+) -> (String, Vec<storeit_core::ParamValue>) {
+    let mut params = Vec::new();
+    let mut next = first;
+    let sql = render_node(dialect, criteria, &mut next, &mut params);
+    (sql, params)
+}
 
-    #[cfg(not(feature = "tokio_postgres"))]
-    fn test_insert_default_q() {
-        let sql = insert::<User>("id");
-        assert_eq!(sql, "INSERT INTO users (email) VALUES (?)");
-    }
+/// The SQL expression for a criteria column. A plain column renders as itself; a
+/// `column->key->...` reference renders as the text at that key path of the JSON column:
+/// `column->'a'->>'b'` on Postgres, `JSON_UNQUOTE(JSON_EXTRACT(column, '$."a"."b"'))` on MySQL
+/// and `json_extract(column, '$."a"."b"')` on SQLite (JSON1). Keys are quoted, so they may hold
+/// any character except the `->` separator.
+///
+/// Postgres compares the extracted text with text parameters only; the other dialects also
+/// compare numbers and booleans by value.
+pub fn json_path_expr(dialect: Dialect, column: &str) -> std::borrow::Cow<'_, str> {
+    let mut parts = column.split("->");
+    let base = parts.next().unwrap_or_default();
+    let keys: Vec<&str> = parts.collect();
+    let Some((last, init)) = keys.split_last() else {
+        return std::borrow::Cow::Borrowed(column);
+    };
+    let literal = |s: &str| format!("'{}'", s.replace('\'', "''"));
+    let path = || {
+        let mut path = String::from("$");
+        for key in &keys {
+            path.push_str(&format!(
+                ".\"{}\"",
+                key.replace('\\', "\\\\").replace('"', "\\\"")
+            ));
+        }
+        literal(&path)
+    };
+    std::borrow::Cow::Owned(match dialect {
+        Dialect::Postgres => {
+            let mut expr = base.to_string();
+            for key in init {
+                expr.push_str("->");
+                expr.push_str(&literal(key));
+            }
+            format!("{}->>{}", expr, literal(last))
+        }
+        Dialect::MySql => format!("JSON_UNQUOTE(JSON_EXTRACT({}, {}))", base, path()),
+        Dialect::Sqlite => format!("json_extract({}, {})", base, path()),
+    })
+}
 
-    #[test]
-    #[cfg(all(not(feature = "tokio_postgres"), feature = "libsql_returning"))]
-    fn test_insert_with_libsql_returning() {
-        // When libsql_returning is enabled (and not Postgres), the builder should append RETURNING id
-        let sql = insert::<User>("id");
-        assert_eq!(sql, "INSERT INTO users (email) VALUES (?) RETURNING id");
+fn render_node(
+    dialect: Dialect,
+    criteria: &storeit_core::Criteria,
+    next: &mut usize,
+    params: &mut Vec<storeit_core::ParamValue>,
+) -> String {
+    use storeit_core::{Criteria, ParamValue};
+
+    let style = dialect.placeholder();
+    let mut bind = |value: &ParamValue| {
+        params.push(value.clone());
+        let ph = placeholder_n(style, *next);
+        *next += 1;
+        ph
+    };
+    let col = |column: &str| json_path_expr(dialect, column).into_owned();
+    match criteria {
+        Criteria::Eq(c, ParamValue::Null) => format!("{} IS NULL", col(c)),
+        Criteria::Ne(c, ParamValue::Null) => format!("{} IS NOT NULL", col(c)),
+        Criteria::Eq(c, v) => format!("{} = {}", col(c), bind(v)),
+        Criteria::Ne(c, v) => format!("{} <> {}", col(c), bind(v)),
+        Criteria::Lt(c, v) => format!("{} < {}", col(c), bind(v)),
+        Criteria::Le(c, v) => format!("{} <= {}", col(c), bind(v)),
+        Criteria::Gt(c, v) => format!("{} > {}", col(c), bind(v)),
+        Criteria::Ge(c, v) => format!("{} >= {}", col(c), bind(v)),
+        Criteria::Like(c, v) => format!("{} LIKE {}", col(c), bind(v)),
+        Criteria::In(_, values) if values.is_empty() => "1 = 0".to_string(),
+        Criteria::In(c, values) => {
+            let phs: Vec<String> = values.iter().map(&mut bind).collect();
+            format!("{} IN ({})", col(c), phs.join(", "))
+        }
+        Criteria::IsNull(c) => format!("{} IS NULL", col(c)),
+        Criteria::Between(c, low, high) => {
+            let low = bind(low);
+            format!("{} BETWEEN {} AND {}", col(c), low, bind(high))
+        }
+        Criteria::And(parts) => render_group(dialect, parts, " AND ", "1 = 1", next, params),
+        Criteria::Or(parts) => render_group(dialect, parts, " OR ", "1 = 0", next, params),
+        Criteria::Not(inner) => format!("NOT ({})", render_node(dialect, inner, next, params)),
     }
+}
 
-    #[test]
-    fn test_update_default() {
-        let sql = update_by_id::<User>("id");
-        let style = placeholder_style();
-        let expected = format!(
-            "UPDATE users SET email = {} WHERE id = {}",
-            placeholder_n(style, 1),
-            placeholder_n(style, 2)
-        );
-        assert_eq!(sql, expected);
+fn render_group(
+    dialect: Dialect,
+    parts: &[storeit_core::Criteria],
+    joiner: &str,
+    empty: &str,
+    next: &mut usize,
+    params: &mut Vec<storeit_core::ParamValue>,
+) -> String {
+    match parts {
+        [] => empty.to_string(),
+        [only] => render_node(dialect, only, next, params),
+        _ => {
+            // Only nested groups need parentheses; comparisons and NOT bind tighter than AND/OR.
+            let rendered: Vec<String> = parts
+                .iter()
+                .map(|p| {
+                    let sql = render_node(dialect, p, next, params);
+                    match p {
+                        storeit_core::Criteria::And(_) | storeit_core::Criteria::Or(_) => {
+                            format!("({})", sql)
+                        }
+                        _ => sql,
+                    }
+                })
+                .collect();
+            rendered.join(joiner)
+        }
     }
+}
 
-    #[test]
-    fn test_select_all_default() {
-        let sql = select_all::<User>();
-        assert_eq!(sql, "SELECT id, email FROM users");
-    }
+/// Build SELECT <cols> FROM <table> WHERE <criteria>. Returns (SQL, params).
+pub fn select_by_criteria<E>(
+    dialect: Dialect,
+    tenant: bool,
+    criteria: &storeit_core::Criteria,
+) -> storeit_core::RepoResult<(String, Vec<storeit_core::ParamValue>)>
+where
+    E: storeit_core::Fetchable,
+{
+    let (cond, params) = scoped_criteria::<E>(dialect, tenant, criteria, 1)?;
+    let sql = format!(
+        "SELECT {} FROM {} WHERE {}",
+        E::SELECT_COLUMNS.join(", "),
+        E::TABLE,
+        cond
+    );
+    Ok((sql, params))
+}
 
-    #[test]
-    fn test_select_by_field_default() {
-        let sql = select_by_field::<User>("email");
-        let expected = format!(
-            "SELECT id, email FROM users WHERE email = {}",
-            first_placeholder(placeholder_style())
-        );
-        assert_eq!(sql, expected);
-    }
+/// Build SELECT COUNT(*) FROM <table> WHERE <criteria>. Returns (SQL, params).
+pub fn count_by_criteria<E>(
+    dialect: Dialect,
+    tenant: bool,
+    criteria: &storeit_core::Criteria,
+) -> storeit_core::RepoResult<(String, Vec<storeit_core::ParamValue>)>
+where
+    E: storeit_core::Fetchable,
+{
+    let (cond, params) = scoped_criteria::<E>(dialect, tenant, criteria, 1)?;
+    Ok((
+        format!("SELECT COUNT(*) FROM {} WHERE {}", E::TABLE, cond),
+        params,
+    ))
+}
 
-    #[test]
-    fn test_select_by_is_null_default() {
-        let sql = select_by_is_null::<User>("email");
-        assert_eq!(sql, "SELECT id, email FROM users WHERE email IS NULL");
-    }
+/// Build SELECT 1 FROM <table> WHERE <criteria> LIMIT 1. A returned row means a match exists.
+pub fn exists_by_criteria<E>(
+    dialect: Dialect,
+    tenant: bool,
+    criteria: &storeit_core::Criteria,
+) -> storeit_core::RepoResult<(String, Vec<storeit_core::ParamValue>)>
+where
+    E: storeit_core::Fetchable,
+{
+    let (cond, params) = scoped_criteria::<E>(dialect, tenant, criteria, 1)?;
+    Ok((
+        format!("SELECT 1 FROM {} WHERE {} LIMIT 1", E::TABLE, cond),
+        params,
+    ))
+}
+
+/// Build DELETE FROM <table> WHERE <criteria>. Returns (SQL, params).
+/// For entities with a `SOFT_DELETE_COLUMN` this is instead
+/// `UPDATE <table> SET <column> = <now> WHERE <criteria> AND <column> IS NULL`, with the
+/// deletion time from [`storeit_core::clock::now`] as the first parameter.
+pub fn delete_by_criteria<E>(
+    dialect: Dialect,
+    tenant: bool,
+    criteria: &storeit_core::Criteria,
+) -> storeit_core::RepoResult<(String, Vec<storeit_core::ParamValue>)>
+where
+    E: storeit_core::Fetchable,
+{
+    use storeit_core::ToParamValue;
+
+    let Some(col) = E::SOFT_DELETE_COLUMN else {
+        let (cond, params) = scoped_criteria::<E>(dialect, tenant, criteria, 1)?;
+        return Ok((format!("DELETE FROM {} WHERE {}", E::TABLE, cond), params));
+    };
+    let (cond, mut values) = scoped_criteria::<E>(dialect, tenant, criteria, 2)?;
+    let mut params = vec![storeit_core::clock::now().to_param_value()];
+    params.append(&mut values);
+    Ok((
+        format!(
+            "UPDATE {} SET {} = {} WHERE {}",
+            E::TABLE,
+            col,
+            placeholder_n(dialect.placeholder(), 1),
+            cond
+        ),
+        params,
+    ))
+}
+
+// --- DDL from `storeit_core::schema` table definitions ---
+
+/// The type of `column` in `dialect`. MySQL cannot index `TEXT`/`BLOB` columns without a
+/// prefix length, so key, unique and indexed ones become `VARCHAR(255)`/`VARBINARY(255)`.
+pub fn column_type(
+    dialect: Dialect,
+    column: &storeit_core::schema::ColumnDef,
+    keyed: bool,
+) -> String {
+    use storeit_core::schema::SqlType;
+
+    let indexed = keyed || column.unique || column.indexed;
+    let name = match (&column.sql_type, dialect) {
+        (SqlType::Custom(custom), _) => return custom.clone(),
+        (SqlType::Integer, Dialect::MySql) => "INT",
+        (SqlType::Integer | SqlType::BigInt | SqlType::Boolean, Dialect::Sqlite) => "INTEGER",
+        (SqlType::Integer, _) => "INTEGER",
+        (SqlType::BigInt, _) => "BIGINT",
+        (SqlType::Double, Dialect::Postgres) => "DOUBLE PRECISION",
+        (SqlType::Double, Dialect::MySql) => "DOUBLE",
+        (SqlType::Double, Dialect::Sqlite) => "REAL",
+        (SqlType::Boolean, _) => "BOOLEAN",
+        (SqlType::Text, Dialect::MySql) if indexed => "VARCHAR(255)",
+        (SqlType::Bytes, Dialect::MySql) if indexed => "VARBINARY(255)",
+        (SqlType::Text, _) => "TEXT",
+        (SqlType::Bytes, Dialect::Postgres) => "BYTEA",
+        (SqlType::Bytes, _) => "BLOB",
+        (SqlType::Uuid, Dialect::Postgres) => "UUID",
+        (SqlType::Uuid, Dialect::MySql) => "CHAR(36)",
+        (SqlType::Timestamp, Dialect::Postgres) => "TIMESTAMP",
+        (SqlType::TimestampTz, Dialect::Postgres) => "TIMESTAMPTZ",
+        (SqlType::Timestamp | SqlType::TimestampTz, Dialect::MySql) => "DATETIME(6)",
+        (SqlType::Date, Dialect::Postgres | Dialect::MySql) => "DATE",
+        (SqlType::Time, Dialect::Postgres) => "TIME",
+        (SqlType::Time, Dialect::MySql) => "TIME(6)",
+        (SqlType::Decimal, Dialect::Postgres) => "NUMERIC",
+        (SqlType::Decimal, Dialect::MySql) => "DECIMAL(38, 10)",
+        (SqlType::Json, Dialect::Postgres) => "JSONB",
+        (SqlType::Json, Dialect::MySql) => "JSON",
+        // SQLite stores UUIDs, temporal values, decimals and JSON as their text forms.
+        (_, Dialect::Sqlite) => "TEXT",
+    };
+    name.to_string()
+}
+
+/// Build `CREATE TABLE IF NOT EXISTS` for `table`, one column per line. A database-generated
+/// integer key becomes `BIGSERIAL` (Postgres), `AUTO_INCREMENT` (MySQL) or
+/// `INTEGER PRIMARY KEY AUTOINCREMENT` (SQLite); a composite key gets a trailing
+/// `PRIMARY KEY (...)` clause. Indexes are separate statements, see [`create_indexes`].
+pub fn create_table(dialect: Dialect, table: &storeit_core::schema::TableDef) -> String {
+    use storeit_core::schema::SqlType;
+
+    let single_key = match table.primary_key.as_slice() {
+        [only] => Some(only.as_str()),
+        _ => None,
+    };
+    let mut lines: Vec<String> = table
+        .columns
+        .iter()
+        .map(|c| {
+            let keyed = table.primary_key.contains(&c.name);
+            if single_key == Some(c.name.as_str()) {
+                let integer = matches!(c.sql_type, SqlType::Integer | SqlType::BigInt);
+                let ty = match dialect {
+                    Dialect::Postgres if table.key_generated && integer => {
+                        if c.sql_type == SqlType::Integer {
+                            "SERIAL"
+                        } else {
+                            "BIGSERIAL"
+                        }
+                        .to_string()
+                    }
+                    Dialect::MySql if table.key_generated && integer => {
+                        format!("{} AUTO_INCREMENT", column_type(dialect, c, true))
+                    }
+                    _ => column_type(dialect, c, true),
+                };
+                let autoincrement = if dialect == Dialect::Sqlite && table.key_generated && integer
+                {
+                    " AUTOINCREMENT"
+                } else {
+                    ""
+                };
+                return format!("  {} {} PRIMARY KEY{}", c.name, ty, autoincrement);
+            }
+            let mut line = format!("  {} {}", c.name, column_type(dialect, c, keyed));
+            if keyed || !c.nullable {
+                line.push_str(" NOT NULL");
+            }
+            if c.unique {
+                line.push_str(" UNIQUE");
+            }
+            line
+        })
+        .collect();
+    if single_key.is_none() && !table.primary_key.is_empty() {
+        lines.push(format!("  PRIMARY KEY ({})", table.primary_key.join(", ")));
+    }
+    format!(
+        "CREATE TABLE IF NOT EXISTS {} (\n{}\n)",
+        table.name,
+        lines.join(",\n")
+    )
+}
+
+/// Build one `CREATE INDEX idx_<table>_<column>` statement per `#[fetch(index)]` column.
+/// Postgres and SQLite add `IF NOT EXISTS`, which MySQL does not support.
+pub fn create_indexes(dialect: Dialect, table: &storeit_core::schema::TableDef) -> Vec<String> {
+    table
+        .columns
+        .iter()
+        .filter(|c| c.indexed)
+        .map(|c| create_index(dialect, &table.name, &c.name, false))
+        .collect()
+}
+
+fn create_index(dialect: Dialect, table: &str, column: &str, unique: bool) -> String {
+    let if_not_exists = match dialect {
+        Dialect::MySql => "",
+        Dialect::Postgres | Dialect::Sqlite => "IF NOT EXISTS ",
+    };
+    format!(
+        "CREATE {}INDEX {}idx_{}_{} ON {} ({})",
+        if unique { "UNIQUE " } else { "" },
+        if_not_exists,
+        table,
+        column,
+        table,
+        column
+    )
+}
+
+/// Build the statements applying `changes` (from `storeit_core::schema::diff`) to the live
+/// table of `table`:
+/// - `CreateTable` renders [`create_table`] and [`create_indexes`] and ignores the rest.
+/// - Columns are added with `ADD COLUMN`, without `UNIQUE`: uniqueness comes from the
+///   `CreateIndex` change that accompanies them.
+/// - Existing rows need a value for a new NOT NULL column. MySQL fills in the type's implicit
+///   default; elsewhere the statement fills in a placeholder (the type's zero value, `NULL`
+///   for a custom type) behind a `--` comment saying so, to be edited before applying. On
+///   Postgres that is a `DEFAULT`, dropped again right after.
+/// - Nullability changes use `ALTER COLUMN ... SET/DROP NOT NULL` on Postgres and
+///   `MODIFY COLUMN` with the full column type on MySQL.
+/// - SQLite cannot change nullability, add a NOT NULL column without a default, or drop an
+///   indexed column in place. Changes involving those rebuild the table instead: create
+///   `<table>__storeit_new`, copy the kept columns, drop the old table, rename the new one
+///   and recreate the indexes. Indexes not described by the entity are lost. The rebuild runs
+///   between `PRAGMA foreign_keys=OFF` and `PRAGMA foreign_key_check`/`foreign_keys=ON`, so
+///   dropping the old table does not cascade to rows referencing it; the pragma has no
+///   effect inside a transaction, so apply the script outside one (`storeit_migrate`
+///   switches foreign keys off for its runs itself).
+pub fn alter_table(
+    dialect: Dialect,
+    table: &storeit_core::schema::TableDef,
+    changes: &[storeit_core::schema::SchemaChange],
+) -> Vec<String> {
+    use storeit_core::schema::SchemaChange;
+
+    if changes.contains(&SchemaChange::CreateTable) {
+        let mut statements = vec![create_table(dialect, table)];
+        statements.extend(create_indexes(dialect, table));
+        return statements;
+    }
+    let rebuild = dialect == Dialect::Sqlite
+        && changes.iter().any(|change| match change {
+            SchemaChange::SetNullable { .. } | SchemaChange::DropColumn(_) => true,
+            SchemaChange::AddColumn(name) => table.column(name).is_some_and(|c| !c.nullable),
+            _ => false,
+        });
+    if rebuild {
+        return rebuild_table(table, changes);
+    }
+    changes
+        .iter()
+        .filter_map(|change| {
+            let statement = match change {
+                SchemaChange::CreateTable => return None,
+                SchemaChange::AddColumn(name) => {
+                    let column = table.column(name)?;
+                    let add = format!(
+                        "ALTER TABLE {} ADD COLUMN {} {}",
+                        table.name,
+                        name,
+                        column_type(dialect, column, false)
+                    );
+                    match dialect {
+                        _ if column.nullable => add,
+                        Dialect::MySql => format!("{} NOT NULL", add),
+                        _ => {
+                            let fill = fill_value(dialect, &column.sql_type);
+                            return Some(vec![
+                                format!(
+                                    "{}{} NOT NULL DEFAULT {}",
+                                    fill_comment(&table.name, &[column], dialect),
+                                    add,
+                                    fill
+                                ),
+                                format!(
+                                    "ALTER TABLE {} ALTER COLUMN {} DROP DEFAULT",
+                                    table.name, name
+                                ),
+                            ]);
+                        }
+                    }
+                }
+                SchemaChange::DropColumn(name) => {
+                    format!("ALTER TABLE {} DROP COLUMN {}", table.name, name)
+                }
+                SchemaChange::SetNullable { column, nullable } => match dialect {
+                    Dialect::MySql => {
+                        let def = table.column(column)?;
+                        format!(
+                            "ALTER TABLE {} MODIFY COLUMN {} {}{}",
+                            table.name,
+                            column,
+                            column_type(dialect, def, false),
+                            if *nullable { " NULL" } else { " NOT NULL" }
+                        )
+                    }
+                    _ => format!(
+                        "ALTER TABLE {} ALTER COLUMN {} {} NOT NULL",
+                        table.name,
+                        column,
+                        if *nullable { "DROP" } else { "SET" }
+                    ),
+                },
+                SchemaChange::CreateIndex { column, unique } => {
+                    create_index(dialect, &table.name, column, *unique)
+                }
+            };
+            Some(vec![statement])
+        })
+        .flatten()
+        .collect()
+}
+
+// The value existing rows get for a new NOT NULL column: the type's zero value, or NULL (which
+// fails) for a custom type.
+fn fill_value(dialect: Dialect, sql_type: &storeit_core::schema::SqlType) -> &'static str {
+    use storeit_core::schema::SqlType;
+
+    match (sql_type, dialect) {
+        (SqlType::Integer | SqlType::BigInt | SqlType::Double | SqlType::Decimal, _) => "0",
+        (SqlType::Boolean, Dialect::Sqlite) => "0",
+        (SqlType::Boolean, _) => "FALSE",
+        (SqlType::Text, _) => "''",
+        (SqlType::Bytes, Dialect::Sqlite) => "X''",
+        (SqlType::Bytes, _) => "''",
+        (SqlType::Uuid, _) => "'00000000-0000-0000-0000-000000000000'",
+        (SqlType::Timestamp | SqlType::TimestampTz, _) => "CURRENT_TIMESTAMP",
+        (SqlType::Date, _) => "CURRENT_DATE",
+        (SqlType::Time, _) => "CURRENT_TIME",
+        (SqlType::Json, _) => "'{}'",
+        (SqlType::Custom(_), _) => "NULL",
+    }
+}
+
+// A `--` line, ending in a newline, flagging the placeholders `fill_value` supplies.
+fn fill_comment(
+    table: &str,
+    columns: &[&storeit_core::schema::ColumnDef],
+    dialect: Dialect,
+) -> String {
+    let fills: Vec<String> = columns
+        .iter()
+        .map(|c| {
+            format!(
+                "{}.{} = {}",
+                table,
+                c.name,
+                fill_value(dialect, &c.sql_type)
+            )
+        })
+        .collect();
+    format!(
+        "-- Existing rows need a value for new NOT NULL columns; placeholders: {}. Edit before applying.\n",
+        fills.join(", ")
+    )
+}
+
+// The SQLite table rebuild of `alter_table`.
+fn rebuild_table(
+    table: &storeit_core::schema::TableDef,
+    changes: &[storeit_core::schema::SchemaChange],
+) -> Vec<String> {
+    use storeit_core::schema::SchemaChange;
+
+    let staging = storeit_core::schema::TableDef {
+        name: format!("{}__storeit_new", table.name),
+        ..table.clone()
+    };
+    let (added, kept): (Vec<_>, Vec<_>) = table
+        .columns
+        .iter()
+        .partition(|c| changes.contains(&SchemaChange::AddColumn(c.name.clone())));
+    // New nullable columns are left NULL; new NOT NULL ones get a placeholder.
+    let filled: Vec<_> = added.into_iter().filter(|c| !c.nullable).collect();
+    let targets: Vec<&str> = kept
+        .iter()
+        .chain(&filled)
+        .map(|c| c.name.as_str())
+        .collect();
+    let sources: Vec<&str> = kept
+        .iter()
+        .map(|c| c.name.as_str())
+        .chain(
+            filled
+                .iter()
+                .map(|c| fill_value(Dialect::Sqlite, &c.sql_type)),
+        )
+        .collect();
+    let comment = if filled.is_empty() {
+        String::new()
+    } else {
+        fill_comment(&table.name, &filled, Dialect::Sqlite)
+    };
+    let mut statements = vec![
+        "PRAGMA foreign_keys=OFF".to_string(),
+        create_table(Dialect::Sqlite, &staging),
+        format!(
+            "{}INSERT INTO {} ({}) SELECT {} FROM {}",
+            comment,
+            staging.name,
+            targets.join(", "),
+            sources.join(", "),
+            table.name
+        ),
+        format!("DROP TABLE {}", table.name),
+        format!("ALTER TABLE {} RENAME TO {}", staging.name, table.name),
+    ];
+    statements.extend(create_indexes(Dialect::Sqlite, table));
+    statements.push("PRAGMA foreign_key_check".to_string());
+    statements.push("PRAGMA foreign_keys=ON".to_string());
+    statements
+}
+
+/// The text of a migration file applying `changes`: [`alter_table`]'s statements, each
+/// terminated by `;` and a newline. Empty when there is nothing to change.
+pub fn migration_script(
+    dialect: Dialect,
+    table: &storeit_core::schema::TableDef,
+    changes: &[storeit_core::schema::SchemaChange],
+) -> String {
+    alter_table(dialect, table, changes)
+        .iter()
+        .map(|statement| format!("{};\n", statement))
+        .collect()
+}
+
+/// The statements creating the table of `E` and its indexes, in execution order.
+pub fn create_schema<E>(dialect: Dialect) -> Vec<String>
+where
+    E: storeit_core::Schema,
+{
+    let table = E::table_def();
+    let mut statements = vec![create_table(dialect, &table)];
+    statements.extend(create_indexes(dialect, &table));
+    statements
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use storeit_macros::Entity; // derive macro
+
+    #[derive(Entity)]
+
+    struct User {
+        #[fetch(id)]
+        id: i64,
+        email: String,
+    }
+
+    const ALL_DIALECTS: [Dialect; 3] = [Dialect::Postgres, Dialect::MySql, Dialect::Sqlite];
+
+    #[test]
+    fn test_dialect_placeholder_styles() {
+        assert_eq!(Dialect::Postgres.placeholder(), Placeholder::Dollar);
+        assert_eq!(Dialect::MySql.placeholder(), Placeholder::Question);
+        assert_eq!(Dialect::Sqlite.placeholder(), Placeholder::Question);
+        assert!(Dialect::Postgres.returning_inserts());
+        assert!(!Dialect::MySql.returning_inserts());
+        assert_eq!(
+            Dialect::Sqlite.returning_inserts(),
+            cfg!(feature = "libsql_returning")
+        );
+    }
+
+    #[test]
+    fn test_select_default_pg() {
+        let sql = select_by_id::<User>(Dialect::Postgres, true, &["id"]);
+        assert_eq!(sql, "SELECT id, email FROM users WHERE id = $1");
+    }
+
+    #[test]
+    fn test_select_default_q() {
+        let sql = select_by_id::<User>(Dialect::MySql, true, &["id"]);
+        assert_eq!(sql, "SELECT id, email FROM users WHERE id = ?");
+    }
+
+    #[test]
+    fn test_delete_default_pg() {
+        let sql = delete_by_id::<User>(Dialect::Postgres, true, &["id"]);
+        assert_eq!(sql, "DELETE FROM users WHERE id = $1");
+    }
+
+    #[test]
+    fn test_delete_default_q() {
+        let sql = delete_by_id::<User>(Dialect::Sqlite, true, &["id"]);
+        assert_eq!(sql, "DELETE FROM users WHERE id = ?");
+    }
+
+    #[test]
+    fn test_insert_default_pg() {
+        let sql = insert::<User>(Dialect::Postgres, "id");
+        assert_eq!(sql, "INSERT INTO users (email) VALUES ($1) RETURNING id");
+    }
+
+    #[test]
+    fn test_insert_default_q() {
+        let sql = insert::<User>(Dialect::MySql, "id");
+        assert_eq!(sql, "INSERT INTO users (email) VALUES (?)");
+    }
+
+    #[test]
+    #[cfg(not(feature = "libsql_returning"))]
+    fn test_insert_sqlite_without_returning() {
+        let sql = insert::<User>(Dialect::Sqlite, "id");
+        assert_eq!(sql, "INSERT INTO users (email) VALUES (?)");
+    }
+
+    #[test]
+    #[cfg(feature = "libsql_returning")]
+    fn test_insert_with_libsql_returning() {
+        // When libsql_returning is enabled, SQLite inserts append RETURNING id
+        let sql = insert::<User>(Dialect::Sqlite, "id");
+        assert_eq!(sql, "INSERT INTO users (email) VALUES (?) RETURNING id");
+    }
+
+    #[test]
+    fn test_mixed_dialects_in_one_binary() {
+        // Statements for different backends can be built side by side.
+        assert_eq!(
+            select_by_field::<User>(Dialect::Postgres, true, "email"),
+            "SELECT id, email FROM users WHERE email = $1"
+        );
+        assert_eq!(
+            select_by_field::<User>(Dialect::MySql, true, "email"),
+            "SELECT id, email FROM users WHERE email = ?"
+        );
+    }
+
+    #[test]
+    fn test_update_default() {
+        for dialect in ALL_DIALECTS {
+            let sql = update_by_id::<User>(dialect, true, &["id"]);
+            let style = dialect.placeholder();
+            let expected = format!(
+                "UPDATE users SET email = {} WHERE id = {}",
+                placeholder_n(style, 1),
+                placeholder_n(style, 2)
+            );
+            assert_eq!(sql, expected);
+        }
+    }
+
+    #[derive(Entity)]
+    #[allow(dead_code)]
+    #[entity(table = "accounts")]
+    struct Account {
+        #[fetch(id)]
+        id: i64,
+        email: String,
+        #[fetch(version)]
+        version: i64,
+    }
+
+    #[test]
+    fn test_update_versioned() {
+        use storeit_core::{ParamValue, Updatable};
+
+        assert_eq!(Account::VERSION_COLUMN, Some("version"));
+        assert_eq!(Account::UPDATE_COLUMNS, &["email"]);
+        assert_eq!(
+            update_by_id::<Account>(Dialect::Postgres, true, &["id"]),
+            "UPDATE accounts SET email = $1, version = version + 1 WHERE id = $2 AND version = $3"
+        );
+        assert_eq!(
+            update_by_id::<Account>(Dialect::MySql, true, &["id"]),
+            "UPDATE accounts SET email = ?, version = version + 1 WHERE id = ? AND version = ?"
+        );
+
+        let mut a = Account {
+            id: 4,
+            email: "a@x".into(),
+            version: 2,
+        };
+        let values = a.update_values();
+        assert!(matches!(
+            values.as_slice(),
+            [
+                ParamValue::String(_),
+                ParamValue::I64(4),
+                ParamValue::I64(2)
+            ]
+        ));
+        a.bump_version();
+        assert_eq!(a.version, 3);
+        assert_eq!(User::VERSION_COLUMN, None);
+    }
+
+    #[derive(Entity)]
+    #[allow(dead_code)]
+    #[entity(table = "customers", soft_delete = "deleted_at")]
+    struct Customer {
+        #[fetch(id)]
+        id: i64,
+        email: String,
+        deleted_at: Option<std::time::SystemTime>,
+    }
+
+    #[test]
+    fn test_soft_delete_statements() {
+        use storeit_core::{Criteria, ParamValue};
+
+        let pg = Dialect::Postgres;
+        assert_eq!(
+            select_by_id::<Customer>(pg, true, &["id"]),
+            "SELECT id, email, deleted_at FROM customers WHERE id = $1 AND deleted_at IS NULL"
+        );
+        assert_eq!(
+            select_by_id_with_deleted::<Customer>(pg, true, &["id"]),
+            "SELECT id, email, deleted_at FROM customers WHERE id = $1"
+        );
+        assert_eq!(
+            soft_delete_by_id::<Customer>(pg, true, &["id"]).unwrap(),
+            "UPDATE customers SET deleted_at = $1 WHERE id = $2 AND deleted_at IS NULL"
+        );
+        assert_eq!(
+            update_by_id::<Customer>(pg, true, &["id"]),
+            "UPDATE customers SET email = $1 WHERE id = $2 AND deleted_at IS NULL"
+        );
+        assert_eq!(
+            restore_by_id::<Customer>(pg, true, &["id"]).unwrap(),
+            "UPDATE customers SET deleted_at = NULL WHERE id = $1 AND deleted_at IS NOT NULL"
+        );
+        assert!(soft_delete_by_id::<User>(pg, true, &["id"]).is_none());
+        assert_eq!(
+            select_by_field::<Customer>(pg, true, "email"),
+            "SELECT id, email, deleted_at FROM customers WHERE email = $1 AND deleted_at IS NULL"
+        );
+        assert_eq!(
+            select_count_all::<Customer>(pg, true),
+            "SELECT COUNT(*) FROM customers WHERE deleted_at IS NULL"
+        );
+
+        let (sql, params) =
+            select_by_criteria::<Customer>(pg, true, &Criteria::eq("email", "a@x")).unwrap();
+        assert_eq!(
+            sql,
+            "SELECT id, email, deleted_at FROM customers WHERE email = $1 AND deleted_at IS NULL"
+        );
+        assert_eq!(params.len(), 1);
+
+        let (sql, params) = delete_by_criteria::<Customer>(
+            pg,
+            true,
+            &Criteria::eq("email", "a@x").or(Criteria::eq("email", "b@x")),
+        )
+        .unwrap();
+        assert_eq!(
+            sql,
+            "UPDATE customers SET deleted_at = $1 WHERE (email = $2 OR email = $3) AND deleted_at IS NULL"
+        );
+        assert!(matches!(params[0], ParamValue::Timestamp(_)));
+        assert_eq!(params.len(), 3);
+
+        let (sql, _) =
+            keyset_select::<Customer>(pg, true, &[storeit_core::Order::asc("id")], None, 2)
+                .expect("keyset");
+        assert_eq!(
+            sql,
+            "SELECT id, email, deleted_at FROM customers WHERE deleted_at IS NULL ORDER BY id ASC NULLS FIRST LIMIT 3"
+        );
+    }
+
+    #[derive(Entity)]
+    #[allow(dead_code)]
+    #[entity(table = "notes", tenant = "tenant_id")]
+    struct Note {
+        #[fetch(id)]
+        id: i64,
+        tenant_id: String,
+        body: String,
+    }
+
+    #[test]
+    fn test_tenant_statements() {
+        use storeit_core::{Criteria, Fetchable, Insertable, Updatable};
+
+        let pg = Dialect::Postgres;
+        assert_eq!(Note::TENANT_COLUMN, Some("tenant_id"));
+        assert_eq!(Note::INSERT_COLUMNS, &["tenant_id", "body"]);
+        assert_eq!(Note::UPDATE_COLUMNS, &["body"]);
+        assert_eq!(
+            select_by_id::<Note>(pg, true, &["id"]),
+            "SELECT id, tenant_id, body FROM notes WHERE id = $1 AND tenant_id = $2"
+        );
+        assert_eq!(
+            update_by_id::<Note>(pg, true, &["id"]),
+            "UPDATE notes SET body = $1 WHERE id = $2 AND tenant_id = $3"
+        );
+        assert_eq!(
+            delete_by_id::<Note>(Dialect::MySql, true, &["id"]),
+            "DELETE FROM notes WHERE id = ? AND tenant_id = ?"
+        );
+        assert_eq!(
+            select_by_in::<Note>(pg, true, "body", 2),
+            "SELECT id, tenant_id, body FROM notes WHERE body IN ($1, $2) AND tenant_id = $3"
+        );
+        assert_eq!(
+            select_columns_by_id::<Note>(pg, true, &["id", "body"], &["id"]),
+            "SELECT id, body FROM notes WHERE id = $1 AND tenant_id = $2"
+        );
+        assert_eq!(
+            select_columns_by_field::<Note>(Dialect::Sqlite, true, &["body"], "body"),
+            "SELECT body FROM notes WHERE body = ? AND tenant_id = ?"
+        );
+        assert_eq!(
+            select_count_all::<Note>(pg, true),
+            "SELECT COUNT(*) FROM notes WHERE tenant_id = $1"
+        );
+        assert_eq!(
+            select_page::<Note>(pg, true, &storeit_core::Pageable::new(1, 5)).unwrap(),
+            "SELECT id, tenant_id, body FROM notes WHERE tenant_id = $1 LIMIT 5 OFFSET 5"
+        );
+
+        let (sql, params) = select_by_criteria::<Note>(
+            pg,
+            true,
+            &Criteria::eq("body", "a").or(Criteria::eq("body", "b")),
+        )
+        .unwrap();
+        assert_eq!(
+            sql,
+            "SELECT id, tenant_id, body FROM notes WHERE (body = $1 OR body = $2) AND tenant_id = $3"
+        );
+        assert_eq!(params.len(), 2);
+
+        let (sql, params) = keyset_select::<Note>(
+            pg,
+            true,
+            &[storeit_core::Order::asc("id")],
+            Some(&storeit_core::Cursor::after(
+                vec!["id".into()],
+                vec![storeit_core::ParamValue::I64(3)],
+            )),
+            2,
+        )
+        .expect("keyset");
+        assert_eq!(
+            sql,
+            "SELECT id, tenant_id, body FROM notes WHERE (id > $1) AND tenant_id = $2 ORDER BY id ASC NULLS FIRST LIMIT 3"
+        );
+        assert_eq!(params.len(), 1);
+
+        assert_eq!(
+            select_by_is_null::<Note>(pg, true, "body"),
+            "SELECT id, tenant_id, body FROM notes WHERE body IS NULL AND tenant_id = $1"
+        );
+        assert_eq!(
+            select_by_is_not_null::<Note>(Dialect::MySql, true, "body"),
+            "SELECT id, tenant_id, body FROM notes WHERE body IS NOT NULL AND tenant_id = ?"
+        );
+        assert_eq!(
+            select_with_pagination::<Note>(pg, true, Some("id"), Some(2), None),
+            "SELECT id, tenant_id, body FROM notes WHERE tenant_id = $1 ORDER BY id LIMIT 2"
+        );
+    }
+
+    #[test]
+    fn test_tenant_flag_off_leaves_the_condition_out() {
+        use storeit_core::Criteria;
+
+        let pg = Dialect::Postgres;
+        assert_eq!(
+            select_by_id::<Note>(pg, false, &["id"]),
+            "SELECT id, tenant_id, body FROM notes WHERE id = $1"
+        );
+        assert_eq!(
+            update_by_id::<Note>(pg, false, &["id"]),
+            "UPDATE notes SET body = $1 WHERE id = $2"
+        );
+        assert_eq!(
+            select_count_all::<Note>(pg, false),
+            "SELECT COUNT(*) FROM notes"
+        );
+        let (sql, _) = select_by_criteria::<Note>(pg, false, &Criteria::eq("body", "a")).unwrap();
+        assert_eq!(sql, "SELECT id, tenant_id, body FROM notes WHERE body = $1");
+        // The flag alone decides, not the ambient tenant context.
+        futures::executor::block_on(storeit_core::tenant::bypass(async {
+            assert_eq!(
+                select_by_id::<Note>(pg, true, &["id"]),
+                "SELECT id, tenant_id, body FROM notes WHERE id = $1 AND tenant_id = $2"
+            );
+        }));
+    }
+
+    #[derive(Entity)]
+    #[allow(dead_code)]
+    #[entity(table = "user_roles")]
+    struct UserRole {
+        #[fetch(id)]
+        user_id: i64,
+        #[fetch(id)]
+        role_id: i64,
+        granted_by: String,
+    }
+
+    #[test]
+    fn test_composite_key_statements() {
+        let cols = <UserRole as storeit_core::Identifiable>::ID_COLUMNS;
+        assert_eq!(cols, &["user_id", "role_id"]);
+        assert_eq!(
+            select_by_id::<UserRole>(Dialect::Postgres, true, cols),
+            "SELECT user_id, role_id, granted_by FROM user_roles WHERE user_id = $1 AND role_id = $2"
+        );
+        assert_eq!(
+            delete_by_id::<UserRole>(Dialect::MySql, true, cols),
+            "DELETE FROM user_roles WHERE user_id = ? AND role_id = ?"
+        );
+        assert_eq!(
+            update_by_id::<UserRole>(Dialect::Postgres, true, cols),
+            "UPDATE user_roles SET granted_by = $1 WHERE user_id = $2 AND role_id = $3"
+        );
+        // Composite key columns are supplied by the caller, so they are part of the INSERT.
+        assert_eq!(
+            insert::<UserRole>(Dialect::MySql, "user_id"),
+            "INSERT INTO user_roles (user_id, role_id, granted_by) VALUES (?, ?, ?)"
+        );
+    }
+
+    #[test]
+    fn test_insert_returning_select_columns() {
+        assert_eq!(
+            insert_returning::<User>(Dialect::Postgres),
+            "INSERT INTO users (email) VALUES ($1) RETURNING id, email"
+        );
+    }
+
+    #[test]
+    fn test_select_all_default() {
+        let sql = select_all::<User>();
+        assert_eq!(sql, "SELECT id, email FROM users");
+    }
+
+    #[test]
+    fn test_select_by_field_default() {
+        for dialect in ALL_DIALECTS {
+            let sql = select_by_field::<User>(dialect, true, "email");
+            let expected = format!(
+                "SELECT id, email FROM users WHERE email = {}",
+                first_placeholder(dialect.placeholder())
+            );
+            assert_eq!(sql, expected);
+        }
+    }
+
+    #[test]
+    fn test_select_by_is_null_default() {
+        let sql = select_by_is_null::<User>(Dialect::Postgres, true, "email");
+        assert_eq!(sql, "SELECT id, email FROM users WHERE email IS NULL");
+    }
 
     #[test]
     fn test_select_by_is_not_null_default() {
-        let sql = select_by_is_not_null::<User>("email");
+        let sql = select_by_is_not_null::<User>(Dialect::Postgres, true, "email");
         assert_eq!(sql, "SELECT id, email FROM users WHERE email IS NOT NULL");
     }
 
     #[test]
     fn test_select_by_in_default() {
-        let sql = select_by_in::<User>("id", 3);
-        let style = placeholder_style();
-        let phs = vec![
-            placeholder_n(style, 1),
-            placeholder_n(style, 2),
-            placeholder_n(style, 3),
-        ]
-        .join(", ");
-        let expected = format!("SELECT id, email FROM users WHERE id IN ({})", phs);
-        assert_eq!(sql, expected);
+        for dialect in ALL_DIALECTS {
+            let sql = select_by_in::<User>(dialect, true, "id", 3);
+            let style = dialect.placeholder();
+            let phs = [
+                placeholder_n(style, 1),
+                placeholder_n(style, 2),
+                placeholder_n(style, 3),
+            ]
+            .join(", ");
+            let expected = format!("SELECT id, email FROM users WHERE id IN ({})", phs);
+            assert_eq!(sql, expected);
+        }
     }
 
     #[test]
     fn test_select_by_not_in_default() {
-        let sql = select_by_not_in::<User>("id", 2);
-        let style = placeholder_style();
-        let phs = vec![placeholder_n(style, 1), placeholder_n(style, 2)].join(", ");
-        let expected = format!("SELECT id, email FROM users WHERE id NOT IN ({})", phs);
-        assert_eq!(sql, expected);
+        for dialect in ALL_DIALECTS {
+            let sql = select_by_not_in::<User>(dialect, true, "id", 2);
+            let style = dialect.placeholder();
+            let phs = [placeholder_n(style, 1), placeholder_n(style, 2)].join(", ");
+            let expected = format!("SELECT id, email FROM users WHERE id NOT IN ({})", phs);
+            assert_eq!(sql, expected);
+        }
     }
 
     #[test]
     fn test_select_with_pagination_none() {
-        let sql = select_with_pagination::<User>(None, None, None);
+        let sql = select_with_pagination::<User>(Dialect::Postgres, true, None, None, None);
         assert_eq!(sql, "SELECT id, email FROM users");
     }
 
     #[test]
     fn test_select_with_pagination_full() {
-        let sql = select_with_pagination::<User>(Some("email DESC"), Some(10), Some(20));
+        let sql = select_with_pagination::<User>(
+            Dialect::Postgres,
+            true,
+            Some("email DESC"),
+            Some(10),
+            Some(20),
+        );
         assert_eq!(
             sql,
             "SELECT id, email FROM users ORDER BY email DESC LIMIT 10 OFFSET 20"
@@ -684,37 +2085,45 @@ mod tests {
 
     #[test]
     fn test_select_with_pagination_order_by_only() {
-        let sql = select_with_pagination::<User>(Some("email ASC"), None, None);
+        let sql =
+            select_with_pagination::<User>(Dialect::Postgres, true, Some("email ASC"), None, None);
         assert_eq!(sql, "SELECT id, email FROM users ORDER BY email ASC");
     }
 
     #[test]
     fn test_select_with_pagination_order_by_empty_only_ignored() {
-        let sql = select_with_pagination::<User>(Some(""), None, None);
+        let sql = select_with_pagination::<User>(Dialect::Postgres, true, Some(""), None, None);
         assert_eq!(sql, "SELECT id, email FROM users");
     }
 
     #[test]
     fn test_select_with_pagination_order_by_whitespace_only_ignored() {
-        let sql = select_with_pagination::<User>(Some("   \t"), None, None);
+        let sql =
+            select_with_pagination::<User>(Dialect::Postgres, true, Some("   \t"), None, None);
         assert_eq!(sql, "SELECT id, email FROM users");
     }
 
     #[test]
     fn test_select_with_pagination_order_by_empty_ignored() {
-        let sql = select_with_pagination::<User>(Some("   \t"), Some(5), Some(0));
+        let sql = select_with_pagination::<User>(
+            Dialect::Postgres,
+            true,
+            Some("   \t"),
+            Some(5),
+            Some(0),
+        );
         assert_eq!(sql, "SELECT id, email FROM users LIMIT 5 OFFSET 0");
     }
 
     #[test]
     fn test_select_with_pagination_limit_only() {
-        let sql = select_with_pagination::<User>(None, Some(7), None);
+        let sql = select_with_pagination::<User>(Dialect::Postgres, true, None, Some(7), None);
         assert_eq!(sql, "SELECT id, email FROM users LIMIT 7");
     }
 
     #[test]
     fn test_select_with_pagination_offset_only() {
-        let sql = select_with_pagination::<User>(None, None, Some(42));
+        let sql = select_with_pagination::<User>(Dialect::Postgres, true, None, None, Some(42));
         assert_eq!(sql, "SELECT id, email FROM users OFFSET 42");
     }
 
@@ -748,37 +2157,41 @@ mod tests {
 
     #[test]
     fn test_custom_table_and_columns_insert_and_update() {
-        let insert_sql = insert::<Person>("id");
-        let style = placeholder_style();
-        let mut expected_insert = format!(
-            "INSERT INTO people (email_address, full_name) VALUES ({}, {})",
-            placeholder_n(style, 1),
-            placeholder_n(style, 2)
-        );
-        if matches!(style, Placeholder::Dollar) {
-            expected_insert.push_str(" RETURNING id");
-        }
-        assert_eq!(insert_sql, expected_insert);
+        for dialect in ALL_DIALECTS {
+            let insert_sql = insert::<Person>(dialect, "id");
+            let style = dialect.placeholder();
+            let mut expected_insert = format!(
+                "INSERT INTO people (email_address, full_name) VALUES ({}, {})",
+                placeholder_n(style, 1),
+                placeholder_n(style, 2)
+            );
+            if dialect.returning_inserts() {
+                expected_insert.push_str(" RETURNING id");
+            }
+            assert_eq!(insert_sql, expected_insert);
 
-        let update_sql = update_by_id::<Person>("id");
-        let expected_update = format!(
-            "UPDATE people SET email_address = {}, full_name = {} WHERE id = {}",
-            placeholder_n(style, 1),
-            placeholder_n(style, 2),
-            placeholder_n(style, 3)
-        );
-        assert_eq!(update_sql, expected_update);
+            let update_sql = update_by_id::<Person>(dialect, true, &["id"]);
+            let expected_update = format!(
+                "UPDATE people SET email_address = {}, full_name = {} WHERE id = {}",
+                placeholder_n(style, 1),
+                placeholder_n(style, 2),
+                placeholder_n(style, 3)
+            );
+            assert_eq!(update_sql, expected_update);
+        }
     }
 
     #[test]
     fn test_select_with_pagination_order_by_and_limit_only() {
-        let sql = select_with_pagination::<User>(Some("id DESC"), Some(3), None);
+        let sql =
+            select_with_pagination::<User>(Dialect::Postgres, true, Some("id DESC"), Some(3), None);
         assert_eq!(sql, "SELECT id, email FROM users ORDER BY id DESC LIMIT 3");
     }
 
     #[test]
     fn test_select_with_pagination_order_by_and_offset_only() {
-        let sql = select_with_pagination::<User>(Some("id ASC"), None, Some(9));
+        let sql =
+            select_with_pagination::<User>(Dialect::Postgres, true, Some("id ASC"), None, Some(9));
         assert_eq!(sql, "SELECT id, email FROM users ORDER BY id ASC OFFSET 9");
     }
 
@@ -790,12 +2203,11 @@ mod tests {
             id: i64,
             email: String,
         }
-        let sql = select_count_all::<User2>();
+        let sql = select_count_all::<User2>(Dialect::Postgres, true);
         assert_eq!(sql, "SELECT COUNT(*) FROM user2s");
     }
 
     #[test]
-    #[cfg(feature = "tokio_postgres")]
     fn test_select_count_by_field_pg() {
         #[derive(Entity)]
         struct User3 {
@@ -803,12 +2215,11 @@ mod tests {
             id: i64,
             email: String,
         }
-        let sql = select_count_by_field::<User3>("email");
+        let sql = select_count_by_field::<User3>(Dialect::Postgres, true, "email");
         assert_eq!(sql, "SELECT COUNT(*) FROM user3s WHERE email = $1");
     }
 
     #[test]
-    #[cfg(not(feature = "tokio_postgres"))]
     fn test_select_count_by_field_q() {
         #[derive(Entity)]
         struct User3 {
@@ -816,22 +2227,20 @@ mod tests {
             id: i64,
             email: String,
         }
-        let sql = select_count_by_field::<User3>("email");
+        let sql = select_count_by_field::<User3>(Dialect::MySql, true, "email");
         assert_eq!(sql, "SELECT COUNT(*) FROM user3s WHERE email = ?");
     }
 
     #[test]
-    #[cfg(feature = "tokio_postgres")]
     fn test_insert_many_pg_numbering_and_returning() {
         // Use Person (2 insert columns) to exercise numbering across multiple rows
-        let sql = insert_many::<Person>(3, "id");
+        let sql = insert_many::<Person>(Dialect::Postgres, 3, "id");
         assert_eq!(sql, "INSERT INTO people (email_address, full_name) VALUES ($1, $2), ($3, $4), ($5, $6) RETURNING id");
     }
 
     #[test]
-    #[cfg(not(feature = "tokio_postgres"))]
     fn test_insert_many_q_placeholders() {
-        let sql = insert_many::<Person>(2, "id");
+        let sql = insert_many::<Person>(Dialect::MySql, 2, "id");
         assert_eq!(
             sql,
             "INSERT INTO people (email_address, full_name) VALUES (?, ?), (?, ?)"
@@ -874,30 +2283,31 @@ mod tests {
         let ins = <TypesEntity as storeit_core::Insertable>::insert_values(&e);
         // Expect 6 values (all except id)
         assert_eq!(ins.len(), 6);
-        // Spot-check that string variants are produced for new types
+        // Portable types map to their native variants.
         match &ins[0] {
-            ParamValue::String(_) => {}
-            _ => panic!("expected String for NaiveDateTime"),
+            // 2020-01-02T03:04:05Z
+            ParamValue::Timestamp(t) => assert_eq!(*t, 1_577_934_245_000_000),
+            _ => panic!("expected Timestamp for NaiveDateTime"),
         }
         match &ins[1] {
-            ParamValue::String(_) => {}
-            _ => panic!("expected String for NaiveDate"),
+            ParamValue::Date(d) => assert_eq!(*d, 18_263),
+            _ => panic!("expected Date for NaiveDate"),
         }
         match &ins[2] {
-            ParamValue::String(_) => {}
-            _ => panic!("expected String for Decimal"),
+            ParamValue::Decimal(s) => assert_eq!(s, "12.345"),
+            _ => panic!("expected Decimal for Decimal"),
         }
         match &ins[3] {
-            ParamValue::String(s) => assert!(!s.is_empty()),
-            _ => panic!("expected String for Uuid"),
+            ParamValue::Uuid(b) => assert_eq!(b, &[0u8; 16]),
+            _ => panic!("expected Uuid for Uuid"),
         }
         match &ins[4] {
             ParamValue::Null => {}
             _ => panic!("expected Null for Option<NaiveDateTime> None"),
         }
         match &ins[5] {
-            ParamValue::String(s) => assert!(!s.is_empty()),
-            _ => panic!("expected String for Option<Uuid> Some"),
+            ParamValue::Uuid(b) => assert_eq!(b, &[0u8; 16]),
+            _ => panic!("expected Uuid for Option<Uuid> Some"),
         }
 
         let upd = <TypesEntity as storeit_core::Updatable>::update_values(&e);
@@ -910,7 +2320,36 @@ mod tests {
     }
 
     #[test]
-    #[cfg(feature = "tokio_postgres")]
+    #[allow(unreachable_code)]
+    fn test_derive_paramvalue_for_bytes_and_times() {
+        use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+        use storeit_core::ParamValue;
+
+        #[derive(Entity)]
+        struct BlobEntity {
+            #[fetch(id)]
+            id: i64,
+            data: Vec<u8>,
+            at: DateTime<Utc>,
+            tod: NaiveTime,
+            opt_d: Option<NaiveDate>,
+        }
+
+        let e = BlobEntity {
+            id: 1,
+            data: vec![0xde, 0xad],
+            at: DateTime::<Utc>::from_timestamp(1, 500_000_000).unwrap(),
+            tod: NaiveTime::from_hms_micro_opt(1, 2, 3, 4).unwrap(),
+            opt_d: NaiveDate::from_ymd_opt(1969, 12, 31),
+        };
+        let ins = <BlobEntity as storeit_core::Insertable>::insert_values(&e);
+        assert!(matches!(&ins[0], ParamValue::Bytes(b) if b == &[0xde, 0xad]));
+        assert!(matches!(ins[1], ParamValue::Timestamp(1_500_000)));
+        assert!(matches!(ins[2], ParamValue::Time(3_723_000_004)));
+        assert!(matches!(ins[3], ParamValue::Date(-1)));
+    }
+
+    #[test]
     fn test_upsert_pg_on_conflict_do_update_single_col() {
         #[derive(Entity)]
         struct UserU {
@@ -923,7 +2362,6 @@ mod tests {
     }
 
     #[test]
-    #[cfg(feature = "tokio_postgres")]
     fn test_upsert_pg_on_conflict_do_update_multi_col() {
         #[derive(Entity)]
         #[entity(table = "people")]
@@ -954,21 +2392,21 @@ mod tests {
             email: "a@x".into(),
             active: true,
         };
-        let (where_sql, params) = build_where_and(&[
-            ("email", storeit_core::ParamValue::String("a@x".into())),
-            ("active", storeit_core::ParamValue::Bool(true)),
-        ]);
-        let style = placeholder_style();
-        let expected = match style {
-            Placeholder::Dollar => format!(
-                "WHERE email = {} AND active = {}",
-                placeholder_n(style, 1),
-                placeholder_n(style, 2)
-            ),
-            Placeholder::Question => "WHERE email = ? AND active = ?".to_string(),
-        };
-        assert_eq!(where_sql, expected);
-        assert_eq!(params.len(), 2);
+        for dialect in ALL_DIALECTS {
+            let (where_sql, params) = build_where_and(
+                dialect,
+                &[
+                    ("email", storeit_core::ParamValue::String("a@x".into())),
+                    ("active", storeit_core::ParamValue::Bool(true)),
+                ],
+            );
+            let expected = match dialect.placeholder() {
+                Placeholder::Dollar => "WHERE email = $1 AND active = $2",
+                Placeholder::Question => "WHERE email = ? AND active = ?",
+            };
+            assert_eq!(where_sql, expected);
+            assert_eq!(params.len(), 2);
+        }
     }
 
     #[test]
@@ -990,30 +2428,15 @@ mod tests {
             vec![("email", storeit_core::ParamValue::String("a@x".into()))],
             vec![("active", storeit_core::ParamValue::Bool(true))],
         ];
-        let (where_sql, params) = build_where_or(&groups);
-        let style = placeholder_style();
-        let expected = match style {
-            Placeholder::Dollar => format!(
-                "WHERE ({}) OR ({})",
-                format!("email = {}", placeholder_n(style, 1)),
-                format!("active = {}", placeholder_n(style, 2))
-            ),
-            Placeholder::Question => "WHERE (email = ?) OR (active = ?)".to_string(),
-        };
-        assert_eq!(where_sql, expected);
-        assert_eq!(params.len(), 2);
-    }
-
-    #[test]
-    fn test_select_where_wraps() {
-        #[derive(Entity)]
-        struct U3 {
-            #[fetch(id)]
-            id: i64,
-            email: String,
+        for dialect in ALL_DIALECTS {
+            let (where_sql, params) = build_where_or(dialect, &groups);
+            let expected = match dialect.placeholder() {
+                Placeholder::Dollar => "WHERE (email = $1) OR (active = $2)",
+                Placeholder::Question => "WHERE (email = ?) OR (active = ?)",
+            };
+            assert_eq!(where_sql, expected);
+            assert_eq!(params.len(), 2);
         }
-        let sql = select_where::<U3>("WHERE email = ?");
-        assert!(sql.starts_with("SELECT id, email FROM u3s WHERE email"));
     }
 
     #[test]
@@ -1024,21 +2447,25 @@ mod tests {
             id: i64,
             email: String,
         }
-        let (sql, params) =
-            keyset_by_id::<U4>("id", Some(storeit_core::ParamValue::I64(10)), 25, true);
-        let style = placeholder_style();
-        let ph = match style {
-            Placeholder::Dollar => placeholder_n(style, 1),
-            Placeholder::Question => "?".to_string(),
-        };
-        assert_eq!(
-            sql,
-            format!(
-                "SELECT id, email FROM u4s WHERE id > {} ORDER BY id ASC LIMIT 25",
-                ph
-            )
-        );
-        assert_eq!(params.len(), 1);
+        for dialect in ALL_DIALECTS {
+            let (sql, params) = keyset_by_id::<U4>(
+                dialect,
+                true,
+                "id",
+                Some(storeit_core::ParamValue::I64(10)),
+                25,
+                true,
+            );
+            let ph = first_placeholder(dialect.placeholder());
+            assert_eq!(
+                sql,
+                format!(
+                    "SELECT id, email FROM u4s WHERE id > {} ORDER BY id ASC LIMIT 25",
+                    ph
+                )
+            );
+            assert_eq!(params.len(), 1);
+        }
     }
 
     #[test]
@@ -1048,13 +2475,12 @@ mod tests {
             #[fetch(id)]
             id: i64,
         }
-        let (sql, params) = keyset_by_id::<U5>("id", None, 5, false);
+        let (sql, params) = keyset_by_id::<U5>(Dialect::Postgres, true, "id", None, 5, false);
         assert_eq!(sql, "SELECT id FROM u5s ORDER BY id DESC LIMIT 5");
         assert!(params.is_empty());
     }
 
     #[test]
-    #[cfg(feature = "mysql_async")]
     fn test_upsert_mysql_on_duplicate_key_update() {
         #[derive(Entity)]
         struct UserM {
@@ -1063,10 +2489,393 @@ mod tests {
             email: String,
         }
         let sql = upsert_mysql_on_duplicate_key_update::<UserM>();
-        let expected = match placeholder_style() {
-            Placeholder::Dollar => "INSERT INTO user_ms (email) VALUES ($1) ON DUPLICATE KEY UPDATE email = VALUES(email)".to_string(),
-            Placeholder::Question => "INSERT INTO user_ms (email) VALUES (?) ON DUPLICATE KEY UPDATE email = VALUES(email)".to_string(),
+        assert_eq!(
+            sql,
+            "INSERT INTO user_ms (email) VALUES (?) ON DUPLICATE KEY UPDATE email = VALUES(email)"
+        );
+    }
+
+    #[test]
+    fn test_render_criteria_numbers_placeholders_across_nodes() {
+        use storeit_core::Criteria;
+        let c = Criteria::ge("age", 18)
+            .and(Criteria::in_list("role", ["admin", "owner"]))
+            .and(Criteria::like("email", "%@x").or(Criteria::is_null("email")))
+            .and(!Criteria::between("score", 1, 5));
+        let (sql, params) = render_criteria(Dialect::Postgres, &c, 3);
+        assert_eq!(
+            sql,
+            "age >= $3 AND role IN ($4, $5) AND (email LIKE $6 OR email IS NULL) \
+             AND NOT (score BETWEEN $7 AND $8)"
+        );
+        assert_eq!(params.len(), 6);
+        assert!(matches!(params[1], storeit_core::ParamValue::String(ref s) if s == "admin"));
+
+        let (sql, _) = render_criteria(Dialect::MySql, &c, 3);
+        assert_eq!(
+            sql,
+            "age >= ? AND role IN (?, ?) AND (email LIKE ? OR email IS NULL) \
+             AND NOT (score BETWEEN ? AND ?)"
+        );
+    }
+
+    #[test]
+    fn test_render_criteria_edge_cases() {
+        use storeit_core::{Criteria, ParamValue};
+        let (sql, params) = render_criteria(
+            Dialect::Sqlite,
+            &Criteria::Eq("email".into(), ParamValue::Null),
+            1,
+        );
+        assert_eq!(sql, "email IS NULL");
+        assert!(params.is_empty());
+        let (sql, _) = render_criteria(Dialect::Sqlite, &Criteria::ne("email", None::<String>), 1);
+        assert_eq!(sql, "email IS NOT NULL");
+        let (sql, params) = render_criteria(
+            Dialect::Postgres,
+            &Criteria::in_list("id", Vec::<i64>::new()),
+            1,
+        );
+        assert_eq!(sql, "1 = 0");
+        assert!(params.is_empty());
+        assert_eq!(
+            render_criteria(Dialect::Postgres, &Criteria::all(), 1).0,
+            "1 = 1"
+        );
+        assert_eq!(
+            render_criteria(Dialect::Postgres, &Criteria::Or(vec![]), 1).0,
+            "1 = 0"
+        );
+    }
+
+    #[test]
+    fn test_render_criteria_json_paths() {
+        use storeit_core::Criteria;
+        let c = Criteria::eq("settings->ui->theme", "dark").and(Criteria::is_null("meta->tag"));
+        let (sql, params) = render_criteria(Dialect::Postgres, &c, 2);
+        assert_eq!(
+            sql,
+            "settings->'ui'->>'theme' = $2 AND meta->>'tag' IS NULL"
+        );
+        assert_eq!(params.len(), 1);
+        assert_eq!(
+            render_criteria(Dialect::MySql, &c, 1).0,
+            "JSON_UNQUOTE(JSON_EXTRACT(settings, '$.\"ui\".\"theme\"')) = ? AND JSON_UNQUOTE(JSON_EXTRACT(meta, '$.\"tag\"')) IS NULL"
+        );
+        assert_eq!(
+            render_criteria(Dialect::Sqlite, &Criteria::gt("stats->it's", 3), 1).0,
+            "json_extract(stats, '$.\"it''s\"') > ?"
+        );
+        assert_eq!(json_path_expr(Dialect::Postgres, "email"), "email");
+        assert_eq!(
+            json_path_expr(Dialect::Postgres, "doc->it's"),
+            "doc->>'it''s'"
+        );
+        assert_eq!(
+            json_path_expr(Dialect::Sqlite, r#"doc->a"b"#),
+            r#"json_extract(doc, '$."a\"b"')"#
+        );
+    }
+
+    #[test]
+    fn test_statements_by_criteria() {
+        use storeit_core::Criteria;
+        let c = Criteria::eq("email", "a@x").and(Criteria::ne("id", 1i64));
+        let (sql, params) = select_by_criteria::<User>(Dialect::Postgres, true, &c).unwrap();
+        assert_eq!(
+            sql,
+            "SELECT id, email FROM users WHERE email = $1 AND id <> $2"
+        );
+        assert_eq!(params.len(), 2);
+        let (sql, _) = count_by_criteria::<User>(Dialect::MySql, true, &c).unwrap();
+        assert_eq!(
+            sql,
+            "SELECT COUNT(*) FROM users WHERE email = ? AND id <> ?"
+        );
+        let (sql, _) = exists_by_criteria::<User>(Dialect::Sqlite, true, &c).unwrap();
+        assert_eq!(
+            sql,
+            "SELECT 1 FROM users WHERE email = ? AND id <> ? LIMIT 1"
+        );
+        let (sql, _) = delete_by_criteria::<User>(Dialect::Postgres, true, &c).unwrap();
+        assert_eq!(sql, "DELETE FROM users WHERE email = $1 AND id <> $2");
+    }
+
+    #[test]
+    fn test_criteria_rejects_unknown_columns() {
+        use storeit_core::{Criteria, RepoError};
+        let bad = Criteria::eq("email", "a@x").and(Criteria::is_null("1 = 1 OR email"));
+        assert!(matches!(
+            select_by_criteria::<User>(Dialect::Postgres, true, &bad),
+            Err(RepoError::Backend { .. })
+        ));
+        assert!(count_by_criteria::<User>(Dialect::MySql, true, &bad).is_err());
+        assert!(exists_by_criteria::<User>(Dialect::Sqlite, true, &bad).is_err());
+        let nested = Criteria::Not(Box::new(Criteria::eq("nope->key", "x")));
+        assert!(delete_by_criteria::<User>(Dialect::Postgres, true, &nested).is_err());
+        // A JSON path is checked by its base column; the keys are quoted.
+        assert!(select_by_criteria::<User>(
+            Dialect::Postgres,
+            true,
+            &Criteria::eq("email->k", "x")
+        )
+        .is_ok());
+        // The soft-delete column can be filtered on even when no field maps to it.
+        assert!(count_by_criteria::<Customer>(
+            Dialect::Postgres,
+            true,
+            &Criteria::is_not_null("deleted_at")
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_select_page_validates_sort_and_size() {
+        use storeit_core::{Pageable, RepoError, Sort};
+        let p = Pageable::new(2, 10).with_sort(Sort::desc("email").then_asc("id"));
+        assert_eq!(
+            select_page::<User>(Dialect::Postgres, true, &p).unwrap(),
+            "SELECT id, email FROM users ORDER BY email DESC, id ASC LIMIT 10 OFFSET 20"
+        );
+        assert_eq!(
+            select_page::<User>(Dialect::Postgres, true, &Pageable::new(0, 5)).unwrap(),
+            "SELECT id, email FROM users LIMIT 5 OFFSET 0"
+        );
+        assert_eq!(
+            select_page_by_field::<User>(Dialect::Postgres, true, "email", &p).unwrap(),
+            "SELECT id, email FROM users WHERE email = $1 ORDER BY email DESC, id ASC LIMIT 10 OFFSET 20"
+        );
+
+        let injected = Pageable::new(0, 5).with_sort(Sort::asc("id; DROP TABLE users"));
+        assert!(matches!(
+            select_page::<User>(Dialect::Postgres, true, &injected),
+            Err(RepoError::Backend { .. })
+        ));
+        assert!(
+            select_page_by_field::<User>(Dialect::MySql, true, "email", &Pageable::new(0, 0))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_keyset_select_expands_mixed_directions() {
+        use storeit_core::{Cursor, Order, ParamValue};
+        let orders = keyset_orders::<User>(&[Order::desc("email")], &["id"]).unwrap();
+        assert_eq!(orders, vec![Order::desc("email"), Order::asc("id")]);
+
+        let (sql, params) =
+            keyset_select::<User>(Dialect::Postgres, true, &orders, None, 10).unwrap();
+        assert_eq!(
+            sql,
+            "SELECT id, email FROM users ORDER BY email DESC NULLS LAST, id ASC NULLS FIRST LIMIT 11"
+        );
+        assert!(params.is_empty());
+
+        let keys = vec![ParamValue::String("m@x".into()), ParamValue::I64(7)];
+        let cols = vec!["email".to_string(), "id".to_string()];
+        let after = Cursor::after(cols.clone(), keys.clone());
+        let (sql, params) =
+            keyset_select::<User>(Dialect::Postgres, true, &orders, Some(&after), 10).unwrap();
+        assert_eq!(
+            sql,
+            "SELECT id, email FROM users WHERE (email < $1 OR email IS NULL) \
+             OR (email = $2 AND id > $3) \
+             ORDER BY email DESC NULLS LAST, id ASC NULLS FIRST LIMIT 11"
+        );
+        assert_eq!(params.len(), 3);
+
+        // A NULL key compares with IS NULL; nothing sorts before it descending.
+        let null_key = Cursor::after(cols.clone(), vec![ParamValue::Null, ParamValue::I64(7)]);
+        let (sql, params) =
+            keyset_select::<User>(Dialect::Sqlite, true, &orders, Some(&null_key), 10).unwrap();
+        assert_eq!(
+            sql,
+            "SELECT id, email FROM users WHERE email IS NULL AND id > ? \
+             ORDER BY email DESC, id ASC LIMIT 11"
+        );
+        assert!(matches!(params[..], [ParamValue::I64(7)]));
+        let null_before = Cursor::before(cols.clone(), vec![ParamValue::Null, ParamValue::I64(7)]);
+        let (sql, _) =
+            keyset_select::<User>(Dialect::Sqlite, true, &orders, Some(&null_before), 10).unwrap();
+        assert_eq!(
+            sql,
+            "SELECT id, email FROM users WHERE NOT (email IS NULL) \
+             OR (email IS NULL AND (id < ? OR id IS NULL)) \
+             ORDER BY email ASC, id DESC LIMIT 11"
+        );
+
+        let before = Cursor::before(cols, keys);
+        let (sql, _) =
+            keyset_select::<User>(Dialect::MySql, true, &orders, Some(&before), 10).unwrap();
+        assert_eq!(
+            sql,
+            "SELECT id, email FROM users WHERE email > ? OR (email = ? AND (id < ? OR id IS NULL)) \
+             ORDER BY email ASC, id DESC LIMIT 11"
+        );
+
+        let other_sort = keyset_orders::<User>(&[], &["id"]).unwrap();
+        assert!(
+            keyset_select::<User>(Dialect::Sqlite, true, &other_sort, Some(&after), 10).is_err()
+        );
+        assert!(keyset_select::<User>(Dialect::Sqlite, true, &orders, None, 0).is_err());
+        assert!(keyset_orders::<User>(&[Order::asc("nope")], &["id"]).is_err());
+    }
+
+    #[test]
+    fn test_create_table_per_dialect() {
+        use chrono::{DateTime, NaiveDate, Utc};
+        use rust_decimal::Decimal;
+        use uuid::Uuid;
+
+        #[derive(Entity)]
+        #[allow(dead_code)]
+        #[entity(table = "articles")]
+        struct Article {
+            #[fetch(id)]
+            id: Option<i64>,
+            #[fetch(unique)]
+            slug: String,
+            #[fetch(index)]
+            author_id: i32,
+            body: Option<String>,
+            rating: f64,
+            published: bool,
+            published_on: Option<NaiveDate>,
+            updated: DateTime<Utc>,
+            price: Decimal,
+            token: Uuid,
+            #[fetch(sql_type = "SMALLINT")]
+            kind: String,
+            #[fetch(skip)]
+            cached: bool,
+        }
+
+        assert_eq!(
+            create_table(Dialect::Postgres, &<Article as storeit_core::Schema>::table_def()),
+            "CREATE TABLE IF NOT EXISTS articles (\n  id BIGSERIAL PRIMARY KEY,\n  slug TEXT NOT NULL UNIQUE,\n  author_id INTEGER NOT NULL,\n  body TEXT,\n  rating DOUBLE PRECISION NOT NULL,\n  published BOOLEAN NOT NULL,\n  published_on DATE,\n  updated TIMESTAMPTZ NOT NULL,\n  price NUMERIC NOT NULL,\n  token UUID NOT NULL,\n  kind SMALLINT NOT NULL\n)"
+        );
+        assert_eq!(
+            create_schema::<Article>(Dialect::MySql),
+            [
+                "CREATE TABLE IF NOT EXISTS articles (\n  id BIGINT AUTO_INCREMENT PRIMARY KEY,\n  slug VARCHAR(255) NOT NULL UNIQUE,\n  author_id INT NOT NULL,\n  body TEXT,\n  rating DOUBLE NOT NULL,\n  published BOOLEAN NOT NULL,\n  published_on DATE,\n  updated DATETIME(6) NOT NULL,\n  price DECIMAL(38, 10) NOT NULL,\n  token CHAR(36) NOT NULL,\n  kind SMALLINT NOT NULL\n)",
+                "CREATE INDEX idx_articles_author_id ON articles (author_id)",
+            ]
+        );
+        assert_eq!(
+            create_schema::<Article>(Dialect::Sqlite),
+            [
+                "CREATE TABLE IF NOT EXISTS articles (\n  id INTEGER PRIMARY KEY AUTOINCREMENT,\n  slug TEXT NOT NULL UNIQUE,\n  author_id INTEGER NOT NULL,\n  body TEXT,\n  rating REAL NOT NULL,\n  published INTEGER NOT NULL,\n  published_on TEXT,\n  updated TEXT NOT NULL,\n  price TEXT NOT NULL,\n  token TEXT NOT NULL,\n  kind SMALLINT NOT NULL\n)",
+                "CREATE INDEX IF NOT EXISTS idx_articles_author_id ON articles (author_id)",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_create_table_keys_and_soft_delete() {
+        assert_eq!(
+            create_table(Dialect::Postgres, &<UserRole as storeit_core::Schema>::table_def()),
+            "CREATE TABLE IF NOT EXISTS user_roles (\n  user_id BIGINT NOT NULL,\n  role_id BIGINT NOT NULL,\n  granted_by TEXT NOT NULL,\n  PRIMARY KEY (user_id, role_id)\n)"
+        );
+        // A soft-delete column without a field is still created.
+        #[derive(Entity)]
+        #[allow(dead_code)]
+        #[entity(table = "tags", soft_delete = "removed_at")]
+        struct Tag {
+            #[fetch(id, generated = false)]
+            name: String,
+        }
+        assert_eq!(
+            create_table(Dialect::MySql, &<Tag as storeit_core::Schema>::table_def()),
+            "CREATE TABLE IF NOT EXISTS tags (\n  name VARCHAR(255) PRIMARY KEY,\n  removed_at DATETIME(6)\n)"
+        );
+        let customers = <Customer as storeit_core::Schema>::table_def();
+        assert_eq!(customers.columns.len(), 3);
+        assert!(customers.column("deleted_at").unwrap().nullable);
+        assert!(create_indexes(Dialect::Postgres, &customers).is_empty());
+    }
+
+    #[test]
+    fn test_alter_table_per_dialect() {
+        use storeit_core::schema::{ColumnDef, SchemaChange, SqlType, TableDef};
+
+        let table = TableDef {
+            name: "users".into(),
+            columns: vec![
+                ColumnDef::new("id", SqlType::BigInt),
+                ColumnDef {
+                    unique: true,
+                    ..ColumnDef::new("email", SqlType::Text)
+                },
+                ColumnDef {
+                    nullable: true,
+                    ..ColumnDef::new("nickname", SqlType::Text)
+                },
+            ],
+            primary_key: vec!["id".into()],
+            key_generated: true,
         };
-        assert_eq!(sql, expected);
+        let changes = [
+            SchemaChange::AddColumn("email".into()),
+            SchemaChange::SetNullable {
+                column: "nickname".into(),
+                nullable: true,
+            },
+            SchemaChange::DropColumn("legacy".into()),
+            SchemaChange::CreateIndex {
+                column: "email".into(),
+                unique: true,
+            },
+        ];
+        assert_eq!(
+            migration_script(Dialect::Postgres, &table, &changes),
+            "-- Existing rows need a value for new NOT NULL columns; placeholders: users.email = ''. Edit before applying.\n\
+             ALTER TABLE users ADD COLUMN email TEXT NOT NULL DEFAULT '';\n\
+             ALTER TABLE users ALTER COLUMN email DROP DEFAULT;\n\
+             ALTER TABLE users ALTER COLUMN nickname DROP NOT NULL;\n\
+             ALTER TABLE users DROP COLUMN legacy;\n\
+             CREATE UNIQUE INDEX IF NOT EXISTS idx_users_email ON users (email);\n"
+        );
+        assert_eq!(
+            alter_table(Dialect::MySql, &table, &changes),
+            vec![
+                "ALTER TABLE users ADD COLUMN email VARCHAR(255) NOT NULL",
+                "ALTER TABLE users MODIFY COLUMN nickname TEXT NULL",
+                "ALTER TABLE users DROP COLUMN legacy",
+                "CREATE UNIQUE INDEX idx_users_email ON users (email)",
+            ]
+        );
+        assert_eq!(
+            alter_table(Dialect::Sqlite, &table, &changes),
+            vec![
+                "PRAGMA foreign_keys=OFF".to_string(),
+                "CREATE TABLE IF NOT EXISTS users__storeit_new (\n  id INTEGER PRIMARY KEY AUTOINCREMENT,\n  email TEXT NOT NULL UNIQUE,\n  nickname TEXT\n)".into(),
+                "-- Existing rows need a value for new NOT NULL columns; placeholders: users.email = ''. Edit before applying.\n\
+                 INSERT INTO users__storeit_new (id, nickname, email) SELECT id, nickname, '' FROM users".into(),
+                "DROP TABLE users".into(),
+                "ALTER TABLE users__storeit_new RENAME TO users".into(),
+                "PRAGMA foreign_key_check".into(),
+                "PRAGMA foreign_keys=ON".into(),
+            ]
+        );
+        // Nullable additions and new indexes need no rebuild on SQLite.
+        let in_place = [
+            SchemaChange::AddColumn("nickname".into()),
+            SchemaChange::CreateIndex {
+                column: "nickname".into(),
+                unique: false,
+            },
+        ];
+        assert_eq!(
+            alter_table(Dialect::Sqlite, &table, &in_place),
+            vec![
+                "ALTER TABLE users ADD COLUMN nickname TEXT",
+                "CREATE INDEX IF NOT EXISTS idx_users_nickname ON users (nickname)",
+            ]
+        );
+        assert_eq!(
+            alter_table(Dialect::Postgres, &table, &[SchemaChange::CreateTable]),
+            vec![create_table(Dialect::Postgres, &table)]
+        );
+        assert!(migration_script(Dialect::MySql, &table, &[]).is_empty());
     }
 }
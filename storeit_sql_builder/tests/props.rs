@@ -2,12 +2,14 @@
 
 use proptest::prelude::*;
 use storeit_macros::Entity;
+use storeit_sql_builder::{Dialect, Placeholder};
 
-#[test]
-fn cover_placeholder_variants() {
-    // Ensure both variants are referenced so the enum variants are considered constructed
-    let _d = Placeholder::Dollar;
-    let _q = Placeholder::Question;
+fn dialect_strategy() -> impl Strategy<Value = Dialect> {
+    prop_oneof![
+        Just(Dialect::Postgres),
+        Just(Dialect::MySql),
+        Just(Dialect::Sqlite)
+    ]
 }
 
 #[derive(Entity)]
@@ -24,11 +26,11 @@ struct PersonP {
 proptest! {
     // Property: insert_many placeholders equal rows * columns; numbering continuous in PG.
     #[test]
-    fn insert_many_placeholder_count(rows in 1usize..10) {
-        let sql = storeit_sql_builder::insert_many::<PersonP>(rows, "id");
+    fn insert_many_placeholder_count(dialect in dialect_strategy(), rows in 1usize..10) {
+        let sql = storeit_sql_builder::insert_many::<PersonP>(dialect, rows, "id");
         let cols = <PersonP as storeit_core::Insertable>::INSERT_COLUMNS.len();
         let expected = rows * cols;
-        match placeholder_style() {
+        match dialect.placeholder() {
             Placeholder::Question => {
                 let count = sql.matches('?').count();
                 prop_assert_eq!(count, expected);
@@ -47,11 +49,11 @@ proptest! {
 proptest! {
     // Property: build_where_and/build_where_or placeholder count equals params length.
     #[test]
-    fn where_builders_placeholder_count(a in any::<bool>(), b in any::<bool>()) {
-        let params = vec![("flag_a", storeit_core::ParamValue::Bool(a)), ("flag_b", storeit_core::ParamValue::Bool(b))];
-        let (wa_sql, wa_params) = storeit_sql_builder::build_where_and(&params);
-        let (wo_sql, wo_params) = storeit_sql_builder::build_where_or(&vec![vec![("flag_a", storeit_core::ParamValue::Bool(a))], vec![("flag_b", storeit_core::ParamValue::Bool(b))]]);
-        match placeholder_style() {
+    fn where_builders_placeholder_count(dialect in dialect_strategy(), a in any::<bool>(), b in any::<bool>()) {
+        let params = [("flag_a", storeit_core::ParamValue::Bool(a)), ("flag_b", storeit_core::ParamValue::Bool(b))];
+        let (wa_sql, wa_params) = storeit_sql_builder::build_where_and(dialect, &params);
+        let (wo_sql, wo_params) = storeit_sql_builder::build_where_or(dialect, &[vec![("flag_a", storeit_core::ParamValue::Bool(a))], vec![("flag_b", storeit_core::ParamValue::Bool(b))]]);
+        match dialect.placeholder() {
             Placeholder::Question => {
                 prop_assert_eq!(wa_sql.matches('?').count(), wa_params.len());
                 prop_assert_eq!(wo_sql.matches('?').count(), wo_params.len());
@@ -65,21 +67,3 @@ proptest! {
         }
     }
 }
-
-// Local helpers mirroring crate-internal types to avoid private visibility issues.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Placeholder {
-    Dollar,
-    Question,
-}
-
-fn placeholder_style() -> Placeholder {
-    #[cfg(feature = "tokio_postgres")]
-    {
-        Placeholder::Dollar
-    }
-    #[cfg(not(feature = "tokio_postgres"))]
-    {
-        Placeholder::Question
-    }
-}
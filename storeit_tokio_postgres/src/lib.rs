@@ -10,18 +10,19 @@ mod backend {
     use std::cell::RefCell;
     use std::collections::HashMap;
     use std::marker::PhantomData;
-    use std::sync::Mutex;
+    use std::sync::{Mutex, OnceLock};
+    use storeit_core::schema::{self, LiveColumn, LiveIndex, SchemaChange, SchemaReport};
     use storeit_core::transactions::{
         Isolation, Propagation, TransactionContext, TransactionDefinition, TransactionManager,
     };
     use storeit_core::{
-        Fetchable, Identifiable, Insertable, ParamValue, RepoError, RepoResult, Repository,
-        RowAdapter, Updatable,
-    };
-    use tokio_postgres::{
-        types::{FromSql, ToSql},
-        Client, NoTls, Row,
+        Criteria, Cursor, CursorPage, EntityListener, Fetchable, Identifiable, Insertable,
+        KeyValues, Listenable, Listeners, Order, Page, Pageable, ParamValue, Projection, RepoError,
+        RepoResult, Repository, RowAdapter, Schema, ToParamValue, Updatable,
     };
+    use storeit_sql_builder::Dialect;
+    use tokio_postgres::types::{to_sql_checked, Format, FromSql, IsNull, Kind, ToSql, Type};
+    use tokio_postgres::{Client, NoTls, Row};
 
     // Task-local state for current transaction client and savepoint depth.
     tokio::task_local! {
@@ -29,6 +30,66 @@ mod backend {
         static PG_SP_DEPTH: RefCell<usize>;
     }
 
+    /// Translate a tokio-postgres error into a structured [`RepoError`] using its SQLSTATE.
+    ///
+    /// Errors without a SQLSTATE are treated as connection loss when the client reports it
+    /// is closed, and remain an opaque `RepoError::Backend` otherwise.
+    pub fn map_pg_error(e: tokio_postgres::Error) -> RepoError {
+        let (state, detail) = match e.as_db_error() {
+            Some(db) => (
+                db.code().code().to_string(),
+                if db.code() == &tokio_postgres::error::SqlState::NOT_NULL_VIOLATION {
+                    db.column().map(str::to_string)
+                } else {
+                    db.constraint().map(str::to_string)
+                },
+            ),
+            None => match e.code() {
+                Some(code) => (code.code().to_string(), None),
+                None if e.is_closed() => {
+                    return RepoError::ConnectionLost {
+                        source: Box::new(e),
+                    }
+                }
+                None => return RepoError::backend(e),
+            },
+        };
+        classify_sqlstate(&state, detail, Box::new(e))
+    }
+
+    /// Classify a SQLSTATE. `detail` is the constraint name, or the column name for
+    /// not-null violations.
+    fn classify_sqlstate(
+        state: &str,
+        detail: Option<String>,
+        source: Box<dyn std::error::Error + Send + Sync>,
+    ) -> RepoError {
+        match state {
+            "23505" => RepoError::UniqueViolation {
+                constraint: detail,
+                source,
+            },
+            "23503" => RepoError::ForeignKeyViolation {
+                constraint: detail,
+                source,
+            },
+            "23502" => RepoError::NotNullViolation {
+                column: detail,
+                source,
+            },
+            "40001" => RepoError::SerializationFailure { source },
+            "40P01" => RepoError::Deadlock { source },
+            // query_canceled (statement_timeout) and lock_not_available (lock_timeout)
+            "57014" | "55P03" => RepoError::Timeout { source },
+            "25006" => RepoError::ReadOnlyViolation { source },
+            // connection_exception class, admin/crash shutdown
+            s if s.starts_with("08") || s == "57P01" || s == "57P02" => {
+                RepoError::ConnectionLost { source }
+            }
+            _ => RepoError::Backend { source },
+        }
+    }
+
     fn isolation_sql(isolation: Isolation) -> Option<&'static str> {
         match isolation {
             Isolation::Default => None,
@@ -51,10 +112,11 @@ mod backend {
             }
         }
 
-        async fn connect(&self) -> RepoResult<Client> {
+        /// Open a new client connection, outside any transaction.
+        pub async fn connect(&self) -> RepoResult<Client> {
             let (client, connection) = tokio_postgres::connect(&self.conn_str, NoTls)
                 .await
-                .map_err(RepoError::backend)?;
+                .map_err(map_pg_error)?;
             tokio::spawn(async move {
                 if let Err(e) = connection.await {
                     eprintln!("Postgres connection error: {}", e);
@@ -73,7 +135,7 @@ mod backend {
         where
             T: Fetchable + Identifiable + Insertable + Updatable + Send + Sync + 'static,
             A: RowAdapter<T, Row = Row> + Send + Sync + 'static,
-            T::Key: Clone + Send + Sync + ToSql + Sync,
+            T::Key: Clone + Send + Sync + KeyValues,
         {
             // If a transaction-bound client is present in task-local storage, use it.
             if let Ok(Some(arc_client)) = PG_TX_STACK.try_with(|cell| cell.borrow().last().cloned())
@@ -81,7 +143,9 @@ mod backend {
                 return Ok(TokioPostgresRepository {
                     client: arc_client,
                     adapter,
-                    sql: RepoSql::<T>::new(),
+                    sql: RepoSql::<T>::new(true),
+                    sql_bypass: OnceLock::new(),
+                    listeners: Listeners::default(),
                     _marker: PhantomData,
                 });
             }
@@ -89,6 +153,88 @@ mod backend {
             let client = self.connect().await?;
             Ok(TokioPostgresRepository::new(client, adapter))
         }
+
+        /// Compare `T` with its live table (`information_schema`); see `storeit_core::schema`.
+        pub async fn validate_schema<T: Schema>(&self) -> RepoResult<SchemaReport> {
+            let live = live_columns(&self.connect().await?, T::TABLE).await?;
+            Ok(schema::check(&T::table_def(), &live))
+        }
+
+        /// The changes that would bring `T`'s live table in line with it, for
+        /// `storeit_sql_builder::migration_script`; see `storeit_core::schema::diff`.
+        pub async fn schema_diff<T: Schema>(&self) -> RepoResult<Vec<SchemaChange>> {
+            let client = self.connect().await?;
+            let columns = live_columns(&client, T::TABLE).await?;
+            let indexes = live_indexes(&client, T::TABLE).await?;
+            Ok(schema::diff(&T::table_def(), &columns, &indexes))
+        }
+    }
+
+    // The columns of `table` in the current schema, for `storeit_core::schema::check`.
+    // `information_schema` identifiers are domains over `name`, so they are cast to text.
+    const LIVE_COLUMNS_SQL: &str = "SELECT c.column_name::text, c.is_nullable = 'YES', \
+         c.column_default IS NOT NULL OR c.is_identity = 'YES' OR c.is_generated = 'ALWAYS', \
+         EXISTS (SELECT 1 FROM information_schema.table_constraints tc \
+         JOIN information_schema.key_column_usage k \
+         ON k.constraint_schema = tc.constraint_schema AND k.constraint_name = tc.constraint_name \
+         WHERE tc.constraint_type = 'PRIMARY KEY' AND tc.table_schema = c.table_schema \
+         AND tc.table_name = c.table_name AND k.column_name = c.column_name) \
+         FROM information_schema.columns c \
+         WHERE c.table_schema = current_schema() AND c.table_name = $1::text \
+         ORDER BY c.ordinal_position";
+
+    async fn live_columns(client: &Client, table: &str) -> RepoResult<Vec<LiveColumn>> {
+        let rows = client
+            .query(LIVE_COLUMNS_SQL, &[&table])
+            .await
+            .map_err(map_pg_error)?;
+        rows.iter()
+            .map(|row| {
+                Ok(LiveColumn {
+                    name: row.try_get(0).map_err(RepoError::mapping)?,
+                    nullable: row.try_get(1).map_err(RepoError::mapping)?,
+                    has_default: row.try_get(2).map_err(RepoError::mapping)?,
+                    primary_key: row.try_get(3).map_err(RepoError::mapping)?,
+                })
+            })
+            .collect()
+    }
+
+    // The indexes of `table` in the current schema, including those backing constraints,
+    // with their columns in index order (expression entries are skipped).
+    const LIVE_INDEXES_SQL: &str = "SELECT i.relname::text, ix.indisunique, a.attname::text \
+         FROM pg_index ix \
+         JOIN pg_class t ON t.oid = ix.indrelid \
+         JOIN pg_class i ON i.oid = ix.indexrelid \
+         JOIN pg_namespace n ON n.oid = t.relnamespace \
+         CROSS JOIN LATERAL unnest(ix.indkey::int2[]) WITH ORDINALITY AS k(attnum, ord) \
+         JOIN pg_attribute a ON a.attrelid = t.oid AND a.attnum = k.attnum \
+         WHERE n.nspname = current_schema() AND t.relname = $1::text \
+         ORDER BY i.relname, k.ord";
+
+    async fn live_indexes(client: &Client, table: &str) -> RepoResult<Vec<LiveIndex>> {
+        let rows = client
+            .query(LIVE_INDEXES_SQL, &[&table])
+            .await
+            .map_err(map_pg_error)?;
+        let entries = rows
+            .iter()
+            .map(|row| {
+                Ok((
+                    row.try_get(0).map_err(RepoError::mapping)?,
+                    row.try_get(1).map_err(RepoError::mapping)?,
+                    row.try_get(2).map_err(RepoError::mapping)?,
+                ))
+            })
+            .collect::<RepoResult<Vec<(String, bool, String)>>>()?;
+        Ok(LiveIndex::group(entries))
+    }
+
+    // `values` followed by the bind value of the tenant condition ending every statement of a
+    // tenant-scoped entity.
+    fn scoped<T: Fetchable>(mut values: Vec<ParamValue>) -> RepoResult<Vec<ParamValue>> {
+        values.extend(storeit_core::tenant::filter_values::<T>()?);
+        Ok(values)
     }
 
     /// A helper to convert `ParamValue`s into a `Vec` of owned, boxed `ToSql` trait objects.
@@ -98,21 +244,301 @@ mod backend {
             .iter()
             .map(|v| -> Box<dyn ToSql + Sync + Send> {
                 match v {
-                    ParamValue::String(s) => Box::new(s.clone()),
+                    ParamValue::String(s) => Box::new(TextParam(s.clone())),
                     ParamValue::I32(i) => Box::new(*i),
                     ParamValue::I64(i) => Box::new(*i),
                     ParamValue::F64(f) => Box::new(*f),
                     ParamValue::Bool(b) => Box::new(*b),
-                    ParamValue::Null => Box::new(Option::<i32>::None),
+                    ParamValue::Bytes(b) => Box::new(b.clone()),
+                    ParamValue::Uuid(u) => Box::new(NativeParam::Uuid(*u)),
+                    ParamValue::Timestamp(t) => Box::new(NativeParam::Timestamp(*t)),
+                    ParamValue::Date(d) => Box::new(NativeParam::Date(*d)),
+                    ParamValue::Time(t) => Box::new(NativeParam::Time(*t)),
+                    ParamValue::Decimal(d) => Box::new(NativeParam::Decimal(d.clone())),
+                    ParamValue::Json(j) => Box::new(NativeParam::Json(j.clone())),
+                    ParamValue::Null => Box::new(NullParam),
                 }
             })
             .collect()
     }
 
+    /// Microseconds and days between the Unix epoch and the Postgres epoch (2000-01-01).
+    const PG_EPOCH_MICROS: i64 = 946_684_800_000_000;
+    const PG_EPOCH_DAYS: i32 = 10_957;
+
+    /// Binds the `ParamValue` variants whose native Postgres types have no `ToSql` impl
+    /// without optional driver features, using the binary wire format directly.
+    ///
+    /// Text columns receive the canonical text form, so entities whose columns are still
+    /// `text` keep working.
+    #[derive(Debug)]
+    enum NativeParam {
+        Uuid([u8; 16]),
+        Timestamp(i64),
+        Date(i32),
+        Time(i64),
+        Decimal(String),
+        Json(String),
+    }
+
+    impl NativeParam {
+        fn text(&self) -> String {
+            match self {
+                NativeParam::Uuid(u) => storeit_core::value::format_uuid(u),
+                NativeParam::Timestamp(t) => storeit_core::value::format_timestamp(*t),
+                NativeParam::Date(d) => storeit_core::value::format_date(*d),
+                NativeParam::Time(t) => storeit_core::value::format_time(*t),
+                NativeParam::Decimal(s) | NativeParam::Json(s) => s.clone(),
+            }
+        }
+    }
+
+    impl ToSql for NativeParam {
+        fn to_sql(
+            &self,
+            ty: &Type,
+            out: &mut bytes::BytesMut,
+        ) -> Result<IsNull, Box<dyn std::error::Error + Sync + Send>> {
+            match (self, ty) {
+                (_, &Type::TEXT | &Type::VARCHAR | &Type::BPCHAR) => {
+                    out.extend_from_slice(self.text().as_bytes())
+                }
+                (NativeParam::Uuid(u), &Type::UUID) => out.extend_from_slice(u),
+                (NativeParam::Timestamp(t), &Type::TIMESTAMP | &Type::TIMESTAMPTZ) => {
+                    let micros = t
+                        .checked_sub(PG_EPOCH_MICROS)
+                        .ok_or("timestamp out of range for postgres")?;
+                    out.extend_from_slice(&micros.to_be_bytes())
+                }
+                (NativeParam::Date(d), &Type::DATE) => {
+                    let days = d
+                        .checked_sub(PG_EPOCH_DAYS)
+                        .ok_or("date out of range for postgres")?;
+                    out.extend_from_slice(&days.to_be_bytes())
+                }
+                (NativeParam::Time(t), &Type::TIME) => out.extend_from_slice(&t.to_be_bytes()),
+                // Sent in text format (see `encode_format`); the server parses it.
+                (NativeParam::Decimal(d), &Type::NUMERIC) => out.extend_from_slice(d.as_bytes()),
+                (NativeParam::Json(j), &Type::JSON) => out.extend_from_slice(j.as_bytes()),
+                (NativeParam::Json(j), &Type::JSONB) => {
+                    // jsonb binary format: version byte followed by the JSON text.
+                    out.extend_from_slice(&[1]);
+                    out.extend_from_slice(j.as_bytes());
+                }
+                (param, ty) => {
+                    return Err(
+                        format!("cannot bind {:?} to a column of type {}", param, ty).into(),
+                    )
+                }
+            }
+            Ok(IsNull::No)
+        }
+
+        fn accepts(ty: &Type) -> bool {
+            // Per-variant compatibility is checked in `to_sql`.
+            matches!(
+                *ty,
+                Type::TEXT
+                    | Type::VARCHAR
+                    | Type::BPCHAR
+                    | Type::UUID
+                    | Type::TIMESTAMP
+                    | Type::TIMESTAMPTZ
+                    | Type::DATE
+                    | Type::TIME
+                    | Type::NUMERIC
+                    | Type::JSON
+                    | Type::JSONB
+            )
+        }
+
+        fn encode_format(&self, ty: &Type) -> Format {
+            match (self, ty) {
+                (NativeParam::Decimal(_), &Type::NUMERIC) => Format::Text,
+                _ => Format::Binary,
+            }
+        }
+
+        to_sql_checked!();
+    }
+
+    /// Text for the columns `String` binds to, plus native enum types, which take their
+    /// label as text (e.g. a `#[derive(DbEnum)]` value).
+    #[derive(Debug)]
+    struct TextParam(String);
+
+    impl ToSql for TextParam {
+        fn to_sql(
+            &self,
+            ty: &Type,
+            out: &mut bytes::BytesMut,
+        ) -> Result<IsNull, Box<dyn std::error::Error + Sync + Send>> {
+            if let Kind::Enum(_) = ty.kind() {
+                out.extend_from_slice(self.0.as_bytes());
+                return Ok(IsNull::No);
+            }
+            self.0.to_sql(ty, out)
+        }
+
+        fn accepts(ty: &Type) -> bool {
+            matches!(ty.kind(), Kind::Enum(_)) || <String as ToSql>::accepts(ty)
+        }
+
+        to_sql_checked!();
+    }
+
+    /// SQL `NULL` for a column of any type (`Option::<i32>::None` only binds to `int4`).
+    #[derive(Debug)]
+    struct NullParam;
+
+    impl ToSql for NullParam {
+        fn to_sql(
+            &self,
+            _ty: &Type,
+            _out: &mut bytes::BytesMut,
+        ) -> Result<IsNull, Box<dyn std::error::Error + Sync + Send>> {
+            Ok(IsNull::Yes)
+        }
+
+        fn accepts(_ty: &Type) -> bool {
+            true
+        }
+
+        to_sql_checked!();
+    }
+
+    /// Read column `idx` of `row` as a [`ParamValue`], decoding the Postgres binary format
+    /// of every type a `ParamValue` can represent. Backs `FromColumn`-based entity mapping.
+    pub fn column_value(row: &Row, idx: usize) -> RepoResult<ParamValue> {
+        row.try_get::<_, ColumnValue>(idx)
+            .map(|c| c.0)
+            .map_err(RepoError::mapping)
+    }
+
+    struct ColumnValue(ParamValue);
+
+    impl<'a> FromSql<'a> for ColumnValue {
+        fn from_sql(
+            ty: &Type,
+            raw: &'a [u8],
+        ) -> Result<Self, Box<dyn std::error::Error + Sync + Send>> {
+            fn be<const N: usize>(
+                raw: &[u8],
+            ) -> Result<[u8; N], Box<dyn std::error::Error + Sync + Send>> {
+                raw.try_into()
+                    .map_err(|_| format!("expected {} bytes, got {}", N, raw.len()).into())
+            }
+            let text = |raw: &[u8]| -> Result<String, Box<dyn std::error::Error + Sync + Send>> {
+                Ok(std::str::from_utf8(raw)?.to_string())
+            };
+            let value = match *ty {
+                Type::BOOL => ParamValue::Bool(raw.first().copied().unwrap_or(0) != 0),
+                Type::INT2 => ParamValue::I32(i16::from_be_bytes(be(raw)?).into()),
+                Type::INT4 => ParamValue::I32(i32::from_be_bytes(be(raw)?)),
+                Type::INT8 => ParamValue::I64(i64::from_be_bytes(be(raw)?)),
+                Type::FLOAT4 => ParamValue::F64(f32::from_be_bytes(be(raw)?).into()),
+                Type::FLOAT8 => ParamValue::F64(f64::from_be_bytes(be(raw)?)),
+                Type::TEXT | Type::VARCHAR | Type::BPCHAR | Type::NAME => {
+                    ParamValue::String(text(raw)?)
+                }
+                Type::BYTEA => ParamValue::Bytes(raw.to_vec()),
+                Type::UUID => ParamValue::Uuid(be(raw)?),
+                // `infinity` and `-infinity` are the extreme values and do not fit.
+                Type::TIMESTAMP | Type::TIMESTAMPTZ => ParamValue::Timestamp(
+                    i64::from_be_bytes(be(raw)?)
+                        .checked_add(PG_EPOCH_MICROS)
+                        .ok_or("timestamp out of range")?,
+                ),
+                Type::DATE => ParamValue::Date(
+                    i32::from_be_bytes(be(raw)?)
+                        .checked_add(PG_EPOCH_DAYS)
+                        .ok_or("date out of range")?,
+                ),
+                Type::TIME => ParamValue::Time(i64::from_be_bytes(be(raw)?)),
+                Type::NUMERIC => ParamValue::Decimal(decode_numeric(raw)?),
+                Type::JSON => ParamValue::Json(text(raw)?),
+                // jsonb binary format: version byte followed by the JSON text.
+                Type::JSONB => ParamValue::Json(text(raw.get(1..).unwrap_or_default())?),
+                // Native enum values arrive as their label.
+                ref other if matches!(other.kind(), Kind::Enum(_)) => {
+                    ParamValue::String(text(raw)?)
+                }
+                ref other => return Err(format!("unsupported column type {}", other).into()),
+            };
+            Ok(ColumnValue(value))
+        }
+
+        fn from_sql_null(_ty: &Type) -> Result<Self, Box<dyn std::error::Error + Sync + Send>> {
+            Ok(ColumnValue(ParamValue::Null))
+        }
+
+        fn accepts(_ty: &Type) -> bool {
+            true
+        }
+    }
+
+    /// Render the binary `numeric` format (base-10000 digit groups) as decimal text.
+    fn decode_numeric(raw: &[u8]) -> Result<String, Box<dyn std::error::Error + Sync + Send>> {
+        let word = |i: usize| -> Result<u16, Box<dyn std::error::Error + Sync + Send>> {
+            raw.get(i * 2..i * 2 + 2)
+                .map(|b| u16::from_be_bytes([b[0], b[1]]))
+                .ok_or_else(|| "truncated numeric".into())
+        };
+        let ndigits = word(0)? as usize;
+        let weight = word(1)? as i16 as i64;
+        let sign = word(2)?;
+        let dscale = word(3)? as usize;
+        // Special values (infinities since Postgres 14) carry no digits.
+        match sign {
+            0xC000 => return Ok("NaN".to_string()),
+            0xD000 => return Ok("Infinity".to_string()),
+            0xF000 => return Ok("-Infinity".to_string()),
+            _ => {}
+        }
+        let digit = |k: i64| -> Result<u16, Box<dyn std::error::Error + Sync + Send>> {
+            if k < 0 || k as usize >= ndigits {
+                Ok(0)
+            } else {
+                word(4 + k as usize)
+            }
+        };
+        let mut out = String::new();
+        if sign == 0x4000 {
+            out.push('-');
+        }
+        if weight < 0 {
+            out.push('0');
+        } else {
+            for k in 0..=weight {
+                if k == 0 {
+                    out.push_str(&digit(k)?.to_string());
+                } else {
+                    out.push_str(&format!("{:04}", digit(k)?));
+                }
+            }
+        }
+        if dscale > 0 {
+            let mut frac = String::new();
+            for j in 1..=((dscale + 3) / 4) as i64 {
+                frac.push_str(&format!("{:04}", digit(weight + j)?));
+            }
+            frac.truncate(dscale);
+            out.push('.');
+            out.push_str(&frac);
+        }
+        Ok(out)
+    }
+
     /// Prebuilt SQL strings for common operations, computed once per repository instance.
     struct RepoSql<T> {
+        dialect: Dialect,
+        /// Whether the statements carry the tenant condition (see `statements()`).
+        tenant: bool,
         select_by_id: String,
+        select_by_id_with_deleted: String,
         delete_by_id: String,
+        soft_delete_by_id: Option<String>,
+        restore_by_id: Option<String>,
         insert: String,
         update_by_id: String,
         find_by_field_cache: Mutex<HashMap<String, String>>,
@@ -123,14 +549,36 @@ mod backend {
     where
         T: Fetchable + Identifiable + Insertable + Updatable,
     {
-        fn new() -> Self {
-            let select_by_id = storeit_sql_builder::select_by_id::<T>(T::ID_COLUMN);
-            let delete_by_id = storeit_sql_builder::delete_by_id::<T>(T::ID_COLUMN);
-            let insert = storeit_sql_builder::insert::<T>(T::ID_COLUMN);
-            let update_by_id = storeit_sql_builder::update_by_id::<T>(T::ID_COLUMN);
+        fn new(tenant: bool) -> Self {
+            let dialect = Dialect::Postgres;
+            let select_by_id =
+                storeit_sql_builder::select_by_id::<T>(dialect, tenant, T::ID_COLUMNS);
+            let delete_by_id =
+                storeit_sql_builder::delete_by_id::<T>(dialect, tenant, T::ID_COLUMNS);
+            // RETURNING the selected columns maps the stored row directly, whatever the key shape.
+            let insert = storeit_sql_builder::insert_returning::<T>(dialect);
+            let update_by_id =
+                storeit_sql_builder::update_by_id::<T>(dialect, tenant, T::ID_COLUMNS);
             Self {
+                dialect,
+                tenant,
                 select_by_id,
+                select_by_id_with_deleted: storeit_sql_builder::select_by_id_with_deleted::<T>(
+                    dialect,
+                    tenant,
+                    T::ID_COLUMNS,
+                ),
                 delete_by_id,
+                soft_delete_by_id: storeit_sql_builder::soft_delete_by_id::<T>(
+                    dialect,
+                    tenant,
+                    T::ID_COLUMNS,
+                ),
+                restore_by_id: storeit_sql_builder::restore_by_id::<T>(
+                    dialect,
+                    tenant,
+                    T::ID_COLUMNS,
+                ),
                 insert,
                 update_by_id,
                 find_by_field_cache: Mutex::new(HashMap::new()),
@@ -146,7 +594,7 @@ mod backend {
             if let Some(s) = guard.get(field) {
                 return s.clone();
             }
-            let built = storeit_sql_builder::select_by_field::<T>(field);
+            let built = storeit_sql_builder::select_by_field::<T>(self.dialect, self.tenant, field);
             guard.insert(field.to_string(), built.clone());
             built
         }
@@ -161,25 +609,46 @@ mod backend {
         client: std::sync::Arc<Client>,
         adapter: A,
         sql: RepoSql<T>,
+        /// `sql` without the tenant condition, for `tenant::bypass`; built on first use.
+        sql_bypass: OnceLock<RepoSql<T>>,
+        listeners: Listeners<T>,
         _marker: PhantomData<T>,
     }
 
+    impl<T, A> TokioPostgresRepository<T, A>
+    where
+        T: Fetchable + Identifiable + Insertable + Updatable + 'static,
+        A: RowAdapter<T> + Send + Sync + 'static,
+    {
+        // The cached statements for the current tenant context. Both sets are built with an
+        // explicit tenant flag, so where the repository was constructed does not matter.
+        fn statements(&self) -> &RepoSql<T> {
+            if T::TENANT_COLUMN.is_some() && storeit_core::tenant::is_bypassed() {
+                self.sql_bypass.get_or_init(|| RepoSql::new(false))
+            } else {
+                &self.sql
+            }
+        }
+    }
+
     impl<T, A> TokioPostgresRepository<T, A>
     where
         T: Fetchable + Identifiable + Send + Sync + 'static,
         A: RowAdapter<T, Row = Row> + Send + Sync + 'static,
-        T::Key: Clone + Send + Sync + ToSql + Sync,
+        T::Key: Clone + Send + Sync + KeyValues,
     {
         /// Creates a new repository from an existing `tokio_postgres::Client`.
         pub fn new(client: Client, adapter: A) -> Self
         where
             T: Insertable + Updatable,
         {
-            let sql = RepoSql::<T>::new();
+            let sql = RepoSql::<T>::new(true);
             Self {
                 client: std::sync::Arc::new(client),
                 adapter,
                 sql,
+                sql_bypass: OnceLock::new(),
+                listeners: Listeners::default(),
                 _marker: PhantomData,
             }
         }
@@ -195,7 +664,7 @@ mod backend {
         {
             let (client, connection) = tokio_postgres::connect(conn_str, NoTls)
                 .await
-                .map_err(RepoError::backend)?;
+                .map_err(map_pg_error)?;
             // The connection object must be spawned to process network events.
             tokio::spawn(async move {
                 if let Err(e) = connection.await {
@@ -252,7 +721,7 @@ mod backend {
                     client_arc
                         .batch_execute("BEGIN")
                         .await
-                        .map_err(RepoError::backend)?;
+                        .map_err(map_pg_error)?;
                     if let Some(sql) = isolation_sql(def.isolation) {
                         client_arc.batch_execute(sql).await.ok();
                     }
@@ -297,12 +766,12 @@ mod backend {
                         client_arc
                             .batch_execute("COMMIT")
                             .await
-                            .map_err(RepoError::backend)?;
+                            .map_err(map_pg_error)?;
                     } else {
                         client_arc
                             .batch_execute("ROLLBACK")
                             .await
-                            .map_err(RepoError::backend)?;
+                            .map_err(map_pg_error)?;
                     }
                     PG_TX_STACK.with(|cell| {
                         let _ = cell.borrow_mut().pop();
@@ -348,20 +817,214 @@ mod backend {
         }
     }
 
-    #[async_trait]
-    impl<T, A> Repository<T> for TokioPostgresRepository<T, A>
+    // Lifecycle listeners (see `storeit_core::listener`).
+    impl<T, A> TokioPostgresRepository<T, A>
+    where
+        T: Fetchable
+            + Identifiable
+            + Insertable
+            + Updatable
+            + Listenable
+            + Send
+            + Sync
+            + Clone
+            + 'static,
+        A: RowAdapter<T, Row = Row> + Send + Sync + 'static,
+        T::Key: Clone + Send + Sync + 'static + KeyValues,
+    {
+        /// Register a listener that runs after the entity's own and earlier registrations.
+        pub fn with_listener(mut self, listener: impl EntityListener<T> + 'static) -> Self {
+            self.listeners.push(std::sync::Arc::new(listener));
+            self
+        }
+
+        // Map a row to an entity and run the `post_load` hooks on it.
+        fn load(&self, row: &Row) -> RepoResult<T> {
+            self.listeners.loaded(self.adapter.from_row(row)?)
+        }
+    }
+
+    // Explicit access to soft-deleted rows (see `Fetchable::SOFT_DELETE_COLUMN`).
+    impl<T, A> TokioPostgresRepository<T, A>
+    where
+        T: Fetchable
+            + Identifiable
+            + Insertable
+            + Updatable
+            + Listenable
+            + Send
+            + Sync
+            + Clone
+            + 'static,
+        A: RowAdapter<T, Row = Row> + Send + Sync + 'static,
+        T::Key: Clone + Send + Sync + 'static + KeyValues,
+    {
+        // Prefer an active transaction-bound client if present in task-local storage.
+        fn client(&self) -> std::sync::Arc<Client> {
+            if let Ok(Some(arc_client)) = PG_TX_STACK.try_with(|cell| cell.borrow().last().cloned())
+            {
+                arc_client
+            } else {
+                self.client.clone()
+            }
+        }
+
+        /// Find an entity by id whether or not it is soft-deleted.
+        pub async fn find_with_deleted(&self, id: &T::Key) -> RepoResult<Option<T>> {
+            let owned_params = to_postgres_params(&scoped::<T>(id.key_values())?);
+            let params: Vec<&(dyn ToSql + Sync)> = owned_params
+                .iter()
+                .map(|p| p.as_ref() as &(dyn ToSql + Sync))
+                .collect();
+            let row_opt = self
+                .client()
+                .query_opt(&self.statements().select_by_id_with_deleted, &params[..])
+                .await
+                .map_err(map_pg_error)?;
+            row_opt.map(|row| self.load(&row)).transpose()
+        }
+
+        /// Clear the soft-delete column of a deleted row. Returns whether a row was restored.
+        /// Fails for entities without a soft-delete column.
+        pub async fn restore_by_id(&self, id: &T::Key) -> RepoResult<bool> {
+            let sql = self.statements().restore_by_id.as_ref().ok_or_else(|| {
+                RepoError::backend(std::io::Error::new(
+                    std::io::ErrorKind::InvalidInput,
+                    format!("{} has no soft-delete column", T::TABLE),
+                ))
+            })?;
+            let owned_params = to_postgres_params(&scoped::<T>(id.key_values())?);
+            let params: Vec<&(dyn ToSql + Sync)> = owned_params
+                .iter()
+                .map(|p| p.as_ref() as &(dyn ToSql + Sync))
+                .collect();
+            let n = self
+                .client()
+                .execute(sql, &params[..])
+                .await
+                .map_err(map_pg_error)?;
+            Ok(n > 0)
+        }
+
+        /// Physically delete a row, soft-deleted or not. Returns whether a row was removed.
+        pub async fn purge_by_id(&self, id: &T::Key) -> RepoResult<bool> {
+            self.listeners.pre_delete(id)?;
+            let owned_params = to_postgres_params(&scoped::<T>(id.key_values())?);
+            let params: Vec<&(dyn ToSql + Sync)> = owned_params
+                .iter()
+                .map(|p| p.as_ref() as &(dyn ToSql + Sync))
+                .collect();
+            let n = self
+                .client()
+                .execute(&self.statements().delete_by_id, &params[..])
+                .await
+                .map_err(map_pg_error)?;
+            Ok(n > 0)
+        }
+    }
+
+    // Schema validation (see `storeit_core::schema`).
+    impl<T, A> TokioPostgresRepository<T, A>
     where
-        T: Fetchable + Identifiable + Insertable + Updatable + Send + Sync + Clone + 'static,
+        T: Schema + Insertable + Updatable + Listenable + Send + Sync + Clone + 'static,
         A: RowAdapter<T, Row = Row> + Send + Sync + 'static,
-        T::Key: Clone
+        T::Key: Clone + Send + Sync + 'static + KeyValues,
+    {
+        /// Compare `T` with its live table (`information_schema`); see `storeit_core::schema`.
+        pub async fn validate_schema(&self) -> RepoResult<SchemaReport> {
+            let live = live_columns(&self.client(), T::TABLE).await?;
+            Ok(schema::check(&T::table_def(), &live))
+        }
+    }
+
+    // Projections (see `storeit_core::Projection`): only the projected columns are selected.
+    impl<T, A> TokioPostgresRepository<T, A>
+    where
+        T: Fetchable
+            + Identifiable
+            + Insertable
+            + Updatable
+            + Listenable
             + Send
             + Sync
-            + 'static
-            + ToSql
+            + Clone
+            + 'static,
+        A: RowAdapter<T, Row = Row> + Send + Sync + 'static,
+        T::Key: Clone + Send + Sync + 'static + KeyValues,
+    {
+        /// Find the projection `P` of the entity with the given id.
+        pub async fn find_by_id_as<P>(&self, id: &T::Key) -> RepoResult<Option<P>>
+        where
+            P: Projection<Entity = T>,
+            P::Adapter<Row>: RowAdapter<P, Row = Row>,
+        {
+            let sql = storeit_sql_builder::select_columns_by_id::<T>(
+                self.statements().dialect,
+                self.statements().tenant,
+                P::COLUMNS,
+                T::ID_COLUMNS,
+            );
+            let owned_params = to_postgres_params(&scoped::<T>(id.key_values())?);
+            let params: Vec<&(dyn ToSql + Sync)> = owned_params
+                .iter()
+                .map(|p| p.as_ref() as &(dyn ToSql + Sync))
+                .collect();
+            let row_opt = self
+                .client()
+                .query_opt(&sql, &params[..])
+                .await
+                .map_err(map_pg_error)?;
+            row_opt
+                .map(|row| P::adapter::<Row>().from_row(&row))
+                .transpose()
+        }
+
+        /// Find the projections `P` of the entities whose `field_name` column equals `value`.
+        pub async fn find_by_field_as<P>(
+            &self,
+            field_name: &str,
+            value: ParamValue,
+        ) -> RepoResult<Vec<P>>
+        where
+            P: Projection<Entity = T>,
+            P::Adapter<Row>: RowAdapter<P, Row = Row>,
+        {
+            let sql = storeit_sql_builder::select_columns_by_field::<T>(
+                self.statements().dialect,
+                self.statements().tenant,
+                P::COLUMNS,
+                field_name,
+            );
+            let owned_params = to_postgres_params(&scoped::<T>(vec![value])?);
+            let params: Vec<&(dyn ToSql + Sync)> = owned_params
+                .iter()
+                .map(|p| p.as_ref() as &(dyn ToSql + Sync))
+                .collect();
+            let rows = self
+                .client()
+                .query(&sql, &params[..])
+                .await
+                .map_err(map_pg_error)?;
+
+            let adapter = P::adapter::<Row>();
+            rows.iter().map(|row| adapter.from_row(row)).collect()
+        }
+    }
+
+    #[async_trait]
+    impl<T, A> Repository<T> for TokioPostgresRepository<T, A>
+    where
+        T: Fetchable
+            + Identifiable
+            + Insertable
+            + Updatable
+            + Listenable
+            + Send
             + Sync
-            + Default
-            + PartialEq
-            + for<'b> FromSql<'b>,
+            + Clone
+            + 'static,
+        A: RowAdapter<T, Row = Row> + Send + Sync + 'static,
+        T::Key: Clone + Send + Sync + 'static + KeyValues,
     {
         async fn find_by_id(&self, id: &T::Key) -> RepoResult<Option<T>> {
             // Prefer an active transaction-bound client if present in task-local storage.
@@ -372,19 +1035,24 @@ mod backend {
             } else {
                 self.client.clone()
             };
+            let owned_params = to_postgres_params(&scoped::<T>(id.key_values())?);
+            let params: Vec<&(dyn ToSql + Sync)> = owned_params
+                .iter()
+                .map(|p| p.as_ref() as &(dyn ToSql + Sync))
+                .collect();
             let row_opt = client
-                .query_opt(&self.sql.select_by_id, &[id])
+                .query_opt(&self.statements().select_by_id, &params[..])
                 .await
-                .map_err(RepoError::backend)?;
+                .map_err(map_pg_error)?;
 
             match row_opt {
-                Some(row) => Ok(Some(self.adapter.from_row(&row)?)),
+                Some(row) => Ok(Some(self.load(&row)?)),
                 None => Ok(None),
             }
         }
 
         async fn find_by_field(&self, field_name: &str, value: ParamValue) -> RepoResult<Vec<T>> {
-            let sql = self.sql.get_select_by_field(field_name);
+            let sql = self.statements().get_select_by_field(field_name);
 
             // Prefer an active transaction-bound client if present in task-local storage.
             let client = if let Ok(Some(arc_client)) =
@@ -395,42 +1063,58 @@ mod backend {
                 self.client.clone()
             };
 
-            // Match on the ParamValue to create a temporary value of the correct type,
-            // which can then be passed as a `ToSql` trait object.
-            let rows = match value {
-                ParamValue::String(s) => client
-                    .query(&sql, &[&s])
-                    .await
-                    .map_err(RepoError::backend)?,
-                ParamValue::I32(i) => client
-                    .query(&sql, &[&i])
-                    .await
-                    .map_err(RepoError::backend)?,
-                ParamValue::I64(i) => client
-                    .query(&sql, &[&i])
-                    .await
-                    .map_err(RepoError::backend)?,
-                ParamValue::F64(f) => client
-                    .query(&sql, &[&f])
-                    .await
-                    .map_err(RepoError::backend)?,
-                ParamValue::Bool(b) => client
-                    .query(&sql, &[&b])
-                    .await
-                    .map_err(RepoError::backend)?,
-                ParamValue::Null => client
-                    .query(&sql, &[&Option::<bool>::None])
-                    .await
-                    .map_err(RepoError::backend)?,
-            };
+            let owned_params = to_postgres_params(&scoped::<T>(vec![value])?);
+            let params: Vec<&(dyn ToSql + Sync)> = owned_params
+                .iter()
+                .map(|p| p.as_ref() as &(dyn ToSql + Sync))
+                .collect();
+            let rows = client
+                .query(&sql, &params[..])
+                .await
+                .map_err(map_pg_error)?;
+
+            rows.iter()
+                .map(|row| self.load(row))
+                .collect::<RepoResult<Vec<T>>>()
+        }
+
+        async fn find_by_field_in(
+            &self,
+            field_name: &str,
+            values: Vec<ParamValue>,
+        ) -> RepoResult<Vec<T>> {
+            if values.is_empty() {
+                return Ok(Vec::new());
+            }
+            let sql = storeit_sql_builder::select_by_in::<T>(
+                self.statements().dialect,
+                self.statements().tenant,
+                field_name,
+                values.len(),
+            );
+            let owned_params = to_postgres_params(&scoped::<T>(values)?);
+            let params: Vec<&(dyn ToSql + Sync)> = owned_params
+                .iter()
+                .map(|p| p.as_ref() as &(dyn ToSql + Sync))
+                .collect();
+            let rows = self
+                .client()
+                .query(&sql, &params[..])
+                .await
+                .map_err(map_pg_error)?;
 
             rows.iter()
-                .map(|row| self.adapter.from_row(row))
+                .map(|row| self.load(row))
                 .collect::<RepoResult<Vec<T>>>()
         }
 
         async fn insert(&self, entity: &T) -> RepoResult<T> {
-            let param_values = entity.insert_values();
+            // Listeners may rewrite the entity before it is bound.
+            let mut entity = entity.clone();
+            self.listeners.pre_insert(&mut entity)?;
+            let entity = &entity;
+            let mut param_values = entity.insert_values();
+            storeit_core::tenant::stamp_insert::<T>(&mut param_values)?;
             let owned_params = to_postgres_params(&param_values);
             let params: Vec<&(dyn ToSql + Sync)> = owned_params
                 .iter()
@@ -447,23 +1131,20 @@ mod backend {
             };
 
             let row = client
-                .query_one(&self.sql.insert, &params[..])
+                .query_one(&self.statements().insert, &params[..])
                 .await
-                .map_err(RepoError::backend)?;
-            let new_id: T::Key = row.get(0);
-
-            self.find_by_id(&new_id).await.and_then(|opt| {
-                opt.ok_or_else(|| {
-                    RepoError::backend(std::io::Error::new(
-                        std::io::ErrorKind::Other,
-                        "Failed to fetch entity after insert",
-                    ))
-                })
-            })
+                .map_err(map_pg_error)?;
+            self.listeners.inserted(self.adapter.from_row(&row)?)
         }
 
         async fn update(&self, entity: &T) -> RepoResult<T> {
-            let param_values = entity.update_values();
+            let mut entity = entity.clone();
+            self.listeners.pre_update(&mut entity)?;
+            let entity = &entity;
+            let now = storeit_core::clock::now();
+            let param_values = scoped::<T>(storeit_core::clock::with_now(now, || {
+                entity.update_values()
+            }))?;
             let owned_params = to_postgres_params(&param_values);
             let params: Vec<&(dyn ToSql + Sync)> = owned_params
                 .iter()
@@ -479,15 +1160,27 @@ mod backend {
                 self.client.clone()
             };
 
-            client
-                .execute(&self.sql.update_by_id, &params[..])
+            let n = client
+                .execute(&self.statements().update_by_id, &params[..])
                 .await
-                .map_err(RepoError::backend)?;
+                .map_err(map_pg_error)?;
 
-            Ok(entity.clone())
+            // A versioned update that matched nothing lost a race with another writer.
+            if T::VERSION_COLUMN.is_some() && n == 0 {
+                return Err(RepoError::OptimisticLock { table: T::TABLE });
+            }
+            // Otherwise, for a tenant-scoped entity, the row belongs to another tenant (or is gone).
+            if T::TENANT_COLUMN.is_some() && n == 0 {
+                return Err(RepoError::NotFound);
+            }
+            let mut updated = entity.clone();
+            updated.bump_version();
+            updated.touch(now);
+            Ok(updated)
         }
 
         async fn delete_by_id(&self, id: &T::Key) -> RepoResult<bool> {
+            self.listeners.pre_delete(id)?;
             // Prefer an active transaction-bound client if present in task-local storage.
             let client = if let Ok(Some(arc_client)) =
                 PG_TX_STACK.try_with(|cell| cell.borrow().last().cloned())
@@ -496,18 +1189,297 @@ mod backend {
             } else {
                 self.client.clone()
             };
+            // Soft-deleting entities only stamp their deletion column.
+            let (sql, values) = match &self.statements().soft_delete_by_id {
+                Some(sql) => {
+                    let mut values = vec![storeit_core::clock::now().to_param_value()];
+                    values.extend(scoped::<T>(id.key_values())?);
+                    (sql, values)
+                }
+                None => (
+                    &self.statements().delete_by_id,
+                    scoped::<T>(id.key_values())?,
+                ),
+            };
+            let owned_params = to_postgres_params(&values);
+            let params: Vec<&(dyn ToSql + Sync)> = owned_params
+                .iter()
+                .map(|p| p.as_ref() as &(dyn ToSql + Sync))
+                .collect();
             let n = client
-                .execute(&self.sql.delete_by_id, &[id])
+                .execute(sql, &params[..])
                 .await
-                .map_err(RepoError::backend)?;
+                .map_err(map_pg_error)?;
             Ok(n > 0)
         }
+
+        async fn find_where(&self, criteria: &Criteria) -> RepoResult<Vec<T>> {
+            let (sql, values) = storeit_sql_builder::select_by_criteria::<T>(
+                self.statements().dialect,
+                self.statements().tenant,
+                criteria,
+            )?;
+            let owned_params = to_postgres_params(&scoped::<T>(values)?);
+            let params: Vec<&(dyn ToSql + Sync)> = owned_params
+                .iter()
+                .map(|p| p.as_ref() as &(dyn ToSql + Sync))
+                .collect();
+            // Prefer an active transaction-bound client if present in task-local storage.
+            let client = if let Ok(Some(arc_client)) =
+                PG_TX_STACK.try_with(|cell| cell.borrow().last().cloned())
+            {
+                arc_client
+            } else {
+                self.client.clone()
+            };
+            let rows = client
+                .query(&sql, &params[..])
+                .await
+                .map_err(map_pg_error)?;
+
+            rows.iter()
+                .map(|row| self.load(row))
+                .collect::<RepoResult<Vec<T>>>()
+        }
+
+        async fn count_where(&self, criteria: &Criteria) -> RepoResult<u64> {
+            let (sql, values) = storeit_sql_builder::count_by_criteria::<T>(
+                self.statements().dialect,
+                self.statements().tenant,
+                criteria,
+            )?;
+            let owned_params = to_postgres_params(&scoped::<T>(values)?);
+            let params: Vec<&(dyn ToSql + Sync)> = owned_params
+                .iter()
+                .map(|p| p.as_ref() as &(dyn ToSql + Sync))
+                .collect();
+            // Prefer an active transaction-bound client if present in task-local storage.
+            let client = if let Ok(Some(arc_client)) =
+                PG_TX_STACK.try_with(|cell| cell.borrow().last().cloned())
+            {
+                arc_client
+            } else {
+                self.client.clone()
+            };
+            let row = client
+                .query_one(&sql, &params[..])
+                .await
+                .map_err(map_pg_error)?;
+            let count: i64 = row.try_get(0).map_err(RepoError::mapping)?;
+            Ok(count as u64)
+        }
+
+        async fn exists_where(&self, criteria: &Criteria) -> RepoResult<bool> {
+            let (sql, values) = storeit_sql_builder::exists_by_criteria::<T>(
+                self.statements().dialect,
+                self.statements().tenant,
+                criteria,
+            )?;
+            let owned_params = to_postgres_params(&scoped::<T>(values)?);
+            let params: Vec<&(dyn ToSql + Sync)> = owned_params
+                .iter()
+                .map(|p| p.as_ref() as &(dyn ToSql + Sync))
+                .collect();
+            // Prefer an active transaction-bound client if present in task-local storage.
+            let client = if let Ok(Some(arc_client)) =
+                PG_TX_STACK.try_with(|cell| cell.borrow().last().cloned())
+            {
+                arc_client
+            } else {
+                self.client.clone()
+            };
+            let row = client
+                .query_opt(&sql, &params[..])
+                .await
+                .map_err(map_pg_error)?;
+            Ok(row.is_some())
+        }
+
+        async fn delete_where(&self, criteria: &Criteria) -> RepoResult<u64> {
+            let (sql, values) = storeit_sql_builder::delete_by_criteria::<T>(
+                self.statements().dialect,
+                self.statements().tenant,
+                criteria,
+            )?;
+            let owned_params = to_postgres_params(&scoped::<T>(values)?);
+            let params: Vec<&(dyn ToSql + Sync)> = owned_params
+                .iter()
+                .map(|p| p.as_ref() as &(dyn ToSql + Sync))
+                .collect();
+            // Prefer an active transaction-bound client if present in task-local storage.
+            let client = if let Ok(Some(arc_client)) =
+                PG_TX_STACK.try_with(|cell| cell.borrow().last().cloned())
+            {
+                arc_client
+            } else {
+                self.client.clone()
+            };
+            client
+                .execute(&sql, &params[..])
+                .await
+                .map_err(map_pg_error)
+        }
+
+        async fn find_page(&self, pageable: &Pageable) -> RepoResult<Page<T>> {
+            let sql = storeit_sql_builder::select_page::<T>(
+                self.statements().dialect,
+                self.statements().tenant,
+                pageable,
+            )?;
+            let count_sql = storeit_sql_builder::select_count_all::<T>(
+                self.statements().dialect,
+                self.statements().tenant,
+            );
+            let owned_params = to_postgres_params(&scoped::<T>(Vec::new())?);
+            let params: Vec<&(dyn ToSql + Sync)> = owned_params
+                .iter()
+                .map(|p| p.as_ref() as &(dyn ToSql + Sync))
+                .collect();
+            // Prefer an active transaction-bound client if present in task-local storage.
+            let client = if let Ok(Some(arc_client)) =
+                PG_TX_STACK.try_with(|cell| cell.borrow().last().cloned())
+            {
+                arc_client
+            } else {
+                self.client.clone()
+            };
+            let (total, rows) = page_rows(&client, &count_sql, &sql, &params, pageable).await?;
+
+            let items = rows
+                .iter()
+                .map(|row| self.load(row))
+                .collect::<RepoResult<Vec<T>>>()?;
+            Ok(Page::new(items, pageable, total))
+        }
+
+        async fn find_page_by_field(
+            &self,
+            field_name: &str,
+            value: ParamValue,
+            pageable: &Pageable,
+        ) -> RepoResult<Page<T>> {
+            let sql = storeit_sql_builder::select_page_by_field::<T>(
+                self.statements().dialect,
+                self.statements().tenant,
+                field_name,
+                pageable,
+            )?;
+            let count_sql = storeit_sql_builder::select_count_by_field::<T>(
+                self.statements().dialect,
+                self.statements().tenant,
+                field_name,
+            );
+            // Prefer an active transaction-bound client if present in task-local storage.
+            let client = if let Ok(Some(arc_client)) =
+                PG_TX_STACK.try_with(|cell| cell.borrow().last().cloned())
+            {
+                arc_client
+            } else {
+                self.client.clone()
+            };
+            let owned_params = to_postgres_params(&scoped::<T>(vec![value])?);
+            let params: Vec<&(dyn ToSql + Sync)> = owned_params
+                .iter()
+                .map(|p| p.as_ref() as &(dyn ToSql + Sync))
+                .collect();
+            let (total, rows) = page_rows(&client, &count_sql, &sql, &params, pageable).await?;
+
+            let items = rows
+                .iter()
+                .map(|row| self.load(row))
+                .collect::<RepoResult<Vec<T>>>()?;
+            Ok(Page::new(items, pageable, total))
+        }
+
+        async fn find_page_after(
+            &self,
+            cursor: Option<Cursor>,
+            limit: usize,
+            order: &[Order],
+        ) -> RepoResult<CursorPage<T>> {
+            let orders = storeit_sql_builder::keyset_orders::<T>(order, T::ID_COLUMNS)?;
+            let (sql, params) = storeit_sql_builder::keyset_select::<T>(
+                self.statements().dialect,
+                self.statements().tenant,
+                &orders,
+                cursor.as_ref(),
+                limit,
+            )?;
+            let owned_params = to_postgres_params(&scoped::<T>(params)?);
+            let params: Vec<&(dyn ToSql + Sync)> = owned_params
+                .iter()
+                .map(|p| p.as_ref() as &(dyn ToSql + Sync))
+                .collect();
+            // Prefer an active transaction-bound client if present in task-local storage.
+            let client = if let Ok(Some(arc_client)) =
+                PG_TX_STACK.try_with(|cell| cell.borrow().last().cloned())
+            {
+                arc_client
+            } else {
+                self.client.clone()
+            };
+            let mut rows = client
+                .query(&sql, &params[..])
+                .await
+                .map_err(map_pg_error)?;
+            let has_more = rows.len() > limit;
+            rows.truncate(limit);
+            if cursor.as_ref().is_some_and(Cursor::is_before) {
+                rows.reverse();
+            }
+
+            let key_idx: Vec<usize> = orders
+                .iter()
+                .filter_map(|o| T::SELECT_COLUMNS.iter().position(|c| *c == o.column))
+                .collect();
+            let key_of = |row: &Row| {
+                key_idx
+                    .iter()
+                    .map(|&i| column_value(row, i))
+                    .collect::<RepoResult<Vec<_>>>()
+            };
+            let first_key = rows.first().map(key_of).transpose()?;
+            let last_key = rows.last().map(key_of).transpose()?;
+            let items = rows
+                .iter()
+                .map(|row| self.load(row))
+                .collect::<RepoResult<Vec<T>>>()?;
+            Ok(CursorPage::new(
+                items,
+                orders.into_iter().map(|o| o.column).collect(),
+                first_key,
+                last_key,
+                cursor.as_ref(),
+                has_more,
+            ))
+        }
+    }
+
+    // Run the COUNT query for a page, then its SELECT unless the page lies past the end.
+    async fn page_rows(
+        client: &Client,
+        count_sql: &str,
+        sql: &str,
+        params: &[&(dyn ToSql + Sync)],
+        pageable: &Pageable,
+    ) -> RepoResult<(u64, Vec<Row>)> {
+        let row = client
+            .query_one(count_sql, params)
+            .await
+            .map_err(map_pg_error)?;
+        let total: i64 = row.try_get(0).map_err(RepoError::mapping)?;
+        let total = total as u64;
+        if (pageable.page.saturating_mul(pageable.size) as u64) >= total {
+            return Ok((total, Vec::new()));
+        }
+        let rows = client.query(sql, params).await.map_err(map_pg_error)?;
+        Ok((total, rows))
     }
 
     #[cfg(test)]
     mod tests {
         use super::*;
-        use crate::backend::{isolation_sql, to_postgres_params, RepoSql};
+        use crate::backend::{classify_sqlstate, isolation_sql, to_postgres_params, RepoSql};
         use storeit_core::{Fetchable, Identifiable, Insertable, ParamValue, Updatable};
 
         // A tiny dummy entity to exercise RepoSql without any database.
@@ -525,6 +1497,7 @@ mod backend {
                 None
             }
         }
+
         impl Insertable for Dummy {
             const INSERT_COLUMNS: &'static [&'static str] = &["name"];
             fn insert_values(&self) -> Vec<ParamValue> {
@@ -548,12 +1521,187 @@ mod backend {
                 ParamValue::I64(2),
                 ParamValue::F64(3.5),
                 ParamValue::Bool(true),
+                ParamValue::Bytes(vec![1]),
+                ParamValue::Uuid([0; 16]),
+                ParamValue::Timestamp(0),
+                ParamValue::Date(0),
+                ParamValue::Time(0),
+                ParamValue::Decimal("1.5".to_string()),
+                ParamValue::Json("{}".to_string()),
                 ParamValue::Null,
             ];
             let boxed = to_postgres_params(&values);
             assert_eq!(boxed.len(), values.len());
         }
 
+        fn encode(param: &NativeParam, ty: &Type) -> Vec<u8> {
+            let mut out = bytes::BytesMut::new();
+            param.to_sql(ty, &mut out).expect("encode");
+            out.to_vec()
+        }
+
+        #[test]
+        fn native_params_use_postgres_binary_formats() {
+            // Timestamps and dates are offsets from the Postgres epoch, 2000-01-01.
+            assert_eq!(
+                encode(
+                    &NativeParam::Timestamp(PG_EPOCH_MICROS + 1),
+                    &Type::TIMESTAMPTZ
+                ),
+                1i64.to_be_bytes()
+            );
+            assert_eq!(
+                encode(&NativeParam::Date(PG_EPOCH_DAYS - 1), &Type::DATE),
+                (-1i32).to_be_bytes()
+            );
+            assert_eq!(encode(&NativeParam::Uuid([9; 16]), &Type::UUID), [9; 16]);
+            assert_eq!(
+                encode(&NativeParam::Json("{}".into()), &Type::JSONB),
+                b"\x01{}"
+            );
+            // Text columns get the canonical text form.
+            assert_eq!(encode(&NativeParam::Date(0), &Type::TEXT), b"1970-01-01");
+            assert!(matches!(
+                NativeParam::Decimal("1.5".into()).encode_format(&Type::NUMERIC),
+                Format::Text
+            ));
+            let mut out = bytes::BytesMut::new();
+            assert!(NativeParam::Uuid([0; 16])
+                .to_sql(&Type::INT4, &mut out)
+                .is_err());
+            assert!(NativeParam::Timestamp(i64::MIN)
+                .to_sql(&Type::TIMESTAMP, &mut out)
+                .is_err());
+            assert!(NativeParam::Date(i32::MIN)
+                .to_sql(&Type::DATE, &mut out)
+                .is_err());
+            assert!(NullParam::accepts(&Type::UUID));
+        }
+
+        #[test]
+        fn text_binds_to_native_enum_types() {
+            let mood = Type::new(
+                "mood".into(),
+                0,
+                Kind::Enum(vec!["happy".into(), "sad".into()]),
+                "public".into(),
+            );
+            assert!(TextParam::accepts(&mood));
+            assert!(TextParam::accepts(&Type::VARCHAR));
+            assert!(!TextParam::accepts(&Type::INT4));
+            let mut out = bytes::BytesMut::new();
+            TextParam("happy".into()).to_sql(&mood, &mut out).unwrap();
+            assert_eq!(&out[..], b"happy");
+            assert!(matches!(decode(&mood, b"sad"), ParamValue::String(s) if s == "sad"));
+        }
+
+        fn decode(ty: &Type, raw: &[u8]) -> ParamValue {
+            ColumnValue::from_sql(ty, raw).expect("decode").0
+        }
+
+        #[test]
+        fn column_values_decode_postgres_binary_formats() {
+            assert!(matches!(
+                decode(&Type::TIMESTAMPTZ, &1i64.to_be_bytes()),
+                ParamValue::Timestamp(t) if t == PG_EPOCH_MICROS + 1
+            ));
+            assert!(matches!(
+                decode(&Type::DATE, &0i32.to_be_bytes()),
+                ParamValue::Date(PG_EPOCH_DAYS)
+            ));
+            assert!(matches!(
+                decode(&Type::INT2, &7i16.to_be_bytes()),
+                ParamValue::I32(7)
+            ));
+            assert!(matches!(decode(&Type::JSONB, b"\x01[]"), ParamValue::Json(j) if j == "[]"));
+            assert!(ColumnValue::from_sql(&Type::INT4, &[0]).is_err());
+            // `infinity` is stored as i64::MAX.
+            assert!(ColumnValue::from_sql(&Type::TIMESTAMPTZ, &i64::MAX.to_be_bytes()).is_err());
+            assert!(ColumnValue::from_sql(&Type::DATE, &i32::MAX.to_be_bytes()).is_err());
+        }
+
+        fn numeric(weight: i16, sign: u16, dscale: u16, digits: &[u16]) -> Vec<u8> {
+            let mut raw = Vec::new();
+            for w in [digits.len() as u16, weight as u16, sign, dscale] {
+                raw.extend_from_slice(&w.to_be_bytes());
+            }
+            for d in digits {
+                raw.extend_from_slice(&d.to_be_bytes());
+            }
+            raw
+        }
+
+        #[test]
+        fn numeric_binary_format_renders_as_text() {
+            // 12345.678 = [1, 2345, 6780] with weight 1
+            assert_eq!(
+                decode_numeric(&numeric(1, 0, 3, &[1, 2345, 6780])).unwrap(),
+                "12345.678"
+            );
+            // -0.0042 = [42] with weight -1
+            assert_eq!(
+                decode_numeric(&numeric(-1, 0x4000, 4, &[42])).unwrap(),
+                "-0.0042"
+            );
+            // 10000 = [1] with weight 1 (trailing zero groups are omitted)
+            assert_eq!(decode_numeric(&numeric(1, 0, 0, &[1])).unwrap(), "10000");
+            assert_eq!(decode_numeric(&numeric(0, 0, 2, &[])).unwrap(), "0.00");
+            assert_eq!(decode_numeric(&numeric(0, 0xC000, 0, &[])).unwrap(), "NaN");
+            assert_eq!(
+                decode_numeric(&numeric(0, 0xD000, 0, &[])).unwrap(),
+                "Infinity"
+            );
+            assert_eq!(
+                decode_numeric(&numeric(0, 0xF000, 0, &[])).unwrap(),
+                "-Infinity"
+            );
+        }
+
+        #[test]
+        fn classify_sqlstate_maps_known_codes() {
+            let src = || -> Box<dyn std::error::Error + Send + Sync> {
+                Box::new(std::io::Error::new(std::io::ErrorKind::Other, "pg"))
+            };
+            match classify_sqlstate("23505", Some("users_email_key".into()), src()) {
+                RepoError::UniqueViolation { constraint, .. } => {
+                    assert_eq!(constraint.as_deref(), Some("users_email_key"))
+                }
+                other => panic!("unexpected: {:?}", other),
+            }
+            assert!(matches!(
+                classify_sqlstate("23503", None, src()),
+                RepoError::ForeignKeyViolation { .. }
+            ));
+            assert!(matches!(
+                classify_sqlstate("23502", Some("email".into()), src()),
+                RepoError::NotNullViolation { column: Some(c), .. } if c == "email"
+            ));
+            assert!(matches!(
+                classify_sqlstate("40001", None, src()),
+                RepoError::SerializationFailure { .. }
+            ));
+            assert!(matches!(
+                classify_sqlstate("40P01", None, src()),
+                RepoError::Deadlock { .. }
+            ));
+            assert!(matches!(
+                classify_sqlstate("57014", None, src()),
+                RepoError::Timeout { .. }
+            ));
+            assert!(matches!(
+                classify_sqlstate("25006", None, src()),
+                RepoError::ReadOnlyViolation { .. }
+            ));
+            assert!(matches!(
+                classify_sqlstate("08006", None, src()),
+                RepoError::ConnectionLost { .. }
+            ));
+            assert!(matches!(
+                classify_sqlstate("42703", None, src()),
+                RepoError::Backend { .. }
+            ));
+        }
+
         #[test]
         fn isolation_sql_maps_variants() {
             // Default has no statement; others do.
@@ -571,10 +1719,13 @@ mod backend {
 
         #[test]
         fn repo_sql_builds_expected_statements_and_caches() {
-            let sql = RepoSql::<Dummy>::new();
+            let sql = RepoSql::<Dummy>::new(true);
             // Basic statements should mention table name "dummy".
             assert!(sql.select_by_id.to_lowercase().contains("dummy"));
             assert!(sql.delete_by_id.to_lowercase().contains("dummy"));
+            // Without a soft-delete column deletes are physical and nothing can be restored.
+            assert!(sql.soft_delete_by_id.is_none() && sql.restore_by_id.is_none());
+            assert_eq!(sql.select_by_id_with_deleted, sql.select_by_id);
             assert!(sql.insert.to_lowercase().contains("dummy"));
             assert!(sql.update_by_id.to_lowercase().contains("dummy"));
 
@@ -592,4 +1743,6 @@ mod backend {
 }
 
 #[cfg(feature = "postgres-backend")]
-pub use backend::{TokioPostgresRepository, TokioPostgresTransactionManager};
+pub use backend::{
+    column_value, map_pg_error, TokioPostgresRepository, TokioPostgresTransactionManager,
+};
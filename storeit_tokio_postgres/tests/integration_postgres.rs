@@ -2,6 +2,7 @@
 #![allow(unexpected_cfgs)]
 
 use storeit_core::{Identifiable, RepoError, RepoResult, Repository, RowAdapter};
+use storeit_migrate::{Migration, MigrationError, Migrator};
 use storeit_tokio_postgres::TokioPostgresRepository;
 use testcontainers::runners::AsyncRunner;
 use testcontainers_modules::postgres::Postgres;
@@ -74,6 +75,30 @@ impl RowAdapter<tests_common::User> for A {
     }
 }
 
+struct M; // membership row adapter
+impl RowAdapter<tests_common::Membership> for M {
+    type Row = tokio_postgres::Row;
+    fn from_row(&self, row: &Self::Row) -> RepoResult<tests_common::Membership> {
+        Ok(tests_common::Membership {
+            id: Some(row.try_get("id").map_err(RepoError::mapping)?),
+            user_id: row.try_get("user_id").map_err(RepoError::mapping)?,
+            role: row.try_get("role").map_err(RepoError::mapping)?,
+        })
+    }
+}
+
+struct N; // note row adapter
+impl RowAdapter<tests_common::Note> for N {
+    type Row = tokio_postgres::Row;
+    fn from_row(&self, row: &Self::Row) -> RepoResult<tests_common::Note> {
+        Ok(tests_common::Note {
+            id: Some(row.try_get("id").map_err(RepoError::mapping)?),
+            tenant_id: row.try_get("tenant_id").map_err(RepoError::mapping)?,
+            body: row.try_get("body").map_err(RepoError::mapping)?,
+        })
+    }
+}
+
 struct PgFactory {
     url: String,
 }
@@ -91,6 +116,36 @@ impl tests_common::RepoFactory for PgFactory {
     }
 }
 
+#[async_trait::async_trait]
+impl tests_common::MembershipRepoFactory for PgFactory {
+    async fn new_membership_repo(
+        &self,
+    ) -> RepoResult<Box<dyn Repository<tests_common::Membership> + Send + Sync>> {
+        let repo = TokioPostgresRepository::<tests_common::Membership, M>::from_url(
+            &self.url,
+            tests_common::Membership::ID_COLUMN,
+            M,
+        )
+        .await?;
+        Ok(Box::new(repo))
+    }
+}
+
+#[async_trait::async_trait]
+impl tests_common::NoteRepoFactory for PgFactory {
+    async fn new_note_repo(
+        &self,
+    ) -> RepoResult<Box<dyn Repository<tests_common::Note> + Send + Sync>> {
+        let repo = TokioPostgresRepository::<tests_common::Note, N>::from_url(
+            &self.url,
+            tests_common::Note::ID_COLUMN,
+            N,
+        )
+        .await?;
+        Ok(Box::new(repo))
+    }
+}
+
 fn skip_containers() -> bool {
     std::env::var("SKIP_CONTAINER_TESTS")
         .map(|v| v == "1" || v.to_lowercase() == "true")
@@ -133,10 +188,10 @@ async fn apply_migration(url: &str) -> RepoResult<()> {
         .await
         .map_err(RepoError::backend)?;
     // Apply schema
-    client
-        .batch_execute(tests_common::migrations::POSTGRES_USERS_SQL)
-        .await
-        .map_err(RepoError::backend)?;
+    let mgr = storeit_tokio_postgres::TokioPostgresTransactionManager::new(url.to_string());
+    Migrator::new(tests_common::migrations::POSTGRES)?
+        .migrate(&mgr)
+        .await?;
     Ok(())
 }
 
@@ -184,6 +239,12 @@ async fn postgres_crud_and_find_by_field() -> RepoResult<()> {
     let factory = PgFactory { url: url.clone() };
     tests_common::test_crud_roundtrip(&factory).await?;
     tests_common::test_find_by_field(&factory).await?;
+    tests_common::test_unique_violation(&factory).await?;
+    tests_common::test_foreign_key_violation(&factory).await?;
+    tests_common::test_not_null_violation(&factory).await?;
+    let mgr = storeit_tokio_postgres::TokioPostgresTransactionManager::new(url.clone());
+    tests_common::test_read_only_violation(&factory, &mgr).await?;
+    tests_common::test_tenant_isolation(&factory).await?;
 
     // Also verify delete_by_id returns false for non-existent id
     let repo = TokioPostgresRepository::<tests_common::User, A>::from_url(
@@ -201,6 +262,24 @@ async fn postgres_crud_and_find_by_field() -> RepoResult<()> {
     Ok(())
 }
 
+#[ignore]
+#[tokio::test(flavor = "multi_thread")]
+async fn postgres_schema_matches_migration() -> RepoResult<()> {
+    if !containers_usable() {
+        eprintln!("[integration] Skipping: Docker not available");
+        return Ok(());
+    }
+    let _lock = acquire_it_lock().await;
+    let url = shared_db_url().await;
+
+    let mgr = storeit_tokio_postgres::TokioPostgresTransactionManager::new(url.clone());
+    let report = mgr.validate_schema::<tests_common::User>().await?;
+    assert!(report.is_ok(), "unexpected schema drift: {report}");
+    let changes = mgr.schema_diff::<tests_common::User>().await?;
+    assert!(changes.is_empty(), "unexpected changes: {changes:?}");
+    Ok(())
+}
+
 #[ignore]
 #[tokio::test(flavor = "multi_thread")]
 async fn postgres_unique_violation_on_duplicate_email() -> RepoResult<()> {
@@ -869,3 +948,166 @@ async fn postgres_delete_idempotent() -> RepoResult<()> {
     assert!(after.is_none());
     Ok(())
 }
+
+// Applied on top of the shared schema by the storeit_migrate tests, which roll them back
+// again so the shared database keeps the schema the other tests expect.
+const TEAMS: [Migration; 2] = [
+    Migration {
+        version: 101,
+        name: "create_teams",
+        up: "CREATE TABLE teams (id BIGSERIAL PRIMARY KEY, name TEXT NOT NULL);\n\
+             CREATE INDEX idx_teams_name ON teams (name);\n\
+             INSERT INTO teams (name) VALUES ('core');",
+        down: Some("DROP TABLE teams;"),
+    },
+    Migration {
+        version: 102,
+        name: "add_rank",
+        up: "ALTER TABLE teams ADD COLUMN rank BIGINT NOT NULL DEFAULT 0;\n\
+             UPDATE teams SET rank = 1;",
+        down: Some("ALTER TABLE teams DROP COLUMN rank;"),
+    },
+];
+
+// How many migrations the shared schema has, and the newest of their versions.
+const SHARED_LEN: usize = tests_common::migrations::POSTGRES.len();
+const SHARED: i64 = tests_common::migrations::POSTGRES[SHARED_LEN - 1].version;
+
+fn with_teams() -> Vec<Migration> {
+    tests_common::migrations::POSTGRES
+        .iter()
+        .copied()
+        .chain(TEAMS)
+        .collect()
+}
+
+async fn history(client: &tokio_postgres::Client) -> RepoResult<Vec<(i64, String, i64)>> {
+    let rows = client
+        .query(
+            "SELECT version, name, checksum FROM storeit_schema_history ORDER BY version",
+            &[],
+        )
+        .await
+        .map_err(RepoError::backend)?;
+    Ok(rows
+        .iter()
+        .map(|r| (r.get(0), r.get(1), r.get(2)))
+        .collect())
+}
+
+#[ignore]
+#[tokio::test(flavor = "multi_thread")]
+async fn postgres_migrator_applies_scripts_and_records_history() -> RepoResult<()> {
+    if !containers_usable() {
+        eprintln!("[integration] Skipping: Docker not available");
+        return Ok(());
+    }
+    let _lock = acquire_it_lock().await;
+    let url = shared_db_url().await;
+    let mgr = storeit_tokio_postgres::TokioPostgresTransactionManager::new(url.clone());
+    let client = pg_connect_with_retry(&url).await?;
+    let migrations = with_teams();
+    let migrator = Migrator::new(&migrations)?;
+
+    // Every statement of the multi-statement scripts ran.
+    assert_eq!(migrator.migrate(&mgr).await?, vec![101, 102]);
+    let row = client
+        .query_one("SELECT name, rank FROM teams", &[])
+        .await
+        .map_err(RepoError::backend)?;
+    assert_eq!(
+        (row.get::<_, String>(0), row.get::<_, i64>(1)),
+        ("core".into(), 1)
+    );
+    let expected: Vec<(i64, String, i64)> = migrations
+        .iter()
+        .map(|m| (m.version, m.name.to_string(), m.checksum()))
+        .collect();
+    assert_eq!(history(&client).await?, expected);
+    assert!(migrator.migrate(&mgr).await?.is_empty());
+
+    // A failing script leaves neither its DDL nor a history row behind.
+    let mut broken = migrations.clone();
+    broken.push(Migration {
+        version: 103,
+        name: "broken",
+        up: "CREATE TABLE audit (id BIGINT); INSERT INTO missing VALUES (1);",
+        down: None,
+    });
+    let err = Migrator::new(&broken)?.migrate(&mgr).await.unwrap_err();
+    let RepoError::Backend { source } = err else {
+        panic!("unexpected error {err:?}");
+    };
+    assert!(matches!(
+        source.downcast_ref::<MigrationError>(),
+        Some(MigrationError::Failed { version: 103, .. })
+    ));
+    let audit = client
+        .query_one("SELECT to_regclass('audit') IS NULL", &[])
+        .await
+        .map_err(RepoError::backend)?;
+    assert!(audit.get::<_, bool>(0));
+    assert_eq!(history(&client).await?, expected);
+
+    assert_eq!(migrator.rollback_to(&mgr, SHARED).await?, vec![102, 101]);
+    assert_eq!(history(&client).await?, expected[..SHARED_LEN].to_vec());
+    Ok(())
+}
+
+#[ignore]
+#[tokio::test(flavor = "multi_thread")]
+async fn postgres_migrator_waits_for_the_advisory_lock() -> RepoResult<()> {
+    if !containers_usable() {
+        eprintln!("[integration] Skipping: Docker not available");
+        return Ok(());
+    }
+    let _lock = acquire_it_lock().await;
+    let url = shared_db_url().await;
+    let holder = pg_connect_with_retry(&url).await?;
+    holder
+        .execute(
+            "SELECT pg_advisory_lock($1)",
+            &[&storeit_migrate::postgres::LOCK_KEY],
+        )
+        .await
+        .map_err(RepoError::backend)?;
+
+    let run = {
+        let url = url.clone();
+        tokio::spawn(async move {
+            let mgr = storeit_tokio_postgres::TokioPostgresTransactionManager::new(url);
+            Migrator::new(&with_teams())?.migrate(&mgr).await
+        })
+    };
+    tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+    assert!(
+        !run.is_finished(),
+        "migrate ran while another session held the lock"
+    );
+    assert_eq!(history(&holder).await?.len(), SHARED_LEN);
+
+    holder
+        .execute(
+            "SELECT pg_advisory_unlock($1)",
+            &[&storeit_migrate::postgres::LOCK_KEY],
+        )
+        .await
+        .map_err(RepoError::backend)?;
+    let applied = tokio::time::timeout(std::time::Duration::from_secs(30), run)
+        .await
+        .expect("migrate finishes once the lock is released")
+        .expect("migrate task")?;
+    assert_eq!(applied, vec![101, 102]);
+
+    // Concurrent runs apply each migration once.
+    let mgr = storeit_tokio_postgres::TokioPostgresTransactionManager::new(url.clone());
+    let migrator = Migrator::new(&with_teams())?;
+    assert_eq!(migrator.rollback_to(&mgr, SHARED).await?, vec![102, 101]);
+    let (a, b) = tokio::join!(migrator.migrate(&mgr), migrator.migrate(&mgr));
+    let mut applied = [a?, b?].concat();
+    applied.sort_unstable();
+    assert_eq!(applied, vec![101, 102]);
+    assert_eq!(history(&holder).await?.len(), SHARED_LEN + 2);
+    migrator.rollback_to(&mgr, SHARED).await?;
+    Ok(())
+}
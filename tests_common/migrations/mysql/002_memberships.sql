@@ -0,0 +1,6 @@
+CREATE TABLE IF NOT EXISTS memberships (
+  id BIGINT AUTO_INCREMENT PRIMARY KEY,
+  user_id BIGINT NOT NULL,
+  role VARCHAR(255) NOT NULL,
+  FOREIGN KEY (user_id) REFERENCES users (id)
+);
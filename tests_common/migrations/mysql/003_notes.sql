@@ -0,0 +1,5 @@
+CREATE TABLE IF NOT EXISTS notes (
+  id BIGINT AUTO_INCREMENT PRIMARY KEY,
+  tenant_id VARCHAR(255) NOT NULL,
+  body VARCHAR(255) NOT NULL
+);
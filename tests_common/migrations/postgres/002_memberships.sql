@@ -0,0 +1,5 @@
+CREATE TABLE IF NOT EXISTS memberships (
+  id BIGSERIAL PRIMARY KEY,
+  user_id BIGINT NOT NULL REFERENCES users (id),
+  role TEXT NOT NULL
+);
@@ -3,6 +3,7 @@
 
 use async_trait::async_trait;
 use storeit::Entity;
+use storeit_core::transactions::{TransactionDefinition, TransactionManager};
 use storeit_core::Repository;
 
 #[derive(Entity, Clone, Debug, PartialEq)]
@@ -10,15 +11,47 @@ use storeit_core::Repository;
 pub struct User {
     #[fetch(id)]
     pub id: Option<i64>,
+    #[fetch(unique)]
     pub email: String,
     pub active: bool,
 }
 
+/// A user's role; `memberships` references `users` and requires a role, which the
+/// constraint checks below rely on.
+#[derive(Entity, Clone, Debug, PartialEq)]
+#[entity(table = "memberships")]
+pub struct Membership {
+    #[fetch(id)]
+    pub id: Option<i64>,
+    pub user_id: i64,
+    /// Optional here but `NOT NULL` in the migrations, so `None` reaches the database.
+    pub role: Option<String>,
+}
+
+/// A note owned by a tenant; the tenant check below relies on `tenant_id` being its
+/// tenant column.
+#[derive(Entity, Clone, Debug, PartialEq)]
+#[entity(table = "notes", tenant = "tenant_id")]
+pub struct Note {
+    #[fetch(id)]
+    pub id: Option<i64>,
+    pub tenant_id: String,
+    pub body: String,
+}
+
 /// Expose migration SQL via constants for harnesses.
 pub mod migrations {
+    use storeit_migrate::{embed_migrations, Migration};
+
     pub const POSTGRES_USERS_SQL: &str = include_str!("../migrations/postgres/001_users.sql");
     pub const MYSQL_USERS_SQL: &str = include_str!("../migrations/mysql/001_users.sql");
     pub const LIBSQL_USERS_SQL: &str = include_str!("../migrations/libsql/001_users.sql");
+
+    /// The users schema followed by `memberships` and `notes`, as versioned migrations; the
+    /// backend suites apply them with `storeit_migrate::Migrator`.
+    pub const POSTGRES: &[Migration] = embed_migrations!("migrations/postgres");
+    pub const MYSQL: &[Migration] = embed_migrations!("migrations/mysql");
+    pub const LIBSQL: &[Migration] = embed_migrations!("migrations/libsql");
 }
 
 #[async_trait]
@@ -29,6 +62,23 @@ pub trait RepoFactory {
     ) -> storeit_core::RepoResult<Box<dyn Repository<User> + Send + Sync>>;
 }
 
+/// A [`RepoFactory`] that can also reach the `memberships` table, for the foreign key and
+/// NOT NULL checks.
+#[async_trait]
+pub trait MembershipRepoFactory: RepoFactory {
+    async fn new_membership_repo(
+        &self,
+    ) -> storeit_core::RepoResult<Box<dyn Repository<Membership> + Send + Sync>>;
+}
+
+/// A [`RepoFactory`] that can also reach the tenant-scoped `notes` table.
+#[async_trait]
+pub trait NoteRepoFactory: RepoFactory {
+    async fn new_note_repo(
+        &self,
+    ) -> storeit_core::RepoResult<Box<dyn Repository<Note> + Send + Sync>>;
+}
+
 /// Generic CRUD roundtrip test.
 pub async fn test_crud_roundtrip<F: RepoFactory + Sync>(f: &F) -> storeit_core::RepoResult<()> {
     let repo = f.new_user_repo().await?;
@@ -77,6 +127,185 @@ pub async fn test_find_by_field<F: RepoFactory + Sync>(f: &F) -> storeit_core::R
     Ok(())
 }
 
+/// Generic constraint-mapping test: inserting a duplicate email must surface a
+/// `RepoError::UniqueViolation` rather than an opaque backend error.
+pub async fn test_unique_violation<F: RepoFactory + Sync>(f: &F) -> storeit_core::RepoResult<()> {
+    let repo = f.new_user_repo().await?;
+    let u = User {
+        id: None,
+        email: "dup@example.com".into(),
+        active: true,
+    };
+    let created = repo.insert(&u).await?;
+
+    let err = repo
+        .insert(&u)
+        .await
+        .expect_err("duplicate email should violate the unique constraint");
+    assert!(
+        matches!(err, storeit_core::RepoError::UniqueViolation { .. }),
+        "expected UniqueViolation, got {:?}",
+        err
+    );
+
+    repo.delete_by_id(&created.id.expect("id after insert"))
+        .await?;
+    Ok(())
+}
+
+/// Generic constraint-mapping test: a membership of a user that does not exist must
+/// surface a `RepoError::ForeignKeyViolation`.
+pub async fn test_foreign_key_violation<F: MembershipRepoFactory + Sync>(
+    f: &F,
+) -> storeit_core::RepoResult<()> {
+    let memberships = f.new_membership_repo().await?;
+    let err = memberships
+        .insert(&Membership {
+            id: None,
+            user_id: i64::MAX,
+            role: Some("owner".into()),
+        })
+        .await
+        .expect_err("a missing user should violate the foreign key");
+    assert!(
+        matches!(err, storeit_core::RepoError::ForeignKeyViolation { .. }),
+        "expected ForeignKeyViolation, got {:?}",
+        err
+    );
+    Ok(())
+}
+
+/// Generic constraint-mapping test: a NULL role must surface a
+/// `RepoError::NotNullViolation` naming the column.
+pub async fn test_not_null_violation<F: MembershipRepoFactory + Sync>(
+    f: &F,
+) -> storeit_core::RepoResult<()> {
+    let users = f.new_user_repo().await?;
+    let user = users
+        .insert(&User {
+            id: None,
+            email: "not-null@example.com".into(),
+            active: true,
+        })
+        .await?;
+    let user_id = user.id.expect("id after insert");
+
+    let memberships = f.new_membership_repo().await?;
+    let err = memberships
+        .insert(&Membership {
+            id: None,
+            user_id,
+            role: None,
+        })
+        .await
+        .expect_err("a NULL role should violate the NOT NULL constraint");
+    // SQLite qualifies the column with its table.
+    assert!(
+        matches!(
+            &err,
+            storeit_core::RepoError::NotNullViolation { column: Some(c), .. } if c.ends_with("role")
+        ),
+        "expected NotNullViolation on role, got {:?}",
+        err
+    );
+
+    users.delete_by_id(&user_id).await?;
+    Ok(())
+}
+
+/// Generic constraint-mapping test: a write in a `read_only` transaction of `mgr` must
+/// surface a `RepoError::ReadOnlyViolation`.
+pub async fn test_read_only_violation<F, M>(f: &F, mgr: &M) -> storeit_core::RepoResult<()>
+where
+    F: RepoFactory + Sync,
+    M: TransactionManager,
+{
+    let read_only = TransactionDefinition {
+        read_only: true,
+        ..TransactionDefinition::default()
+    };
+    let err = mgr
+        .execute(&read_only, |_ctx| async move {
+            let repo = f.new_user_repo().await?;
+            repo.insert(&User {
+                id: None,
+                email: "read-only@example.com".into(),
+                active: true,
+            })
+            .await
+        })
+        .await
+        .expect_err("a write in a read-only transaction should fail");
+    assert!(
+        matches!(err, storeit_core::RepoError::ReadOnlyViolation { .. }),
+        "expected ReadOnlyViolation, got {:?}",
+        err
+    );
+
+    let found = f
+        .new_user_repo()
+        .await?
+        .find_by_field(
+            "email",
+            storeit_core::ParamValue::String("read-only@example.com".into()),
+        )
+        .await?;
+    assert!(found.is_empty());
+    Ok(())
+}
+
+/// Generic tenant test: a repository constructed inside `tenant::bypass` must still scope
+/// calls made inside `tenant::scope`, and updating another tenant's row must surface a
+/// `RepoError::NotFound`.
+pub async fn test_tenant_isolation<F: NoteRepoFactory + Sync>(
+    f: &F,
+) -> storeit_core::RepoResult<()> {
+    use storeit_core::tenant;
+
+    let notes = tenant::bypass(f.new_note_repo()).await?;
+    // Fresh tenants keep rows from earlier runs out of sight.
+    let nonce = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .expect("clock after epoch")
+        .as_nanos();
+    let (acme, globex) = (format!("acme-{nonce}"), format!("globex-{nonce}"));
+    let note = Note {
+        id: None,
+        tenant_id: String::new(),
+        body: "shared".into(),
+    };
+    let a = tenant::scope(acme.clone(), notes.insert(&note)).await?;
+    assert_eq!(a.tenant_id, acme);
+    let a_id = a.id.expect("id after insert");
+    let g = tenant::scope(globex.clone(), notes.insert(&note)).await?;
+
+    tenant::scope(globex, async {
+        assert!(notes.find_by_id(&a_id).await?.is_none());
+        let shared = notes
+            .find_by_field("body", storeit_core::ParamValue::String("shared".into()))
+            .await?;
+        assert_eq!(shared, vec![g.clone()]);
+        let err = notes
+            .update(&Note {
+                body: "hijacked".into(),
+                ..a.clone()
+            })
+            .await
+            .expect_err("another tenant's row should not be updated");
+        assert!(
+            matches!(err, storeit_core::RepoError::NotFound),
+            "expected NotFound, got {:?}",
+            err
+        );
+        Ok::<_, storeit_core::RepoError>(())
+    })
+    .await?;
+
+    let kept = tenant::scope(acme, notes.find_by_id(&a_id)).await?;
+    assert_eq!(kept.map(|n| n.body), Some("shared".to_string()));
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -99,10 +328,35 @@ mod tests {
         assert!(pg.contains("CREATE TABLE") && pg.contains("users"));
         assert!(my.contains("CREATE TABLE") && my.contains("users"));
         assert!(ls.contains("CREATE TABLE") && ls.contains("users"));
+        for (set, sql) in [
+            (migrations::POSTGRES, pg),
+            (migrations::MYSQL, my),
+            (migrations::LIBSQL, ls),
+        ] {
+            let names: Vec<(i64, &str)> = set.iter().map(|m| (m.version, m.name)).collect();
+            assert_eq!(names, [(1, "users"), (2, "memberships"), (3, "notes")]);
+            assert_eq!(set[0].up, sql);
+            assert!(set[1].up.contains("REFERENCES users (id)"));
+        }
         // Also touch some additional entity metadata to improve coverage stability
         let _id_col = User::ID_COLUMN;
         let _sel = User::SELECT_COLUMNS;
     }
+
+    #[test]
+    fn migrations_match_generated_ddl() {
+        use storeit_sql_builder::{create_schema, Dialect};
+        for (dialect, sql) in [
+            (Dialect::Postgres, migrations::POSTGRES_USERS_SQL),
+            (Dialect::MySql, migrations::MYSQL_USERS_SQL),
+            (Dialect::Sqlite, migrations::LIBSQL_USERS_SQL),
+        ] {
+            assert_eq!(
+                format!("{};\n", create_schema::<User>(dialect).join(";\n")),
+                sql
+            );
+        }
+    }
 }
 
 #[cfg(test)]